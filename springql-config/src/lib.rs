@@ -57,6 +57,11 @@ memory_state_transition_interval_msec = 10
 # Interval for PerformanceMonitorWorker to publish ReportMetricsSummary event.
 performance_metrics_summary_report_interval_msec = 10
 
+# Max number of rows in a table (`CREATE TABLE`). Rows with new keys are rejected when a table is full.
+# Bytes of the rows in tables count toward `upper_limit_bytes`.
+# Can be overwritten by `MAX_ROWS` option of each table.
+table_max_rows = 10_000
+
 [web_console]
 # Whether to enable POST API request to web console.
 enable_report_post = false
@@ -74,12 +79,39 @@ net_read_timeout_msec = 100
 
 can_read_timeout_msec = 100
 
+# HTTP1_SERVER source readers respond `413 Payload Too Large` to requests with larger bodies.
+http_max_body_bytes = 1_048_576
+
 [sink_writer]
 net_connect_timeout_msec = 1_000
 net_write_timeout_msec = 100
 
 http_connect_timeout_msec = 1_000
 http_timeout_msec = 100
+
+# HTTP1_CLIENT sink writers with `BODY_FORMAT` 'NDJSON' or 'JSON_ARRAY' send a batch when it has this many rows,
+# when its body reaches this many bytes, or when its oldest row has waited for this long.
+# Can be overwritten by `BATCH_MAX_ROWS`, `BATCH_MAX_BYTES` and `BATCH_MAX_LATENCY_MSEC` options.
+http_batch_max_rows = 100
+http_batch_max_bytes = 1_048_576
+http_batch_max_latency_msec = 1_000
+
+# How many times a row is re-sent after a sink writer failed to send it. 0 disables retries.
+# Can be overwritten by `RETRY_MAX` option of each sink writer.
+retry_max = 0
+
+# Backoff before the first retry. It is doubled on every retry up to `retry_max_backoff_msec`.
+# Can be overwritten by `RETRY_INITIAL_BACKOFF_MSEC` and `RETRY_MAX_BACKOFF_MSEC` options.
+retry_initial_backoff_msec = 100
+retry_max_backoff_msec = 10_000
+
+# Each backoff is randomly shortened or lengthened by up to this percentage.
+retry_jitter_percent = 20
+
+# Max number of rows waiting for retries in a sink writer. Rows failed while the buffer is full are not retried.
+# Bytes of the rows in the buffer count toward `memory.upper_limit_bytes`.
+# Can be overwritten by `RETRY_BUFFER_MAX_ROWS` option.
+retry_buffer_max_rows = 1_000
 "#;
 
 /// Top-level config.
@@ -177,6 +209,8 @@ pub struct SpringMemoryConfig {
 
     pub memory_state_transition_interval_msec: u32,
     pub performance_metrics_summary_report_interval_msec: u32,
+
+    pub table_max_rows: u32,
 }
 
 /// Config related to web console.
@@ -203,6 +237,8 @@ pub struct SpringSourceReaderConfig {
     pub net_read_timeout_msec: u32,
 
     pub can_read_timeout_msec: u32,
+
+    pub http_max_body_bytes: u32,
 }
 
 /// Config related to sink writer.
@@ -215,4 +251,14 @@ pub struct SpringSinkWriterConfig {
 
     pub http_timeout_msec: u32,
     pub http_connect_timeout_msec: u32,
+
+    pub http_batch_max_rows: u32,
+    pub http_batch_max_bytes: u32,
+    pub http_batch_max_latency_msec: u32,
+
+    pub retry_max: u32,
+    pub retry_initial_backoff_msec: u32,
+    pub retry_max_backoff_msec: u32,
+    pub retry_jitter_percent: u8,
+    pub retry_buffer_max_rows: u32,
 }
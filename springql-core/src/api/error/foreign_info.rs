@@ -5,6 +5,7 @@
 use std::{
     fmt::{Debug, Display},
     net::SocketAddr,
+    path::PathBuf,
 };
 
 use crate::pipeline::QueueName;
@@ -17,6 +18,8 @@ pub enum ForeignInfo {
     /// HTTP client
     Http(SocketAddr),
 
+    /// File
+    File(PathBuf),
 
     /// In memory queue
     InMemoryQueue(QueueName),
@@ -27,6 +30,7 @@ impl Display for ForeignInfo {
         let detail = match self {
             ForeignInfo::GenericTcp(addr) => format!("TCP connection to {:?}", addr),
             ForeignInfo::Http(addr) => format!("HTTP connection to {:?}", addr),
+            ForeignInfo::File(path) => format!("File {}", path.display()),
             ForeignInfo::InMemoryQueue(queue_name) => format!("In-memory queue {}", queue_name),
         };
 
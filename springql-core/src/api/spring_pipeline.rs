@@ -26,6 +26,8 @@ impl SpringPipeline {
     ///   - Other semantic errors.
     /// - [SpringError::InvalidOption](crate::api::error::SpringError::Sql) when:
     ///   - `OPTIONS` in `CREATE` statement includes invalid key or value.
+    /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
+    ///   - the pipeline has been stopped by a fatal error (e.g. a row rejected with `ON_SCHEMA_ERROR 'FAIL'`).
     pub fn command<S: AsRef<str>>(&self, sql: S) -> Result<()> {
         self.0.command(sql.as_ref())
     }
@@ -40,6 +42,7 @@ impl SpringPipeline {
     ///
     /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
     ///   - queue named `queue` does not exist.
+    ///   - the pipeline has been stopped by a fatal error.
     pub fn pop(&self, queue: &str) -> Result<SpringSinkRow> {
         self.0.pop(queue).map(SpringSinkRow::new)
     }
@@ -55,6 +58,7 @@ impl SpringPipeline {
     ///
     /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
     ///   - queue named `queue` does not exist.
+    ///   - the pipeline has been stopped by a fatal error.
     pub fn pop_non_blocking(&self, queue: &str) -> Result<Option<SpringSinkRow>> {
         self.0
             .pop_non_blocking(queue)
@@ -67,6 +71,7 @@ impl SpringPipeline {
     ///
     /// - [SpringError::Unavailable](crate::api::error::SpringError::Unavailable) when:
     ///   - queue named `queue` does not exist.
+    ///   - the pipeline has been stopped by a fatal error.
     pub fn push(&self, queue: &str, row: SpringSourceRow) -> Result<()> {
         self.0.push(queue, row.into_schemaless_row()?)
     }
@@ -10,9 +10,9 @@ use anyhow::anyhow;
 use crate::{
     api::error::{Result, SpringError},
     expression::{AggrExpr, ValueExpr},
-    pipeline::{AggrAlias, ValueAlias},
+    pipeline::{AggrAlias, ColumnReference, ValueAlias},
     sql_processor::SelectFieldSyntax,
-    stream_engine::{SqlValue, Tuple},
+    stream_engine::{AggregatedAndGroupingValues, SqlValue, Tuple},
 };
 
 /// ExprResolver is to:
@@ -37,45 +37,36 @@ impl ExprResolver {
     ///
     /// `(instance, value/aggr expr labels in select_list)
     pub fn new(select_list: Vec<SelectFieldSyntax>) -> (Self, Vec<ExprLabel>) {
-        let mut label_gen = ExprLabelGenerator::default();
-        let mut value_expressions = HashMap::new();
-        let mut value_aliased_labels = HashMap::new();
-        let mut aggr_expressions = HashMap::new();
-        let mut aggr_aliased_labels = HashMap::new();
+        let mut resolver = Self {
+            label_gen: ExprLabelGenerator::default(),
+            value_expressions: HashMap::new(),
+            value_aliased_labels: HashMap::new(),
+            aggr_expressions: HashMap::new(),
+            aggr_aliased_labels: HashMap::new(),
+            aggr_expression_results: HashMap::new(),
+        };
 
         let expr_labels = select_list
             .into_iter()
             .map(|select_field| match select_field {
                 SelectFieldSyntax::ValueExpr { value_expr, alias } => {
-                    let label = label_gen.next_value();
-                    value_expressions.insert(label, value_expr);
+                    let label = resolver.register_value_expr(value_expr);
                     if let Some(alias) = alias {
-                        value_aliased_labels.insert(alias, label);
+                        resolver.value_aliased_labels.insert(alias, label);
                     }
                     ExprLabel::Value(label)
                 }
                 SelectFieldSyntax::AggrExpr { aggr_expr, alias } => {
-                    let label = label_gen.next_aggr();
-                    aggr_expressions.insert(label, aggr_expr);
+                    let label = resolver.register_aggr_expr(aggr_expr);
                     if let Some(alias) = alias {
-                        aggr_aliased_labels.insert(alias, label);
+                        resolver.aggr_aliased_labels.insert(alias, label);
                     }
                     ExprLabel::Aggr(label)
                 }
             })
             .collect();
 
-        (
-            Self {
-                label_gen,
-                value_expressions,
-                value_aliased_labels,
-                aggr_expressions,
-                aggr_aliased_labels,
-                aggr_expression_results: HashMap::new(),
-            },
-            expr_labels,
-        )
+        (resolver, expr_labels)
     }
 
     /// # Failures
@@ -121,21 +112,41 @@ impl ExprResolver {
             .unwrap_or_else(|| panic!("label {:?} not found", label))
     }
 
-    /// Register value expression which is not in select_list
+    /// Labels of all the registered aggregate expressions, including ones inside value expressions.
+    pub fn aggr_labels(&self) -> Vec<AggrExprLabel> {
+        let mut labels = self.aggr_expressions.keys().copied().collect::<Vec<_>>();
+        labels.sort();
+        labels
+    }
+
+    /// Register value expression which is not in select_list.
+    ///
+    /// Aggregate expressions inside `value_expr` are also registered.
     pub fn register_value_expr(&mut self, value_expr: ValueExpr) -> ValueExprLabel {
+        for aggr_expr in value_expr.aggr_exprs() {
+            self.register_aggr_expr(aggr_expr.clone());
+        }
+
         let label = self.label_gen.next_value();
         self.value_expressions.insert(label, value_expr);
         label
     }
 
-    /// TODO use in HAVING clause
+    /// Register aggregate expression which is not in select_list.
     ///
-    /// Register aggregate expression which is not in select_list
-    #[allow(dead_code)]
+    /// An aggregate expression equal to already registered one gets the existing label.
     pub fn register_aggr_expr(&mut self, aggr_expr: AggrExpr) -> AggrExprLabel {
-        let label = self.label_gen.next_aggr();
-        self.aggr_expressions.insert(label, aggr_expr);
-        label
+        self.find_aggr_label(&aggr_expr).unwrap_or_else(|| {
+            let label = self.label_gen.next_aggr();
+            self.aggr_expressions.insert(label, aggr_expr);
+            label
+        })
+    }
+
+    fn find_aggr_label(&self, aggr_expr: &AggrExpr) -> Option<AggrExprLabel> {
+        self.aggr_expressions
+            .iter()
+            .find_map(|(label, registered)| (registered == aggr_expr).then_some(*label))
     }
 
     /// label -> (internal) value expression + tuple (for ColumnReference) -> SqlValue.
@@ -160,6 +171,73 @@ impl ExprResolver {
         value_expr_ph2.eval()
     }
 
+    /// label -> (internal) value expression + aggregated values (for aggregate expressions inside) -> SqlValue.
+    ///
+    /// A column reference outside of aggregate expressions is resolved from GROUP BY values.
+    ///
+    /// # Panics
+    ///
+    /// -  `label` is not found
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - expression has a column reference neither in GROUP BY clause nor in aggregate expressions.
+    ///   - somehow failed to eval expression.
+    pub fn eval_value_expr_with_aggr(
+        &self,
+        label: ValueExprLabel,
+        aggregated_and_grouping_values: &AggregatedAndGroupingValues,
+    ) -> Result<SqlValue> {
+        let value_expr = self
+            .value_expressions
+            .get(&label)
+            .cloned()
+            .unwrap_or_else(|| panic!("label {:?} not found", label));
+
+        let value_expr_ph2 = value_expr.resolve_aggr_expr(
+            &|aggr_expr| {
+                let aggr_label = self.find_aggr_label(aggr_expr).ok_or_else(|| {
+                    SpringError::Sql(anyhow!(
+                        "aggregate expression not registered: {:?}",
+                        aggr_expr
+                    ))
+                })?;
+                aggregated_and_grouping_values
+                    .get_aggregated_value(&aggr_label)
+                    .cloned()
+            },
+            &|colref| {
+                let group_by_label = self
+                    .find_group_by_label(colref, aggregated_and_grouping_values)
+                    .ok_or_else(|| {
+                        SpringError::Sql(anyhow!(
+                            "column reference `{:?}` must be in GROUP BY clause or used in an aggregate function",
+                            colref
+                        ))
+                    })?;
+                aggregated_and_grouping_values
+                    .get_group_by_value(&group_by_label)
+                    .cloned()
+            },
+        )?;
+        value_expr_ph2.eval()
+    }
+
+    fn find_group_by_label(
+        &self,
+        colref: &ColumnReference,
+        aggregated_and_grouping_values: &AggregatedAndGroupingValues,
+    ) -> Option<ValueExprLabel> {
+        self.value_expressions
+            .iter()
+            .find_map(|(label, registered)| {
+                (matches!(registered, ValueExpr::ColumnReference(c) if c == colref)
+                    && aggregated_and_grouping_values.has_group_by_value(label))
+                .then_some(*label)
+            })
+    }
+
     /// label -> (internal) value expression inside aggr expr + tuple (for ColumnReference) -> SqlValue.
     ///
     /// _inner_ means: AGGR_FUNC(inner_value_expr)
@@ -185,6 +263,7 @@ impl ExprResolver {
 mod tests {
     use crate::{
         expression::ValueExpr,
+        pipeline::AggregateFunctionParameter,
         stream_engine::{time::SpringTimestamp, RowTime},
     };
 
@@ -258,4 +337,61 @@ mod tests {
             unreachable!()
         }
     }
+
+    #[test]
+    fn test_expr_resolver_aggr_in_value_expr() {
+        let max_x = AggrExpr {
+            func: AggregateFunctionParameter::Max,
+            aggregated: ValueExpr::factory_colref("s", "x"),
+        };
+        let min_x = AggrExpr {
+            func: AggregateFunctionParameter::Min,
+            aggregated: ValueExpr::factory_colref("s", "x"),
+        };
+
+        // SELECT MAX(s.x), MAX(s.x) - MIN(s.x) AS range
+        let select_list = vec![
+            SelectFieldSyntax::AggrExpr {
+                aggr_expr: max_x.clone(),
+                alias: None,
+            },
+            SelectFieldSyntax::ValueExpr {
+                value_expr: ValueExpr::factory_sub(
+                    ValueExpr::AggrExpr(Box::new(max_x)),
+                    ValueExpr::AggrExpr(Box::new(min_x)),
+                ),
+                alias: Some(ValueAlias::new("range".to_string())),
+            },
+        ];
+
+        let (resolver, labels_select_list) = ExprResolver::new(select_list);
+
+        if let &[ExprLabel::Aggr(max_label), ExprLabel::Value(range_label)] =
+            &labels_select_list[..]
+        {
+            let aggr_labels = resolver.aggr_labels();
+            assert_eq!(aggr_labels.len(), 2, "MAX(s.x) is registered once");
+            assert_eq!(aggr_labels[0], max_label);
+            let min_label = aggr_labels[1];
+
+            let aggregated = AggregatedAndGroupingValues::new(
+                vec![
+                    (max_label, SqlValue::factory_integer(30)),
+                    (min_label, SqlValue::factory_integer(10)),
+                ],
+                vec![],
+            );
+            assert_eq!(
+                resolver
+                    .eval_value_expr_with_aggr(range_label, &aggregated)
+                    .unwrap(),
+                SqlValue::factory_integer(20)
+            );
+
+            let empty_tuple = Tuple::new(RowTime::EventTime(SpringTimestamp::fx_ts1()), vec![]);
+            assert!(resolver.eval_value_expr(range_label, &empty_tuple).is_err());
+        } else {
+            unreachable!()
+        }
+    }
 }
@@ -3,18 +3,23 @@
 #![doc = include_str!("expression.md")]
 
 mod boolean_expression;
+mod conditional_function;
 mod function_call;
+mod json_function;
+mod math_function;
 mod operator;
+mod string_function;
+mod time_function;
 
 pub use boolean_expression::{BinaryExpr, ComparisonFunction, LogicalFunction, NumericalFunction};
-pub use function_call::FunctionCall;
+pub use function_call::{ExtractField, FunctionCall, MathFunction};
 pub use operator::{BinaryOperator, UnaryOperator};
 
 use anyhow::anyhow;
 
 use crate::{
     api::error::{Result, SpringError},
-    pipeline::{AggregateFunctionParameter, ColumnReference},
+    pipeline::{AggregateFunctionParameter, ColumnReference, SqlType},
     stream_engine::{
         time::{SpringDuration, SpringEventDuration},
         NnSqlValue, SqlCompareResult, SqlValue, Tuple,
@@ -35,10 +40,20 @@ pub enum ValueExpr {
     FunctionCall(FunctionCall<Self>),
 
     ColumnReference(ColumnReference),
+
+    /// Aggregate function call as an operand, e.g. `MAX(x)` in `MAX(x) - MIN(x)`.
+    ///
+    /// Aggregated value is resolved from a window's output instead of a tuple.
+    AggrExpr(Box<AggrExpr>),
 }
 impl ValueExprType for ValueExpr {}
 
 impl ValueExpr {
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - column reference in expression is not found in `tuple`.
+    ///   - expression contains an aggregate function call.
     pub fn resolve_colref(self, tuple: &Tuple) -> Result<ValueExprPh2> {
         match self {
             Self::Constant(value) => Ok(ValueExprPh2::Constant(value)),
@@ -48,90 +63,84 @@ impl ValueExpr {
                 Ok(ValueExprPh2::Constant(value))
             }
 
-            Self::FunctionCall(function_call) => match function_call {
-                FunctionCall::DurationMillis { duration_millis } => {
-                    let duration_millis_ph2 = duration_millis.resolve_colref(tuple)?;
-                    Ok(ValueExprPh2::FunctionCall(FunctionCall::DurationMillis {
-                        duration_millis: Box::new(duration_millis_ph2),
-                    }))
-                }
-                FunctionCall::DurationSecs { duration_secs } => {
-                    let duration_secs_ph2 = duration_secs.resolve_colref(tuple)?;
-                    Ok(ValueExprPh2::FunctionCall(FunctionCall::DurationSecs {
-                        duration_secs: Box::new(duration_secs_ph2),
-                    }))
-                }
-                FunctionCall::FloorTime { target, resolution } => {
-                    let target_ph2 = target.resolve_colref(tuple)?;
-                    let resolution_ph2 = resolution.resolve_colref(tuple)?;
-                    Ok(ValueExprPh2::FunctionCall(FunctionCall::FloorTime {
-                        target: Box::new(target_ph2),
-                        resolution: Box::new(resolution_ph2),
-                    }))
-                }
-            },
+            Self::AggrExpr(aggr_expr) => Err(SpringError::Sql(anyhow!(
+                "aggregate function is not allowed here: {:?}",
+                aggr_expr
+            ))),
+
+            Self::FunctionCall(function_call) => Ok(ValueExprPh2::FunctionCall(
+                function_call.try_map_args(|arg| arg.resolve_colref(tuple))?,
+            )),
             Self::UnaryOperator(op, expr_ph1) => {
                 let expr_ph2 = expr_ph1.resolve_colref(tuple)?;
                 Ok(ValueExprPh2::UnaryOperator(op, Box::new(expr_ph2)))
             }
-            Self::BinaryExpr(bool_expr) => match bool_expr {
-                BinaryExpr::LogicalFunctionVariant(logical_function) => match logical_function {
-                    LogicalFunction::AndVariant { left, right } => {
-                        let left_ph2 = left.resolve_colref(tuple)?;
-                        let right_ph2 = right.resolve_colref(tuple)?;
-                        Ok(ValueExprPh2::BinaryExpr(
-                            BinaryExpr::LogicalFunctionVariant(LogicalFunction::AndVariant {
-                                left: Box::new(left_ph2),
-                                right: Box::new(right_ph2),
-                            }),
-                        ))
-                    }
-                },
-                BinaryExpr::ComparisonFunctionVariant(comparison_function) => {
-                    match comparison_function {
-                        ComparisonFunction::EqualVariant { left, right } => {
-                            let left_ph2 = left.resolve_colref(tuple)?;
-                            let right_ph2 = right.resolve_colref(tuple)?;
-                            Ok(ValueExprPh2::BinaryExpr(
-                                BinaryExpr::ComparisonFunctionVariant(
-                                    ComparisonFunction::EqualVariant {
-                                        left: Box::new(left_ph2),
-                                        right: Box::new(right_ph2),
-                                    },
-                                ),
-                            ))
-                        }
-                    }
-                }
-                BinaryExpr::NumericalFunctionVariant(numerical_function) => {
-                    match numerical_function {
-                        NumericalFunction::AddVariant { left, right } => {
-                            let left_ph2 = left.resolve_colref(tuple)?;
-                            let right_ph2 = right.resolve_colref(tuple)?;
-                            Ok(ValueExprPh2::BinaryExpr(
-                                BinaryExpr::NumericalFunctionVariant(
-                                    NumericalFunction::AddVariant {
-                                        left: Box::new(left_ph2),
-                                        right: Box::new(right_ph2),
-                                    },
-                                ),
-                            ))
-                        }
-                        NumericalFunction::MulVariant { left, right } => {
-                            let left_ph2 = left.resolve_colref(tuple)?;
-                            let right_ph2 = right.resolve_colref(tuple)?;
-                            Ok(ValueExprPh2::BinaryExpr(
-                                BinaryExpr::NumericalFunctionVariant(
-                                    NumericalFunction::MulVariant {
-                                        left: Box::new(left_ph2),
-                                        right: Box::new(right_ph2),
-                                    },
-                                ),
-                            ))
-                        }
-                    }
-                }
-            },
+            Self::BinaryExpr(binary_expr) => Ok(ValueExprPh2::BinaryExpr(
+                binary_expr.try_map_operands(|operand| operand.resolve_colref(tuple))?,
+            )),
+        }
+    }
+
+    /// Resolves aggregate function calls by `aggregated_value` and column references outside of them by `grouping_value`.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `aggregated_value` or `grouping_value` fails.
+    pub fn resolve_aggr_expr<F, G>(
+        self,
+        aggregated_value: &F,
+        grouping_value: &G,
+    ) -> Result<ValueExprPh2>
+    where
+        F: Fn(&AggrExpr) -> Result<SqlValue>,
+        G: Fn(&ColumnReference) -> Result<SqlValue>,
+    {
+        match self {
+            Self::Constant(value) => Ok(ValueExprPh2::Constant(value)),
+
+            Self::ColumnReference(colref) => {
+                let value = grouping_value(&colref)?;
+                Ok(ValueExprPh2::Constant(value))
+            }
+
+            Self::AggrExpr(aggr_expr) => {
+                let value = aggregated_value(&aggr_expr)?;
+                Ok(ValueExprPh2::Constant(value))
+            }
+
+            Self::FunctionCall(function_call) => Ok(ValueExprPh2::FunctionCall(
+                function_call
+                    .try_map_args(|arg| arg.resolve_aggr_expr(aggregated_value, grouping_value))?,
+            )),
+            Self::UnaryOperator(op, expr_ph1) => {
+                let expr_ph2 = expr_ph1.resolve_aggr_expr(aggregated_value, grouping_value)?;
+                Ok(ValueExprPh2::UnaryOperator(op, Box::new(expr_ph2)))
+            }
+            Self::BinaryExpr(binary_expr) => {
+                Ok(ValueExprPh2::BinaryExpr(binary_expr.try_map_operands(
+                    |operand| operand.resolve_aggr_expr(aggregated_value, grouping_value),
+                )?))
+            }
+        }
+    }
+
+    /// Aggregate function calls in this expression (not including ones nested in another aggregate function call).
+    pub fn aggr_exprs(&self) -> Vec<&AggrExpr> {
+        match self {
+            Self::Constant(_) | Self::ColumnReference(_) => vec![],
+            Self::AggrExpr(aggr_expr) => vec![aggr_expr.as_ref()],
+            Self::UnaryOperator(_, expr) => expr.aggr_exprs(),
+            Self::FunctionCall(function_call) => function_call
+                .args()
+                .into_iter()
+                .flat_map(Self::aggr_exprs)
+                .collect(),
+            Self::BinaryExpr(binary_expr) => binary_expr
+                .operands()
+                .into_iter()
+                .flat_map(Self::aggr_exprs)
+                .collect(),
         }
     }
 }
@@ -159,34 +168,26 @@ impl ValueExprPh2 {
                     (UnaryOperator::Minus, SqlValue::NotNull(nn_sql_value)) => {
                         Ok(SqlValue::NotNull(nn_sql_value.negate()?))
                     }
+                    (UnaryOperator::Not, child_sql_value) => Ok(child_sql_value
+                        .to_nullable_bool()?
+                        .map_or(SqlValue::Null, |b| {
+                            SqlValue::NotNull(NnSqlValue::Boolean(!b))
+                        })),
+                    (UnaryOperator::IsNull, child_sql_value) => Ok(SqlValue::NotNull(
+                        NnSqlValue::Boolean(matches!(child_sql_value, SqlValue::Null)),
+                    )),
+                    (UnaryOperator::IsNotNull, child_sql_value) => Ok(SqlValue::NotNull(
+                        NnSqlValue::Boolean(!matches!(child_sql_value, SqlValue::Null)),
+                    )),
                 }
             }
             Self::BinaryExpr(bool_expr) => match bool_expr {
                 BinaryExpr::ComparisonFunctionVariant(comparison_function) => {
-                    match comparison_function {
-                        ComparisonFunction::EqualVariant { left, right } => {
-                            let left_sql_value = left.eval()?;
-                            let right_sql_value = right.eval()?;
-                            left_sql_value
-                                .sql_compare(&right_sql_value)
-                                .map(|sql_compare_result| {
-                                    SqlValue::NotNull(NnSqlValue::Boolean(matches!(
-                                        sql_compare_result,
-                                        SqlCompareResult::Eq
-                                    )))
-                                })
-                        }
-                    }
+                    Self::eval_comparison_function(comparison_function)
+                }
+                BinaryExpr::LogicalFunctionVariant(logical_function) => {
+                    Self::eval_logical_function(logical_function)
                 }
-                BinaryExpr::LogicalFunctionVariant(logical_function) => match logical_function {
-                    LogicalFunction::AndVariant { left, right } => {
-                        let left_sql_value = left.eval()?;
-                        let right_sql_value = right.eval()?;
-
-                        let b = left_sql_value.to_bool()? && right_sql_value.to_bool()?;
-                        Ok(SqlValue::NotNull(NnSqlValue::Boolean(b)))
-                    }
-                },
                 BinaryExpr::NumericalFunctionVariant(numerical_function) => {
                     Self::eval_numerical_function(numerical_function)
                 }
@@ -194,6 +195,65 @@ impl ValueExprPh2 {
             Self::FunctionCall(function_call) => Self::eval_function_call(function_call),
         }
     }
+    /// Evaluates AND / OR in SQL three-valued logic (NULL as UNKNOWN).
+    fn eval_logical_function(logical_function: LogicalFunction<Self>) -> Result<SqlValue> {
+        let b = match logical_function {
+            LogicalFunction::AndVariant { left, right } => {
+                let left_b = left.eval()?.to_nullable_bool()?;
+                let right_b = right.eval()?.to_nullable_bool()?;
+                match (left_b, right_b) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                }
+            }
+            LogicalFunction::OrVariant { left, right } => {
+                let left_b = left.eval()?.to_nullable_bool()?;
+                let right_b = right.eval()?.to_nullable_bool()?;
+                match (left_b, right_b) {
+                    (Some(true), _) | (_, Some(true)) => Some(true),
+                    (Some(false), Some(false)) => Some(false),
+                    _ => None,
+                }
+            }
+        };
+        Ok(b.map_or(SqlValue::Null, |b| {
+            SqlValue::NotNull(NnSqlValue::Boolean(b))
+        }))
+    }
+
+    fn eval_comparison_function(comparison_function: ComparisonFunction<Self>) -> Result<SqlValue> {
+        let (left, right, pred): (_, _, fn(SqlCompareResult) -> bool) = match comparison_function {
+            ComparisonFunction::EqualVariant { left, right } => {
+                (left, right, |r| matches!(r, SqlCompareResult::Eq))
+            }
+            ComparisonFunction::NotEqualVariant { left, right } => {
+                (left, right, |r| !matches!(r, SqlCompareResult::Eq))
+            }
+            ComparisonFunction::LessThanVariant { left, right } => {
+                (left, right, |r| matches!(r, SqlCompareResult::LessThan))
+            }
+            ComparisonFunction::LessThanOrEqualVariant { left, right } => (left, right, |r| {
+                matches!(r, SqlCompareResult::LessThan | SqlCompareResult::Eq)
+            }),
+            ComparisonFunction::GreaterThanVariant { left, right } => {
+                (left, right, |r| matches!(r, SqlCompareResult::GreaterThan))
+            }
+            ComparisonFunction::GreaterThanOrEqualVariant { left, right } => (left, right, |r| {
+                matches!(r, SqlCompareResult::GreaterThan | SqlCompareResult::Eq)
+            }),
+        };
+
+        let left_sql_value = left.eval()?;
+        let right_sql_value = right.eval()?;
+        match left_sql_value.sql_compare(&right_sql_value)? {
+            SqlCompareResult::Null => Ok(SqlValue::Null),
+            sql_compare_result => Ok(SqlValue::NotNull(NnSqlValue::Boolean(pred(
+                sql_compare_result,
+            )))),
+        }
+    }
+
     fn eval_numerical_function(numerical_function: NumericalFunction<Self>) -> Result<SqlValue> {
         match numerical_function {
             NumericalFunction::AddVariant { left, right } => {
@@ -201,11 +261,26 @@ impl ValueExprPh2 {
                 let right_sql_value = right.eval()?;
                 left_sql_value + right_sql_value
             }
+            NumericalFunction::SubVariant { left, right } => {
+                let left_sql_value = left.eval()?;
+                let right_sql_value = right.eval()?;
+                left_sql_value - right_sql_value
+            }
             NumericalFunction::MulVariant { left, right } => {
                 let left_sql_value = left.eval()?;
                 let right_sql_value = right.eval()?;
                 left_sql_value * right_sql_value
             }
+            NumericalFunction::DivVariant { left, right } => {
+                let left_sql_value = left.eval()?;
+                let right_sql_value = right.eval()?;
+                left_sql_value / right_sql_value
+            }
+            NumericalFunction::ModVariant { left, right } => {
+                let left_sql_value = left.eval()?;
+                let right_sql_value = right.eval()?;
+                left_sql_value % right_sql_value
+            }
         }
     }
 
@@ -214,32 +289,67 @@ impl ValueExprPh2 {
             FunctionCall::FloorTime { target, resolution } => {
                 Self::eval_function_floor_time(*target, *resolution)
             }
+            FunctionCall::CeilTime { target, resolution } => {
+                Self::eval_function_ceil_time(*target, *resolution)
+            }
+            FunctionCall::Extract { field, target } => Self::eval_function_extract(field, *target),
+            FunctionCall::ToEpochMillis { target } => Self::eval_function_to_epoch_millis(*target),
+            FunctionCall::FromEpochMillis { epoch_millis } => {
+                Self::eval_function_from_epoch_millis(*epoch_millis)
+            }
+            FunctionCall::FormatTimestamp { target, format } => {
+                Self::eval_function_format_timestamp(*target, *format)
+            }
+            FunctionCall::ParseTimestamp { target, format } => {
+                Self::eval_function_parse_timestamp(*target, *format)
+            }
             FunctionCall::DurationMillis { duration_millis } => {
                 Self::eval_function_duration_millis(*duration_millis)
             }
             FunctionCall::DurationSecs { duration_secs } => {
                 Self::eval_function_duration_secs(*duration_secs)
             }
-        }
-    }
-
-    fn eval_function_floor_time(target: Self, resolution: Self) -> Result<SqlValue> {
-        let target_value = target.eval()?;
-        let resolution_value = resolution.eval()?;
-
-        match (&target_value, &resolution_value) {
-            (
-                SqlValue::NotNull(NnSqlValue::Timestamp(ts)),
-                SqlValue::NotNull(NnSqlValue::Duration(resolution)),
-            ) => {
-                let ts_floor = ts.floor(resolution.to_duration())?;
-                Ok(SqlValue::NotNull(NnSqlValue::Timestamp(ts_floor)))
+            FunctionCall::Concat { left, right } => Self::eval_function_concat(*left, *right),
+            FunctionCall::Like { target, pattern } => Self::eval_function_like(*target, *pattern),
+            FunctionCall::Upper { target } => Self::eval_function_upper(*target),
+            FunctionCall::Lower { target } => Self::eval_function_lower(*target),
+            FunctionCall::Length { target } => Self::eval_function_length(*target),
+            FunctionCall::Substring {
+                target,
+                start,
+                length,
+            } => Self::eval_function_substring(*target, *start, length.map(|length| *length)),
+            FunctionCall::Trim { target } => Self::eval_function_trim(*target),
+            FunctionCall::Replace { target, from, to } => {
+                Self::eval_function_replace(*target, *from, *to)
+            }
+            FunctionCall::SplitPart {
+                target,
+                delimiter,
+                index,
+            } => Self::eval_function_split_part(*target, *delimiter, *index),
+            FunctionCall::JsonExtract { target, path } => {
+                Self::eval_function_json_extract(*target, *path)
+            }
+            FunctionCall::Math { func, target } => Self::eval_function_math(func, *target),
+            FunctionCall::Round { target, digits } => {
+                Self::eval_function_round(*target, digits.map(|digits| *digits))
+            }
+            FunctionCall::Power { base, exponent } => Self::eval_function_power(*base, *exponent),
+            FunctionCall::Atan2 { y, x } => Self::eval_function_atan2(*y, *x),
+            FunctionCall::Greatest { left, right } => Self::eval_function_greatest(*left, *right),
+            FunctionCall::Least { left, right } => Self::eval_function_least(*left, *right),
+            FunctionCall::If {
+                condition,
+                then_value,
+                else_value,
+            } => Self::eval_function_if(*condition, *then_value, *else_value),
+            FunctionCall::Coalesce { left, right } => Self::eval_function_coalesce(*left, *right),
+            FunctionCall::NullIf { left, right } => Self::eval_function_null_if(*left, *right),
+            FunctionCall::Cast { target, sql_type } => Self::eval_function_cast(*target, &sql_type),
+            FunctionCall::TryCast { target, sql_type } => {
+                Self::eval_function_try_cast(*target, &sql_type)
             }
-            _ => Err(SpringError::Sql(anyhow!(
-                "invalid parameter to FLOOR_TIME: `({}, {})`",
-                target_value,
-                resolution_value
-            ))),
         }
     }
 
@@ -269,10 +379,75 @@ impl ValueExprPh2 {
             )))
         }
     }
+
+    /// Evaluates `args` and applies `f` unless any of them is NULL (evaluated to NULL then).
+    fn eval_strict_function<F>(args: Vec<Self>, f: F) -> Result<SqlValue>
+    where
+        F: FnOnce(Vec<NnSqlValue>) -> Result<NnSqlValue>,
+    {
+        let values = args
+            .into_iter()
+            .map(Self::eval)
+            .collect::<Result<Vec<_>>>()?;
+
+        let nn_values = values
+            .into_iter()
+            .map(|value| match value {
+                SqlValue::Null => None,
+                SqlValue::NotNull(nn_value) => Some(nn_value),
+            })
+            .collect::<Option<Vec<_>>>();
+
+        match nn_values {
+            None => Ok(SqlValue::Null),
+            Some(nn_values) => f(nn_values).map(SqlValue::NotNull),
+        }
+    }
+
+    fn eval_function_cast(target: Self, sql_type: &SqlType) -> Result<SqlValue> {
+        match target.eval()? {
+            SqlValue::Null => Ok(SqlValue::Null),
+            SqlValue::NotNull(nn_value) => nn_value.try_cast(sql_type).map(SqlValue::NotNull),
+        }
+    }
+    /// Only the conversion failure turns into NULL. Errors in evaluating `target` are propagated.
+    fn eval_function_try_cast(target: Self, sql_type: &SqlType) -> Result<SqlValue> {
+        match target.eval()? {
+            SqlValue::Null => Ok(SqlValue::Null),
+            SqlValue::NotNull(nn_value) => Ok(nn_value
+                .try_cast(sql_type)
+                .map_or(SqlValue::Null, SqlValue::NotNull)),
+        }
+    }
+}
+
+fn text_arg<'a>(function_name: &str, value: &'a NnSqlValue) -> Result<&'a str> {
+    match value {
+        NnSqlValue::Text(s) => Ok(s),
+        _ => Err(SpringError::Sql(anyhow!(
+            "{} takes TEXT but got {:?}",
+            function_name,
+            value.sql_type()
+        ))),
+    }
+}
+
+fn integer_arg(function_name: &str, value: &NnSqlValue) -> Result<i64> {
+    match value {
+        NnSqlValue::SmallInt(_)
+        | NnSqlValue::Integer(_)
+        | NnSqlValue::BigInt(_)
+        | NnSqlValue::UnsignedInteger(_) => value.unpack::<i64>(),
+        _ => Err(SpringError::Sql(anyhow!(
+            "{} takes an integer but got {:?}",
+            function_name,
+            value.sql_type()
+        ))),
+    }
 }
 
 /// Aggregate expression.
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Hash, Debug)]
 pub struct AggrExpr {
     pub func: AggregateFunctionParameter,
     pub aggregated: ValueExpr,
@@ -8,7 +8,7 @@ pub use comparison_function::ComparisonFunction;
 pub use logical_function::LogicalFunction;
 pub use numerical_function::NumericalFunction;
 
-use crate::expression::ValueExprType;
+use crate::{api::error::Result, expression::ValueExprType};
 
 /// Boolean expression.
 #[allow(clippy::enum_variant_names)]
@@ -25,3 +25,36 @@ where
 
     NumericalFunctionVariant(NumericalFunction<E>),
 }
+
+impl<E> BinaryExpr<E>
+where
+    E: ValueExprType,
+{
+    /// Converts each operand with `f`, keeping the operation.
+    pub fn try_map_operands<E2, F>(self, f: F) -> Result<BinaryExpr<E2>>
+    where
+        E2: ValueExprType,
+        F: FnMut(E) -> Result<E2>,
+    {
+        let mapped = match self {
+            Self::LogicalFunctionVariant(function) => {
+                BinaryExpr::LogicalFunctionVariant(function.try_map_operands(f)?)
+            }
+            Self::ComparisonFunctionVariant(function) => {
+                BinaryExpr::ComparisonFunctionVariant(function.try_map_operands(f)?)
+            }
+            Self::NumericalFunctionVariant(function) => {
+                BinaryExpr::NumericalFunctionVariant(function.try_map_operands(f)?)
+            }
+        };
+        Ok(mapped)
+    }
+
+    pub fn operands(&self) -> [&E; 2] {
+        match self {
+            Self::LogicalFunctionVariant(function) => function.operands(),
+            Self::ComparisonFunctionVariant(function) => function.operands(),
+            Self::NumericalFunctionVariant(function) => function.operands(),
+        }
+    }
+}
@@ -1,8 +1,9 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::expression::ValueExprType;
+use crate::{api::error::Result, expression::ValueExprType};
 
 /// Comparison function and its operands
+#[allow(clippy::enum_variant_names)]
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ComparisonFunction<E>
 where
@@ -15,4 +16,95 @@ where
         /// Right operand
         right: Box<E>,
     },
+    /// `<>` or `!=` operation
+    NotEqualVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+    /// `<` operation
+    LessThanVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+    /// `<=` operation
+    LessThanOrEqualVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+    /// `>` operation
+    GreaterThanVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+    /// `>=` operation
+    GreaterThanOrEqualVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+}
+
+impl<E> ComparisonFunction<E>
+where
+    E: ValueExprType,
+{
+    /// Converts each operand with `f`, keeping the operation.
+    pub fn try_map_operands<E2, F>(self, mut f: F) -> Result<ComparisonFunction<E2>>
+    where
+        E2: ValueExprType,
+        F: FnMut(E) -> Result<E2>,
+    {
+        let mut f = |operand: Box<E>| f(*operand).map(Box::new);
+        let mapped = match self {
+            Self::EqualVariant { left, right } => ComparisonFunction::EqualVariant {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::NotEqualVariant { left, right } => ComparisonFunction::NotEqualVariant {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::LessThanVariant { left, right } => ComparisonFunction::LessThanVariant {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::LessThanOrEqualVariant { left, right } => {
+                ComparisonFunction::LessThanOrEqualVariant {
+                    left: f(left)?,
+                    right: f(right)?,
+                }
+            }
+            Self::GreaterThanVariant { left, right } => ComparisonFunction::GreaterThanVariant {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::GreaterThanOrEqualVariant { left, right } => {
+                ComparisonFunction::GreaterThanOrEqualVariant {
+                    left: f(left)?,
+                    right: f(right)?,
+                }
+            }
+        };
+        Ok(mapped)
+    }
+
+    pub fn operands(&self) -> [&E; 2] {
+        match self {
+            Self::EqualVariant { left, right }
+            | Self::NotEqualVariant { left, right }
+            | Self::LessThanVariant { left, right }
+            | Self::LessThanOrEqualVariant { left, right }
+            | Self::GreaterThanVariant { left, right }
+            | Self::GreaterThanOrEqualVariant { left, right } => [left, right],
+        }
+    }
 }
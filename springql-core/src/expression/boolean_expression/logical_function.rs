@@ -1,8 +1,8 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::expression::ValueExprType;
+use crate::{api::error::Result, expression::ValueExprType};
 
-/// AND, OR, NOT
+/// AND, OR (NOT is a `UnaryOperator`)
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum LogicalFunction<E>
 where
@@ -15,4 +15,42 @@ where
         /// Right operand
         right: Box<E>,
     },
+    /// `OR` operation
+    OrVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
+}
+
+impl<E> LogicalFunction<E>
+where
+    E: ValueExprType,
+{
+    /// Converts each operand with `f`, keeping the operation.
+    pub fn try_map_operands<E2, F>(self, mut f: F) -> Result<LogicalFunction<E2>>
+    where
+        E2: ValueExprType,
+        F: FnMut(E) -> Result<E2>,
+    {
+        let mut f = |operand: Box<E>| f(*operand).map(Box::new);
+        let mapped = match self {
+            Self::AndVariant { left, right } => LogicalFunction::AndVariant {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::OrVariant { left, right } => LogicalFunction::OrVariant {
+                left: f(left)?,
+                right: f(right)?,
+            },
+        };
+        Ok(mapped)
+    }
+
+    pub fn operands(&self) -> [&E; 2] {
+        match self {
+            Self::AndVariant { left, right } | Self::OrVariant { left, right } => [left, right],
+        }
+    }
 }
@@ -1,7 +1,8 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::expression::ValueExprType;
+use crate::{api::error::Result, expression::ValueExprType};
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum NumericalFunction<E>
 where
@@ -10,6 +11,62 @@ where
     /// `+` operation
     AddVariant { left: Box<E>, right: Box<E> },
 
+    /// `-` operation
+    SubVariant { left: Box<E>, right: Box<E> },
+
     /// `*` operation
     MulVariant { left: Box<E>, right: Box<E> },
+
+    /// `/` operation
+    DivVariant { left: Box<E>, right: Box<E> },
+
+    /// `%` operation
+    ModVariant { left: Box<E>, right: Box<E> },
+}
+
+impl<E> NumericalFunction<E>
+where
+    E: ValueExprType,
+{
+    /// Converts each operand with `f`, keeping the operation.
+    pub fn try_map_operands<E2, F>(self, mut f: F) -> Result<NumericalFunction<E2>>
+    where
+        E2: ValueExprType,
+        F: FnMut(E) -> Result<E2>,
+    {
+        let mut f = |operand: Box<E>| f(*operand).map(Box::new);
+        let mapped = match self {
+            Self::AddVariant { left, right } => NumericalFunction::AddVariant {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::SubVariant { left, right } => NumericalFunction::SubVariant {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::MulVariant { left, right } => NumericalFunction::MulVariant {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::DivVariant { left, right } => NumericalFunction::DivVariant {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::ModVariant { left, right } => NumericalFunction::ModVariant {
+                left: f(left)?,
+                right: f(right)?,
+            },
+        };
+        Ok(mapped)
+    }
+
+    pub fn operands(&self) -> [&E; 2] {
+        match self {
+            Self::AddVariant { left, right }
+            | Self::SubVariant { left, right }
+            | Self::MulVariant { left, right }
+            | Self::DivVariant { left, right }
+            | Self::ModVariant { left, right } => [left, right],
+        }
+    }
 }
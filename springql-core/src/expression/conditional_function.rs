@@ -0,0 +1,346 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Evaluation of conditional expressions (`CASE`, `IF`, `COALESCE` and `NULLIF`).
+//!
+//! The result type of `CASE`, `IF` and `COALESCE` is decided at plan time ([ValueExpr::unify_branch_types]):
+//! branches are cast into their common type, so that e.g. `CASE WHEN c THEN 1 ELSE 0.5 END` is always a FLOAT.
+//! Only the branch taken is evaluated.
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    expression::{AggrExpr, BinaryExpr, FunctionCall, UnaryOperator, ValueExpr, ValueExprPh2},
+    pipeline::{ColumnReference, NumericComparableType, SqlType},
+    stream_engine::{SqlCompareResult, SqlValue},
+};
+
+impl ValueExpr {
+    /// Casts the branches of conditional expressions (`CASE`, `IF` and `COALESCE`) into their common type.
+    ///
+    /// Types of branches are inferred without evaluation, with `column_type` for column references.
+    /// Branches whose types are unknown at plan time (e.g. results of most functions) are left as they are.
+    ///
+    /// - Integer types of the same loose type are unified into the widest one.
+    /// - Integers and FLOAT are unified into FLOAT.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - branches of a conditional expression have incompatible types.
+    pub fn unify_branch_types<F>(self, column_type: &F) -> Result<Self>
+    where
+        F: Fn(&ColumnReference) -> Option<SqlType>,
+    {
+        match self {
+            Self::Constant(_) | Self::ColumnReference(_) => Ok(self),
+            Self::UnaryOperator(op, expr) => Ok(Self::UnaryOperator(
+                op,
+                Box::new(expr.unify_branch_types(column_type)?),
+            )),
+            Self::BinaryExpr(binary_expr) => binary_expr
+                .try_map_operands(|expr| expr.unify_branch_types(column_type))
+                .map(Self::BinaryExpr),
+            Self::AggrExpr(aggr_expr) => {
+                let AggrExpr { func, aggregated } = *aggr_expr;
+                Ok(Self::AggrExpr(Box::new(AggrExpr {
+                    func,
+                    aggregated: aggregated.unify_branch_types(column_type)?,
+                })))
+            }
+            Self::FunctionCall(function_call) => {
+                let function_call =
+                    function_call.try_map_args(|arg| arg.unify_branch_types(column_type))?;
+                let function_call = match function_call {
+                    FunctionCall::If {
+                        condition,
+                        then_value,
+                        else_value,
+                    } => {
+                        let [then_value, else_value] =
+                            Self::cast_branches([*then_value, *else_value], column_type)?;
+                        FunctionCall::If {
+                            condition,
+                            then_value: Box::new(then_value),
+                            else_value: Box::new(else_value),
+                        }
+                    }
+                    FunctionCall::Coalesce { left, right } => {
+                        let [left, right] = Self::cast_branches([*left, *right], column_type)?;
+                        FunctionCall::Coalesce {
+                            left: Box::new(left),
+                            right: Box::new(right),
+                        }
+                    }
+                    function_call => function_call,
+                };
+                Ok(Self::FunctionCall(function_call))
+            }
+        }
+    }
+
+    fn cast_branches<F>(branches: [Self; 2], column_type: &F) -> Result<[Self; 2]>
+    where
+        F: Fn(&ColumnReference) -> Option<SqlType>,
+    {
+        let branch_types = branches
+            .iter()
+            .map(|branch| branch.plan_time_type(column_type))
+            .collect::<Vec<_>>();
+        let common_type = match common_type(branch_types.iter().flatten().collect())? {
+            Some(common_type) => common_type,
+            None => return Ok(branches),
+        };
+
+        let mut casted = branches
+            .into_iter()
+            .zip(branch_types)
+            .map(|(branch, t)| match t {
+                Some(t) if t != common_type => Self::FunctionCall(FunctionCall::Cast {
+                    target: Box::new(branch),
+                    sql_type: common_type.clone(),
+                }),
+                _ => branch,
+            });
+        Ok([casted.next().unwrap(), casted.next().unwrap()])
+    }
+
+    /// Type of the value this expression is evaluated into, as far as it is known without evaluation.
+    fn plan_time_type<F>(&self, column_type: &F) -> Option<SqlType>
+    where
+        F: Fn(&ColumnReference) -> Option<SqlType>,
+    {
+        match self {
+            Self::Constant(SqlValue::NotNull(v)) => Some(v.sql_type()),
+            Self::Constant(SqlValue::Null) | Self::AggrExpr(_) => None,
+            Self::ColumnReference(colref) => column_type(colref),
+            Self::UnaryOperator(UnaryOperator::Minus, expr) => expr.plan_time_type(column_type),
+            Self::UnaryOperator(_, _) => Some(SqlType::boolean()),
+            Self::BinaryExpr(
+                BinaryExpr::LogicalFunctionVariant(_) | BinaryExpr::ComparisonFunctionVariant(_),
+            ) => Some(SqlType::boolean()),
+            Self::BinaryExpr(binary_expr @ BinaryExpr::NumericalFunctionVariant(_)) => {
+                let [left, right] = binary_expr.operands();
+                match (
+                    left.plan_time_type(column_type)?,
+                    right.plan_time_type(column_type)?,
+                ) {
+                    (SqlType::NumericComparable(left), SqlType::NumericComparable(right)) => {
+                        match (left, right) {
+                            (NumericComparableType::F32Loose(_), _)
+                            | (_, NumericComparableType::F32Loose(_)) => Some(SqlType::float()),
+                            (
+                                NumericComparableType::I64Loose(_),
+                                NumericComparableType::I64Loose(_),
+                            ) => Some(SqlType::big_int()),
+                            (
+                                NumericComparableType::U64Loose(_),
+                                NumericComparableType::U64Loose(_),
+                            ) => Some(SqlType::unsigned_big_int()),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            Self::FunctionCall(
+                FunctionCall::Cast { sql_type, .. } | FunctionCall::TryCast { sql_type, .. },
+            ) => Some(sql_type.clone()),
+            // branches are already unified
+            Self::FunctionCall(
+                FunctionCall::If {
+                    then_value: left,
+                    else_value: right,
+                    ..
+                }
+                | FunctionCall::Coalesce { left, right },
+            ) => left
+                .plan_time_type(column_type)
+                .or_else(|| right.plan_time_type(column_type)),
+            Self::FunctionCall(_) => None,
+        }
+    }
+}
+
+/// Common type of `types`. None if `types` is empty.
+fn common_type(types: Vec<&SqlType>) -> Result<Option<SqlType>> {
+    match types.as_slice() {
+        [] => return Ok(None),
+        [t, ..] if types.iter().all(|other| other == t) => return Ok(Some((*t).clone())),
+        _ => {}
+    }
+
+    let numeric_types = types
+        .iter()
+        .map(|t| match t {
+            SqlType::NumericComparable(n) => Some(n),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| {
+            SpringError::Sql(anyhow!(
+                "branches of conditional expression have incompatible types: {:?}",
+                types
+            ))
+        })?;
+
+    let common_type = if numeric_types
+        .iter()
+        .any(|n| matches!(n, NumericComparableType::F32Loose(_)))
+    {
+        SqlType::float()
+    } else if numeric_types
+        .iter()
+        .all(|n| matches!(n, NumericComparableType::U64Loose(_)))
+    {
+        SqlType::unsigned_big_int()
+    } else {
+        SqlType::big_int()
+    };
+    Ok(Some(common_type))
+}
+
+impl ValueExprPh2 {
+    /// NULL condition is treated as FALSE.
+    pub(super) fn eval_function_if(
+        condition: Self,
+        then_value: Self,
+        else_value: Self,
+    ) -> Result<SqlValue> {
+        if condition.eval()?.to_nullable_bool()?.unwrap_or(false) {
+            then_value.eval()
+        } else {
+            else_value.eval()
+        }
+    }
+
+    pub(super) fn eval_function_coalesce(left: Self, right: Self) -> Result<SqlValue> {
+        match left.eval()? {
+            SqlValue::Null => right.eval(),
+            left_value => Ok(left_value),
+        }
+    }
+
+    pub(super) fn eval_function_null_if(left: Self, right: Self) -> Result<SqlValue> {
+        match (left.eval()?, right.eval()?) {
+            (SqlValue::NotNull(left), SqlValue::NotNull(right))
+                if left.sql_compare(&right)? == SqlCompareResult::Eq =>
+            {
+                Ok(SqlValue::Null)
+            }
+            (left, _) => Ok(left),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ordered_float::OrderedFloat;
+
+    use super::*;
+    use crate::{
+        pipeline::{ColumnName, StreamName},
+        stream_engine::NnSqlValue,
+    };
+
+    fn constant(v: NnSqlValue) -> ValueExprPh2 {
+        ValueExprPh2::Constant(SqlValue::NotNull(v))
+    }
+    fn null() -> ValueExprPh2 {
+        ValueExprPh2::Constant(SqlValue::Null)
+    }
+
+    fn if_expr(condition: ValueExpr, then_value: ValueExpr, else_value: ValueExpr) -> ValueExpr {
+        ValueExpr::FunctionCall(FunctionCall::If {
+            condition: Box::new(condition),
+            then_value: Box::new(then_value),
+            else_value: Box::new(else_value),
+        })
+    }
+    fn cast(target: ValueExpr, sql_type: SqlType) -> ValueExpr {
+        ValueExpr::FunctionCall(FunctionCall::Cast {
+            target: Box::new(target),
+            sql_type,
+        })
+    }
+
+    /// `SqlValue::Null` is not equal to itself.
+    fn assert_eq_debug(left: ValueExpr, right: ValueExpr) {
+        assert_eq!(format!("{:?}", left), format!("{:?}", right));
+    }
+
+    #[test]
+    fn test_unify_branch_types() -> Result<()> {
+        let c = || ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::Boolean(true)));
+        let small_int = || ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::SmallInt(1)));
+        let big_int = || ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::BigInt(2)));
+        let float = || ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(0.5))));
+        let null = || ValueExpr::Constant(SqlValue::Null);
+
+        let colref = ColumnReference::Column {
+            stream_name: StreamName::new("s".to_string()),
+            column_name: ColumnName::new("c".to_string()),
+        };
+        let column_type = |r: &ColumnReference| (r == &colref).then(SqlType::integer);
+
+        assert_eq_debug(
+            if_expr(c(), small_int(), float()).unify_branch_types(&column_type)?,
+            if_expr(c(), cast(small_int(), SqlType::float()), float()),
+        );
+
+        // NULL does not affect the common type
+        assert_eq_debug(
+            if_expr(c(), big_int(), null()).unify_branch_types(&column_type)?,
+            if_expr(c(), big_int(), null()),
+        );
+
+        // nested branches
+        assert_eq_debug(
+            if_expr(c(), small_int(), if_expr(c(), big_int(), null()))
+                .unify_branch_types(&column_type)?,
+            if_expr(
+                c(),
+                cast(small_int(), SqlType::big_int()),
+                if_expr(c(), big_int(), null()),
+            ),
+        );
+
+        // column reference
+        assert_eq_debug(
+            if_expr(c(), ValueExpr::ColumnReference(colref.clone()), float())
+                .unify_branch_types(&column_type)?,
+            if_expr(
+                c(),
+                cast(ValueExpr::ColumnReference(colref.clone()), SqlType::float()),
+                float(),
+            ),
+        );
+
+        assert!(matches!(
+            if_expr(
+                c(),
+                small_int(),
+                ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::Text("a".to_string())))
+            )
+            .unify_branch_types(&column_type),
+            Err(SpringError::Sql(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_coalesce_null_if() -> Result<()> {
+        let v = ValueExprPh2::eval_function_coalesce(null(), constant(NnSqlValue::Integer(1)))?;
+        assert_eq!(v.unwrap(), NnSqlValue::Integer(1));
+
+        let v = ValueExprPh2::eval_function_null_if(
+            constant(NnSqlValue::Integer(1)),
+            constant(NnSqlValue::BigInt(1)),
+        )?;
+        assert!(matches!(v, SqlValue::Null));
+
+        let v = ValueExprPh2::eval_function_null_if(constant(NnSqlValue::Integer(1)), null())?;
+        assert_eq!(v.unwrap(), NnSqlValue::Integer(1));
+        Ok(())
+    }
+}
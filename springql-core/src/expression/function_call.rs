@@ -1,6 +1,6 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::expression::ValueExprType;
+use crate::{api::error::Result, expression::ValueExprType, pipeline::SqlType};
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum FunctionCall<E>
@@ -21,4 +21,391 @@ where
     /// FLOOR_TIME("2020-01-01 01:11:11.000000000", DURATION_SECS(10 * 60)) -> "2020-01-01 01:10:00.000000000"
     /// ```
     FloorTime { target: Box<E>, resolution: Box<E> },
+
+    /// ```text
+    /// CEIL_TIME("2020-01-01 01:11:11.000000000", DURATION_SECS(10 * 60)) -> "2020-01-01 01:20:00.000000000"
+    /// ```
+    CeilTime { target: Box<E>, resolution: Box<E> },
+
+    /// ```text
+    /// EXTRACT(HOUR FROM "2020-01-01 01:11:11.000000000") -> 1
+    /// ```
+    Extract { field: ExtractField, target: Box<E> },
+
+    /// ```text
+    /// TO_EPOCH_MILLIS("2020-01-01 00:00:01.000000000") -> 1577836801000
+    /// ```
+    ToEpochMillis { target: Box<E> },
+
+    /// ```text
+    /// FROM_EPOCH_MILLIS(1577836801000) -> "2020-01-01 00:00:01.000000000"
+    /// ```
+    FromEpochMillis { epoch_millis: Box<E> },
+
+    /// ```text
+    /// FORMAT_TIMESTAMP("2020-01-02 03:04:05.000000000", '[day]/[month] [hour]:[minute]') -> '02/01 03:04'
+    /// ```
+    FormatTimestamp { target: Box<E>, format: Box<E> },
+
+    /// ```text
+    /// PARSE_TIMESTAMP('02/01/2020 03:04', '[day]/[month]/[year] [hour]:[minute]') -> "2020-01-02 03:04:00.000000000"
+    /// ```
+    ParseTimestamp { target: Box<E>, format: Box<E> },
+
+    /// ```text
+    /// 'abc' || 'def' -> 'abcdef'
+    /// CONCAT('abc', 'def', 'ghi') -> 'abc' || 'def' || 'ghi'
+    /// ```
+    Concat { left: Box<E>, right: Box<E> },
+
+    /// ```text
+    /// 'abc' LIKE 'a%' -> TRUE
+    /// 'abc' LIKE 'a_' -> FALSE
+    /// ```
+    Like { target: Box<E>, pattern: Box<E> },
+
+    /// ```text
+    /// UPPER('abc') -> 'ABC'
+    /// ```
+    Upper { target: Box<E> },
+
+    /// ```text
+    /// LOWER('ABC') -> 'abc'
+    /// ```
+    Lower { target: Box<E> },
+
+    /// ```text
+    /// LENGTH('abc') -> 3
+    /// ```
+    Length { target: Box<E> },
+
+    /// ```text
+    /// SUBSTRING('abcdef', 2) -> 'bcdef'
+    /// SUBSTRING('abcdef', 2, 3) -> 'bcd'
+    /// ```
+    Substring {
+        target: Box<E>,
+        start: Box<E>,
+        length: Option<Box<E>>,
+    },
+
+    /// ```text
+    /// TRIM('  abc ') -> 'abc'
+    /// ```
+    Trim { target: Box<E> },
+
+    /// ```text
+    /// REPLACE('a-b-c', '-', '+') -> 'a+b+c'
+    /// ```
+    Replace {
+        target: Box<E>,
+        from: Box<E>,
+        to: Box<E>,
+    },
+
+    /// ```text
+    /// SPLIT_PART('a,b,c', ',', 2) -> 'b'
+    /// SPLIT_PART('a,b,c', ',', -1) -> 'c'
+    /// ```
+    SplitPart {
+        target: Box<E>,
+        delimiter: Box<E>,
+        index: Box<E>,
+    },
+
+    /// ```text
+    /// '{"engine":{"rpm":2500}}' -> '$.engine.rpm' -> 2500
+    /// JSON_EXTRACT('[1,{"a":2}]', '$[1]') -> '{"a":2}'
+    /// ```
+    JsonExtract { target: Box<E>, path: Box<E> },
+
+    /// ```text
+    /// ABS(-1) -> 1
+    /// SQRT(4) -> 2.0
+    /// ```
+    Math { func: MathFunction, target: Box<E> },
+
+    /// ```text
+    /// ROUND(1.5) -> 2.0
+    /// ROUND(1.25, 1) -> 1.3
+    /// ROUND(1234, -2) -> 1200
+    /// ```
+    Round {
+        target: Box<E>,
+        digits: Option<Box<E>>,
+    },
+
+    /// ```text
+    /// POWER(2, 3) -> 8.0
+    /// ```
+    Power { base: Box<E>, exponent: Box<E> },
+
+    /// ```text
+    /// ATAN2(1, 1) -> 0.7853982
+    /// ```
+    Atan2 { y: Box<E>, x: Box<E> },
+
+    /// ```text
+    /// GREATEST(1, NULL, 3) -> GREATEST(GREATEST(1, NULL), 3) -> 3
+    /// ```
+    Greatest { left: Box<E>, right: Box<E> },
+
+    /// ```text
+    /// LEAST(1, NULL, 3) -> LEAST(LEAST(1, NULL), 3) -> 1
+    /// ```
+    Least { left: Box<E>, right: Box<E> },
+
+    /// ```text
+    /// IF(cond, a, b) -> CASE WHEN cond THEN a ELSE b END
+    /// CASE WHEN c1 THEN a WHEN c2 THEN b END -> IF(c1, a, IF(c2, b, NULL))
+    /// CASE x WHEN v1 THEN a ELSE b END -> IF(x = v1, a, b)
+    /// ```
+    If {
+        condition: Box<E>,
+        then_value: Box<E>,
+        else_value: Box<E>,
+    },
+
+    /// ```text
+    /// COALESCE(NULL, 1, 2) -> COALESCE(COALESCE(NULL, 1), 2) -> 1
+    /// ```
+    Coalesce { left: Box<E>, right: Box<E> },
+
+    /// ```text
+    /// NULLIF(1, 1) -> NULL
+    /// NULLIF(1, 2) -> 1
+    /// ```
+    NullIf { left: Box<E>, right: Box<E> },
+
+    /// ```text
+    /// CAST('1.5' AS FLOAT) -> 1.5
+    /// CAST('abc' AS FLOAT) -> error
+    /// ```
+    Cast { target: Box<E>, sql_type: SqlType },
+
+    /// ```text
+    /// TRY_CAST('1.5' AS FLOAT) -> 1.5
+    /// TRY_CAST('abc' AS FLOAT) -> NULL
+    /// ```
+    TryCast { target: Box<E>, sql_type: SqlType },
+}
+
+/// Field of a timestamp to `EXTRACT`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ExtractField {
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+    /// 0 (Sunday) to 6 (Saturday)
+    DayOfWeek,
+}
+
+/// Math function taking a numeric argument.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum MathFunction {
+    /// Keeps the type of the argument.
+    Abs,
+    /// Keeps the type of the argument.
+    Ceil,
+    /// Keeps the type of the argument.
+    Floor,
+    /// FLOAT result.
+    Sqrt,
+    /// FLOAT result.
+    Exp,
+    /// FLOAT result.
+    Ln,
+    /// FLOAT result.
+    Log10,
+    /// FLOAT result.
+    Sin,
+    /// FLOAT result.
+    Cos,
+}
+
+impl<E> FunctionCall<E>
+where
+    E: ValueExprType,
+{
+    /// Converts each argument with `f`, keeping the function.
+    pub fn try_map_args<E2, F>(self, mut f: F) -> Result<FunctionCall<E2>>
+    where
+        E2: ValueExprType,
+        F: FnMut(E) -> Result<E2>,
+    {
+        let mut f = |arg: Box<E>| f(*arg).map(Box::new);
+        let mapped = match self {
+            Self::DurationMillis { duration_millis } => FunctionCall::DurationMillis {
+                duration_millis: f(duration_millis)?,
+            },
+            Self::DurationSecs { duration_secs } => FunctionCall::DurationSecs {
+                duration_secs: f(duration_secs)?,
+            },
+            Self::FloorTime { target, resolution } => FunctionCall::FloorTime {
+                target: f(target)?,
+                resolution: f(resolution)?,
+            },
+            Self::CeilTime { target, resolution } => FunctionCall::CeilTime {
+                target: f(target)?,
+                resolution: f(resolution)?,
+            },
+            Self::Extract { field, target } => FunctionCall::Extract {
+                field,
+                target: f(target)?,
+            },
+            Self::ToEpochMillis { target } => FunctionCall::ToEpochMillis { target: f(target)? },
+            Self::FromEpochMillis { epoch_millis } => FunctionCall::FromEpochMillis {
+                epoch_millis: f(epoch_millis)?,
+            },
+            Self::FormatTimestamp { target, format } => FunctionCall::FormatTimestamp {
+                target: f(target)?,
+                format: f(format)?,
+            },
+            Self::ParseTimestamp { target, format } => FunctionCall::ParseTimestamp {
+                target: f(target)?,
+                format: f(format)?,
+            },
+            Self::Concat { left, right } => FunctionCall::Concat {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::Like { target, pattern } => FunctionCall::Like {
+                target: f(target)?,
+                pattern: f(pattern)?,
+            },
+            Self::Upper { target } => FunctionCall::Upper { target: f(target)? },
+            Self::Lower { target } => FunctionCall::Lower { target: f(target)? },
+            Self::Length { target } => FunctionCall::Length { target: f(target)? },
+            Self::Substring {
+                target,
+                start,
+                length,
+            } => FunctionCall::Substring {
+                target: f(target)?,
+                start: f(start)?,
+                length: length.map(&mut f).transpose()?,
+            },
+            Self::Trim { target } => FunctionCall::Trim { target: f(target)? },
+            Self::Replace { target, from, to } => FunctionCall::Replace {
+                target: f(target)?,
+                from: f(from)?,
+                to: f(to)?,
+            },
+            Self::SplitPart {
+                target,
+                delimiter,
+                index,
+            } => FunctionCall::SplitPart {
+                target: f(target)?,
+                delimiter: f(delimiter)?,
+                index: f(index)?,
+            },
+            Self::JsonExtract { target, path } => FunctionCall::JsonExtract {
+                target: f(target)?,
+                path: f(path)?,
+            },
+            Self::Math { func, target } => FunctionCall::Math {
+                func,
+                target: f(target)?,
+            },
+            Self::Round { target, digits } => FunctionCall::Round {
+                target: f(target)?,
+                digits: digits.map(&mut f).transpose()?,
+            },
+            Self::Power { base, exponent } => FunctionCall::Power {
+                base: f(base)?,
+                exponent: f(exponent)?,
+            },
+            Self::Atan2 { y, x } => FunctionCall::Atan2 { y: f(y)?, x: f(x)? },
+            Self::Greatest { left, right } => FunctionCall::Greatest {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::Least { left, right } => FunctionCall::Least {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::If {
+                condition,
+                then_value,
+                else_value,
+            } => FunctionCall::If {
+                condition: f(condition)?,
+                then_value: f(then_value)?,
+                else_value: f(else_value)?,
+            },
+            Self::Coalesce { left, right } => FunctionCall::Coalesce {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::NullIf { left, right } => FunctionCall::NullIf {
+                left: f(left)?,
+                right: f(right)?,
+            },
+            Self::Cast { target, sql_type } => FunctionCall::Cast {
+                target: f(target)?,
+                sql_type,
+            },
+            Self::TryCast { target, sql_type } => FunctionCall::TryCast {
+                target: f(target)?,
+                sql_type,
+            },
+        };
+        Ok(mapped)
+    }
+
+    pub fn args(&self) -> Vec<&E> {
+        match self {
+            Self::DurationMillis { duration_millis } => vec![duration_millis],
+            Self::DurationSecs { duration_secs } => vec![duration_secs],
+            Self::FloorTime { target, resolution } | Self::CeilTime { target, resolution } => {
+                vec![target, resolution]
+            }
+            Self::Extract { target, .. } | Self::ToEpochMillis { target } => vec![target],
+            Self::FromEpochMillis { epoch_millis } => vec![epoch_millis],
+            Self::FormatTimestamp { target, format } | Self::ParseTimestamp { target, format } => {
+                vec![target, format]
+            }
+            Self::Concat { left, right } => vec![left, right],
+            Self::Like { target, pattern } => vec![target, pattern],
+            Self::Upper { target }
+            | Self::Lower { target }
+            | Self::Length { target }
+            | Self::Trim { target }
+            | Self::Math { target, .. }
+            | Self::Cast { target, .. }
+            | Self::TryCast { target, .. } => vec![target],
+            Self::Substring {
+                target,
+                start,
+                length,
+            } => {
+                let mut args = vec![target.as_ref(), start.as_ref()];
+                args.extend(length.as_deref());
+                args
+            }
+            Self::Replace { target, from, to } => vec![target, from, to],
+            Self::Round { target, digits } => {
+                let mut args = vec![target.as_ref()];
+                args.extend(digits.as_deref());
+                args
+            }
+            Self::Power { base, exponent } => vec![base, exponent],
+            Self::Atan2 { y, x } => vec![y, x],
+            Self::Greatest { left, right } | Self::Least { left, right } => vec![left, right],
+            Self::If {
+                condition,
+                then_value,
+                else_value,
+            } => vec![condition, then_value, else_value],
+            Self::Coalesce { left, right } | Self::NullIf { left, right } => vec![left, right],
+            Self::SplitPart {
+                target,
+                delimiter,
+                index,
+            } => vec![target, delimiter, index],
+            Self::JsonExtract { target, path } => vec![target, path],
+        }
+    }
 }
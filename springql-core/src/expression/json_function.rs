@@ -0,0 +1,80 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Evaluation of JSON functions.
+//!
+//! Every function here evaluates to NULL when any of its arguments is NULL.
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    expression::{text_arg, ValueExprPh2},
+    stream_engine::{JsonPath, SqlValue},
+};
+
+impl ValueExprPh2 {
+    /// Evaluates to NULL also when the path does not exist.
+    /// Objects and arrays are extracted as TEXT in JSON format so that `->` can be chained.
+    pub(super) fn eval_function_json_extract(target: Self, path: Self) -> Result<SqlValue> {
+        let target = target.eval()?;
+        let path = path.eval()?;
+        match (target, path) {
+            (SqlValue::NotNull(target), SqlValue::NotNull(path)) => {
+                let target = text_arg("JSON_EXTRACT", &target)?;
+                let path = text_arg("JSON_EXTRACT", &path)?;
+
+                let json: serde_json::Value = serde_json::from_str(target).map_err(|e| {
+                    SpringError::Sql(anyhow!(
+                        "JSON_EXTRACT got invalid JSON `{}`: {:?}",
+                        target,
+                        e
+                    ))
+                })?;
+                let path: JsonPath = path.parse().map_err(|e| {
+                    SpringError::Sql(anyhow!("JSON_EXTRACT got invalid path: {:?}", e))
+                })?;
+                path.extract_sql_value(&json)
+            }
+            _ => Ok(SqlValue::Null),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream_engine::NnSqlValue;
+
+    fn text(s: &str) -> ValueExprPh2 {
+        ValueExprPh2::Constant(SqlValue::NotNull(NnSqlValue::Text(s.to_string())))
+    }
+
+    #[test]
+    fn test_json_extract() -> Result<()> {
+        let json = r#"{"engine":{"rpm":2500,"status":"ok"},"wheels":[2.1,2.3]}"#;
+
+        for (path, expected) in [
+            ("$.engine.rpm", "BigInt(2500)"),
+            ("$.engine.status", r#"Text("ok")"#),
+            ("$.engine", r#"Text("{\"rpm\":2500,\"status\":\"ok\"}")"#),
+            ("$.wheels[1]", "Float(OrderedFloat(2.3))"),
+        ] {
+            let v = ValueExprPh2::eval_function_json_extract(text(json), text(path))?;
+            assert_eq!(format!("{:?}", v.unwrap()), expected, "{}", path);
+        }
+
+        assert!(matches!(
+            ValueExprPh2::eval_function_json_extract(text(json), text("$.speed"))?,
+            SqlValue::Null
+        ));
+        assert!(matches!(
+            ValueExprPh2::eval_function_json_extract(text("{"), text("$")).unwrap_err(),
+            SpringError::Sql(_)
+        ));
+        assert!(matches!(
+            ValueExprPh2::eval_function_json_extract(text(json), text("engine")).unwrap_err(),
+            SpringError::Sql(_)
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,418 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Evaluation of math functions.
+//!
+//! Functions keeping the type of the argument (`ABS`, `CEIL`, `FLOOR`, `ROUND`) work on each loose type of `NumericComparableType`.
+//! Others calculate in double precision and evaluate to FLOAT.
+//!
+//! Every function here except `GREATEST` and `LEAST` evaluates to NULL when any of its arguments is NULL.
+//! `GREATEST` and `LEAST` ignore NULL arguments.
+
+use anyhow::anyhow;
+use ordered_float::OrderedFloat;
+
+use crate::{
+    api::error::{Result, SpringError},
+    expression::{integer_arg, MathFunction, ValueExprPh2},
+    pipeline::{NumericComparableType, SqlType},
+    stream_engine::{NnSqlValue, SqlCompareResult, SqlValue},
+};
+
+impl ValueExprPh2 {
+    pub(super) fn eval_function_math(func: MathFunction, target: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target], |args| {
+            let target = &args[0];
+            match func {
+                MathFunction::Abs => abs(target),
+                MathFunction::Ceil => round_float_only("CEIL", target, f64::ceil),
+                MathFunction::Floor => round_float_only("FLOOR", target, f64::floor),
+                MathFunction::Sqrt => {
+                    let x = float_arg("SQRT", target)?;
+                    ensure_domain("SQRT", x >= 0.0, target)?;
+                    float_result("SQRT", x.sqrt())
+                }
+                MathFunction::Exp => float_result("EXP", float_arg("EXP", target)?.exp()),
+                MathFunction::Ln => {
+                    let x = float_arg("LN", target)?;
+                    ensure_domain("LN", x > 0.0, target)?;
+                    float_result("LN", x.ln())
+                }
+                MathFunction::Log10 => {
+                    let x = float_arg("LOG10", target)?;
+                    ensure_domain("LOG10", x > 0.0, target)?;
+                    float_result("LOG10", x.log10())
+                }
+                MathFunction::Sin => float_result("SIN", float_arg("SIN", target)?.sin()),
+                MathFunction::Cos => float_result("COS", float_arg("COS", target)?.cos()),
+            }
+        })
+    }
+
+    /// Rounds half away from zero. Negative `digits` rounds integer part.
+    pub(super) fn eval_function_round(target: Self, digits: Option<Self>) -> Result<SqlValue> {
+        let has_digits = digits.is_some();
+        let args = vec![target].into_iter().chain(digits).collect();
+
+        Self::eval_strict_function(args, |args| {
+            let target = &args[0];
+            let digits = if has_digits {
+                integer_arg("ROUND", &args[1])?
+            } else {
+                0
+            };
+
+            match target.sql_type() {
+                SqlType::NumericComparable(NumericComparableType::I64Loose(_)) => {
+                    let i = target.unpack::<i64>()?;
+                    let rounded = match pow10_i64(digits) {
+                        None => Some(i),
+                        Some(None) => Some(0),
+                        Some(Some(p)) => {
+                            let mut q = i / p;
+                            if (i % p).abs() >= p - p / 2 {
+                                q += i.signum();
+                            }
+                            q.checked_mul(p)
+                        }
+                    };
+                    int_result("ROUND", rounded.map(NnSqlValue::BigInt), target)
+                }
+                SqlType::NumericComparable(NumericComparableType::U64Loose(_)) => {
+                    let u = target.unpack::<u64>()?;
+                    let rounded = match pow10_u64(digits) {
+                        None => Some(u),
+                        Some(None) => Some(0),
+                        Some(Some(p)) => {
+                            let mut q = u / p;
+                            if u % p >= p - p / 2 {
+                                q += 1;
+                            }
+                            q.checked_mul(p)
+                        }
+                    };
+                    int_result("ROUND", rounded.map(NnSqlValue::UnsignedBigInt), target)
+                }
+                _ => {
+                    let x = float_arg("ROUND", target)?;
+                    // digits out of FLOAT range make no difference
+                    let factor = 10_f64.powi(digits.clamp(-40, 40) as i32);
+                    float_result("ROUND", (x * factor).round() / factor)
+                }
+            }
+        })
+    }
+
+    pub(super) fn eval_function_power(base: Self, exponent: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![base, exponent], |args| {
+            let base = float_arg("POWER", &args[0])?;
+            let exponent = float_arg("POWER", &args[1])?;
+            float_result("POWER", base.powf(exponent))
+        })
+    }
+
+    pub(super) fn eval_function_atan2(y: Self, x: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![y, x], |args| {
+            let y = float_arg("ATAN2", &args[0])?;
+            let x = float_arg("ATAN2", &args[1])?;
+            float_result("ATAN2", y.atan2(x))
+        })
+    }
+
+    pub(super) fn eval_function_greatest(left: Self, right: Self) -> Result<SqlValue> {
+        Self::eval_extremum(left, right, SqlCompareResult::GreaterThan)
+    }
+
+    pub(super) fn eval_function_least(left: Self, right: Self) -> Result<SqlValue> {
+        Self::eval_extremum(left, right, SqlCompareResult::LessThan)
+    }
+
+    /// Picks `left` if `left` compared to `right` is `picks_left_if` (or equal), `right` otherwise.
+    fn eval_extremum(left: Self, right: Self, picks_left_if: SqlCompareResult) -> Result<SqlValue> {
+        match (left.eval()?, right.eval()?) {
+            (SqlValue::Null, v) | (v, SqlValue::Null) => Ok(v),
+            (SqlValue::NotNull(left), SqlValue::NotNull(right)) => {
+                let cmp = left.sql_compare(&right)?;
+                let picked = if cmp == picks_left_if || cmp == SqlCompareResult::Eq {
+                    left
+                } else {
+                    right
+                };
+                Ok(SqlValue::NotNull(promote_integer(picked)?))
+            }
+        }
+    }
+}
+
+fn abs(target: &NnSqlValue) -> Result<NnSqlValue> {
+    match target.sql_type() {
+        SqlType::NumericComparable(NumericComparableType::I64Loose(_)) => {
+            let i = target.unpack::<i64>()?;
+            int_result("ABS", i.checked_abs().map(NnSqlValue::BigInt), target)
+        }
+        SqlType::NumericComparable(NumericComparableType::U64Loose(_)) => Ok(target.clone()),
+        _ => float_result("ABS", float_arg("ABS", target)?.abs()),
+    }
+}
+
+/// Integers are kept as they are.
+fn round_float_only(
+    function_name: &str,
+    target: &NnSqlValue,
+    f: impl Fn(f64) -> f64,
+) -> Result<NnSqlValue> {
+    match target.sql_type() {
+        SqlType::NumericComparable(
+            NumericComparableType::I64Loose(_) | NumericComparableType::U64Loose(_),
+        ) => Ok(target.clone()),
+        _ => float_result(function_name, f(float_arg(function_name, target)?)),
+    }
+}
+
+/// `Some(None)` if `10^-digits` is larger than any integer. `None` if `digits` is not negative.
+fn pow10_i64(digits: i64) -> Option<Option<i64>> {
+    (digits < 0).then(|| {
+        u32::try_from(digits.unsigned_abs())
+            .ok()
+            .and_then(|exp| 10_i64.checked_pow(exp))
+    })
+}
+fn pow10_u64(digits: i64) -> Option<Option<u64>> {
+    (digits < 0).then(|| {
+        u32::try_from(digits.unsigned_abs())
+            .ok()
+            .and_then(|exp| 10_u64.checked_pow(exp))
+    })
+}
+
+/// Converts `result` into the type of `target`.
+fn int_result(
+    function_name: &str,
+    result: Option<NnSqlValue>,
+    target: &NnSqlValue,
+) -> Result<NnSqlValue> {
+    result
+        .and_then(|v| v.try_convert(&target.sql_type()).ok())
+        .ok_or_else(|| {
+            SpringError::Sql(anyhow!(
+                "integer overflow on {}({}) - {:?}",
+                function_name,
+                target,
+                target.sql_type()
+            ))
+        })
+}
+
+fn float_result(function_name: &str, result: f64) -> Result<NnSqlValue> {
+    let result = result as f32;
+    if result.is_finite() {
+        Ok(NnSqlValue::Float(OrderedFloat(result)))
+    } else {
+        Err(SpringError::Sql(anyhow!(
+            "{} evaluated to out of FLOAT range: {}",
+            function_name,
+            result
+        )))
+    }
+}
+
+fn float_arg(function_name: &str, value: &NnSqlValue) -> Result<f64> {
+    match value.sql_type() {
+        SqlType::NumericComparable(NumericComparableType::I64Loose(_)) => {
+            value.unpack::<i64>().map(|i| i as f64)
+        }
+        SqlType::NumericComparable(NumericComparableType::U64Loose(_)) => {
+            value.unpack::<u64>().map(|u| u as f64)
+        }
+        SqlType::NumericComparable(NumericComparableType::F32Loose(_)) => {
+            value.unpack::<f32>().map(f64::from)
+        }
+        _ => Err(SpringError::Sql(anyhow!(
+            "{} takes a number but got {:?}",
+            function_name,
+            value.sql_type()
+        ))),
+    }
+}
+
+fn ensure_domain(function_name: &str, in_domain: bool, value: &NnSqlValue) -> Result<()> {
+    if in_domain {
+        Ok(())
+    } else {
+        Err(SpringError::Sql(anyhow!(
+            "{} is not defined for {}",
+            function_name,
+            value
+        )))
+    }
+}
+
+/// Integers are promoted to the widest type of their loose type not to depend on which argument is picked.
+fn promote_integer(value: NnSqlValue) -> Result<NnSqlValue> {
+    match value.sql_type() {
+        SqlType::NumericComparable(NumericComparableType::I64Loose(_)) => {
+            value.unpack::<i64>().map(NnSqlValue::BigInt)
+        }
+        SqlType::NumericComparable(NumericComparableType::U64Loose(_)) => {
+            value.unpack::<u64>().map(NnSqlValue::UnsignedBigInt)
+        }
+        _ => Ok(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constant(v: NnSqlValue) -> ValueExprPh2 {
+        ValueExprPh2::Constant(SqlValue::NotNull(v))
+    }
+    fn float(f: f32) -> NnSqlValue {
+        NnSqlValue::Float(OrderedFloat(f))
+    }
+
+    #[test]
+    fn test_round() -> Result<()> {
+        for (target, digits, expected) in [
+            (float(1.5), None, float(2.0)),
+            (float(-1.5), None, float(-2.0)),
+            (float(1.25), Some(1), float(1.3)),
+            (float(1234.5), Some(-2), float(1200.0)),
+            (
+                NnSqlValue::Integer(1234),
+                Some(1),
+                NnSqlValue::Integer(1234),
+            ),
+            (
+                NnSqlValue::Integer(1250),
+                Some(-2),
+                NnSqlValue::Integer(1300),
+            ),
+            (
+                NnSqlValue::Integer(-1250),
+                Some(-2),
+                NnSqlValue::Integer(-1300),
+            ),
+            (
+                NnSqlValue::Integer(-1249),
+                Some(-2),
+                NnSqlValue::Integer(-1200),
+            ),
+            (
+                NnSqlValue::Integer(1234),
+                Some(-100),
+                NnSqlValue::Integer(0),
+            ),
+            (
+                NnSqlValue::UnsignedInteger(15),
+                Some(-1),
+                NnSqlValue::UnsignedInteger(20),
+            ),
+        ] {
+            let rounded = ValueExprPh2::eval_function_round(
+                constant(target.clone()),
+                digits.map(|d| constant(NnSqlValue::Integer(d))),
+            )?
+            .unwrap();
+            assert_eq!(
+                format!("{:?}", rounded),
+                format!("{:?}", expected),
+                "ROUND({}, {:?})",
+                target,
+                digits
+            );
+        }
+
+        assert!(matches!(
+            ValueExprPh2::eval_function_round(
+                constant(NnSqlValue::SmallInt(32_765)),
+                Some(constant(NnSqlValue::Integer(-1))),
+            ),
+            Err(SpringError::Sql(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_math() -> Result<()> {
+        for (func, target, expected) in [
+            (
+                MathFunction::Abs,
+                NnSqlValue::SmallInt(-3),
+                NnSqlValue::SmallInt(3),
+            ),
+            (MathFunction::Abs, float(-1.5), float(1.5)),
+            (MathFunction::Ceil, float(1.2), float(2.0)),
+            (MathFunction::Floor, float(-1.2), float(-2.0)),
+            (
+                MathFunction::Floor,
+                NnSqlValue::BigInt(3),
+                NnSqlValue::BigInt(3),
+            ),
+            (MathFunction::Sqrt, NnSqlValue::Integer(9), float(3.0)),
+            (MathFunction::Exp, NnSqlValue::Integer(0), float(1.0)),
+            (MathFunction::Ln, NnSqlValue::Integer(1), float(0.0)),
+            (
+                MathFunction::Log10,
+                NnSqlValue::UnsignedInteger(1000),
+                float(3.0),
+            ),
+            (MathFunction::Sin, NnSqlValue::Integer(0), float(0.0)),
+            (MathFunction::Cos, NnSqlValue::Integer(0), float(1.0)),
+        ] {
+            let v = ValueExprPh2::eval_function_math(func, constant(target.clone()))?.unwrap();
+            assert_eq!(
+                format!("{:?}", v),
+                format!("{:?}", expected),
+                "{:?}({})",
+                func,
+                target
+            );
+        }
+
+        for (func, target) in [
+            (MathFunction::Abs, NnSqlValue::SmallInt(i16::MIN)),
+            (MathFunction::Sqrt, NnSqlValue::Integer(-1)),
+            (MathFunction::Ln, NnSqlValue::Integer(0)),
+            (MathFunction::Exp, NnSqlValue::Integer(1000)),
+            (MathFunction::Abs, NnSqlValue::Text("1".to_string())),
+        ] {
+            assert!(
+                matches!(
+                    ValueExprPh2::eval_function_math(func, constant(target.clone())),
+                    Err(SpringError::Sql(_))
+                ),
+                "{:?}({})",
+                func,
+                target
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_greatest_least() -> Result<()> {
+        let null = || ValueExprPh2::Constant(SqlValue::Null);
+
+        let v = ValueExprPh2::eval_function_greatest(
+            constant(NnSqlValue::SmallInt(1)),
+            constant(NnSqlValue::Integer(3)),
+        )?;
+        assert_eq!(format!("{:?}", v.unwrap()), "BigInt(3)");
+
+        let v = ValueExprPh2::eval_function_least(null(), constant(float(1.5)))?;
+        assert_eq!(v.unwrap(), float(1.5));
+
+        assert!(matches!(
+            ValueExprPh2::eval_function_least(null(), null())?,
+            SqlValue::Null
+        ));
+        assert!(matches!(
+            ValueExprPh2::eval_function_greatest(
+                constant(NnSqlValue::Integer(1)),
+                constant(float(1.5))
+            ),
+            Err(SpringError::Sql(_))
+        ));
+        Ok(())
+    }
+}
@@ -5,6 +5,12 @@
 pub enum UnaryOperator {
     /// -
     Minus,
+    /// NOT
+    Not,
+    /// IS NULL (postfix)
+    IsNull,
+    /// IS NOT NULL (postfix)
+    IsNotNull,
 }
 
 /// binary operator for an expression
@@ -12,13 +18,42 @@ pub enum UnaryOperator {
 pub enum BinaryOperator {
     /// =
     Equal,
+    /// <> or !=
+    NotEqual,
+    /// <
+    LessThan,
+    /// <=
+    LessThanOrEqual,
+    /// >
+    GreaterThan,
+    /// >=
+    GreaterThanOrEqual,
 
     /// +
     Add,
+    /// - (binary)
+    Sub,
 
     /// *
     Mul,
+    /// /
+    Div,
+    /// %
+    Mod,
+
+    /// ||
+    Concat,
+
+    /// ->
+    JsonExtract,
+
+    /// LIKE
+    Like,
+    /// NOT LIKE
+    NotLike,
 
     /// AND
     And,
+    /// OR
+    Or,
 }
@@ -0,0 +1,279 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Evaluation of string functions.
+//!
+//! Every function here evaluates to NULL when any of its arguments is NULL.
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    expression::{integer_arg, text_arg, ValueExprPh2},
+    stream_engine::{NnSqlValue, SqlValue},
+};
+
+impl ValueExprPh2 {
+    pub(super) fn eval_function_concat(left: Self, right: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![left, right], |args| {
+            let left = text_arg("||", &args[0])?;
+            let right = text_arg("||", &args[1])?;
+            Ok(NnSqlValue::Text(format!("{}{}", left, right)))
+        })
+    }
+
+    pub(super) fn eval_function_like(target: Self, pattern: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target, pattern], |args| {
+            let target = text_arg("LIKE", &args[0])?;
+            let pattern = text_arg("LIKE", &args[1])?;
+            Ok(NnSqlValue::Boolean(like_match(target, pattern)))
+        })
+    }
+
+    pub(super) fn eval_function_upper(target: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target], |args| {
+            let target = text_arg("UPPER", &args[0])?;
+            Ok(NnSqlValue::Text(target.to_uppercase()))
+        })
+    }
+
+    pub(super) fn eval_function_lower(target: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target], |args| {
+            let target = text_arg("LOWER", &args[0])?;
+            Ok(NnSqlValue::Text(target.to_lowercase()))
+        })
+    }
+
+    /// Number of characters (not bytes).
+    pub(super) fn eval_function_length(target: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target], |args| {
+            let target = text_arg("LENGTH", &args[0])?;
+            let length = i32::try_from(target.chars().count())
+                .map_err(|e| SpringError::Sql(anyhow!("LENGTH overflows INTEGER: {}", e)))?;
+            Ok(NnSqlValue::Integer(length))
+        })
+    }
+
+    /// 1-origin like PostgreSQL's `substring(string, start, length)`.
+    ///
+    /// Characters before the 1st one are counted for `start` and `length` but never returned.
+    pub(super) fn eval_function_substring(
+        target: Self,
+        start: Self,
+        length: Option<Self>,
+    ) -> Result<SqlValue> {
+        let has_length = length.is_some();
+        let args = vec![target, start].into_iter().chain(length).collect();
+
+        Self::eval_strict_function(args, |args| {
+            let target = text_arg("SUBSTRING", &args[0])?;
+            let start = integer_arg("SUBSTRING", &args[1])?;
+            let end = if has_length {
+                let length = integer_arg("SUBSTRING", &args[2])?;
+                if length < 0 {
+                    return Err(SpringError::Sql(anyhow!(
+                        "negative substring length not allowed: `{}`",
+                        length
+                    )));
+                }
+                Some(start.saturating_add(length))
+            } else {
+                None
+            };
+
+            let first = start.max(1);
+            let skip = (first - 1) as usize;
+            let take = end.map_or(usize::MAX, |end| (end - first).max(0) as usize);
+            Ok(NnSqlValue::Text(
+                target.chars().skip(skip).take(take).collect(),
+            ))
+        })
+    }
+
+    /// Removes leading and trailing spaces.
+    pub(super) fn eval_function_trim(target: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target], |args| {
+            let target = text_arg("TRIM", &args[0])?;
+            Ok(NnSqlValue::Text(target.trim_matches(' ').to_string()))
+        })
+    }
+
+    pub(super) fn eval_function_replace(target: Self, from: Self, to: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target, from, to], |args| {
+            let target = text_arg("REPLACE", &args[0])?;
+            let from = text_arg("REPLACE", &args[1])?;
+            let to = text_arg("REPLACE", &args[2])?;
+            if from.is_empty() {
+                Ok(NnSqlValue::Text(target.to_string()))
+            } else {
+                Ok(NnSqlValue::Text(target.replace(from, to)))
+            }
+        })
+    }
+
+    /// 1-origin `index`. Negative `index` counts from the last field.
+    ///
+    /// Evaluated to an empty string when `index` is out of range.
+    pub(super) fn eval_function_split_part(
+        target: Self,
+        delimiter: Self,
+        index: Self,
+    ) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target, delimiter, index], |args| {
+            let target = text_arg("SPLIT_PART", &args[0])?;
+            let delimiter = text_arg("SPLIT_PART", &args[1])?;
+            let index = integer_arg("SPLIT_PART", &args[2])?;
+
+            let fields: Vec<&str> = if delimiter.is_empty() {
+                vec![target]
+            } else {
+                target.split(delimiter).collect()
+            };
+            let field = match index {
+                0 => Err(SpringError::Sql(anyhow!(
+                    "field position in SPLIT_PART must not be zero"
+                ))),
+                i if i > 0 => Ok(fields.get((i - 1) as usize)),
+                i => Ok(fields
+                    .len()
+                    .checked_sub(i.unsigned_abs() as usize)
+                    .and_then(|idx| fields.get(idx))),
+            }?;
+            Ok(NnSqlValue::Text(field.map_or("", |f| f).to_string()))
+        })
+    }
+}
+
+/// SQL `LIKE` pattern matching.
+///
+/// - `%` matches any sequence of characters (including an empty one).
+/// - `_` matches any single character.
+/// - `\` escapes the following character.
+fn like_match(target: &str, pattern: &str) -> bool {
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    enum Token {
+        Char(char),
+        AnyChar,
+        AnySeq,
+    }
+
+    let mut tokens = Vec::new();
+    let mut pattern_chars = pattern.chars();
+    while let Some(c) = pattern_chars.next() {
+        let token = match c {
+            '%' => Token::AnySeq,
+            '_' => Token::AnyChar,
+            '\\' => Token::Char(pattern_chars.next().unwrap_or('\\')),
+            c => Token::Char(c),
+        };
+        tokens.push(token);
+    }
+    let target = target.chars().collect::<Vec<_>>();
+
+    // greedy matching with backtracking to the last `%`
+    let (mut t, mut p) = (0, 0);
+    let mut last_any_seq: Option<(usize, usize)> = None;
+    while t < target.len() {
+        match tokens.get(p) {
+            Some(Token::AnySeq) => {
+                last_any_seq = Some((p, t));
+                p += 1;
+            }
+            Some(Token::AnyChar) => {
+                t += 1;
+                p += 1;
+            }
+            Some(Token::Char(c)) if *c == target[t] => {
+                t += 1;
+                p += 1;
+            }
+            _ => match last_any_seq {
+                Some((any_seq_p, any_seq_t)) => {
+                    // let the last `%` consume one more character
+                    last_any_seq = Some((any_seq_p, any_seq_t + 1));
+                    p = any_seq_p + 1;
+                    t = any_seq_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    tokens[p..].iter().all(|token| *token == Token::AnySeq)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_like_match() {
+        for (target, pattern, expected) in [
+            ("abc", "abc", true),
+            ("abc", "ab", false),
+            ("abc", "a%", true),
+            ("abc", "%c", true),
+            ("abc", "%b%", true),
+            ("abc", "%", true),
+            ("", "%", true),
+            ("", "_", false),
+            ("abc", "a_c", true),
+            ("abc", "a_", false),
+            ("aXbXc", "a%b%c", true),
+            ("abcbc", "a%bc", true),
+            ("abcbd", "a%bc", false),
+            ("100%", "100\\%", true),
+            ("1000", "100\\%", false),
+            ("a_c", "a\\_c", true),
+            ("abc", "a\\_c", false),
+            ("日本語", "日_語", true),
+        ] {
+            assert_eq!(
+                like_match(target, pattern),
+                expected,
+                "{} LIKE {}",
+                target,
+                pattern
+            );
+        }
+    }
+
+    #[test]
+    fn test_substring_split_part() -> Result<()> {
+        let text =
+            |s: &str| ValueExprPh2::Constant(SqlValue::NotNull(NnSqlValue::Text(s.to_string())));
+        let int = |i: i32| ValueExprPh2::Constant(SqlValue::NotNull(NnSqlValue::Integer(i)));
+        let unpack = |v: SqlValue| v.unwrap().unpack::<String>().unwrap();
+
+        for (start, length, expected) in [
+            (1, Some(3), "abc"),
+            (2, None, "bcdef"),
+            (0, Some(3), "ab"),
+            (-1, Some(3), "a"),
+            (5, Some(10), "ef"),
+            (7, None, ""),
+        ] {
+            let substring =
+                ValueExprPh2::eval_function_substring(text("abcdef"), int(start), length.map(int))?;
+            assert_eq!(unpack(substring), expected, "{} {:?}", start, length);
+        }
+        assert!(
+            ValueExprPh2::eval_function_substring(text("abcdef"), int(1), Some(int(-1))).is_err()
+        );
+
+        for (index, expected) in [(1, "a"), (3, "c"), (4, ""), (-1, "c"), (-3, "a"), (-4, "")] {
+            let split_part =
+                ValueExprPh2::eval_function_split_part(text("a,b,c"), text(","), int(index))?;
+            assert_eq!(unpack(split_part), expected, "{}", index);
+        }
+        assert!(ValueExprPh2::eval_function_split_part(text("a,b,c"), text(","), int(0)).is_err());
+
+        assert!(matches!(
+            ValueExprPh2::eval_function_split_part(
+                ValueExprPh2::Constant(SqlValue::Null),
+                text(","),
+                int(1)
+            )?,
+            SqlValue::Null
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Evaluation of timestamp functions.
+//!
+//! Every function here evaluates to NULL when any of its arguments is NULL.
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    expression::{integer_arg, text_arg, ExtractField, ValueExprPh2},
+    stream_engine::{
+        time::{SpringDuration, SpringEventDuration, SpringTimestamp},
+        NnSqlValue, SqlValue,
+    },
+};
+
+impl ValueExprPh2 {
+    pub(super) fn eval_function_floor_time(target: Self, resolution: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target, resolution], |args| {
+            let target = timestamp_arg("FLOOR_TIME", &args[0])?;
+            let resolution = resolution_arg("FLOOR_TIME", &args[1])?;
+            target
+                .floor(resolution.to_duration())
+                .map(NnSqlValue::Timestamp)
+        })
+    }
+
+    pub(super) fn eval_function_ceil_time(target: Self, resolution: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target, resolution], |args| {
+            let target = timestamp_arg("CEIL_TIME", &args[0])?;
+            let resolution = resolution_arg("CEIL_TIME", &args[1])?;
+            target
+                .ceil(resolution.to_duration())
+                .map(NnSqlValue::Timestamp)
+        })
+    }
+
+    pub(super) fn eval_function_extract(field: ExtractField, target: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target], |args| {
+            let target = timestamp_arg("EXTRACT", &args[0])?;
+            let v = match field {
+                ExtractField::Hour => i32::from(target.hour()),
+                ExtractField::Minute => i32::from(target.minute()),
+                ExtractField::Second => i32::from(target.second()),
+                ExtractField::Millisecond => i32::from(target.millisecond()),
+                ExtractField::DayOfWeek => i32::from(target.day_of_week()),
+            };
+            Ok(NnSqlValue::Integer(v))
+        })
+    }
+
+    pub(super) fn eval_function_to_epoch_millis(target: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target], |args| {
+            let target = timestamp_arg("TO_EPOCH_MILLIS", &args[0])?;
+            Ok(NnSqlValue::BigInt(target.unix_time_millis()))
+        })
+    }
+
+    pub(super) fn eval_function_from_epoch_millis(epoch_millis: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![epoch_millis], |args| {
+            let epoch_millis = integer_arg("FROM_EPOCH_MILLIS", &args[0])?;
+            SpringTimestamp::from_unix_time_millis(epoch_millis)
+                .map(NnSqlValue::Timestamp)
+                .map_err(|e| {
+                    SpringError::Sql(anyhow!(
+                        "FROM_EPOCH_MILLIS got out of range value `{}`: {:?}",
+                        epoch_millis,
+                        e
+                    ))
+                })
+        })
+    }
+
+    pub(super) fn eval_function_format_timestamp(target: Self, format: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target, format], |args| {
+            let target = timestamp_arg("FORMAT_TIMESTAMP", &args[0])?;
+            let format = text_arg("FORMAT_TIMESTAMP", &args[1])?;
+            target
+                .format_with(format)
+                .map(NnSqlValue::Text)
+                .map_err(|e| {
+                    SpringError::Sql(anyhow!(
+                        "FORMAT_TIMESTAMP got invalid format `{}`: {:?}",
+                        format,
+                        e
+                    ))
+                })
+        })
+    }
+
+    pub(super) fn eval_function_parse_timestamp(target: Self, format: Self) -> Result<SqlValue> {
+        Self::eval_strict_function(vec![target, format], |args| {
+            let target = text_arg("PARSE_TIMESTAMP", &args[0])?;
+            let format = text_arg("PARSE_TIMESTAMP", &args[1])?;
+            SpringTimestamp::parse_with_format(target, format)
+                .map(NnSqlValue::Timestamp)
+                .map_err(|e| SpringError::Sql(anyhow!("PARSE_TIMESTAMP failed: {:?}", e)))
+        })
+    }
+}
+
+fn timestamp_arg(function_name: &str, value: &NnSqlValue) -> Result<SpringTimestamp> {
+    match value {
+        NnSqlValue::Timestamp(ts) => Ok(*ts),
+        _ => Err(SpringError::Sql(anyhow!(
+            "{} takes TIMESTAMP but got {:?}",
+            function_name,
+            value.sql_type()
+        ))),
+    }
+}
+
+fn resolution_arg(function_name: &str, value: &NnSqlValue) -> Result<SpringEventDuration> {
+    match value {
+        NnSqlValue::Duration(d) if !d.as_std().is_zero() => Ok(*d),
+        _ => Err(SpringError::Sql(anyhow!(
+            "{} takes non-zero DURATION as resolution but got `{}`",
+            function_name,
+            value
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(s: &str) -> ValueExprPh2 {
+        ValueExprPh2::Constant(SqlValue::NotNull(NnSqlValue::Timestamp(s.parse().unwrap())))
+    }
+    fn secs(secs: u64) -> ValueExprPh2 {
+        ValueExprPh2::Constant(SqlValue::NotNull(NnSqlValue::Duration(
+            SpringEventDuration::from_secs(secs),
+        )))
+    }
+    fn unpack_ts(v: SqlValue) -> String {
+        v.unwrap().unpack::<SpringTimestamp>().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_floor_ceil_time() -> Result<()> {
+        let floor = ValueExprPh2::eval_function_floor_time(
+            ts("2020-01-01 01:11:11.000000000"),
+            secs(10 * 60),
+        )?;
+        assert_eq!(unpack_ts(floor), "2020-01-01 01:10:00.000000000");
+
+        let ceil = ValueExprPh2::eval_function_ceil_time(
+            ts("2020-01-01 01:11:11.000000000"),
+            secs(10 * 60),
+        )?;
+        assert_eq!(unpack_ts(ceil), "2020-01-01 01:20:00.000000000");
+
+        assert!(matches!(
+            ValueExprPh2::eval_function_ceil_time(ts("2020-01-01 01:11:11.000000000"), secs(0)),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            ValueExprPh2::eval_function_ceil_time(
+                ValueExprPh2::Constant(SqlValue::Null),
+                secs(60)
+            )?,
+            SqlValue::Null
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract() -> Result<()> {
+        // Saturday
+        let target = "2022-01-01 23:59:58.999000000";
+        for (field, expected) in [
+            (ExtractField::Hour, 23),
+            (ExtractField::Minute, 59),
+            (ExtractField::Second, 58),
+            (ExtractField::Millisecond, 999),
+            (ExtractField::DayOfWeek, 6),
+        ] {
+            let v = ValueExprPh2::eval_function_extract(field, ts(target))?;
+            assert_eq!(v.unwrap().unpack::<i32>()?, expected, "{:?}", field);
+        }
+        Ok(())
+    }
+}
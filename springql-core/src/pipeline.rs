@@ -2,6 +2,7 @@
 
 #![doc = include_str!("pipeline.md")]
 
+mod dead_letter_stream_model;
 mod field;
 mod name;
 mod option;
@@ -12,24 +13,28 @@ mod relation;
 mod sink_writer_model;
 mod source_reader_model;
 mod stream_model;
+mod table_model;
 
 #[cfg(test)]
 pub mod test_support;
 
+pub use dead_letter_stream_model::{DeadLetterStreamModel, DeadLetterTarget};
 pub use field::{ColumnReference, Field};
 pub use name::{
     AggrAlias, ColumnName, CorrelationAlias, PumpName, QueueName, SinkWriterName, SourceReaderName,
     StreamName, ValueAlias,
 };
 pub use option::{
-     Http1ClientOptions, HttpMethod, InMemoryQueueOptions, NetClientOptions,
-    NetProtocol, NetServerOptions, Options, OptionsBuilder,
+    FileFormat, FileOptions, Http1ClientOptions, Http1ServerOptions, HttpBodyFormat, HttpMethod,
+    InMemoryQueueOptions, JsonPathOptions, NetClientOptions, NetProtocol, NetServerOptions,
+    OnSchemaError, Options, OptionsBuilder, RetryOptions,
 };
 pub use pipeline_graph::{Edge, PipelineGraph};
 pub use pipeline_version::PipelineVersion;
 pub use pump_model::{
-    AggregateFunctionParameter, AggregateParameter, GroupByLabels, JoinParameter, JoinType,
-    PumpInputType, PumpModel, WindowOperationParameter, WindowParameter,
+    AggrExprParameter, AggregateFunctionParameter, AggregateParameter, GroupByLabels,
+    JoinParameter, JoinType, PumpInputType, PumpModel, WindowLengthPeriod,
+    WindowOperationParameter, WindowParameter,
 };
 pub use relation::{
     ColumnConstraint, ColumnDataType, ColumnDefinition, F32LooseType, I64LooseType,
@@ -38,6 +43,7 @@ pub use relation::{
 pub use sink_writer_model::{SinkWriterModel, SinkWriterType};
 pub use source_reader_model::{SourceReaderModel, SourceReaderType};
 pub use stream_model::{StreamModel, StreamShape};
+pub use table_model::TableModel;
 
 use std::{collections::HashSet, sync::Arc};
 
@@ -50,6 +56,8 @@ pub struct Pipeline {
     version: PipelineVersion,
     object_names: HashSet<String>,
     graph: PipelineGraph,
+    dead_letter_streams: Vec<DeadLetterStreamModel>,
+    tables: Vec<TableModel>,
 }
 
 impl Pipeline {
@@ -58,6 +66,8 @@ impl Pipeline {
             version,
             object_names: HashSet::default(),
             graph: PipelineGraph::default(),
+            dead_letter_streams: Vec::default(),
+            tables: Vec::default(),
         }
     }
 
@@ -99,6 +109,57 @@ impl Pipeline {
         self.graph.add_stream(stream)
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Name of stream is already used in the same pipeline
+    pub fn add_dead_letter_stream(
+        &mut self,
+        dead_letter_stream: DeadLetterStreamModel,
+    ) -> Result<()> {
+        self.add_stream(dead_letter_stream.stream().clone())?;
+        self.dead_letter_streams.push(dead_letter_stream);
+        Ok(())
+    }
+
+    /// Dead-letter streams receiving rows failed in `target`.
+    pub fn dead_letter_streams(&self, target: &DeadLetterTarget) -> Vec<&StreamName> {
+        self.dead_letter_streams
+            .iter()
+            .filter(|dl| dl.target() == target)
+            .map(|dl| dl.stream().name())
+            .collect()
+    }
+
+    /// None if `stream` is not a dead-letter stream.
+    pub fn dead_letter_target(&self, stream: &StreamName) -> Option<&DeadLetterTarget> {
+        self.dead_letter_streams
+            .iter()
+            .find(|dl| dl.stream().name() == stream)
+            .map(DeadLetterStreamModel::target)
+    }
+
+    /// Tables are nodes in the pipeline graph, like source streams, so that source readers can write into them.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Name of table is already used in the same pipeline
+    pub fn add_table(&mut self, table: TableModel) -> Result<()> {
+        self.add_stream(table.stream().clone())?;
+        self.tables.push(table);
+        Ok(())
+    }
+
+    /// None if `name` is not a table (but a stream).
+    pub fn get_table(&self, name: &StreamName) -> Option<&TableModel> {
+        self.tables.iter().find(|table| table.name() == name)
+    }
+
+    pub fn all_tables(&self) -> &[TableModel] {
+        &self.tables
+    }
+
     /// # Failure
     ///
     /// TODO
@@ -117,6 +178,9 @@ impl Pipeline {
     pub fn all_sources(&self) -> Vec<&SourceReaderModel> {
         self.graph.all_sources()
     }
+    pub fn all_pumps(&self) -> Vec<&PumpModel> {
+        self.graph.all_pumps()
+    }
     pub fn all_sinks(&self) -> Vec<&SinkWriterModel> {
         self.graph.all_sinks()
     }
@@ -0,0 +1,76 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::sync::Arc;
+
+use crate::pipeline::{
+    name::{ColumnName, PumpName, SinkWriterName, SourceReaderName, StreamName},
+    relation::{ColumnConstraint, ColumnDataType, ColumnDefinition, SqlType},
+    stream_model::{StreamModel, StreamShape},
+};
+
+/// Task whose failing rows are routed to a dead-letter stream.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum DeadLetterTarget {
+    Pump(PumpName),
+    SourceReader(SourceReaderName),
+    SinkWriter(SinkWriterName),
+}
+
+/// Sink stream receiving rows which failed in its target task.
+///
+/// Dead-letter streams have a fixed shape:
+///
+/// ```sql
+/// (
+///   ts TIMESTAMP NOT NULL ROWTIME,   -- when the error occurred
+///   task_id TEXT NOT NULL,           -- task in which the error occurred
+///   error_kind TEXT NOT NULL,        -- e.g. "Sql", "InvalidFormat", "ForeignIo"
+///   error_message TEXT NOT NULL,
+///   raw_row TEXT                     -- columns of the failing row in JSON (or the unparsable source text)
+/// )
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct DeadLetterStreamModel {
+    stream: Arc<StreamModel>,
+    target: DeadLetterTarget,
+}
+
+impl DeadLetterStreamModel {
+    pub fn new(name: StreamName, target: DeadLetterTarget) -> Self {
+        let col = |name: &str, sql_type: SqlType, nullable: bool| {
+            ColumnDefinition::new(
+                ColumnDataType::new(ColumnName::new(name.to_string()), sql_type, nullable),
+                vec![],
+            )
+        };
+
+        let cols = vec![
+            ColumnDefinition::new(
+                ColumnDataType::new(
+                    ColumnName::new("ts".to_string()),
+                    SqlType::timestamp(),
+                    false,
+                ),
+                vec![ColumnConstraint::Rowtime],
+            ),
+            col("task_id", SqlType::text(), false),
+            col("error_kind", SqlType::text(), false),
+            col("error_message", SqlType::text(), false),
+            col("raw_row", SqlType::text(), true),
+        ];
+        let shape = StreamShape::new(cols).expect("dead-letter stream shape is valid");
+
+        Self {
+            stream: Arc::new(StreamModel::new(name, shape)),
+            target,
+        }
+    }
+
+    pub fn stream(&self) -> &Arc<StreamModel> {
+        &self.stream
+    }
+
+    pub fn target(&self) -> &DeadLetterTarget {
+        &self.target
+    }
+}
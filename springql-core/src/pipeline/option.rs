@@ -1,24 +1,36 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 mod can_options;
+mod file_options;
 mod http1_client_options;
+mod http1_server_options;
 mod in_memory_queue_options;
+mod json_path_options;
 mod net_client_options;
 mod net_protocol;
 mod net_server_options;
+mod on_schema_error;
 mod options_builder;
+mod retry_options;
+mod table_options;
 
 pub use can_options::CANOptions;
-pub use http1_client_options::{Http1ClientOptions, HttpMethod};
+pub use file_options::{FileFormat, FileOptions};
+pub use http1_client_options::{Http1ClientOptions, HttpBodyFormat, HttpMethod};
+pub use http1_server_options::Http1ServerOptions;
 pub use in_memory_queue_options::InMemoryQueueOptions;
+pub use json_path_options::JsonPathOptions;
 pub use net_client_options::NetClientOptions;
 pub use net_protocol::NetProtocol;
 pub use net_server_options::NetServerOptions;
+pub use on_schema_error::OnSchemaError;
 pub use options_builder::OptionsBuilder;
+pub use retry_options::RetryOptions;
+pub use table_options::TableOptions;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 
 use crate::api::error::{Result, SpringError};
 
@@ -46,6 +58,25 @@ impl Options {
             })
     }
 
+    /// Optional non-negative integer option.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - the value is not a non-negative integer (within `V`).
+    pub fn get_opt_uint<V: FromStr>(&self, key: &str) -> Result<Option<V>> {
+        self.0
+            .get(key)
+            .map(|value| {
+                value.parse().map_err(|_| SpringError::InvalidOption {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    source: anyhow!("must be a non-negative integer"),
+                })
+            })
+            .transpose()
+    }
+
     pub fn as_key_values(&self) -> Vec<(&str, &str)> {
         self.0
             .iter()
@@ -0,0 +1,89 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, JsonPathOptions},
+};
+
+/// Format of files read by `FILE` source readers.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FileFormat {
+    /// A JSON object per line, or a JSON array of objects.
+    Json,
+    /// Comma-separated values with a header line of column names.
+    Csv,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct FileOptions {
+    pub path: PathBuf,
+    pub format: FileFormat,
+    pub json_paths: JsonPathOptions,
+}
+
+impl TryFrom<&Options> for FileOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let format = match options
+            .as_key_values()
+            .into_iter()
+            .find(|(key, _)| *key == "FORMAT")
+        {
+            None | Some((_, "JSON")) => FileFormat::Json,
+            Some((_, "CSV")) => FileFormat::Csv,
+            Some((key, value)) => {
+                return Err(SpringError::InvalidOption {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    source: anyhow!("FORMAT must be one of 'JSON' or 'CSV'"),
+                })
+            }
+        };
+
+        Ok(Self {
+            path: options.get("PATH", |path| Ok(PathBuf::from(path)))?,
+            format,
+            json_paths: JsonPathOptions::try_from(options)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::OptionsBuilder;
+
+    #[test]
+    fn test_file_options() {
+        let options = OptionsBuilder::default().add("PATH", "/tmp/a.csv").build();
+        let options = FileOptions::try_from(&options).unwrap();
+        assert_eq!(options.path, PathBuf::from("/tmp/a.csv"));
+        assert_eq!(options.format, FileFormat::Json);
+
+        let options = OptionsBuilder::default()
+            .add("PATH", "/tmp/a.csv")
+            .add("FORMAT", "CSV")
+            .build();
+        assert_eq!(
+            FileOptions::try_from(&options).unwrap().format,
+            FileFormat::Csv
+        );
+
+        let options = OptionsBuilder::default()
+            .add("PATH", "/tmp/a.csv")
+            .add("FORMAT", "XML")
+            .build();
+        assert!(matches!(
+            FileOptions::try_from(&options),
+            Err(SpringError::InvalidOption { .. })
+        ));
+
+        let options = OptionsBuilder::default().build();
+        assert!(FileOptions::try_from(&options).is_err());
+    }
+}
@@ -13,6 +13,8 @@ use crate::{
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum HttpMethod {
     Post,
+    Put,
+    Patch,
 }
 impl FromStr for HttpMethod {
     type Err = SpringError;
@@ -20,6 +22,8 @@ impl FromStr for HttpMethod {
     fn from_str(s: &str) -> Result<Self> {
         match s {
             "POST" => Ok(Self::Post),
+            "PUT" => Ok(Self::Put),
+            "PATCH" => Ok(Self::Patch),
             _ => Err(SpringError::InvalidOption {
                 key: "HTTP_METHOD".to_string(),
                 value: s.to_string(),
@@ -29,6 +33,45 @@ impl FromStr for HttpMethod {
     }
 }
 
+/// `BODY_FORMAT` option of HTTP1_CLIENT sink writers.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum HttpBodyFormat {
+    /// A BLOB column (`BLOB_BODY_COLUMN` option) as the body of each request (default).
+    Blob(ColumnName),
+    /// A row as a JSON object per request.
+    Json,
+    /// Batch of rows, one JSON object per line.
+    Ndjson,
+    /// Batch of rows in a JSON array.
+    JsonArray,
+}
+
+impl HttpBodyFormat {
+    fn parse(options: &Options) -> Result<Self> {
+        let format = options
+            .as_key_values()
+            .into_iter()
+            .find_map(|(k, v)| (k == "BODY_FORMAT").then_some(v))
+            .unwrap_or("BLOB");
+
+        match format {
+            "BLOB" => Ok(Self::Blob(
+                options.get("BLOB_BODY_COLUMN", |column_str| {
+                    Ok(ColumnName::new(column_str.to_string()))
+                })?,
+            )),
+            "JSON" => Ok(Self::Json),
+            "NDJSON" => Ok(Self::Ndjson),
+            "JSON_ARRAY" => Ok(Self::JsonArray),
+            _ => Err(SpringError::InvalidOption {
+                key: "BODY_FORMAT".to_string(),
+                value: format.to_string(),
+                source: anyhow::anyhow!("unsupported BODY_FORMAT {}", format),
+            }),
+        }
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct Http1ClientOptions {
     pub remote_host: IpAddr,
@@ -36,7 +79,12 @@ pub struct Http1ClientOptions {
     pub method: HttpMethod,
     pub url: Url,
     pub headers: HashMap<String, String>,
-    pub blob_body_column: ColumnName,
+    pub body_format: HttpBodyFormat,
+
+    /// `None` falls back to `SpringSinkWriterConfig`.
+    pub batch_max_rows: Option<u32>,
+    pub batch_max_bytes: Option<u32>,
+    pub batch_max_latency_msec: Option<u32>,
 }
 
 impl TryFrom<&Options> for Http1ClientOptions {
@@ -59,9 +107,10 @@ impl TryFrom<&Options> for Http1ClientOptions {
                 Url::parse(path_str.as_str()).context("failed to parse URL")
             })?,
             headers,
-            blob_body_column: options.get("BLOB_BODY_COLUMN", |column_str| {
-                Ok(ColumnName::new(column_str.to_string()))
-            })?,
+            body_format: HttpBodyFormat::parse(options)?,
+            batch_max_rows: options.get_opt_uint("BATCH_MAX_ROWS")?,
+            batch_max_bytes: options.get_opt_uint("BATCH_MAX_BYTES")?,
+            batch_max_latency_msec: options.get_opt_uint("BATCH_MAX_LATENCY_MSEC")?,
         })
     }
 }
@@ -79,3 +128,50 @@ impl Http1ClientOptions {
         headers
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::OptionsBuilder;
+
+    fn builder() -> OptionsBuilder {
+        OptionsBuilder::default()
+            .add("REMOTE_HOST", "127.0.0.1")
+            .add("REMOTE_PORT", "8080")
+            .add("METHOD", "PUT")
+            .add("URL", "http://127.0.0.1:8080/rows")
+    }
+
+    #[test]
+    fn test_body_format() {
+        let options = builder().add("BLOB_BODY_COLUMN", "body").build();
+        let options = Http1ClientOptions::try_from(&options).unwrap();
+        assert_eq!(options.method, HttpMethod::Put);
+        assert_eq!(
+            options.body_format,
+            HttpBodyFormat::Blob(ColumnName::new("body".to_string()))
+        );
+
+        let options = builder()
+            .add("BODY_FORMAT", "NDJSON")
+            .add("BATCH_MAX_ROWS", "10")
+            .build();
+        let options = Http1ClientOptions::try_from(&options).unwrap();
+        assert_eq!(options.body_format, HttpBodyFormat::Ndjson);
+        assert_eq!(options.batch_max_rows, Some(10));
+        assert_eq!(options.batch_max_bytes, None);
+
+        // BLOB_BODY_COLUMN is required for BLOB
+        let options = builder().build();
+        assert!(matches!(
+            Http1ClientOptions::try_from(&options),
+            Err(SpringError::InvalidOption { .. })
+        ));
+
+        let options = builder().add("BODY_FORMAT", "XML").build();
+        assert!(matches!(
+            Http1ClientOptions::try_from(&options),
+            Err(SpringError::InvalidOption { .. })
+        ));
+    }
+}
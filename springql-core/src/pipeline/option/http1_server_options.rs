@@ -0,0 +1,31 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::net::IpAddr;
+
+use anyhow::Context;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, JsonPathOptions, OnSchemaError},
+};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Http1ServerOptions {
+    pub host: IpAddr,
+    pub port: u16,
+    pub json_paths: JsonPathOptions,
+    pub on_schema_error: OnSchemaError,
+}
+
+impl TryFrom<&Options> for Http1ServerOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            host: options.get("HOST", |host_str| host_str.parse().context("invalid host"))?,
+            port: options.get("PORT", |port_str| port_str.parse().context("invalid port"))?,
+            json_paths: JsonPathOptions::try_from(options)?,
+            on_schema_error: OnSchemaError::try_from(options)?,
+        })
+    }
+}
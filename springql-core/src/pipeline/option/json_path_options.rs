@@ -0,0 +1,41 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{option::Options, ColumnName},
+    stream_engine::JsonPath,
+};
+
+/// `JSON_PATH_<column> '<path>'` options of source readers to map nested JSON values onto stream columns.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct JsonPathOptions {
+    pub column_paths: Vec<(ColumnName, JsonPath)>,
+}
+
+impl TryFrom<&Options> for JsonPathOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        let mut column_paths = options
+            .as_key_values()
+            .into_iter()
+            .filter_map(|(key, value)| {
+                key.strip_prefix("JSON_PATH_")
+                    .map(|column_name| (key, column_name, value))
+            })
+            .map(|(key, column_name, value)| {
+                let json_path = value.parse().map_err(|e| SpringError::InvalidOption {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    source: anyhow::Error::from(e),
+                })?;
+                Ok((ColumnName::new(column_name.to_string()), json_path))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // options are in a HashMap
+        column_paths.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(Self { column_paths })
+    }
+}
@@ -6,7 +6,7 @@ use anyhow::Context;
 
 use crate::{
     api::error::{Result, SpringError},
-    pipeline::{option::Options, NetProtocol},
+    pipeline::{option::Options, JsonPathOptions, NetProtocol},
 };
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -14,6 +14,7 @@ pub struct NetClientOptions {
     pub protocol: NetProtocol,
     pub remote_host: IpAddr,
     pub remote_port: u16,
+    pub json_paths: JsonPathOptions,
 }
 
 impl TryFrom<&Options> for NetClientOptions {
@@ -32,6 +33,7 @@ impl TryFrom<&Options> for NetClientOptions {
             remote_port: options.get("REMOTE_PORT", |remote_port_str| {
                 remote_port_str.parse().context("invalid remote port")
             })?,
+            json_paths: JsonPathOptions::try_from(options)?,
         })
     }
 }
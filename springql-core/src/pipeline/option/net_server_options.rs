@@ -4,13 +4,14 @@ use anyhow::Context;
 
 use crate::{
     api::error::{Result, SpringError},
-    pipeline::{option::Options, NetProtocol},
+    pipeline::{option::Options, JsonPathOptions, NetProtocol},
 };
 
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct NetServerOptions {
     pub protocol: NetProtocol,
     pub port: u16,
+    pub json_paths: JsonPathOptions,
 }
 
 impl TryFrom<&Options> for NetServerOptions {
@@ -26,6 +27,7 @@ impl TryFrom<&Options> for NetServerOptions {
             port: options.get("PORT", |remote_port_str| {
                 remote_port_str.parse().context("invalid port")
             })?,
+            json_paths: JsonPathOptions::try_from(options)?,
         })
     }
 }
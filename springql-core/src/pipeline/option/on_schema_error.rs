@@ -0,0 +1,54 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::str::FromStr;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::option::Options,
+};
+
+/// `ON_SCHEMA_ERROR` option of source readers.
+///
+/// Decides what to do with a source row which does not match the source stream definition.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum OnSchemaError {
+    /// Drops the row (default).
+    #[default]
+    Drop,
+    /// Missing or mistyped columns are set to NULL if nullable. Otherwise, drops the row.
+    NullFill,
+    /// Mistyped values are converted as `CAST` does (e.g. TEXT `'12.5'` to FLOAT). Drops the row if it fails.
+    Coerce,
+    /// Drops the row and stops the whole pipeline. API calls fail after that.
+    Fail,
+}
+
+impl FromStr for OnSchemaError {
+    type Err = SpringError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "DROP" => Ok(Self::Drop),
+            "NULL_FILL" => Ok(Self::NullFill),
+            "COERCE" => Ok(Self::Coerce),
+            "FAIL" => Ok(Self::Fail),
+            _ => Err(SpringError::InvalidOption {
+                key: "ON_SCHEMA_ERROR".to_string(),
+                value: s.to_string(),
+                source: anyhow::anyhow!("unsupported ON_SCHEMA_ERROR {}", s),
+            }),
+        }
+    }
+}
+
+impl TryFrom<&Options> for OnSchemaError {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        options
+            .as_key_values()
+            .into_iter()
+            .find(|(key, _)| *key == "ON_SCHEMA_ERROR")
+            .map_or(Ok(Self::default()), |(_, value)| value.parse())
+    }
+}
@@ -0,0 +1,64 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::option::Options,
+};
+
+/// Retry settings of a sink writer.
+///
+/// `None` fields fall back to `SpringSinkWriterConfig`.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct RetryOptions {
+    pub retry_max: Option<u32>,
+    pub retry_initial_backoff_msec: Option<u32>,
+    pub retry_max_backoff_msec: Option<u32>,
+    pub retry_jitter_percent: Option<u8>,
+    pub retry_buffer_max_rows: Option<u32>,
+}
+
+impl TryFrom<&Options> for RetryOptions {
+    type Error = SpringError;
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - a `RETRY_*` option is not a non-negative integer.
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            retry_max: options.get_opt_uint("RETRY_MAX")?,
+            retry_initial_backoff_msec: options.get_opt_uint("RETRY_INITIAL_BACKOFF_MSEC")?,
+            retry_max_backoff_msec: options.get_opt_uint("RETRY_MAX_BACKOFF_MSEC")?,
+            retry_jitter_percent: options.get_opt_uint("RETRY_JITTER_PERCENT")?,
+            retry_buffer_max_rows: options.get_opt_uint("RETRY_BUFFER_MAX_ROWS")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::OptionsBuilder;
+
+    #[test]
+    fn test_retry_options() {
+        let options = OptionsBuilder::default()
+            .add("RETRY_MAX", "3")
+            .add("RETRY_BUFFER_MAX_ROWS", "10")
+            .build();
+        assert_eq!(
+            RetryOptions::try_from(&options).unwrap(),
+            RetryOptions {
+                retry_max: Some(3),
+                retry_buffer_max_rows: Some(10),
+                ..RetryOptions::default()
+            }
+        );
+
+        let options = OptionsBuilder::default().add("RETRY_MAX", "-1").build();
+        assert!(matches!(
+            RetryOptions::try_from(&options),
+            Err(SpringError::InvalidOption { .. })
+        ));
+    }
+}
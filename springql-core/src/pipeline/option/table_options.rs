@@ -0,0 +1,23 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::option::Options,
+};
+
+/// Options of `CREATE TABLE`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TableOptions {
+    /// None to use `memory.table_max_rows` configuration.
+    pub max_rows: Option<u32>,
+}
+
+impl TryFrom<&Options> for TableOptions {
+    type Error = SpringError;
+
+    fn try_from(options: &Options) -> Result<Self> {
+        Ok(Self {
+            max_rows: options.get_opt_uint("MAX_ROWS")?,
+        })
+    }
+}
@@ -90,6 +90,18 @@ impl PipelineGraph {
             })
             .collect()
     }
+    /// A pump with 2 or more upstreams appears only once.
+    pub fn all_pumps(&self) -> Vec<&PumpModel> {
+        let mut pumps: Vec<&PumpModel> = vec![];
+        for edge in self.graph.edge_references() {
+            if let Edge::Pump { pump_model, .. } = edge.weight() {
+                if !pumps.iter().any(|p| p.name() == pump_model.name()) {
+                    pumps.push(pump_model.as_ref());
+                }
+            }
+        }
+        pumps
+    }
     pub fn all_sinks(&self) -> Vec<&SinkWriterModel> {
         self.graph
             .edge_references()
@@ -6,10 +6,10 @@ mod window_parameter;
 
 pub use pump_input_type::PumpInputType;
 pub use window_operation_parameter::{
-    AggregateFunctionParameter, AggregateParameter, GroupByLabels, JoinParameter, JoinType,
-    WindowOperationParameter,
+    AggrExprParameter, AggregateFunctionParameter, AggregateParameter, GroupByLabels,
+    JoinParameter, JoinType, WindowOperationParameter,
 };
-pub use window_parameter::WindowParameter;
+pub use window_parameter::{WindowLengthPeriod, WindowParameter};
 
 use crate::{
     pipeline::name::{PumpName, StreamName},
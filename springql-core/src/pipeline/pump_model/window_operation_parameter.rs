@@ -3,7 +3,9 @@
 mod aggregate;
 mod join_parameter;
 
-pub use aggregate::{AggregateFunctionParameter, AggregateParameter, GroupByLabels};
+pub use aggregate::{
+    AggrExprParameter, AggregateFunctionParameter, AggregateParameter, GroupByLabels,
+};
 pub use join_parameter::{JoinParameter, JoinType};
 
 /// Window operation parameters
@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use ordered_float::OrderedFloat;
+
 use crate::expr_resolver::{AggrExprLabel, ValueExprLabel};
 
 /// [GROUP BY c1, c2, c3...]
@@ -14,23 +16,42 @@ impl GroupByLabels {
     }
 }
 
-/// TODO `support complex expression with aggregations`
-///
 /// ```sql
-/// SELECT group_by, aggr_expr.func(aggr_expr.aggregated)
+/// SELECT group_by, aggr_exprs[0].func(aggr_exprs[0].aggregated), aggr_exprs[1].func(...) ...
 ///   FROM s
 ///   [GROUP BY group_by]
 ///   SLIDING WINDOW ...;
 /// ```
 #[derive(Clone, PartialEq, Eq, Debug, new)]
 pub struct AggregateParameter {
-    // TODO multiple aggr_expr
+    /// Each aggregate expression is calculated only once even if it appears several times in a query.
+    pub aggr_exprs: Vec<AggrExprParameter>,
+    pub group_by: GroupByLabels,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, new)]
+pub struct AggrExprParameter {
     pub aggr_func: AggregateFunctionParameter,
     pub aggr_expr: AggrExprLabel,
-    pub group_by: GroupByLabels,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum AggregateFunctionParameter {
     Avg,
+    Sum,
+    /// `COUNT(*)` is parsed as `COUNT(1)`.
+    Count,
+    Min,
+    Max,
+    VarPop,
+    StddevPop,
+    StddevSamp,
+    /// Value with the earliest rowtime.
+    FirstValue,
+    /// Value with the latest rowtime.
+    LastValue,
+    /// `APPROX_PERCENTILE(x, percentile)`. `percentile` is in `[0.0, 1.0]`.
+    ApproxPercentile {
+        percentile: OrderedFloat<f64>,
+    },
 }
@@ -20,9 +20,17 @@ pub struct JoinParameter {
     pub right_colrefs: Vec<ColumnReference>,
 
     pub on_expr: ValueExprLabel,
+
+    /// `(left column, right column)` pairs which `on_expr` requires to be equal.
+    ///
+    /// When not empty, panes look up right tuples by hash of these columns instead of evaluating `on_expr` for every pair of tuples.
+    pub equi_keys: Vec<(ColumnReference, ColumnReference)>,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum JoinType {
+    Inner,
     LeftOuter,
+    RightOuter,
+    FullOuter,
 }
@@ -1,8 +1,9 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::stream_engine::time::SpringEventDuration;
+use crate::stream_engine::time::{SpringDuration, SpringEventDuration};
 
 /// Window parameters
+#[allow(clippy::enum_variant_names)]
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum WindowParameter {
     /// Time-based sliding window
@@ -39,27 +40,110 @@ pub enum WindowParameter {
         length: SpringEventDuration,
         allowed_delay: SpringEventDuration,
     },
+
+    /// Session window
+    ///
+    /// A session is kept per GROUP BY group. A row opens a session `[rowtime, rowtime + gap)` and
+    /// overlapping sessions are merged. A session closes when watermark passes last rowtime + gap.
+    ///
+    /// ```text
+    /// gap = 5sec, allowed_delay = 0;
+    ///
+    /// rows   x  x    x           x  x
+    /// pane1  |            |
+    /// pane2                      |       |
+    ///
+    /// -------------------------------------------> t
+    ///        :00  :05  :10  :15  :20  :25  :30
+    /// ```
+    SessionWindow {
+        gap: SpringEventDuration,
+        allowed_delay: SpringEventDuration,
+    },
+
+    /// Count-based sliding window
+    ///
+    /// Rows are counted in arrival order regardless of their rowtime. A pane closes as soon as it gets `length` rows.
+    ///
+    /// ```text
+    /// length = 4 rows, period = 2 rows;
+    ///
+    /// pane1 |       |
+    /// pane2     |       |
+    /// pane3         |       |
+    ///
+    /// -----------------------------------> rows
+    ///       0 1 2 3 4 5 6 7 8
+    /// ```
+    CountSlidingWindow { length: u64, period: u64 },
+
+    /// Count-based fixed window
+    ///
+    /// Rows are counted in arrival order regardless of their rowtime. A pane closes as soon as it gets `length` rows.
+    ///
+    /// ```text
+    /// length = 4 rows;
+    ///
+    /// pane1 |       |
+    /// pane2         |       |
+    ///
+    /// -----------------------------------> rows
+    ///       0 1 2 3 4 5 6 7 8
+    /// ```
+    CountFixedWindow { length: u64 },
 }
 
-impl WindowParameter {
-    pub fn length(&self) -> SpringEventDuration {
-        match self {
-            WindowParameter::TimedSlidingWindow { length, .. } => *length,
-            WindowParameter::TimedFixedWindow { length, .. } => *length,
-        }
-    }
+/// Length and period of panes, in event time or in number of rows.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum WindowLengthPeriod {
+    EventTime {
+        length: SpringEventDuration,
+        period: SpringEventDuration,
+    },
+    Count {
+        length: u64,
+        period: u64,
+    },
+}
 
-    pub fn period(&self) -> SpringEventDuration {
+impl WindowParameter {
+    /// Length and period of a session window are the gap (a session with a single row).
+    /// Fixed windows have the length as the period.
+    pub fn length_period(&self) -> WindowLengthPeriod {
         match self {
-            WindowParameter::TimedSlidingWindow { period, .. } => *period,
-            WindowParameter::TimedFixedWindow { length, .. } => *length,
+            WindowParameter::TimedSlidingWindow { length, period, .. } => {
+                WindowLengthPeriod::EventTime {
+                    length: *length,
+                    period: *period,
+                }
+            }
+            WindowParameter::TimedFixedWindow { length, .. } => WindowLengthPeriod::EventTime {
+                length: *length,
+                period: *length,
+            },
+            WindowParameter::SessionWindow { gap, .. } => WindowLengthPeriod::EventTime {
+                length: *gap,
+                period: *gap,
+            },
+            WindowParameter::CountSlidingWindow { length, period } => WindowLengthPeriod::Count {
+                length: *length,
+                period: *period,
+            },
+            WindowParameter::CountFixedWindow { length } => WindowLengthPeriod::Count {
+                length: *length,
+                period: *length,
+            },
         }
     }
 
+    /// Count-based windows do not wait for late rows (zero).
     pub fn allowed_delay(&self) -> SpringEventDuration {
         match self {
             WindowParameter::TimedSlidingWindow { allowed_delay, .. } => *allowed_delay,
             WindowParameter::TimedFixedWindow { allowed_delay, .. } => *allowed_delay,
+            WindowParameter::SessionWindow { allowed_delay, .. } => *allowed_delay,
+            WindowParameter::CountSlidingWindow { .. }
+            | WindowParameter::CountFixedWindow { .. } => SpringEventDuration::from_secs(0),
         }
     }
 }
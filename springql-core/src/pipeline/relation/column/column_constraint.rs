@@ -4,4 +4,7 @@
 #[derive(Clone, Eq, PartialEq, Hash, Debug, new)]
 pub enum ColumnConstraint {
     Rowtime,
+
+    /// Key column of a table (implies NOT NULL).
+    PrimaryKey,
 }
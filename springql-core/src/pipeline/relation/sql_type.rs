@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::mem;
+
 /// SQL type.
 ///
 /// SQL types are hierarchically categorized as follows:
@@ -84,6 +86,18 @@ impl SqlType {
     pub fn duration() -> SqlType {
         SqlType::DurationComparable
     }
+
+    /// Whether values of `self` and `other` are loosely typed as the same Rust type.
+    ///
+    /// Such values have the same `SqlValueHashKey` if they are equal.
+    pub fn is_same_loose_type(&self, other: &SqlType) -> bool {
+        match (self, other) {
+            (SqlType::NumericComparable(n1), SqlType::NumericComparable(n2)) => {
+                mem::discriminant(n1) == mem::discriminant(n2)
+            }
+            _ => mem::discriminant(self) == mem::discriminant(other),
+        }
+    }
 }
 
 /// Numeric types (comparable).
@@ -5,6 +5,8 @@
 pub enum SourceReaderType {
     NetClient,
     NetServer,
+    Http1Server,
+    File,
 
     InMemoryQueue,
 }
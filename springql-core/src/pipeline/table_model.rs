@@ -0,0 +1,92 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{
+        field::ColumnReference,
+        name::{ColumnName, StreamName},
+        option::{Options, TableOptions},
+        relation::ColumnConstraint,
+        stream_model::{StreamModel, StreamShape},
+    },
+};
+
+/// Keyed and bounded in-memory relation.
+///
+/// A table is filled by source readers (a row with an existing key replaces the old row) and looked up from pumps by `JOIN` without windows.
+///
+/// ```sql
+/// CREATE TABLE vehicle_model (
+///   vin TEXT PRIMARY KEY,
+///   model TEXT NOT NULL
+/// ) OPTIONS (
+///   MAX_ROWS '1000'
+/// );
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TableModel {
+    stream: Arc<StreamModel>,
+    key: Vec<ColumnName>,
+    options: TableOptions,
+}
+
+impl TableModel {
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - no column has `PRIMARY KEY` constraint.
+    /// - `SpringError::InvalidOption` when:
+    ///   - `options` are invalid as `TableOptions`.
+    pub fn new(name: StreamName, shape: StreamShape, options: Options) -> Result<Self> {
+        let key = shape
+            .columns()
+            .iter()
+            .filter(|cd| {
+                cd.column_constraints()
+                    .contains(&ColumnConstraint::PrimaryKey)
+            })
+            .map(|cd| cd.column_name().clone())
+            .collect::<Vec<_>>();
+        if key.is_empty() {
+            return Err(SpringError::Sql(anyhow!(
+                r#"table "{}" must have PRIMARY KEY column(s)"#,
+                name
+            )));
+        }
+        let options = TableOptions::try_from(&options)?;
+
+        Ok(Self {
+            stream: Arc::new(StreamModel::new(name, shape)),
+            key,
+            options,
+        })
+    }
+
+    pub fn name(&self) -> &StreamName {
+        self.stream.name()
+    }
+
+    /// Name and shape of the table.
+    pub fn stream(&self) -> &Arc<StreamModel> {
+        &self.stream
+    }
+
+    /// PRIMARY KEY columns in the defined order.
+    pub fn key_colrefs(&self) -> Vec<ColumnReference> {
+        self.key
+            .iter()
+            .map(|column_name| ColumnReference::Column {
+                stream_name: self.name().clone(),
+                column_name: column_name.clone(),
+            })
+            .collect()
+    }
+
+    pub fn options(&self) -> &TableOptions {
+        &self.options
+    }
+}
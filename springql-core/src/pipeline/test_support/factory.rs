@@ -40,10 +40,46 @@ impl ValueExpr {
         Self::BinaryExpr(BinaryExpr::factory_eq(left, right))
     }
 
+    pub fn factory_ne(left: ValueExpr, right: ValueExpr) -> Self {
+        Self::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+            ComparisonFunction::NotEqualVariant {
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        ))
+    }
+
+    pub fn factory_lt(left: ValueExpr, right: ValueExpr) -> Self {
+        Self::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+            ComparisonFunction::LessThanVariant {
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        ))
+    }
+
     pub fn factory_add(left: ValueExpr, right: ValueExpr) -> Self {
         Self::BinaryExpr(BinaryExpr::factory_add(left, right))
     }
 
+    pub fn factory_sub(left: ValueExpr, right: ValueExpr) -> Self {
+        Self::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+            NumericalFunction::SubVariant {
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        ))
+    }
+
+    pub fn factory_mul(left: ValueExpr, right: ValueExpr) -> Self {
+        Self::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+            NumericalFunction::MulVariant {
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        ))
+    }
+
     pub fn factory_and(left: ValueExpr, right: ValueExpr) -> Self {
         Self::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
             LogicalFunction::AndVariant {
@@ -52,6 +88,19 @@ impl ValueExpr {
             },
         ))
     }
+
+    pub fn factory_or(left: ValueExpr, right: ValueExpr) -> Self {
+        Self::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+            LogicalFunction::OrVariant {
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        ))
+    }
+
+    pub fn factory_bool(b: bool) -> Self {
+        Self::Constant(SqlValue::factory_bool(b))
+    }
 }
 
 impl<E: ValueExprType> BinaryExpr<E> {
@@ -9,9 +9,14 @@ pub use sql_parser::{
     SubFromItemSyntax,
 };
 
+use anyhow::anyhow;
+
 use crate::{
-    api::error::Result,
-    pipeline::{Pipeline, PumpModel, SinkWriterModel, SourceReaderModel, StreamModel},
+    api::error::{Result, SpringError},
+    pipeline::{
+        ColumnConstraint, DeadLetterStreamModel, DeadLetterTarget, OnSchemaError, Pipeline,
+        PumpModel, SinkWriterModel, SourceReaderModel, StreamModel, TableModel,
+    },
     sql_processor::query_planner::QueryPlanner,
     stream_engine::command::{AlterPipelineCommand, Command, QueryPlan},
 };
@@ -23,6 +28,7 @@ impl SqlProcessor {
     /// # Failures
     ///
     /// - `SpringError::Sql` on syntax and semantics error.
+    /// - `SpringError::InvalidOption` on invalid `ON_SCHEMA_ERROR` option of source readers.
     pub fn compile<S: Into<String>>(&self, sql: S, pipeline: &Pipeline) -> Result<Command> {
         let command = match self.0.parse(sql)? {
             ParseSuccess::CreateSourceStream(source_stream_model) => {
@@ -37,12 +43,18 @@ impl SqlProcessor {
             ParseSuccess::CreateSinkStream(sink_stream_model) => {
                 self.compile_create_sink_stream(sink_stream_model, pipeline)?
             }
+            ParseSuccess::CreateDeadLetterStream(dead_letter_stream_model) => {
+                self.compile_create_dead_letter_stream(dead_letter_stream_model, pipeline)?
+            }
             ParseSuccess::CreateSinkWriter(sink_writer_model) => {
                 self.compile_create_sink_writer(sink_writer_model, pipeline)?
             }
             ParseSuccess::CreatePump(create_pump) => {
                 self.compile_create_pump(*create_pump, pipeline)?
             }
+            ParseSuccess::CreateTable(table_model) => {
+                self.compile_create_table(table_model, pipeline)?
+            }
         };
         Ok(command)
     }
@@ -53,6 +65,7 @@ impl SqlProcessor {
         _pipeline: &Pipeline,
     ) -> Result<Command> {
         // TODO semantic check
+        Self::validate_no_primary_key(&source_stream_model)?;
         Ok(Command::AlterPipeline(
             AlterPipelineCommand::CreateSourceStream(source_stream_model),
         ))
@@ -64,6 +77,7 @@ impl SqlProcessor {
         _pipeline: &Pipeline,
     ) -> Result<Command> {
         // TODO semantic check
+        OnSchemaError::try_from(source_reader_model.options())?;
         Ok(Command::AlterPipeline(
             AlterPipelineCommand::CreateSourceReader(source_reader_model),
         ))
@@ -75,6 +89,7 @@ impl SqlProcessor {
         _pipeline: &Pipeline,
     ) -> Result<Command> {
         // TODO semantic check
+        Self::validate_no_primary_key(&stream_model)?;
         Ok(Command::AlterPipeline(AlterPipelineCommand::CreateStream(
             stream_model,
         )))
@@ -86,23 +101,83 @@ impl SqlProcessor {
         _pipeline: &Pipeline,
     ) -> Result<Command> {
         // TODO semantic check
+        Self::validate_no_primary_key(&sink_stream_model)?;
         Ok(Command::AlterPipeline(
             AlterPipelineCommand::CreateSinkStream(sink_stream_model),
         ))
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Target pump, source reader or sink writer is not found in pipeline
+    fn compile_create_dead_letter_stream(
+        &self,
+        dead_letter_stream_model: DeadLetterStreamModel,
+        pipeline: &Pipeline,
+    ) -> Result<Command> {
+        let target_exists = match dead_letter_stream_model.target() {
+            DeadLetterTarget::Pump(name) => pipeline.all_pumps().iter().any(|p| p.name() == name),
+            DeadLetterTarget::SourceReader(name) => {
+                pipeline.all_sources().iter().any(|s| s.name() == name)
+            }
+            DeadLetterTarget::SinkWriter(name) => {
+                pipeline.all_sinks().iter().any(|s| s.name() == name)
+            }
+        };
+        if !target_exists {
+            return Err(SpringError::Sql(anyhow!(
+                r#"target of dead-letter stream "{}" is not found: {:?}"#,
+                dead_letter_stream_model.stream().name(),
+                dead_letter_stream_model.target()
+            )));
+        }
+        Ok(Command::AlterPipeline(
+            AlterPipelineCommand::CreateDeadLetterStream(dead_letter_stream_model),
+        ))
+    }
+
     fn compile_create_sink_writer(
         &self,
         sink_writer_model: SinkWriterModel,
-        _pipeline: &Pipeline,
+        pipeline: &Pipeline,
     ) -> Result<Command> {
         // TODO semantic check
+        if pipeline
+            .get_table(sink_writer_model.sink_upstream())
+            .is_some()
+        {
+            return Err(SpringError::Sql(anyhow!(
+                r#"sink writer cannot be created for table "{}""#,
+                sink_writer_model.sink_upstream()
+            )));
+        }
         Ok(Command::AlterPipeline(
             AlterPipelineCommand::CreateSinkWriter(sink_writer_model),
         ))
     }
 
+    fn compile_create_table(
+        &self,
+        table_model: TableModel,
+        _pipeline: &Pipeline,
+    ) -> Result<Command> {
+        Ok(Command::AlterPipeline(AlterPipelineCommand::CreateTable(
+            table_model,
+        )))
+    }
+
     fn compile_create_pump(&self, create_pump: CreatePump, pipeline: &Pipeline) -> Result<Command> {
+        if pipeline
+            .get_table(create_pump.insert_plan.stream())
+            .is_some()
+        {
+            return Err(SpringError::Sql(anyhow!(
+                r#"pump cannot insert into table "{}""#,
+                create_pump.insert_plan.stream()
+            )));
+        }
+
         let query_plan = self.compile_select_stream(create_pump.select_stream_syntax, pipeline)?;
         let pump = PumpModel::new(create_pump.pump_name, query_plan, create_pump.insert_plan);
         Ok(Command::AlterPipeline(AlterPipelineCommand::CreatePump(
@@ -118,6 +193,25 @@ impl SqlProcessor {
         let planner = QueryPlanner::new(select_stream_syntax);
         planner.plan(pipeline)
     }
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - any column has `PRIMARY KEY` constraint, which is only for tables.
+    fn validate_no_primary_key(stream_model: &StreamModel) -> Result<()> {
+        let has_primary_key = stream_model.shape().columns().iter().any(|cd| {
+            cd.column_constraints()
+                .contains(&ColumnConstraint::PrimaryKey)
+        });
+        if has_primary_key {
+            Err(SpringError::Sql(anyhow!(
+                r#"PRIMARY KEY is only for tables but stream "{}" has it"#,
+                stream_model.name()
+            )))
+        } else {
+            Ok(())
+        }
+    }
 }
 
 #[cfg(test)]
@@ -241,4 +335,41 @@ mod tests {
             Command::AlterPipeline(AlterPipelineCommand::CreateSinkWriter(expected_sink))
         );
     }
+
+    #[test]
+    fn test_create_dead_letter_stream() {
+        let processor = SqlProcessor::default();
+        let mut pipeline = Pipeline::fx_sink_only();
+        pipeline
+            .add_sink_writer(SinkWriterModel::new(
+                SinkWriterName::new("tcp_sink_trade".to_string()),
+                SinkWriterType::Net,
+                StreamName::new("sink_1".to_string()),
+                OptionsBuilder::default().build(),
+            ))
+            .unwrap();
+
+        let sql = "CREATE SINK STREAM dl AS DEAD LETTER FOR SINK WRITER tcp_sink_trade;";
+        let command = processor.compile(sql, &pipeline).unwrap();
+        assert_eq!(
+            command,
+            Command::AlterPipeline(AlterPipelineCommand::CreateDeadLetterStream(
+                DeadLetterStreamModel::new(
+                    StreamName::new("dl".to_string()),
+                    DeadLetterTarget::SinkWriter(SinkWriterName::new("tcp_sink_trade".to_string())),
+                )
+            ))
+        );
+
+        for sql in [
+            "CREATE SINK STREAM dl AS DEAD LETTER FOR PUMP pu_a;",
+            "CREATE SINK STREAM dl AS DEAD LETTER FOR SOURCE READER tcp_source;",
+            "CREATE SINK STREAM dl AS DEAD LETTER FOR SINK WRITER tcp_sink_1;",
+        ] {
+            assert!(matches!(
+                processor.compile(sql, &pipeline).unwrap_err(),
+                SpringError::Sql(_)
+            ));
+        }
+    }
 }
@@ -46,6 +46,9 @@
 //! projection
 //!  ^
 //!  |
+//! having
+//!  ^
+//!  |
 //! group aggregation (window)
 //!
 //! Tuple
@@ -57,18 +60,22 @@
 
 mod select_syntax_analyzer;
 
+use anyhow::anyhow;
+
 use crate::{
-    api::error::Result,
-    expr_resolver::{ExprLabel, ExprResolver},
+    api::error::{Result, SpringError},
+    expr_resolver::ExprResolver,
     pipeline::{
-        AggregateParameter, GroupByLabels, Pipeline, WindowOperationParameter, WindowParameter,
+        AggrExprParameter, AggregateParameter, GroupByLabels, Pipeline, WindowOperationParameter,
+        WindowParameter,
     },
     sql_processor::{
         query_planner::select_syntax_analyzer::SelectSyntaxAnalyzer,
         sql_parser::{GroupingElementSyntax, SelectStreamSyntax},
     },
     stream_engine::command::{
-        GroupAggregateWindowOp, JoinOp, LowerOps, ProjectionOp, QueryPlan, UpperOps,
+        GroupAggregateWindowOp, HavingOp, JoinOp, LowerOps, ProjectionOp, QueryPlan, SelectionOp,
+        UpperOps,
     },
 };
 
@@ -84,34 +91,44 @@ impl QueryPlanner {
         }
     }
 
-    pub fn plan(self, pipeline: &Pipeline) -> Result<QueryPlan> {
+    pub fn plan(mut self, pipeline: &Pipeline) -> Result<QueryPlan> {
+        self.analyzer = self.analyzer.unify_branch_types(pipeline)?;
+
         let (mut expr_resolver, labels_select_list) =
             ExprResolver::new(self.analyzer.select_list().to_vec());
         let projection = ProjectionOp {
             expr_labels: labels_select_list,
         };
 
-        let group_aggr_window =
-            self.create_group_aggr_window_op(&projection, &mut expr_resolver)?;
+        // aggregate expressions only in HAVING clause must be registered before aggregation gets planned
+        let having = self.create_having_op(&mut expr_resolver);
+        let group_aggr_window = self.create_group_aggr_window_op(&mut expr_resolver)?;
+
+        if having.is_some() && group_aggr_window.is_none() {
+            return Err(SpringError::Sql(anyhow!(
+                "HAVING clause requires aggregate functions and a window"
+            )));
+        }
 
         let upper_ops = UpperOps {
             projection,
             group_aggr_window,
+            having,
         };
 
         let join = self.create_join_op(&mut expr_resolver, pipeline)?;
-        let lower_ops = LowerOps { join };
+        let selection = self.create_selection_op(&mut expr_resolver);
+        let lower_ops = LowerOps { join, selection };
 
         Ok(QueryPlan::new(upper_ops, lower_ops, expr_resolver))
     }
 
     fn create_group_aggr_window_op(
         &self,
-        projection_op: &ProjectionOp,
         expr_resolver: &mut ExprResolver,
     ) -> Result<Option<GroupAggregateWindowOp>> {
         let window_param = self.create_window_param();
-        let group_aggr_param = self.create_group_aggr_param(expr_resolver, projection_op)?;
+        let group_aggr_param = self.create_group_aggr_param(expr_resolver)?;
 
         match (window_param, group_aggr_param) {
             (Some(window_param), Some(group_aggr_param)) => Ok(Some(GroupAggregateWindowOp {
@@ -129,49 +146,52 @@ impl QueryPlanner {
     fn create_group_aggr_param(
         &self,
         expr_resolver: &mut ExprResolver,
-        projection_op: &ProjectionOp,
     ) -> Result<Option<AggregateParameter>> {
-        let grouping_elements = self.analyzer.grouping_elements();
-        let aggr_labels = projection_op
-            .expr_labels
-            .iter()
-            .filter_map(|label| {
-                if let ExprLabel::Aggr(aggr_label) = label {
-                    Some(*aggr_label)
-                } else {
-                    None
-                }
+        let aggr_exprs = expr_resolver
+            .aggr_labels()
+            .into_iter()
+            .map(|aggr_label| {
+                let aggr_func = expr_resolver.resolve_aggr_expr(aggr_label).func;
+                AggrExprParameter::new(aggr_func, aggr_label)
             })
             .collect::<Vec<_>>();
 
-        match aggr_labels.len() {
-            1 => {
-                let aggr_label = aggr_labels.get(0).expect("len checked");
-                let aggr_func = expr_resolver.resolve_aggr_expr(*aggr_label).func;
-
-                let group_by_labels = grouping_elements
-                    .iter()
-                    .map(|grouping_elem| match grouping_elem {
-                        GroupingElementSyntax::ValueExpr(expr) => {
-                            Ok(expr_resolver.register_value_expr(expr.clone()))
-                        }
-                        GroupingElementSyntax::ValueAlias(alias) => {
-                            expr_resolver.resolve_value_alias(alias.clone())
-                        }
-                    })
-                    .collect::<Result<Vec<_>>>()?;
-
-                Ok(Some(AggregateParameter::new(
-                    aggr_func,
-                    *aggr_label,
-                    GroupByLabels::new(group_by_labels),
-                )))
-            }
-            0 => Ok(None),
-            _ => unimplemented!("2 or more aggregate expressions"),
+        if aggr_exprs.is_empty() {
+            Ok(None)
+        } else {
+            let group_by_labels = self
+                .analyzer
+                .grouping_elements()
+                .iter()
+                .map(|grouping_elem| match grouping_elem {
+                    GroupingElementSyntax::ValueExpr(expr) => {
+                        Ok(expr_resolver.register_value_expr(expr.clone()))
+                    }
+                    GroupingElementSyntax::ValueAlias(alias) => {
+                        expr_resolver.resolve_value_alias(alias.clone())
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(Some(AggregateParameter::new(
+                aggr_exprs,
+                GroupByLabels::new(group_by_labels),
+            )))
         }
     }
 
+    fn create_selection_op(&self, expr_resolver: &mut ExprResolver) -> Option<SelectionOp> {
+        self.analyzer.where_clause().map(|where_expr| SelectionOp {
+            predicate: expr_resolver.register_value_expr(where_expr),
+        })
+    }
+
+    fn create_having_op(&self, expr_resolver: &mut ExprResolver) -> Option<HavingOp> {
+        self.analyzer.having_clause().map(|having_expr| HavingOp {
+            predicate: expr_resolver.register_value_expr(having_expr),
+        })
+    }
+
     fn create_join_op(
         &self,
         expr_resolver: &mut ExprResolver,
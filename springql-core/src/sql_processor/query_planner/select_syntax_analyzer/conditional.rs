@@ -0,0 +1,96 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::Result,
+    expression::{AggrExpr, ValueExpr},
+    pipeline::{ColumnReference, Pipeline, SqlType},
+    sql_processor::{
+        query_planner::SelectSyntaxAnalyzer,
+        sql_parser::{FromItemSyntax, GroupingElementSyntax, SelectFieldSyntax},
+    },
+};
+
+impl SelectSyntaxAnalyzer {
+    /// Casts the branches of conditional expressions in the query into their common types.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - branches of a conditional expression have incompatible types.
+    pub fn unify_branch_types(self, pipeline: &Pipeline) -> Result<Self> {
+        let column_type = |colref: &ColumnReference| match colref {
+            ColumnReference::Column {
+                stream_name,
+                column_name,
+            } => pipeline.get_stream(stream_name).ok().and_then(|stream| {
+                stream
+                    .shape()
+                    .columns()
+                    .iter()
+                    .find(|column| column.column_name() == column_name)
+                    .map(|column| column.column_data_type().sql_type().clone())
+            }),
+            ColumnReference::PTime { .. } => Some(SqlType::timestamp()),
+        };
+        let unify = |expr: ValueExpr| expr.unify_branch_types(&column_type);
+
+        let mut select_syntax = self.select_syntax;
+        select_syntax.fields = select_syntax
+            .fields
+            .into_iter()
+            .map(|field| match field {
+                SelectFieldSyntax::ValueExpr { value_expr, alias } => {
+                    Ok(SelectFieldSyntax::ValueExpr {
+                        value_expr: unify(value_expr)?,
+                        alias,
+                    })
+                }
+                SelectFieldSyntax::AggrExpr { aggr_expr, alias } => {
+                    let aggregated = unify(aggr_expr.aggregated)?;
+                    Ok(SelectFieldSyntax::AggrExpr {
+                        aggr_expr: AggrExpr {
+                            func: aggr_expr.func,
+                            aggregated,
+                        },
+                        alias,
+                    })
+                }
+            })
+            .collect::<Result<_>>()?;
+        select_syntax.from_item = Self::unify_from_item(select_syntax.from_item, &unify)?;
+        select_syntax.where_clause = select_syntax.where_clause.map(unify).transpose()?;
+        select_syntax.grouping_elements = select_syntax
+            .grouping_elements
+            .into_iter()
+            .map(|grouping_elem| match grouping_elem {
+                GroupingElementSyntax::ValueExpr(expr) => {
+                    unify(expr).map(GroupingElementSyntax::ValueExpr)
+                }
+                alias @ GroupingElementSyntax::ValueAlias(_) => Ok(alias),
+            })
+            .collect::<Result<_>>()?;
+        select_syntax.having_clause = select_syntax.having_clause.map(unify).transpose()?;
+
+        Ok(Self { select_syntax })
+    }
+
+    fn unify_from_item<F>(from_item: FromItemSyntax, unify: &F) -> Result<FromItemSyntax>
+    where
+        F: Fn(ValueExpr) -> Result<ValueExpr>,
+    {
+        match from_item {
+            FromItemSyntax::StreamVariant(_) => Ok(from_item),
+            FromItemSyntax::JoinVariant {
+                left,
+                right,
+                join_type,
+                on_expr,
+            } => Ok(FromItemSyntax::JoinVariant {
+                left,
+                right: Box::new(Self::unify_from_item(*right, unify)?),
+                join_type,
+                on_expr: unify(on_expr)?,
+            }),
+        }
+    }
+}
@@ -1,20 +1,26 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use anyhow::anyhow;
+
 use crate::{
-    api::error::Result,
+    api::error::{Result, SpringError},
     expr_resolver::ExprResolver,
-    pipeline::{JoinParameter, Pipeline},
+    expression::{BinaryExpr, ComparisonFunction, LogicalFunction, ValueExpr},
+    pipeline::{
+        ColumnReference, JoinParameter, JoinType, Pipeline, SqlType, StreamModel, TableModel,
+    },
     sql_processor::{
         query_planner::SelectSyntaxAnalyzer,
         sql_parser::{FromItemSyntax, SubFromItemSyntax},
     },
-    stream_engine::command::{CollectOp, JoinOp, JoinWindowOp},
+    stream_engine::command::{CollectOp, JoinOp, JoinWindowOp, LookupJoinOp},
 };
 
 impl SelectSyntaxAnalyzer {
     pub fn join_op(&self, expr_resolver: &mut ExprResolver, pipeline: &Pipeline) -> Result<JoinOp> {
         match self.select_syntax.from_item.clone() {
             FromItemSyntax::StreamVariant(sub_from_item) => {
+                Self::validate_not_table(&sub_from_item, pipeline)?;
                 let collect_op = Self::sub_from_item_to_collect_op(sub_from_item);
                 Ok(JoinOp::Collect(collect_op))
             }
@@ -29,20 +35,32 @@ impl SelectSyntaxAnalyzer {
                     FromItemSyntax::JoinVariant { .. } => unimplemented!("recursive join"),
                 };
 
+                Self::validate_not_table(&left_sub, pipeline)?;
+
                 let left_collect_op = Self::sub_from_item_to_collect_op(left_sub.clone());
                 let right_collect_op = Self::sub_from_item_to_collect_op(right_sub.clone());
 
-                let left_colrefs = pipeline
-                    .get_stream(&left_sub.stream_name)?
-                    .column_references();
-                let right_colrefs = pipeline
-                    .get_stream(&right_sub.stream_name)?
-                    .column_references();
+                let left_stream = pipeline.get_stream(&left_sub.stream_name)?;
+                let right_stream = pipeline.get_stream(&right_sub.stream_name)?;
 
+                let equi_keys = Self::equi_keys(&on_expr, &left_stream, &right_stream);
                 let on_expr_label = expr_resolver.register_value_expr(on_expr);
 
-                let join_param =
-                    JoinParameter::new(join_type, left_colrefs, right_colrefs, on_expr_label);
+                let join_param = JoinParameter::new(
+                    join_type,
+                    left_stream.column_references(),
+                    right_stream.column_references(),
+                    on_expr_label,
+                    equi_keys,
+                );
+
+                if let Some(table) = pipeline.get_table(&right_sub.stream_name) {
+                    return Ok(JoinOp::LookupJoin(Self::lookup_join_op(
+                        left_collect_op,
+                        table,
+                        join_param,
+                    )?));
+                }
 
                 let window_param = self
                     .window_parameter()
@@ -58,9 +76,209 @@ impl SelectSyntaxAnalyzer {
         }
     }
 
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - JOIN type is other than `INNER` and `LEFT OUTER` (a table is not a stream to emit unjoined rows).
+    fn lookup_join_op(
+        left: CollectOp,
+        table: &TableModel,
+        join_param: JoinParameter,
+    ) -> Result<LookupJoinOp> {
+        match join_param.join_type {
+            JoinType::Inner | JoinType::LeftOuter => {}
+            _ => {
+                return Err(SpringError::Sql(anyhow!(
+                    r#"only INNER JOIN and LEFT OUTER JOIN are supported for table "{}""#,
+                    table.name()
+                )))
+            }
+        }
+
+        let lookup_key = table
+            .key_colrefs()
+            .iter()
+            .map(|key_colref| {
+                join_param
+                    .equi_keys
+                    .iter()
+                    .find(|(_, right_colref)| right_colref == key_colref)
+                    .map(|(left_colref, _)| left_colref.clone())
+            })
+            .collect::<Option<Vec<_>>>();
+
+        Ok(LookupJoinOp {
+            left,
+            table: table.name().clone(),
+            join_param,
+            lookup_key,
+        })
+    }
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `sub_from_item` is a table. Tables can only be the right side of JOIN.
+    fn validate_not_table(sub_from_item: &SubFromItemSyntax, pipeline: &Pipeline) -> Result<()> {
+        if pipeline.get_table(&sub_from_item.stream_name).is_some() {
+            Err(SpringError::Sql(anyhow!(
+                r#"table "{}" can only be the right side of JOIN"#,
+                sub_from_item.stream_name
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Extracts `left.c = right.c` terms from the AND-conjunction in ON clause.
+    ///
+    /// Other terms (`OR`, `<`, expressions, ...) are not extracted but still evaluated as a part of ON expression.
+    /// Columns of different loose types (e.g. `INTEGER` and `FLOAT`) are not extracted either because their hash values differ even if they are equal.
+    fn equi_keys(
+        on_expr: &ValueExpr,
+        left_stream: &StreamModel,
+        right_stream: &StreamModel,
+    ) -> Vec<(ColumnReference, ColumnReference)> {
+        match on_expr {
+            ValueExpr::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+                LogicalFunction::AndVariant { left, right },
+            )) => {
+                let mut keys = Self::equi_keys(left, left_stream, right_stream);
+                keys.extend(Self::equi_keys(right, left_stream, right_stream));
+                keys
+            }
+            ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                ComparisonFunction::EqualVariant { left, right },
+            )) => match (left.as_ref(), right.as_ref()) {
+                (ValueExpr::ColumnReference(c1), ValueExpr::ColumnReference(c2)) => {
+                    let key = if Self::sql_type(left_stream, c1).is_some() {
+                        (c1, c2)
+                    } else {
+                        (c2, c1)
+                    };
+                    match (
+                        Self::sql_type(left_stream, key.0),
+                        Self::sql_type(right_stream, key.1),
+                    ) {
+                        (Some(t1), Some(t2)) if t1.is_same_loose_type(t2) => {
+                            vec![(key.0.clone(), key.1.clone())]
+                        }
+                        _ => vec![],
+                    }
+                }
+                _ => vec![],
+            },
+            _ => vec![],
+        }
+    }
+
+    /// SQL type of `colref` if it is a column of `stream`.
+    fn sql_type<'a>(stream: &'a StreamModel, colref: &ColumnReference) -> Option<&'a SqlType> {
+        match colref {
+            ColumnReference::Column {
+                stream_name,
+                column_name,
+            } if stream_name == stream.name() => stream
+                .shape()
+                .columns()
+                .iter()
+                .find(|column| column.column_name() == column_name)
+                .map(|column| column.column_data_type().sql_type()),
+            _ => None,
+        }
+    }
+
     fn sub_from_item_to_collect_op(sub_from_item: SubFromItemSyntax) -> CollectOp {
         CollectOp {
             stream: sub_from_item.stream_name,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{ColumnName, StreamName};
+
+    fn trade_colref(column_name: ColumnName) -> ValueExpr {
+        ValueExpr::factory_colref(StreamName::fx_trade().as_ref(), column_name.as_ref())
+    }
+    fn city_temperature_colref(column_name: ColumnName) -> ValueExpr {
+        ValueExpr::factory_colref(
+            StreamName::fx_city_temperature().as_ref(),
+            column_name.as_ref(),
+        )
+    }
+
+    fn equi_keys(on_expr: ValueExpr) -> Vec<(ColumnReference, ColumnReference)> {
+        SelectSyntaxAnalyzer::equi_keys(
+            &on_expr,
+            &StreamModel::fx_trade(),
+            &StreamModel::fx_city_temperature(),
+        )
+    }
+
+    #[test]
+    fn test_equi_keys() {
+        // trade.timestamp = city_temperature.timestamp AND city_temperature.city = trade.ticker
+        let on_expr = ValueExpr::factory_and(
+            ValueExpr::factory_eq(
+                trade_colref(ColumnName::fx_timestamp()),
+                city_temperature_colref(ColumnName::fx_timestamp()),
+            ),
+            ValueExpr::factory_eq(
+                city_temperature_colref(ColumnName::fx_city()),
+                trade_colref(ColumnName::fx_ticker()),
+            ),
+        );
+        assert_eq!(
+            equi_keys(on_expr),
+            vec![
+                (
+                    ColumnReference::fx_trade_timestamp(),
+                    ColumnReference::fx_city_temperature_timestamp()
+                ),
+                (
+                    ColumnReference::fx_trade_ticker(),
+                    ColumnReference::fx_city_temperature_city()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_equi_keys_not_extracted() {
+        // trade.timestamp = city_temperature.timestamp OR trade.amount = city_temperature.temperature
+        let on_expr = ValueExpr::factory_or(
+            ValueExpr::factory_eq(
+                trade_colref(ColumnName::fx_timestamp()),
+                city_temperature_colref(ColumnName::fx_timestamp()),
+            ),
+            ValueExpr::factory_eq(
+                trade_colref(ColumnName::fx_amount()),
+                city_temperature_colref(ColumnName::fx_temperature()),
+            ),
+        );
+        assert!(equi_keys(on_expr).is_empty());
+
+        // trade.ticker = city_temperature.temperature (different types)
+        let on_expr = ValueExpr::factory_eq(
+            trade_colref(ColumnName::fx_ticker()),
+            city_temperature_colref(ColumnName::fx_temperature()),
+        );
+        assert!(equi_keys(on_expr).is_empty());
+
+        // trade.amount < city_temperature.temperature AND trade.amount = 100
+        let on_expr = ValueExpr::factory_and(
+            ValueExpr::factory_lt(
+                trade_colref(ColumnName::fx_amount()),
+                city_temperature_colref(ColumnName::fx_temperature()),
+            ),
+            ValueExpr::factory_eq(
+                trade_colref(ColumnName::fx_amount()),
+                ValueExpr::factory_integer(100),
+            ),
+        );
+        assert!(equi_keys(on_expr).is_empty());
+    }
+}
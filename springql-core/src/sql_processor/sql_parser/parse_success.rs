@@ -1,7 +1,10 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use crate::{
-    pipeline::{PumpName, SinkWriterModel, SourceReaderModel, StreamModel},
+    pipeline::{
+        DeadLetterStreamModel, PumpName, SinkWriterModel, SourceReaderModel, StreamModel,
+        TableModel,
+    },
     sql_processor::sql_parser::syntax::SelectStreamSyntax,
     stream_engine::command::InsertPlan,
 };
@@ -13,8 +16,10 @@ pub enum ParseSuccess {
     CreateSourceReader(SourceReaderModel),
     CreateStream(StreamModel),
     CreateSinkStream(StreamModel),
+    CreateDeadLetterStream(DeadLetterStreamModel),
     CreateSinkWriter(SinkWriterModel),
     CreatePump(Box<CreatePump>),
+    CreateTable(TableModel),
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -6,27 +6,35 @@ mod helper;
 use std::convert::identity;
 
 use anyhow::{anyhow, Context};
+use once_cell::sync::Lazy;
 use ordered_float::OrderedFloat;
-use pest::{iterators::Pairs, Parser};
+use pest::{
+    iterators::Pairs,
+    pratt_parser::{Assoc, Op, PrattParser},
+    Parser,
+};
 
 use crate::{
     api::error::{Result, SpringError},
     expression::{
-        AggrExpr, BinaryExpr, BinaryOperator, ComparisonFunction, FunctionCall, LogicalFunction,
-        NumericalFunction, UnaryOperator, ValueExpr,
+        AggrExpr, BinaryExpr, BinaryOperator, ComparisonFunction, ExtractField, FunctionCall,
+        LogicalFunction, MathFunction, NumericalFunction, UnaryOperator, ValueExpr,
     },
     pipeline::{
         AggrAlias, AggregateFunctionParameter, ColumnConstraint, ColumnDataType, ColumnDefinition,
-        ColumnName, ColumnReference, CorrelationAlias, JoinType, OptionsBuilder, PumpName,
-        SinkWriterModel, SinkWriterName, SinkWriterType, SourceReaderModel, SourceReaderName,
-        SourceReaderType, SqlType, StreamModel, StreamName, StreamShape, ValueAlias,
-        WindowParameter,
+        ColumnName, ColumnReference, CorrelationAlias, DeadLetterStreamModel, DeadLetterTarget,
+        JoinType, OptionsBuilder, PumpName, SinkWriterModel, SinkWriterName, SinkWriterType,
+        SourceReaderModel, SourceReaderName, SourceReaderType, SqlType, StreamModel, StreamName,
+        StreamShape, TableModel, ValueAlias, WindowParameter,
     },
     sql_processor::sql_parser::{
         parse_success::{CreatePump, ParseSuccess},
         pest_parser_impl::{
             generated_parser::{GeneratedParser, Rule},
-            helper::{parse_child, parse_child_seq, self_as_str, try_parse_child, FnParseParams},
+            helper::{
+                parse_child, parse_child_seq, parse_pair, self_as_str, try_parse_child,
+                FnParseParams,
+            },
         },
         syntax::{
             ColumnConstraintSyntax, DurationFunction, FromItemSyntax, GroupingElementSyntax,
@@ -40,6 +48,22 @@ use crate::{
     },
 };
 
+/// Operator precedence of `value_expr`, from the lowest to the highest (same as PostgreSQL, except for `->` binding the tightest).
+static PRATT_PARSER: Lazy<PrattParser<Rule>> = Lazy::new(|| {
+    PrattParser::new()
+        .op(Op::infix(Rule::or_operator, Assoc::Left))
+        .op(Op::infix(Rule::and_operator, Assoc::Left))
+        .op(Op::prefix(Rule::not_operator))
+        .op(Op::postfix(Rule::is_null_operator) | Op::postfix(Rule::is_not_null_operator))
+        .op(Op::infix(Rule::comparison_operator, Assoc::Left))
+        .op(Op::infix(Rule::like_operator, Assoc::Left))
+        .op(Op::infix(Rule::concat_operator, Assoc::Left))
+        .op(Op::infix(Rule::additive_operator, Assoc::Left))
+        .op(Op::infix(Rule::multiplicative_operator, Assoc::Left))
+        .op(Op::prefix(Rule::minus_operator))
+        .op(Op::infix(Rule::json_extract_operator, Assoc::Left))
+});
+
 #[derive(Debug, Default)]
 pub struct PestParserImpl;
 
@@ -232,21 +256,48 @@ impl PestParserImpl {
 
     fn parse_unary_operator(mut params: FnParseParams) -> Result<UnaryOperator> {
         let s = self_as_str(&mut params);
-        match s {
+        match s.to_lowercase().as_str() {
             "-" => Ok(UnaryOperator::Minus),
+            "not" => Ok(UnaryOperator::Not),
             _ => Err(SpringError::Sql(anyhow!(
                 "Does not match any child rule of unary_operator.",
             ))),
         }
     }
 
+    fn parse_postfix_operator(mut params: FnParseParams) -> Result<UnaryOperator> {
+        let s = self_as_str(&mut params).to_lowercase();
+        let words = s.split_whitespace().collect::<Vec<_>>();
+        match words.as_slice() {
+            ["is", "null"] => Ok(UnaryOperator::IsNull),
+            ["is", "not", "null"] => Ok(UnaryOperator::IsNotNull),
+            _ => Err(SpringError::Sql(anyhow!(
+                "Does not match any child rule of postfix_operator.",
+            ))),
+        }
+    }
+
     fn parse_binary_operator(mut params: FnParseParams) -> Result<BinaryOperator> {
-        let s = self_as_str(&mut params);
-        match s.to_lowercase().as_str() {
+        let s = self_as_str(&mut params).to_lowercase();
+        let words = s.split_whitespace().collect::<Vec<_>>();
+        match words.join(" ").as_str() {
             "=" => Ok(BinaryOperator::Equal),
+            "<>" | "!=" => Ok(BinaryOperator::NotEqual),
+            "<" => Ok(BinaryOperator::LessThan),
+            "<=" => Ok(BinaryOperator::LessThanOrEqual),
+            ">" => Ok(BinaryOperator::GreaterThan),
+            ">=" => Ok(BinaryOperator::GreaterThanOrEqual),
             "+" => Ok(BinaryOperator::Add),
+            "-" => Ok(BinaryOperator::Sub),
             "*" => Ok(BinaryOperator::Mul),
+            "/" => Ok(BinaryOperator::Div),
+            "%" => Ok(BinaryOperator::Mod),
+            "||" => Ok(BinaryOperator::Concat),
+            "->" => Ok(BinaryOperator::JsonExtract),
+            "like" => Ok(BinaryOperator::Like),
+            "not like" => Ok(BinaryOperator::NotLike),
             "and" => Ok(BinaryOperator::And),
+            "or" => Ok(BinaryOperator::Or),
             _ => Err(SpringError::Sql(anyhow!(
                 "Does not match any child rule of binary_operator.",
             ))),
@@ -278,6 +329,12 @@ impl PestParserImpl {
             Self::parse_create_stream_command,
             identity,
         )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::create_dead_letter_stream_command,
+            Self::parse_create_dead_letter_stream_command,
+            identity,
+        )?)
         .or(try_parse_child(
             &mut params,
             Rule::create_sink_stream_command,
@@ -296,6 +353,12 @@ impl PestParserImpl {
             Self::parse_create_pump_command,
             identity,
         )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::create_table_command,
+            Self::parse_create_table_command,
+            identity,
+        )?)
         .ok_or_else(|| {
             SpringError::Sql(anyhow!(
                 "Does not match any child rule of command: {}",
@@ -432,6 +495,57 @@ impl PestParserImpl {
         Ok(ParseSuccess::CreateSinkStream(sink_stream))
     }
 
+    /*
+     * ----------------------------------------------------------------------------
+     * CREATE SINK STREAM ... AS DEAD LETTER
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_create_dead_letter_stream_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let stream_name = parse_child(
+            &mut params,
+            Rule::stream_name,
+            Self::parse_stream_name,
+            identity,
+        )?;
+        let target = parse_child(
+            &mut params,
+            Rule::dead_letter_target,
+            Self::parse_dead_letter_target,
+            identity,
+        )?;
+
+        let dead_letter_stream = DeadLetterStreamModel::new(stream_name, target);
+
+        Ok(ParseSuccess::CreateDeadLetterStream(dead_letter_stream))
+    }
+
+    fn parse_dead_letter_target(mut params: FnParseParams) -> Result<DeadLetterTarget> {
+        try_parse_child(
+            &mut params,
+            Rule::pump_name,
+            Self::parse_pump_name,
+            DeadLetterTarget::Pump,
+        )?
+        .or(try_parse_child(
+            &mut params,
+            Rule::source_reader_name,
+            Self::parse_source_reader_name,
+            DeadLetterTarget::SourceReader,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::sink_writer_name,
+            Self::parse_sink_writer_name,
+            DeadLetterTarget::SinkWriter,
+        )?)
+        .ok_or_else(|| {
+            SpringError::Sql(anyhow!(
+                "Does not match any child rule of dead_letter_target.",
+            ))
+        })
+    }
+
     /*
      * ----------------------------------------------------------------------------
      * CREATE SINK WRITER
@@ -482,6 +596,46 @@ impl PestParserImpl {
         Ok(ParseSuccess::CreateSinkWriter(sink_writer))
     }
 
+    /*
+     * ----------------------------------------------------------------------------
+     * CREATE TABLE
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_create_table_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let table_name = parse_child(
+            &mut params,
+            Rule::stream_name,
+            Self::parse_stream_name,
+            identity,
+        )?;
+        let column_definitions = parse_child_seq(
+            &mut params,
+            Rule::column_definition,
+            &Self::parse_column_definition,
+            &identity,
+        )?;
+        let option_syntaxes = try_parse_child(
+            &mut params,
+            Rule::option_specifications,
+            Self::parse_option_specifications,
+            identity,
+        )?;
+
+        let mut options = OptionsBuilder::default();
+        if let Some(option_syntaxes) = option_syntaxes {
+            for o in option_syntaxes {
+                options = options.add(o.option_name, o.option_value);
+            }
+        }
+        let options = options.build();
+
+        let table_shape = StreamShape::new(column_definitions)?;
+        let table = TableModel::new(table_name, table_shape, options)?;
+
+        Ok(ParseSuccess::CreateTable(table))
+    }
+
     /*
      * ----------------------------------------------------------------------------
      * CREATE PUMP
@@ -540,12 +694,24 @@ impl PestParserImpl {
             Self::parse_from_item,
             identity,
         )?;
+        let where_clause = try_parse_child(
+            &mut params,
+            Rule::where_clause,
+            Self::parse_where_clause,
+            identity,
+        )?;
         let grouping_elements = try_parse_child(
             &mut params,
             Rule::group_by_clause,
             Self::parse_group_by_clause,
             identity,
         )?;
+        let having_clause = try_parse_child(
+            &mut params,
+            Rule::having_clause,
+            Self::parse_having_clause,
+            identity,
+        )?;
         let window_clause = try_parse_child(
             &mut params,
             Rule::window_clause,
@@ -556,53 +722,36 @@ impl PestParserImpl {
         Ok(SelectStreamSyntax {
             fields,
             from_item,
+            where_clause,
             grouping_elements: grouping_elements.unwrap_or_default(),
+            having_clause,
             window_clause,
         })
     }
 
+    /// An aggregate expression without any operator around it is registered as `SelectFieldSyntax::AggrExpr`
+    /// so that its alias can be referred to as an aggregate alias.
     fn parse_select_field(mut params: FnParseParams) -> Result<SelectFieldSyntax> {
-        try_parse_child(
+        let value_expr = parse_child(
             &mut params,
             Rule::value_expr,
             Self::parse_value_expr,
             identity,
-        )?
-        .map(|value_expr| {
-            let alias = try_parse_child(
-                &mut params,
-                Rule::value_alias,
-                Self::parse_value_alias,
-                identity,
-            )?;
-            Ok(SelectFieldSyntax::ValueExpr { value_expr, alias })
-        })
-        .transpose()?
-        .or(try_parse_child(
+        )?;
+        let alias = try_parse_child(
             &mut params,
-            Rule::aggr_expr,
-            Self::parse_aggr_expr,
+            Rule::value_alias,
+            Self::parse_value_alias,
             identity,
-        )?
-        .map(|aggr_expr| {
-            let alias = parse_child(
-                &mut params,
-                Rule::aggr_alias,
-                Self::parse_aggr_alias,
-                identity,
-            )?;
-            Ok(SelectFieldSyntax::AggrExpr {
-                aggr_expr,
-                alias: Some(alias),
-            })
-        })
-        .transpose()?)
-        .ok_or_else(|| {
-            SpringError::Sql(anyhow!(
-                "Does not match any child rule of command: {}",
-                params.sql
-            ))
-        })
+        )?;
+
+        match value_expr {
+            ValueExpr::AggrExpr(aggr_expr) => Ok(SelectFieldSyntax::AggrExpr {
+                aggr_expr: *aggr_expr,
+                alias: alias.map(|alias| AggrAlias::new(alias.as_ref().to_string())),
+            }),
+            value_expr => Ok(SelectFieldSyntax::ValueExpr { value_expr, alias }),
+        }
     }
 
     fn parse_from_item(mut params: FnParseParams) -> Result<FromItemSyntax> {
@@ -662,8 +811,12 @@ impl PestParserImpl {
 
     fn parse_join_type(mut params: FnParseParams) -> Result<JoinType> {
         let s = self_as_str(&mut params);
+        let s = s.split_whitespace().collect::<Vec<_>>().join(" ");
         match s.to_lowercase().as_str() {
-            "left outer join" => Ok(JoinType::LeftOuter),
+            "join" | "inner join" => Ok(JoinType::Inner),
+            "left outer join" | "left join" => Ok(JoinType::LeftOuter),
+            "right outer join" | "right join" => Ok(JoinType::RightOuter),
+            "full outer join" | "full join" => Ok(JoinType::FullOuter),
             _ => Err(SpringError::Sql(anyhow!(
                 "unknown join type {}",
                 s.to_lowercase()
@@ -671,6 +824,23 @@ impl PestParserImpl {
         }
     }
 
+    fn parse_where_clause(mut params: FnParseParams) -> Result<ValueExpr> {
+        let condition = parse_child(
+            &mut params,
+            Rule::condition,
+            Self::parse_condition,
+            identity,
+        )?;
+        if condition.aggr_exprs().is_empty() {
+            Ok(condition)
+        } else {
+            Err(SpringError::Sql(anyhow!(
+                "aggregate functions are not allowed in WHERE clause: {}",
+                params.self_string
+            )))
+        }
+    }
+
     fn parse_group_by_clause(mut params: FnParseParams) -> Result<Vec<GroupingElementSyntax>> {
         parse_child_seq(
             &mut params,
@@ -680,6 +850,15 @@ impl PestParserImpl {
         )
     }
 
+    fn parse_having_clause(mut params: FnParseParams) -> Result<ValueExpr> {
+        parse_child(
+            &mut params,
+            Rule::condition,
+            Self::parse_condition,
+            identity,
+        )
+    }
+
     fn parse_grouping_element(mut params: FnParseParams) -> Result<GroupingElementSyntax> {
         try_parse_child(
             &mut params,
@@ -711,6 +890,24 @@ impl PestParserImpl {
             Self::parse_sliding_window_clause,
             identity,
         )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::session_window_clause,
+            Self::parse_session_window_clause,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::fixed_count_window_clause,
+            Self::parse_fixed_count_window_clause,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::sliding_count_window_clause,
+            Self::parse_sliding_count_window_clause,
+            identity,
+        )?)
         .ok_or_else(|| SpringError::Sql(anyhow!("Failed to parse window clause: {}", params.sql)))
     }
 
@@ -769,6 +966,97 @@ impl PestParserImpl {
         })
     }
 
+    fn parse_session_window_clause(mut params: FnParseParams) -> Result<WindowParameter> {
+        let gap = parse_child(
+            &mut params,
+            Rule::session_gap,
+            Self::parse_session_gap,
+            identity,
+        )?;
+        let gap = gap.to_event_duration()?;
+        if gap == SpringEventDuration::from_secs(0) {
+            return Err(SpringError::Sql(anyhow!(
+                "gap of SESSION WINDOW must be positive: {}",
+                params.sql
+            )));
+        }
+
+        let allowed_delay = parse_child(
+            &mut params,
+            Rule::allowed_delay,
+            Self::parse_allowed_delay,
+            identity,
+        )?;
+        let allowed_delay = allowed_delay.to_event_duration()?;
+
+        Ok(WindowParameter::SessionWindow { gap, allowed_delay })
+    }
+
+    fn parse_fixed_count_window_clause(mut params: FnParseParams) -> Result<WindowParameter> {
+        let length = parse_child(
+            &mut params,
+            Rule::window_length_rows,
+            Self::parse_window_length_rows,
+            identity,
+        )?;
+
+        Ok(WindowParameter::CountFixedWindow { length })
+    }
+
+    fn parse_sliding_count_window_clause(mut params: FnParseParams) -> Result<WindowParameter> {
+        let length = parse_child(
+            &mut params,
+            Rule::window_length_rows,
+            Self::parse_window_length_rows,
+            identity,
+        )?;
+        let period = parse_child(
+            &mut params,
+            Rule::window_period_rows,
+            Self::parse_window_period_rows,
+            identity,
+        )?;
+
+        Ok(WindowParameter::CountSlidingWindow { length, period })
+    }
+
+    fn parse_window_length_rows(mut params: FnParseParams) -> Result<u64> {
+        parse_child(
+            &mut params,
+            Rule::rows_constant,
+            Self::parse_rows_constant,
+            identity,
+        )
+    }
+    fn parse_window_period_rows(mut params: FnParseParams) -> Result<u64> {
+        parse_child(
+            &mut params,
+            Rule::rows_constant,
+            Self::parse_rows_constant,
+            identity,
+        )
+    }
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the number of rows is not positive.
+    fn parse_rows_constant(mut params: FnParseParams) -> Result<u64> {
+        let integer_constant = parse_child(
+            &mut params,
+            Rule::integer_constant,
+            Self::parse_integer_constant,
+            identity,
+        )?;
+        match integer_constant.to_i64()? {
+            n if n > 0 => Ok(n as u64),
+            _ => Err(SpringError::Sql(anyhow!(
+                "number of rows must be positive: {}",
+                params.sql
+            ))),
+        }
+    }
+
     fn parse_window_length(mut params: FnParseParams) -> Result<SqlValue> {
         parse_child(
             &mut params,
@@ -785,6 +1073,14 @@ impl PestParserImpl {
             identity,
         )
     }
+    fn parse_session_gap(mut params: FnParseParams) -> Result<SqlValue> {
+        parse_child(
+            &mut params,
+            Rule::duration_constant,
+            Self::parse_duration_constant,
+            identity,
+        )
+    }
     fn parse_allowed_delay(mut params: FnParseParams) -> Result<SqlValue> {
         parse_child(
             &mut params,
@@ -809,55 +1105,123 @@ impl PestParserImpl {
         )
     }
 
-    fn parse_value_expr(mut params: FnParseParams) -> Result<ValueExpr> {
-        let expr = parse_child(
-            &mut params,
-            Rule::sub_value_expr,
-            Self::parse_sub_value_expr,
-            identity,
-        )?;
-
-        if let Some(bin_op) = try_parse_child(
-            &mut params,
-            Rule::binary_operator,
-            Self::parse_binary_operator,
-            identity,
-        )? {
-            let right_expr = parse_child(
-                &mut params,
-                Rule::value_expr,
-                Self::parse_value_expr,
-                identity,
-            )?;
+    fn parse_value_expr(params: FnParseParams) -> Result<ValueExpr> {
+        let sql = params.sql;
+        PRATT_PARSER
+            .map_primary(|primary| parse_pair(sql, primary, Self::parse_sub_value_expr))
+            .map_prefix(|op, operand| {
+                let uni_op = parse_pair(sql, op, Self::parse_unary_operator)?;
+                Ok(ValueExpr::UnaryOperator(uni_op, Box::new(operand?)))
+            })
+            .map_postfix(|operand, op| {
+                let postfix_op = parse_pair(sql, op, Self::parse_postfix_operator)?;
+                Ok(ValueExpr::UnaryOperator(postfix_op, Box::new(operand?)))
+            })
+            .map_infix(|left, op, right| {
+                let bin_op = parse_pair(sql, op, Self::parse_binary_operator)?;
+                Ok(Self::binary_expr(bin_op, left?, right?))
+            })
+            .parse(params.children_pairs.into_iter())
+    }
 
-            match bin_op {
-                BinaryOperator::Equal => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::EqualVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
-                BinaryOperator::Add => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::NumericalFunctionVariant(NumericalFunction::AddVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
-                BinaryOperator::Mul => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::NumericalFunctionVariant(NumericalFunction::MulVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
-                BinaryOperator::And => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::LogicalFunctionVariant(LogicalFunction::AndVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
+    fn binary_expr(bin_op: BinaryOperator, left: ValueExpr, right: ValueExpr) -> ValueExpr {
+        match bin_op {
+            BinaryOperator::Equal => ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                ComparisonFunction::EqualVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::NotEqual => ValueExpr::BinaryExpr(
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::NotEqualVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }),
+            ),
+            BinaryOperator::LessThan => ValueExpr::BinaryExpr(
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::LessThanVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }),
+            ),
+            BinaryOperator::LessThanOrEqual => ValueExpr::BinaryExpr(
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::LessThanOrEqualVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }),
+            ),
+            BinaryOperator::GreaterThan => ValueExpr::BinaryExpr(
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::GreaterThanVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }),
+            ),
+            BinaryOperator::GreaterThanOrEqual => {
+                ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                    ComparisonFunction::GreaterThanOrEqualVariant {
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    },
+                ))
             }
-        } else {
-            Ok(expr)
+            BinaryOperator::Add => ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::AddVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::Mul => ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::MulVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::Sub => ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::SubVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::Div => ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::DivVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::Mod => ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::ModVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::And => ValueExpr::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+                LogicalFunction::AndVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::Or => ValueExpr::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+                LogicalFunction::OrVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::Concat => ValueExpr::FunctionCall(FunctionCall::Concat {
+                left: Box::new(left),
+                right: Box::new(right),
+            }),
+            BinaryOperator::JsonExtract => ValueExpr::FunctionCall(FunctionCall::JsonExtract {
+                target: Box::new(left),
+                path: Box::new(right),
+            }),
+            BinaryOperator::Like => ValueExpr::FunctionCall(FunctionCall::Like {
+                target: Box::new(left),
+                pattern: Box::new(right),
+            }),
+            BinaryOperator::NotLike => ValueExpr::UnaryOperator(
+                UnaryOperator::Not,
+                Box::new(Self::binary_expr(BinaryOperator::Like, left, right)),
+            ),
         }
     }
 
@@ -874,33 +1238,46 @@ impl PestParserImpl {
             Self::parse_column_reference,
             ValueExpr::ColumnReference,
         )?)
-        .or({
-            if let Some(uni_op) = try_parse_child(
-                &mut params,
-                Rule::unary_operator,
-                Self::parse_unary_operator,
-                identity,
-            )? {
-                Some(parse_child(
-                    &mut params,
-                    Rule::value_expr,
-                    Self::parse_value_expr,
-                    |expr| ValueExpr::UnaryOperator(uni_op.clone(), Box::new(expr)),
-                )?)
-            } else {
-                None
-            }
-        })
         .or(try_parse_child(
             &mut params,
-            Rule::function_call,
-            Self::parse_function_call,
+            Rule::aggr_expr,
+            Self::parse_aggr_expr,
+            |aggr_expr| ValueExpr::AggrExpr(Box::new(aggr_expr)),
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::case_expr,
+            Self::parse_case_expr,
             ValueExpr::FunctionCall,
         )?)
-        .ok_or_else(|| {
-            SpringError::Sql(anyhow!("Does not match any child rule of sub_value_expr.",))
-        })
-    }
+        .or(try_parse_child(
+            &mut params,
+            Rule::cast_expr,
+            Self::parse_cast_expr,
+            ValueExpr::FunctionCall,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::extract_expr,
+            Self::parse_extract_expr,
+            ValueExpr::FunctionCall,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::function_call,
+            Self::parse_function_call,
+            ValueExpr::FunctionCall,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )?)
+        .ok_or_else(|| {
+            SpringError::Sql(anyhow!("Does not match any child rule of sub_value_expr.",))
+        })
+    }
 
     /*
      * ----------------------------------------------------------------------------
@@ -989,7 +1366,316 @@ impl PestParserImpl {
                     )))
                 }
             }
-            "floor" => unimplemented!(),
+            "ceil_time" => {
+                if parameters.len() == 2 {
+                    Ok(FunctionCall::CeilTime {
+                        target: Box::new(parameters[0].clone()),
+                        resolution: Box::new(parameters[1].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "ceil_time() takes exactly two parameters (target, resolution)."
+                    )))
+                }
+            }
+            "to_epoch_millis" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::ToEpochMillis {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "to_epoch_millis() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "from_epoch_millis" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::FromEpochMillis {
+                        epoch_millis: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "from_epoch_millis() takes exactly one parameter (epoch_millis)."
+                    )))
+                }
+            }
+            "format_timestamp" => {
+                if parameters.len() == 2 {
+                    Ok(FunctionCall::FormatTimestamp {
+                        target: Box::new(parameters[0].clone()),
+                        format: Box::new(parameters[1].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "format_timestamp() takes exactly two parameters (target, format)."
+                    )))
+                }
+            }
+            "parse_timestamp" => {
+                if parameters.len() == 2 {
+                    Ok(FunctionCall::ParseTimestamp {
+                        target: Box::new(parameters[0].clone()),
+                        format: Box::new(parameters[1].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "parse_timestamp() takes exactly two parameters (target, format)."
+                    )))
+                }
+            }
+            "concat" => {
+                // CONCAT(a, b, c) -> (a || b) || c
+                let mut parameters = parameters.into_iter();
+                match (parameters.next(), parameters.next()) {
+                    (Some(first), Some(second)) => Ok(parameters.fold(
+                        FunctionCall::Concat {
+                            left: Box::new(first),
+                            right: Box::new(second),
+                        },
+                        |concat, next| FunctionCall::Concat {
+                            left: Box::new(ValueExpr::FunctionCall(concat)),
+                            right: Box::new(next),
+                        },
+                    )),
+                    _ => Err(SpringError::Sql(anyhow!(
+                        "concat() takes two or more parameters."
+                    ))),
+                }
+            }
+            "upper" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Upper {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "upper() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "lower" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Lower {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "lower() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "length" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Length {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "length() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "substring" => {
+                if parameters.len() == 2 || parameters.len() == 3 {
+                    Ok(FunctionCall::Substring {
+                        target: Box::new(parameters[0].clone()),
+                        start: Box::new(parameters[1].clone()),
+                        length: parameters.get(2).cloned().map(Box::new),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "substring() takes two or three parameters (target, start[, length])."
+                    )))
+                }
+            }
+            "trim" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Trim {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "trim() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "replace" => {
+                if parameters.len() == 3 {
+                    Ok(FunctionCall::Replace {
+                        target: Box::new(parameters[0].clone()),
+                        from: Box::new(parameters[1].clone()),
+                        to: Box::new(parameters[2].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "replace() takes exactly three parameters (target, from, to)."
+                    )))
+                }
+            }
+            "split_part" => {
+                if parameters.len() == 3 {
+                    Ok(FunctionCall::SplitPart {
+                        target: Box::new(parameters[0].clone()),
+                        delimiter: Box::new(parameters[1].clone()),
+                        index: Box::new(parameters[2].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "split_part() takes exactly three parameters (target, delimiter, index)."
+                    )))
+                }
+            }
+            "json_extract" => {
+                if parameters.len() == 2 {
+                    Ok(FunctionCall::JsonExtract {
+                        target: Box::new(parameters[0].clone()),
+                        path: Box::new(parameters[1].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "json_extract() takes exactly two parameters (target, path)."
+                    )))
+                }
+            }
+            "abs" | "ceil" | "floor" | "sqrt" | "exp" | "ln" | "log10" | "sin" | "cos" => {
+                let func = match function_name.to_lowercase().as_str() {
+                    "abs" => MathFunction::Abs,
+                    "ceil" => MathFunction::Ceil,
+                    "floor" => MathFunction::Floor,
+                    "sqrt" => MathFunction::Sqrt,
+                    "exp" => MathFunction::Exp,
+                    "ln" => MathFunction::Ln,
+                    "log10" => MathFunction::Log10,
+                    "sin" => MathFunction::Sin,
+                    "cos" => MathFunction::Cos,
+                    _ => unreachable!(),
+                };
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Math {
+                        func,
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "{}() takes exactly one parameter (target).",
+                        function_name.to_lowercase()
+                    )))
+                }
+            }
+            "round" => {
+                if parameters.len() == 1 || parameters.len() == 2 {
+                    Ok(FunctionCall::Round {
+                        target: Box::new(parameters[0].clone()),
+                        digits: parameters.get(1).cloned().map(Box::new),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "round() takes one or two parameters (target[, digits])."
+                    )))
+                }
+            }
+            "power" => {
+                if parameters.len() == 2 {
+                    Ok(FunctionCall::Power {
+                        base: Box::new(parameters[0].clone()),
+                        exponent: Box::new(parameters[1].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "power() takes exactly two parameters (base, exponent)."
+                    )))
+                }
+            }
+            "atan2" => {
+                if parameters.len() == 2 {
+                    Ok(FunctionCall::Atan2 {
+                        y: Box::new(parameters[0].clone()),
+                        x: Box::new(parameters[1].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "atan2() takes exactly two parameters (y, x)."
+                    )))
+                }
+            }
+            "greatest" | "least" => {
+                // GREATEST(a, b, c) -> GREATEST(GREATEST(a, b), c)
+                let is_greatest = function_name.eq_ignore_ascii_case("greatest");
+                let extremum = |left, right| {
+                    if is_greatest {
+                        FunctionCall::Greatest { left, right }
+                    } else {
+                        FunctionCall::Least { left, right }
+                    }
+                };
+
+                let mut parameters = parameters.into_iter();
+                match (parameters.next(), parameters.next()) {
+                    (Some(first), second) => Ok(parameters.fold(
+                        // GREATEST(a) -> GREATEST(a, NULL) (NULL arguments are ignored)
+                        extremum(
+                            Box::new(first),
+                            Box::new(second.unwrap_or(ValueExpr::Constant(SqlValue::Null))),
+                        ),
+                        |extremum_so_far, next| {
+                            extremum(
+                                Box::new(ValueExpr::FunctionCall(extremum_so_far)),
+                                Box::new(next),
+                            )
+                        },
+                    )),
+                    (None, _) => Err(SpringError::Sql(anyhow!(
+                        "{}() takes one or more parameters.",
+                        function_name.to_lowercase()
+                    ))),
+                }
+            }
+            "coalesce" => {
+                // COALESCE(a, b, c) -> COALESCE(a, COALESCE(b, c))
+                let mut parameters = parameters.into_iter().rev();
+                match parameters.next() {
+                    Some(last) => Ok(parameters.fold(
+                        // COALESCE(a) -> COALESCE(a, NULL)
+                        FunctionCall::Coalesce {
+                            left: Box::new(last),
+                            right: Box::new(ValueExpr::Constant(SqlValue::Null)),
+                        },
+                        |coalesce_so_far, prev| FunctionCall::Coalesce {
+                            left: Box::new(prev),
+                            right: Box::new(ValueExpr::FunctionCall(coalesce_so_far)),
+                        },
+                    )),
+                    None => Err(SpringError::Sql(anyhow!(
+                        "coalesce() takes one or more parameters."
+                    ))),
+                }
+            }
+            "nullif" => {
+                if parameters.len() == 2 {
+                    Ok(FunctionCall::NullIf {
+                        left: Box::new(parameters[0].clone()),
+                        right: Box::new(parameters[1].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "nullif() takes exactly two parameters (a, b)."
+                    )))
+                }
+            }
+            "if" => {
+                if parameters.len() == 3 {
+                    Ok(FunctionCall::If {
+                        condition: Box::new(parameters[0].clone()),
+                        then_value: Box::new(parameters[1].clone()),
+                        else_value: Box::new(parameters[2].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "if() takes exactly three parameters (condition, then_value, else_value)."
+                    )))
+                }
+            }
             _ => Err(SpringError::Sql(anyhow!(
                 "unknown function {}",
                 function_name.to_lowercase()
@@ -1001,6 +1687,147 @@ impl PestParserImpl {
         Ok(self_as_str(&mut params).to_string())
     }
 
+    /// Searched and simple CASE are desugared into nested `IF`s:
+    ///
+    /// - `CASE WHEN c1 THEN v1 WHEN c2 THEN v2 ELSE v3 END` -> `IF(c1, v1, IF(c2, v2, v3))`
+    /// - `CASE x WHEN w1 THEN v1 END` -> `IF(x = w1, v1, NULL)`
+    fn parse_case_expr(mut params: FnParseParams) -> Result<FunctionCall<ValueExpr>> {
+        let operand = try_parse_child(
+            &mut params,
+            Rule::case_operand,
+            Self::parse_case_operand,
+            identity,
+        )?;
+        let when_clauses = parse_child_seq(
+            &mut params,
+            Rule::when_clause,
+            &Self::parse_when_clause,
+            &identity,
+        )?;
+        let else_value = try_parse_child(
+            &mut params,
+            Rule::else_clause,
+            Self::parse_else_clause,
+            identity,
+        )?
+        .unwrap_or(ValueExpr::Constant(SqlValue::Null));
+
+        let if_expr = when_clauses.into_iter().rev().fold(
+            else_value,
+            |else_value, (when_value, then_value)| {
+                let condition = match &operand {
+                    Some(operand) => {
+                        Self::binary_expr(BinaryOperator::Equal, operand.clone(), when_value)
+                    }
+                    None => when_value,
+                };
+                ValueExpr::FunctionCall(FunctionCall::If {
+                    condition: Box::new(condition),
+                    then_value: Box::new(then_value),
+                    else_value: Box::new(else_value),
+                })
+            },
+        );
+        match if_expr {
+            ValueExpr::FunctionCall(function_call) => Ok(function_call),
+            _ => unreachable!("CASE has at least one WHEN clause"),
+        }
+    }
+
+    fn parse_case_operand(mut params: FnParseParams) -> Result<ValueExpr> {
+        parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )
+    }
+
+    fn parse_when_clause(mut params: FnParseParams) -> Result<(ValueExpr, ValueExpr)> {
+        let mut value_exprs = parse_child_seq(
+            &mut params,
+            Rule::value_expr,
+            &Self::parse_value_expr,
+            &identity,
+        )?
+        .into_iter();
+        match (value_exprs.next(), value_exprs.next()) {
+            (Some(when_value), Some(then_value)) => Ok((when_value, then_value)),
+            _ => unreachable!(),
+        }
+    }
+
+    fn parse_else_clause(mut params: FnParseParams) -> Result<ValueExpr> {
+        parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )
+    }
+
+    fn parse_cast_expr(mut params: FnParseParams) -> Result<FunctionCall<ValueExpr>> {
+        let cast_function = parse_child(
+            &mut params,
+            Rule::cast_function,
+            Self::parse_cast_function,
+            identity,
+        )?;
+        let target = Box::new(parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )?);
+        let sql_type = parse_child(
+            &mut params,
+            Rule::data_type,
+            Self::parse_data_type,
+            identity,
+        )?;
+
+        match cast_function.as_str() {
+            "cast" => Ok(FunctionCall::Cast { target, sql_type }),
+            "try_cast" => Ok(FunctionCall::TryCast { target, sql_type }),
+            _ => unreachable!(),
+        }
+    }
+
+    fn parse_cast_function(mut params: FnParseParams) -> Result<String> {
+        Ok(self_as_str(&mut params).to_lowercase())
+    }
+
+    fn parse_extract_expr(mut params: FnParseParams) -> Result<FunctionCall<ValueExpr>> {
+        let field = parse_child(
+            &mut params,
+            Rule::extract_field,
+            Self::parse_extract_field,
+            identity,
+        )?;
+        let target = parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )?;
+        Ok(FunctionCall::Extract {
+            field,
+            target: Box::new(target),
+        })
+    }
+
+    fn parse_extract_field(mut params: FnParseParams) -> Result<ExtractField> {
+        let s = self_as_str(&mut params);
+        match s.to_lowercase().as_str() {
+            "hour" => Ok(ExtractField::Hour),
+            "minute" => Ok(ExtractField::Minute),
+            "second" => Ok(ExtractField::Second),
+            "millisecond" => Ok(ExtractField::Millisecond),
+            "dow" => Ok(ExtractField::DayOfWeek),
+            _ => unreachable!(),
+        }
+    }
+
     /*
      * ----------------------------------------------------------------------------
      * Aggregate
@@ -1008,28 +1835,96 @@ impl PestParserImpl {
      */
 
     fn parse_aggr_expr(mut params: FnParseParams) -> Result<AggrExpr> {
-        let func = parse_child(
+        let name = parse_child(
             &mut params,
             Rule::aggregate_name,
             Self::parse_aggregate_name,
             identity,
         )?;
-        let aggregated = parse_child(
+        let aggregated =
+            if try_parse_child(&mut params, Rule::all_rows, |_| Ok(()), identity)?.is_some() {
+                if name != "count" {
+                    return Err(SpringError::Sql(anyhow!(
+                        "`*` is only allowed in COUNT(*): {}",
+                        params.self_string
+                    )));
+                }
+                ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::Integer(1)))
+            } else {
+                parse_child(
+                    &mut params,
+                    Rule::value_expr,
+                    Self::parse_value_expr,
+                    identity,
+                )?
+            };
+        if !aggregated.aggr_exprs().is_empty() {
+            return Err(SpringError::Sql(anyhow!(
+                "aggregate function calls cannot be nested: {}",
+                params.self_string
+            )));
+        }
+
+        let argument = try_parse_child(
             &mut params,
-            Rule::value_expr,
-            Self::parse_value_expr,
+            Rule::constant,
+            |mut params| Ok(self_as_str(&mut params).to_string()),
             identity,
         )?;
+        let func = Self::aggregate_function(&name, argument)?;
+
         Ok(AggrExpr { func, aggregated })
     }
 
-    fn parse_aggregate_name(mut params: FnParseParams) -> Result<AggregateFunctionParameter> {
-        let s = self_as_str(&mut params);
-        match s.to_lowercase().as_str() {
-            "avg" => Ok(AggregateFunctionParameter::Avg),
-            _ => Err(SpringError::Sql(anyhow!(
-                "unknown aggregate function {}",
-                s.to_lowercase()
+    fn parse_aggregate_name(mut params: FnParseParams) -> Result<String> {
+        Ok(self_as_str(&mut params).to_lowercase())
+    }
+
+    /// `argument` is a constant after the aggregated expression, e.g. `0.95` in `APPROX_PERCENTILE(x, 0.95)`.
+    fn aggregate_function(
+        name: &str,
+        argument: Option<String>,
+    ) -> Result<AggregateFunctionParameter> {
+        let func = match name {
+            "avg" => AggregateFunctionParameter::Avg,
+            "sum" => AggregateFunctionParameter::Sum,
+            "count" => AggregateFunctionParameter::Count,
+            "min" => AggregateFunctionParameter::Min,
+            "max" => AggregateFunctionParameter::Max,
+            "var_pop" => AggregateFunctionParameter::VarPop,
+            "stddev_pop" => AggregateFunctionParameter::StddevPop,
+            "stddev_samp" => AggregateFunctionParameter::StddevSamp,
+            "first_value" => AggregateFunctionParameter::FirstValue,
+            "last_value" => AggregateFunctionParameter::LastValue,
+            "approx_percentile" => {
+                let percentile = argument
+                    .as_deref()
+                    .and_then(|argument| argument.parse::<f64>().ok())
+                    .filter(|percentile| (0.0..=1.0).contains(percentile))
+                    .ok_or_else(|| {
+                        SpringError::Sql(anyhow!(
+                            "APPROX_PERCENTILE requires a percentile between 0.0 and 1.0 as the 2nd argument but got {:?}",
+                            argument
+                        ))
+                    })?;
+                return Ok(AggregateFunctionParameter::ApproxPercentile {
+                    percentile: OrderedFloat(percentile),
+                });
+            }
+            _ => {
+                return Err(SpringError::Sql(anyhow!(
+                    "unknown aggregate function {}",
+                    name
+                )))
+            }
+        };
+
+        match argument {
+            None => Ok(func),
+            Some(argument) => Err(SpringError::Sql(anyhow!(
+                "{} does not take the 2nd argument but got {}",
+                name,
+                argument
             ))),
         }
     }
@@ -1105,8 +2000,11 @@ impl PestParserImpl {
     fn parse_integer_type(mut params: FnParseParams) -> Result<SqlType> {
         let s = self_as_str(&mut params);
         match s.to_ascii_uppercase().as_str() {
+            "SMALLINT" => Ok(SqlType::small_int()),
             "INTEGER" => Ok(SqlType::integer()),
+            "BIGINT" => Ok(SqlType::big_int()),
             "UNSIGNED INTEGER" => Ok(SqlType::unsigned_integer()),
+            "UNSIGNED BIGINT" => Ok(SqlType::unsigned_big_int()),
             x => {
                 eprintln!("Unexpected data type parsed: {}", x);
                 unreachable!();
@@ -1217,6 +2115,8 @@ impl PestParserImpl {
         match typ.as_ref() {
             "NET_CLIENT" => Ok(SourceReaderType::NetClient),
             "NET_SERVER" => Ok(SourceReaderType::NetServer),
+            "HTTP1_SERVER" => Ok(SourceReaderType::Http1Server),
+            "FILE" => Ok(SourceReaderType::File),
             "IN_MEMORY_QUEUE" => Ok(SourceReaderType::InMemoryQueue),
             _ => Err(SpringError::Sql(anyhow!(
                 "Invalid source reader name: {}",
@@ -1269,15 +2169,6 @@ impl PestParserImpl {
         )
     }
 
-    fn parse_aggr_alias(mut params: FnParseParams) -> Result<AggrAlias> {
-        parse_child(
-            &mut params,
-            Rule::identifier,
-            Self::parse_identifier,
-            AggrAlias::new,
-        )
-    }
-
     fn parse_correlation(mut params: FnParseParams) -> Result<StreamName> {
         parse_child(
             &mut params,
@@ -1318,9 +2209,11 @@ impl PestParserImpl {
 
     fn parse_column_constraint(mut params: FnParseParams) -> Result<ColumnConstraintSyntax> {
         let s = self_as_str(&mut params);
+        let s = s.split_whitespace().collect::<Vec<_>>().join(" ");
         match s.to_lowercase().as_str() {
             "not null" => Ok(ColumnConstraintSyntax::NotNull),
             "rowtime" => Ok(ColumnConstraintSyntax::Rowtime),
+            "primary key" => Ok(ColumnConstraintSyntax::PrimaryKey),
             x => {
                 eprintln!("Unexpected constraint parsed: {}", x);
                 unreachable!();
@@ -1354,15 +2247,19 @@ impl PestParserImpl {
             &identity,
         )?;
 
-        let not_null = column_constraints_syntax
-            .iter()
-            .any(|constraint_syntax| matches!(constraint_syntax, ColumnConstraintSyntax::NotNull));
+        let not_null = column_constraints_syntax.iter().any(|constraint_syntax| {
+            matches!(
+                constraint_syntax,
+                ColumnConstraintSyntax::NotNull | ColumnConstraintSyntax::PrimaryKey
+            )
+        });
         let column_data_type = ColumnDataType::new(column_name, data_type, !not_null);
 
         let column_constraints = column_constraints_syntax
             .into_iter()
             .filter_map(|constraint_syntax| match constraint_syntax {
                 ColumnConstraintSyntax::Rowtime => Some(ColumnConstraint::Rowtime),
+                ColumnConstraintSyntax::PrimaryKey => Some(ColumnConstraint::PrimaryKey),
                 ColumnConstraintSyntax::NotNull => None,
             })
             .collect::<Vec<_>>();
@@ -1405,3 +2302,579 @@ impl PestParserImpl {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn try_parse_rule<T>(
+        sql: &str,
+        rule: Rule,
+        parser: impl Fn(FnParseParams) -> Result<T>,
+    ) -> Result<T> {
+        let pairs: Pairs<Rule> = GeneratedParser::parse(rule, sql).unwrap();
+        let mut params = FnParseParams {
+            sql,
+            children_pairs: pairs.collect(),
+            self_string: sql.to_string(),
+        };
+        parse_child(&mut params, rule, parser, identity)
+    }
+
+    fn parse_condition(sql: &str) -> ValueExpr {
+        try_parse_rule(sql, Rule::condition, PestParserImpl::parse_condition).unwrap()
+    }
+
+    #[test]
+    fn test_value_expr_precedence() {
+        fn t(sql: &str, expected: ValueExpr) {
+            assert_eq!(parse_condition(sql), expected, "{}", sql);
+        }
+
+        let int = ValueExpr::factory_integer;
+        let colref = ValueExpr::factory_colref;
+
+        // arithmetic
+        t(
+            "1 + 2 * 3",
+            ValueExpr::factory_add(int(1), ValueExpr::factory_mul(int(2), int(3))),
+        );
+        t(
+            "1 * 2 + 3",
+            ValueExpr::factory_add(ValueExpr::factory_mul(int(1), int(2)), int(3)),
+        );
+        t(
+            "(1 + 2) * 3",
+            ValueExpr::factory_mul(ValueExpr::factory_add(int(1), int(2)), int(3)),
+        );
+        t(
+            "10 - 4 - 3",
+            ValueExpr::factory_sub(ValueExpr::factory_sub(int(10), int(4)), int(3)),
+        );
+        t(
+            "-1 + 2",
+            ValueExpr::factory_add(
+                ValueExpr::factory_uni_op(UnaryOperator::Minus, int(1)),
+                int(2),
+            ),
+        );
+        t(
+            "1 - -2",
+            ValueExpr::factory_sub(
+                int(1),
+                ValueExpr::factory_uni_op(UnaryOperator::Minus, int(2)),
+            ),
+        );
+
+        // comparison and logical
+        t(
+            "t.a = 1 AND t.b = 2",
+            ValueExpr::factory_and(
+                ValueExpr::factory_eq(colref("t", "a"), int(1)),
+                ValueExpr::factory_eq(colref("t", "b"), int(2)),
+            ),
+        );
+        t(
+            "t.a + 1 < t.b * 2",
+            ValueExpr::factory_lt(
+                ValueExpr::factory_add(colref("t", "a"), int(1)),
+                ValueExpr::factory_mul(colref("t", "b"), int(2)),
+            ),
+        );
+        t(
+            "TRUE OR FALSE AND FALSE",
+            ValueExpr::factory_or(
+                ValueExpr::factory_bool(true),
+                ValueExpr::factory_and(
+                    ValueExpr::factory_bool(false),
+                    ValueExpr::factory_bool(false),
+                ),
+            ),
+        );
+        t(
+            "(TRUE OR FALSE) AND FALSE",
+            ValueExpr::factory_and(
+                ValueExpr::factory_or(
+                    ValueExpr::factory_bool(true),
+                    ValueExpr::factory_bool(false),
+                ),
+                ValueExpr::factory_bool(false),
+            ),
+        );
+        t(
+            "NOT t.a = 1 OR t.b = 2",
+            ValueExpr::factory_or(
+                ValueExpr::factory_uni_op(
+                    UnaryOperator::Not,
+                    ValueExpr::factory_eq(colref("t", "a"), int(1)),
+                ),
+                ValueExpr::factory_eq(colref("t", "b"), int(2)),
+            ),
+        );
+        t(
+            "NOT t.a + 1 IS NULL",
+            ValueExpr::factory_uni_op(
+                UnaryOperator::Not,
+                ValueExpr::factory_uni_op(
+                    UnaryOperator::IsNull,
+                    ValueExpr::factory_add(colref("t", "a"), int(1)),
+                ),
+            ),
+        );
+        t(
+            "t.a IS NOT NULL AND t.b IS NULL",
+            ValueExpr::factory_and(
+                ValueExpr::factory_uni_op(UnaryOperator::IsNotNull, colref("t", "a")),
+                ValueExpr::factory_uni_op(UnaryOperator::IsNull, colref("t", "b")),
+            ),
+        );
+
+        // string operators
+        let concat = |left, right| {
+            ValueExpr::FunctionCall(FunctionCall::Concat {
+                left: Box::new(left),
+                right: Box::new(right),
+            })
+        };
+        let like = |target, pattern| {
+            ValueExpr::FunctionCall(FunctionCall::Like {
+                target: Box::new(target),
+                pattern: Box::new(pattern),
+            })
+        };
+        t(
+            "t.a || t.b LIKE t.c AND t.d NOT LIKE t.e",
+            ValueExpr::factory_and(
+                like(concat(colref("t", "a"), colref("t", "b")), colref("t", "c")),
+                ValueExpr::factory_uni_op(
+                    UnaryOperator::Not,
+                    like(colref("t", "d"), colref("t", "e")),
+                ),
+            ),
+        );
+        t(
+            "t.a || t.b + 1 = t.c",
+            ValueExpr::factory_eq(
+                concat(
+                    colref("t", "a"),
+                    ValueExpr::factory_add(colref("t", "b"), int(1)),
+                ),
+                colref("t", "c"),
+            ),
+        );
+        t(
+            "CONCAT(t.a, t.b, t.c)",
+            concat(concat(colref("t", "a"), colref("t", "b")), colref("t", "c")),
+        );
+
+        // keyword operators do not eat identifiers
+        t(
+            "note.a = 1 OR t.order = 2",
+            ValueExpr::factory_or(
+                ValueExpr::factory_eq(colref("note", "a"), int(1)),
+                ValueExpr::factory_eq(colref("t", "order"), int(2)),
+            ),
+        );
+    }
+
+    #[test]
+    fn test_aggr_expr_in_value_expr() {
+        let aggr = |func, column_name| {
+            ValueExpr::AggrExpr(Box::new(AggrExpr {
+                func,
+                aggregated: ValueExpr::factory_colref("t", column_name),
+            }))
+        };
+
+        assert_eq!(
+            parse_condition("10 < MAX(t.a) - MIN(t.a)"),
+            ValueExpr::factory_lt(
+                ValueExpr::factory_integer(10),
+                ValueExpr::factory_sub(
+                    aggr(AggregateFunctionParameter::Max, "a"),
+                    aggr(AggregateFunctionParameter::Min, "a"),
+                ),
+            )
+        );
+
+        assert!(matches!(
+            try_parse_rule(
+                "MAX(MIN(t.a))",
+                Rule::condition,
+                PestParserImpl::parse_condition
+            ),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            try_parse_rule(
+                "WHERE MAX(t.a) > 10",
+                Rule::where_clause,
+                PestParserImpl::parse_where_clause
+            ),
+            Err(SpringError::Sql(_))
+        ));
+    }
+
+    #[test]
+    fn test_join_type() {
+        for (sql, expected) in [
+            ("s JOIN t ON s.a = t.a", JoinType::Inner),
+            ("s INNER JOIN t ON s.a = t.a", JoinType::Inner),
+            ("s LEFT OUTER JOIN t ON s.a = t.a", JoinType::LeftOuter),
+            ("s LEFT JOIN t ON s.a = t.a", JoinType::LeftOuter),
+            (
+                "s AS x RIGHT  OUTER JOIN t ON x.a = t.a",
+                JoinType::RightOuter,
+            ),
+            ("s FULL OUTER JOIN t ON s.a = t.a", JoinType::FullOuter),
+            ("s full join t ON s.a = t.a", JoinType::FullOuter),
+        ] {
+            let from_item =
+                try_parse_rule(sql, Rule::from_item, PestParserImpl::parse_from_item).unwrap();
+            match from_item {
+                FromItemSyntax::JoinVariant { join_type, .. } => {
+                    assert_eq!(join_type, expected, "{}", sql)
+                }
+                _ => panic!("{} must be parsed as a join", sql),
+            }
+        }
+    }
+
+    #[test]
+    fn test_having_clause() {
+        let select_stream = try_parse_rule(
+            "SELECT STREAM t.k, COUNT(*) FROM t GROUP BY t.k HAVING 10 < COUNT(*) FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0)",
+            Rule::select_stream_command,
+            PestParserImpl::parse_select_stream,
+        )
+        .unwrap();
+
+        assert_eq!(
+            select_stream.having_clause,
+            Some(ValueExpr::factory_lt(
+                ValueExpr::factory_integer(10),
+                ValueExpr::AggrExpr(Box::new(AggrExpr {
+                    func: AggregateFunctionParameter::Count,
+                    aggregated: ValueExpr::factory_integer(1),
+                })),
+            ))
+        );
+        assert!(select_stream.window_clause.is_some());
+    }
+
+    #[test]
+    fn test_session_window_clause() {
+        let parse = |sql| {
+            try_parse_rule(
+                sql,
+                Rule::window_clause,
+                PestParserImpl::parse_window_clause,
+            )
+        };
+
+        assert_eq!(
+            parse("SESSION WINDOW DURATION_SECS(30), DURATION_SECS(1)").unwrap(),
+            WindowParameter::SessionWindow {
+                gap: SpringEventDuration::from_secs(30),
+                allowed_delay: SpringEventDuration::from_secs(1),
+            }
+        );
+        assert!(matches!(
+            parse("SESSION WINDOW DURATION_SECS(0), DURATION_SECS(0)"),
+            Err(SpringError::Sql(_))
+        ));
+    }
+
+    #[test]
+    fn test_count_window_clause() {
+        let parse = |sql| {
+            try_parse_rule(
+                sql,
+                Rule::window_clause,
+                PestParserImpl::parse_window_clause,
+            )
+        };
+
+        assert_eq!(
+            parse("FIXED WINDOW ROWS(100)").unwrap(),
+            WindowParameter::CountFixedWindow { length: 100 }
+        );
+        assert_eq!(
+            parse("SLIDING WINDOW ROWS(10), ROWS(2)").unwrap(),
+            WindowParameter::CountSlidingWindow {
+                length: 10,
+                period: 2
+            }
+        );
+        assert!(matches!(
+            parse("FIXED WINDOW ROWS(0)"),
+            Err(SpringError::Sql(_))
+        ));
+    }
+
+    #[test]
+    fn test_cast_expr() {
+        let cast = |target, sql_type| {
+            ValueExpr::FunctionCall(FunctionCall::Cast {
+                target: Box::new(target),
+                sql_type,
+            })
+        };
+
+        assert_eq!(
+            parse_condition("CAST(t.a AS UNSIGNED BIGINT) + 1"),
+            ValueExpr::factory_add(
+                cast(
+                    ValueExpr::factory_colref("t", "a"),
+                    SqlType::unsigned_big_int()
+                ),
+                ValueExpr::factory_integer(1),
+            )
+        );
+        assert_eq!(
+            parse_condition("try_cast(cast(t.a as text) AS float)"),
+            ValueExpr::FunctionCall(FunctionCall::TryCast {
+                target: Box::new(cast(ValueExpr::factory_colref("t", "a"), SqlType::text())),
+                sql_type: SqlType::float(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_extract_expr() {
+        assert_eq!(
+            parse_condition("EXTRACT(dow FROM t.ts) = 0"),
+            ValueExpr::factory_eq(
+                ValueExpr::FunctionCall(FunctionCall::Extract {
+                    field: ExtractField::DayOfWeek,
+                    target: Box::new(ValueExpr::factory_colref("t", "ts")),
+                }),
+                ValueExpr::factory_integer(0),
+            )
+        );
+    }
+
+    #[test]
+    fn test_greatest_least_expr() {
+        let greatest = |left, right| {
+            ValueExpr::FunctionCall(FunctionCall::Greatest {
+                left: Box::new(left),
+                right: Box::new(right),
+            })
+        };
+
+        // each argument appears once
+        assert_eq!(
+            parse_condition("GREATEST(t.a, t.b, 3) = 3"),
+            ValueExpr::factory_eq(
+                greatest(
+                    greatest(
+                        ValueExpr::factory_colref("t", "a"),
+                        ValueExpr::factory_colref("t", "b"),
+                    ),
+                    ValueExpr::factory_integer(3),
+                ),
+                ValueExpr::factory_integer(3),
+            )
+        );
+
+        // NULL is not equal to NULL in SQL, so check the single argument by pattern.
+        match parse_condition("least(t.a) = 1") {
+            ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                ComparisonFunction::EqualVariant { left, .. },
+            )) => match *left {
+                ValueExpr::FunctionCall(FunctionCall::Least { left, right }) => {
+                    assert_eq!(*left, ValueExpr::factory_colref("t", "a"));
+                    assert!(matches!(*right, ValueExpr::Constant(SqlValue::Null)))
+                }
+                v => unreachable!("{:?}", v),
+            },
+            v => unreachable!("{:?}", v),
+        }
+    }
+
+    #[test]
+    fn test_case_expr() {
+        let if_expr = |condition, then_value, else_value| {
+            ValueExpr::FunctionCall(FunctionCall::If {
+                condition: Box::new(condition),
+                then_value: Box::new(then_value),
+                else_value: Box::new(else_value),
+            })
+        };
+
+        assert_eq!(
+            parse_condition("CASE WHEN t.a < 1 THEN 1 WHEN t.a < 2 THEN 2 ELSE 3 END = 1"),
+            ValueExpr::factory_eq(
+                if_expr(
+                    ValueExpr::factory_lt(
+                        ValueExpr::factory_colref("t", "a"),
+                        ValueExpr::factory_integer(1)
+                    ),
+                    ValueExpr::factory_integer(1),
+                    if_expr(
+                        ValueExpr::factory_lt(
+                            ValueExpr::factory_colref("t", "a"),
+                            ValueExpr::factory_integer(2)
+                        ),
+                        ValueExpr::factory_integer(2),
+                        ValueExpr::factory_integer(3),
+                    ),
+                ),
+                ValueExpr::factory_integer(1),
+            )
+        );
+
+        assert_eq!(
+            parse_condition("case t.a when 1 then 10 else 0 end = 10"),
+            ValueExpr::factory_eq(
+                if_expr(
+                    ValueExpr::factory_eq(
+                        ValueExpr::factory_colref("t", "a"),
+                        ValueExpr::factory_integer(1)
+                    ),
+                    ValueExpr::factory_integer(10),
+                    ValueExpr::factory_integer(0),
+                ),
+                ValueExpr::factory_integer(10),
+            )
+        );
+
+        // NULL is not equal to NULL in SQL, so check the missing ELSE by pattern.
+        match parse_condition("CASE WHEN t.a < 1 THEN 1 END = 1") {
+            ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                ComparisonFunction::EqualVariant { left, .. },
+            )) => match *left {
+                ValueExpr::FunctionCall(FunctionCall::If { else_value, .. }) => {
+                    assert!(matches!(*else_value, ValueExpr::Constant(SqlValue::Null)))
+                }
+                v => unreachable!("{:?}", v),
+            },
+            v => unreachable!("{:?}", v),
+        }
+    }
+
+    #[test]
+    fn test_null_if_is_not_null_constant() {
+        assert_eq!(
+            parse_condition("NULLIF(t.a, 0) = 1"),
+            ValueExpr::factory_eq(
+                ValueExpr::FunctionCall(FunctionCall::NullIf {
+                    left: Box::new(ValueExpr::factory_colref("t", "a")),
+                    right: Box::new(ValueExpr::factory_integer(0)),
+                }),
+                ValueExpr::factory_integer(1),
+            )
+        );
+    }
+
+    #[test]
+    fn test_json_extract_operator() {
+        let json_extract = |target, path: &str| {
+            ValueExpr::FunctionCall(FunctionCall::JsonExtract {
+                target: Box::new(target),
+                path: Box::new(ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::Text(
+                    path.to_string(),
+                )))),
+            })
+        };
+
+        // `->` binds tighter than arithmetic and is left-associative
+        assert_eq!(
+            parse_condition("t.payload -> '$.engine' -> '$.rpm' - 1 = 0"),
+            ValueExpr::factory_eq(
+                ValueExpr::factory_sub(
+                    json_extract(
+                        json_extract(ValueExpr::factory_colref("t", "payload"), "$.engine"),
+                        "$.rpm"
+                    ),
+                    ValueExpr::factory_integer(1)
+                ),
+                ValueExpr::factory_integer(0),
+            )
+        );
+        assert_eq!(
+            parse_condition("JSON_EXTRACT(t.payload, '$.wheels[0]') = 0"),
+            ValueExpr::factory_eq(
+                json_extract(ValueExpr::factory_colref("t", "payload"), "$.wheels[0]"),
+                ValueExpr::factory_integer(0),
+            )
+        );
+    }
+
+    #[test]
+    fn test_create_dead_letter_stream() {
+        let parse =
+            |sql: &str| try_parse_rule(sql, Rule::command, PestParserImpl::parse_command).unwrap();
+
+        for (sql, target) in [
+            (
+                "CREATE SINK STREAM dl AS DEAD LETTER FOR PUMP pu_a;",
+                DeadLetterTarget::Pump(PumpName::new("pu_a".to_string())),
+            ),
+            (
+                "CREATE SINK STREAM dl AS DEAD LETTER FOR SOURCE READER tcp_1;",
+                DeadLetterTarget::SourceReader(SourceReaderName::new("tcp_1".to_string())),
+            ),
+            (
+                "CREATE SINK STREAM dl AS DEAD LETTER FOR SINK WRITER tcp_sink_1;",
+                DeadLetterTarget::SinkWriter(SinkWriterName::new("tcp_sink_1".to_string())),
+            ),
+        ] {
+            assert_eq!(
+                parse(sql),
+                ParseSuccess::CreateDeadLetterStream(DeadLetterStreamModel::new(
+                    StreamName::new("dl".to_string()),
+                    target
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_table() {
+        let parse = |sql: &str| try_parse_rule(sql, Rule::command, PestParserImpl::parse_command);
+
+        let table = parse(
+            "CREATE TABLE city_info (
+              city TEXT PRIMARY  KEY,
+              population INTEGER
+            ) OPTIONS (
+              MAX_ROWS '100'
+            );",
+        )
+        .unwrap();
+
+        let expected_shape = StreamShape::new(vec![
+            ColumnDefinition::new(
+                ColumnDataType::new(ColumnName::new("city".to_string()), SqlType::text(), false),
+                vec![ColumnConstraint::PrimaryKey],
+            ),
+            ColumnDefinition::new(
+                ColumnDataType::new(
+                    ColumnName::new("population".to_string()),
+                    SqlType::integer(),
+                    true,
+                ),
+                vec![],
+            ),
+        ])
+        .unwrap();
+        let expected_table = TableModel::new(
+            StreamName::new("city_info".to_string()),
+            expected_shape,
+            OptionsBuilder::default().add("MAX_ROWS", "100").build(),
+        )
+        .unwrap();
+        assert_eq!(table, ParseSuccess::CreateTable(expected_table));
+
+        assert!(matches!(
+            parse("CREATE TABLE city_info (city TEXT NOT NULL);"),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            parse("CREATE TABLE city_info (city TEXT PRIMARY KEY) OPTIONS (MAX_ROWS 'x');"),
+            Err(SpringError::InvalidOption { .. })
+        ));
+    }
+}
@@ -134,6 +134,25 @@ pub fn parse_child_seq<T, ChildRet>(
     Ok(children)
 }
 
+/// Parse a pair taken out of the children sequence by `parser`.
+///
+/// Used when children are not consumed in order (e.g. by `pest::pratt_parser::PrattParser`).
+///
+/// # Failures
+/// Raises Err from `parser` as-is.
+pub fn parse_pair<'a, T>(
+    sql: &'a str,
+    pair: Pair<'a, Rule>,
+    parser: impl Fn(FnParseParams) -> Result<T>,
+) -> Result<T> {
+    let params = FnParseParams {
+        sql,
+        self_string: pair.as_str().to_string(),
+        children_pairs: pair.into_inner().collect(),
+    };
+    parser(params)
+}
+
 pub fn self_as_str<'a>(params: &'a mut FnParseParams) -> &'a str {
     params.self_string.as_str()
 }
@@ -9,6 +9,7 @@ use crate::{
 pub enum ColumnConstraintSyntax {
     NotNull, // this is treated as data type in pipeline
     Rowtime,
+    PrimaryKey,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -22,9 +23,15 @@ pub struct SelectStreamSyntax {
     pub fields: Vec<SelectFieldSyntax>,
     pub from_item: FromItemSyntax,
 
+    /// None when no WHERE clause is supplied.
+    pub where_clause: Option<ValueExpr>,
+
     /// Empty when no GROUP BY clause is supplied.
     pub grouping_elements: Vec<GroupingElementSyntax>,
 
+    /// None when no HAVING clause is supplied.
+    pub having_clause: Option<ValueExpr>,
+
     pub window_clause: Option<WindowParameter>,
 }
 
@@ -13,7 +13,10 @@ use std::sync::{Arc, Mutex, MutexGuard};
 use anyhow::anyhow;
 
 pub use crate::stream_engine::autonomous_executor::SpringValue;
-pub use autonomous_executor::{NnSqlValue, RowTime, SqlCompareResult, SqlValue, StreamRow, Tuple};
+pub use autonomous_executor::{
+    AggregatedAndGroupingValues, JsonPath, NnSqlValue, RowTime, SqlCompareResult, SqlValue,
+    StreamRow, Tuple,
+};
 
 use crate::{
     api::{error::Result, SpringConfig, SpringError},
@@ -74,8 +77,13 @@ impl StreamEngine {
         self.sql_executor.current_pipeline()
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - the pipeline has been stopped by a fatal error.
     pub fn alter_pipeline(&mut self, command: AlterPipelineCommand) -> Result<()> {
         log::debug!("[StreamEngine] alter_pipeline({:?})", command);
+        self.autonomous_executor.check_fatal_error()?;
         let pipeline = self.sql_executor.alter_pipeline(command)?;
         self.autonomous_executor.notify_pipeline_update(pipeline)
     }
@@ -89,10 +97,12 @@ impl StreamEngine {
     ///
     /// - `SpringError::Unavailable` when:
     ///   - queue named `queue_name` does not exist.
+    ///   - the pipeline has been stopped by a fatal error.
     pub fn pop_in_memory_queue_non_blocking(
         &mut self,
         queue_name: QueueName,
     ) -> Result<Option<SchemalessRow>> {
+        self.autonomous_executor.check_fatal_error()?;
         let q = InMemoryQueueRepository::instance().get(&queue_name)?;
         let row = q.pop_non_blocking();
         Ok(row)
@@ -102,11 +112,13 @@ impl StreamEngine {
     ///
     /// - `SpringError::Unavailable` when:
     ///   - queue named `queue_name` does not exist.
+    ///   - the pipeline has been stopped by a fatal error.
     pub fn push_in_memory_queue(
         &mut self,
         queue_name: QueueName,
         row: SchemalessRow,
     ) -> Result<()> {
+        self.autonomous_executor.check_fatal_error()?;
         let q = InMemoryQueueRepository::instance().get(&queue_name)?;
         q.push(row);
         Ok(())
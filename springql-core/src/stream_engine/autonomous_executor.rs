@@ -2,6 +2,7 @@
 
 mod args;
 mod event_queue;
+mod fatal_error;
 mod main_job_lock;
 mod memory_state_machine;
 mod memory_state_machine_worker;
@@ -12,6 +13,7 @@ mod purger_worker;
 mod queue;
 mod repositories;
 mod row;
+mod table;
 mod task;
 mod task_executor;
 mod task_graph;
@@ -22,12 +24,13 @@ pub mod test_support;
 
 pub use row::SpringValue;
 pub use row::{
-    ColumnValues, JsonObject, NnSqlValue, RowTime, SchemalessRow, SourceRow, SqlCompareResult,
-    SqlValue, SqlValueHashKey, StreamColumns, StreamRow,
+    ColumnValues, JsonObject, JsonPath, NnSqlValue, RowTime, SchemalessRow, SourceRow,
+    SqlCompareResult, SqlValue, SqlValueHashKey, StreamColumns, StreamRow,
 };
 pub use task::{
-    NetClientSourceReader, NetServerSourceReader, SinkWriterRepository, SourceReader,
-    SourceReaderRepository, SourceTask, Task, TaskContext, Tuple, Window,
+    AggregatedAndGroupingValues, NetClientSourceReader, NetServerSourceReader,
+    SinkWriterRepository, SourceReader, SourceReaderRepository, SourceTask, Task, TaskContext,
+    Tuple, Window,
 };
 
 use std::sync::Arc;
@@ -61,6 +64,7 @@ use crate::{
 pub struct AutonomousExecutor {
     b_event_queue: Arc<BlockingEventQueue>,
 
+    repos: Arc<Repositories>,
     main_job_lock: Arc<MainJobLock>,
     task_executor: TaskExecutor,
 
@@ -104,12 +108,13 @@ impl AutonomousExecutor {
             locks.main_job_lock.clone(),
             event_queues.clone(),
             coordinators.clone(),
+            repos.clone(),
         );
         let purger_worker = PurgerWorker::new(
             locks.main_job_lock.clone(),
             event_queues.clone(),
             coordinators.clone(),
-            PurgerWorkerThreadArg::new(repos, locks.task_executor_lock.clone()),
+            PurgerWorkerThreadArg::new(repos.clone(), locks.task_executor_lock.clone()),
         );
 
         coordinators
@@ -119,6 +124,7 @@ impl AutonomousExecutor {
 
         Self {
             b_event_queue: event_queues.blocking,
+            repos,
             main_job_lock: locks.main_job_lock,
             task_executor,
             _memory_state_machine_worker: memory_state_machine_worker,
@@ -127,6 +133,14 @@ impl AutonomousExecutor {
         }
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - the pipeline has been stopped by a fatal error.
+    pub fn check_fatal_error(&self) -> Result<()> {
+        self.repos.fatal_error().check()
+    }
+
     pub fn notify_pipeline_update(&self, pipeline: Pipeline) -> Result<()> {
         let main_job_lock = &self.main_job_lock;
         let lock = main_job_lock.main_job_barrier();
@@ -0,0 +1,63 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use anyhow::anyhow;
+use parking_lot::RwLock;
+
+use crate::{
+    api::error::{Result, SpringError},
+    stream_engine::autonomous_executor::task::error_message,
+};
+
+/// Error which stops the whole pipeline, like a row rejected with `ON_SCHEMA_ERROR 'FAIL'`.
+///
+/// Once raised, workers stop executing tasks and the API calls fail.
+/// Only the first error is kept.
+#[derive(Debug, Default)]
+pub struct FatalError(RwLock<Option<String>>);
+
+impl FatalError {
+    pub fn raise(&self, e: &SpringError) {
+        let mut message = self.0.write();
+        if message.is_none() {
+            log::error!("[FatalError] Pipeline stopped: {:?}", e);
+            *message = Some(error_message(e));
+        }
+    }
+
+    pub fn is_raised(&self) -> bool {
+        self.0.read().is_some()
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - a fatal error has been raised.
+    pub fn check(&self) -> Result<()> {
+        match self.0.read().as_ref() {
+            Some(message) => Err(SpringError::Unavailable {
+                resource: "pipeline".to_string(),
+                source: anyhow!("pipeline stopped by a fatal error: {}", message),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_error_kept() {
+        let fatal_error = FatalError::default();
+        assert!(fatal_error.check().is_ok());
+
+        fatal_error.raise(&SpringError::Sql(anyhow!("first")));
+        fatal_error.raise(&SpringError::Sql(anyhow!("second")));
+        assert!(fatal_error.is_raised());
+
+        let e = fatal_error.check().unwrap_err();
+        assert!(matches!(e, SpringError::Unavailable { .. }));
+        assert!(format!("{:?}", e).contains("first"));
+    }
+}
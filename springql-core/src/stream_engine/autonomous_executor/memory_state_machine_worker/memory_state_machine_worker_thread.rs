@@ -111,7 +111,7 @@ impl WorkerThread for MemoryStateMachineWorkerThread {
     ) -> Self::LoopState {
         let mut state = current_state;
 
-        let bytes = metrics_summary.queue_total_bytes;
+        let bytes = metrics_summary.purgeable_bytes();
         if let Some(transition) = state.memory_state_machine.update_memory_usage(bytes) {
             log::warn!(
                 "[MemoryStateMachineWorker] Memory state transition: {:?}",
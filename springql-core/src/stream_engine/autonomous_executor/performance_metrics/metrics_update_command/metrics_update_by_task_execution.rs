@@ -44,6 +44,18 @@ impl MetricsUpdateByTaskExecution {
         self.task_gain_bytes() as f32 / self.task_execution_time().as_secs_f32()
     }
 
+    pub fn task_rows_filtered(&self) -> u64 {
+        self.task.rows_filtered
+    }
+
+    pub fn task_rows_rejected(&self) -> u64 {
+        self.task.rows_rejected
+    }
+
+    pub fn task_retry_buffer_gain_bytes(&self) -> i64 {
+        self.task.retry_buffer_gain_bytes
+    }
+
     pub fn row_queue_gain_rows(&self, id: &RowQueueId) -> i64 {
         self.queue_put_rows(&id.clone().into()) as i64 - self.row_queue_used_rows(id) as i64
     }
@@ -208,6 +220,15 @@ impl MetricsUpdateByTaskExecution {
 pub struct TaskMetricsUpdateByTask {
     task_id: TaskId,
     execution_time: WallClockDuration,
+
+    /// Rows dropped by selection (WHERE clause) in this execution. Always 0 for source and sink tasks.
+    rows_filtered: u64,
+
+    /// Source rows rejected by the source stream definition in this execution. Always 0 for pump and sink tasks.
+    rows_rejected: u64,
+
+    /// Gain of the sink writer's retry buffer in this execution. Always 0 for source and pump tasks.
+    retry_buffer_gain_bytes: i64,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
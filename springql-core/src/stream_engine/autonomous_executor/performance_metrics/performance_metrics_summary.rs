@@ -12,23 +12,48 @@ use crate::stream_engine::autonomous_executor::performance_metrics::PerformanceM
 /// - launches purger
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct PerformanceMetricsSummary {
+    /// Bytes of rows in queues.
     pub queue_total_bytes: u64,
-}
 
-impl From<&PerformanceMetrics> for PerformanceMetricsSummary {
-    fn from(pm: &PerformanceMetrics) -> Self {
-        let queue_total_bytes = Self::queue_total_bytes(pm);
-        Self { queue_total_bytes }
-    }
+    /// Bytes of rows in sink writers' retry buffers.
+    pub retry_buffer_total_bytes: u64,
+
+    /// Bytes of rows in tables.
+    pub table_total_bytes: u64,
 }
 
 impl Display for PerformanceMetricsSummary {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.queue_total_bytes)
+        write!(
+            f,
+            "{} (tables: {}, retry buffers: {})",
+            self.purgeable_bytes(),
+            self.table_total_bytes,
+            self.retry_buffer_total_bytes
+        )
     }
 }
 
 impl PerformanceMetricsSummary {
+    /// Tables are not in `PerformanceMetrics` since they live across pipeline updates.
+    pub fn new(pm: &PerformanceMetrics, table_total_bytes: u64) -> Self {
+        let queue_total_bytes = Self::queue_total_bytes(pm);
+        let retry_buffer_total_bytes = Self::retry_buffer_total_bytes(pm);
+        Self {
+            queue_total_bytes,
+            retry_buffer_total_bytes,
+            table_total_bytes,
+        }
+    }
+
+    /// Bytes counted toward `memory.upper_limit_bytes`, which the memory state machine decides whether to purge with.
+    ///
+    /// Rows in retry buffers are routed to dead-letter streams by the purger.
+    /// Tables are counted but not freed by the purger since they are bounded by `MAX_ROWS` instead.
+    pub fn purgeable_bytes(&self) -> u64 {
+        self.queue_total_bytes + self.retry_buffer_total_bytes + self.table_total_bytes
+    }
+
     fn queue_total_bytes(pm: &PerformanceMetrics) -> u64 {
         let window = pm
             .get_window_queues()
@@ -42,4 +67,10 @@ impl PerformanceMetricsSummary {
 
         window + row
     }
+
+    fn retry_buffer_total_bytes(pm: &PerformanceMetrics) -> u64 {
+        pm.get_tasks()
+            .iter()
+            .fold(0, |acc, (_, met)| acc + met.retry_buffer_bytes())
+    }
 }
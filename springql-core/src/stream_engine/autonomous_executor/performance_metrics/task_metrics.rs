@@ -1,7 +1,8 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use crate::stream_engine::autonomous_executor::performance_metrics::{
-    calculation::next_avg, metrics_update_command::MetricsUpdateByTaskExecution,
+    calculation::{floor0, next_avg},
+    metrics_update_command::MetricsUpdateByTaskExecution,
 };
 
 /// Flow monitor of a task (including in-memory queue sink) execution.
@@ -9,6 +10,9 @@ use crate::stream_engine::autonomous_executor::performance_metrics::{
 pub struct TaskMetrics {
     avg_gain_bytes_per_sec: f32,
     n_executions: u64,
+    rows_filtered: u64,
+    rows_rejected: u64,
+    retry_buffer_bytes: i64,
 }
 
 impl TaskMetrics {
@@ -21,9 +25,27 @@ impl TaskMetrics {
             n,
             command.task_gain_bytes_per_sec(),
         );
+        self.rows_filtered += command.task_rows_filtered();
+        self.rows_rejected += command.task_rows_rejected();
+        self.retry_buffer_bytes += command.task_retry_buffer_gain_bytes();
     }
 
     pub fn avg_gain_bytes_per_sec(&self) -> f32 {
         self.avg_gain_bytes_per_sec
     }
+
+    /// Total rows dropped by selection (WHERE clause) so far.
+    pub fn rows_filtered(&self) -> u64 {
+        self.rows_filtered
+    }
+
+    /// Total source rows rejected by the source stream definition (`ON_SCHEMA_ERROR`) so far.
+    pub fn rows_rejected(&self) -> u64 {
+        self.rows_rejected
+    }
+
+    /// Bytes of rows waiting in the sink writer's retry buffer (0 for source and pump tasks).
+    pub fn retry_buffer_bytes(&self) -> u64 {
+        floor0(self.retry_buffer_bytes)
+    }
 }
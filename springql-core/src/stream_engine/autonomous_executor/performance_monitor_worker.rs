@@ -14,6 +14,7 @@ use crate::{
         args::{Coordinators, EventQueues},
         main_job_lock::MainJobLock,
         performance_monitor_worker::performance_monitor_worker_thread::PerformanceMonitorWorkerThreadArg,
+        repositories::Repositories,
         worker::WorkerHandle,
     },
 };
@@ -33,12 +34,13 @@ impl PerformanceMonitorWorker {
         main_job_lock: Arc<MainJobLock>,
         event_queues: EventQueues,
         coordinators: Coordinators,
+        repos: Arc<Repositories>,
     ) -> Self {
         let handle = WorkerHandle::new::<PerformanceMonitorWorkerThread>(
             main_job_lock,
             event_queues,
             coordinators,
-            PerformanceMonitorWorkerThreadArg::from(config),
+            PerformanceMonitorWorkerThreadArg::new(config, repos),
         );
         Self { _handle: handle }
     }
@@ -15,6 +15,7 @@ use crate::{
             },
             performance_monitor_worker::web_console_reporter::WebConsoleReporter,
             pipeline_derivatives::PipelineDerivatives,
+            repositories::Repositories,
             worker::{WorkerSetupCoordinator, WorkerThread, WorkerThreadLoopState},
         },
         time::{SpringDuration, WallClockDuration},
@@ -31,10 +32,11 @@ pub struct PerformanceMonitorWorkerThread;
 pub struct PerformanceMonitorWorkerThreadArg {
     config: SpringConfig,
     web_console_reporter: Option<WebConsoleReporter>,
+    repos: Arc<Repositories>,
 }
 
-impl From<&SpringConfig> for PerformanceMonitorWorkerThreadArg {
-    fn from(config: &SpringConfig) -> Self {
+impl PerformanceMonitorWorkerThreadArg {
+    pub fn new(config: &SpringConfig, repos: Arc<Repositories>) -> Self {
         let web_console_reporter = if config.web_console.enable_report_post {
             Some(WebConsoleReporter::new(
                 &config.web_console.host,
@@ -47,6 +49,7 @@ impl From<&SpringConfig> for PerformanceMonitorWorkerThreadArg {
         Self {
             config: config.clone(),
             web_console_reporter,
+            repos,
         }
     }
 }
@@ -118,6 +121,7 @@ impl WorkerThread for PerformanceMonitorWorkerThread {
             state = Self::report_metrics_summary(
                 state,
                 metrics.as_ref(),
+                thread_arg.repos.as_ref(),
                 event_queue,
                 thread_arg
                     .config
@@ -212,6 +216,7 @@ impl PerformanceMonitorWorkerThread {
     fn report_metrics_summary(
         state: PerformanceMonitorWorkerLoopState,
         metrics: &PerformanceMetrics,
+        repos: &Repositories,
         event_queue: &NonBlockingEventQueue,
         report_interval_msec: i32,
     ) -> PerformanceMonitorWorkerLoopState {
@@ -220,7 +225,10 @@ impl PerformanceMonitorWorkerThread {
         if state.countdown_metrics_summary_msec <= 0 {
             state.countdown_metrics_summary_msec = report_interval_msec;
 
-            let metrics_summary = Arc::new(PerformanceMetricsSummary::from(metrics));
+            let metrics_summary = Arc::new(PerformanceMetricsSummary::new(
+                metrics,
+                repos.table_repository().total_bytes(),
+            ));
             log::trace!(
                 "PerformanceMonitorWorkerThread::report_metrics_summary: metrics_summary={:?}",
                 metrics_summary
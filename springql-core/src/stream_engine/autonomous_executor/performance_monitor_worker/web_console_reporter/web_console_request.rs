@@ -53,6 +53,8 @@ struct TaskRequest {
     id: String,
     type_: String,
     avg_gain_bytes_per_sec: f32,
+    rows_filtered: u64,
+    rows_rejected: u64,
 }
 
 impl TaskRequest {
@@ -66,6 +68,8 @@ impl TaskRequest {
             }
             .to_string(),
             avg_gain_bytes_per_sec: metrics.avg_gain_bytes_per_sec(),
+            rows_filtered: metrics.rows_filtered(),
+            rows_rejected: metrics.rows_rejected(),
         }
     }
 
@@ -75,6 +79,8 @@ impl TaskRequest {
                 "id": self.id.clone(),
                 "type": self.type_.clone(),
                 "avg-gain-bytes-per-sec": self.avg_gain_bytes_per_sec,
+                "rows-filtered": self.rows_filtered,
+                "rows-rejected": self.rows_rejected,
             }
         )
     }
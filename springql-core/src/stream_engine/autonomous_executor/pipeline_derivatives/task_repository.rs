@@ -8,7 +8,10 @@ use crate::{
     api::error::{Result, SpringError},
     pipeline::PipelineGraph,
     stream_engine::autonomous_executor::{
-        task::{Task, Window},
+        performance_metrics::MetricsUpdateByTaskExecution,
+        pipeline_derivatives::PipelineDerivatives,
+        repositories::Repositories,
+        task::{Task, TaskContext, Window},
         task_graph::TaskId,
     },
 };
@@ -32,6 +35,33 @@ impl TaskRepository {
             .map(|t| t.clone())
     }
 
+    /// Routes rows in sink writers' retry buffers to dead-letter streams.
+    ///
+    /// # Returns
+    ///
+    /// Metrics updates of sink tasks whose retry buffers are purged.
+    pub fn purge_retry_buffers(
+        &self,
+        pipeline_derivatives: &Arc<PipelineDerivatives>,
+        repos: &Arc<Repositories>,
+    ) -> Vec<MetricsUpdateByTaskExecution> {
+        self.repo
+            .iter()
+            .filter_map(|(task_id, task)| match task.as_ref() {
+                Task::Sink(sink_task) => {
+                    let context = TaskContext::new(
+                        task_id.clone(),
+                        pipeline_derivatives.clone(),
+                        repos.clone(),
+                    );
+                    Some(sink_task.purge_retry_buffer(&context))
+                }
+                _ => None,
+            })
+            .filter(|metrics| metrics.task_retry_buffer_gain_bytes() != 0)
+            .collect()
+    }
+
     pub fn purge_windows(&self) {
         for task in self.repo.values() {
             if let Task::Pump(pump_task) = task.as_ref() {
@@ -45,6 +45,34 @@ impl WorkerThreadLoopState for PurgerWorkerLoopState {
 #[derive(Debug)]
 pub struct PurgerWorkerThread;
 
+impl PurgerWorkerThread {
+    /// Routes rows in sink writers' retry buffers to dead-letter streams.
+    ///
+    /// Must be called under the task execution barrier.
+    fn purge_retry_buffers(
+        current_state: &PurgerWorkerLoopState,
+        thread_arg: &PurgerWorkerThreadArg,
+        event_queue: &NonBlockingEventQueue,
+    ) {
+        if let Some(pd) = &current_state.pipeline_derivatives {
+            let metrics_updates = pd.task_repo().purge_retry_buffers(pd, &thread_arg.repos);
+            if !metrics_updates.is_empty() {
+                log::warn!(
+                    "[PurgerWorker] Routed retry buffers of {} sink writers to dead-letter streams.",
+                    metrics_updates.len()
+                );
+            }
+            for metrics_update in metrics_updates {
+                event_queue.publish(Event::IncrementalUpdateMetrics {
+                    metrics_update_by_task_execution_or_purge: Arc::new(
+                        MetricsUpdateByTaskExecutionOrPurge::TaskExecution(metrics_update),
+                    ),
+                });
+            }
+        }
+    }
+}
+
 impl WorkerThread for PurgerWorkerThread {
     const THREAD_NAME: &'static str = "PurgerWorker";
 
@@ -93,9 +121,15 @@ impl WorkerThread for PurgerWorkerThread {
         event_queue: Arc<NonBlockingEventQueue>,
     ) -> Self::LoopState {
         match memory_state_transition.to_state() {
-            MemoryState::Moderate | MemoryState::Severe => {
+            MemoryState::Moderate => {
                 // do nothing
             }
+            MemoryState::Severe => {
+                let task_executor_lock = &thread_arg.task_executor_lock;
+                let _lock = task_executor_lock.task_execution_barrier();
+
+                Self::purge_retry_buffers(&current_state, thread_arg, &event_queue);
+            }
             MemoryState::Critical => {
                 log::warn!("[PurgerWorker] Start purging...",);
 
@@ -114,6 +148,8 @@ impl WorkerThread for PurgerWorkerThread {
                     task_repo.purge_windows()
                 }
 
+                // tables are not purged since they are bounded by `MAX_ROWS`
+
                 // reset metrics
                 event_queue.publish(Event::IncrementalUpdateMetrics {
                     metrics_update_by_task_execution_or_purge: Arc::new(
@@ -122,6 +158,9 @@ impl WorkerThread for PurgerWorkerThread {
                 });
 
                 log::warn!("[PurgerWorker] Finished purging. Sent `MetricsUpdateByTaskExecutionOrPurge::Purge` event.");
+
+                // after resetting queue metrics since dead letters are pushed to queues
+                Self::purge_retry_buffers(&current_state, thread_arg, &event_queue);
             }
         }
 
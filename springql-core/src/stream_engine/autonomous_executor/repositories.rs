@@ -3,7 +3,9 @@
 use crate::{
     api::SpringConfig,
     stream_engine::autonomous_executor::{
+        fatal_error::FatalError,
         queue::{RowQueueRepository, WindowQueueRepository},
+        table::TableRepository,
         task::{SinkWriterRepository, SourceReaderRepository},
     },
 };
@@ -15,6 +17,8 @@ pub struct Repositories {
     window_queue_repository: WindowQueueRepository,
     source_reader_repository: SourceReaderRepository,
     sink_writer_repository: SinkWriterRepository,
+    table_repository: TableRepository,
+    fatal_error: FatalError,
 }
 
 impl Repositories {
@@ -24,6 +28,8 @@ impl Repositories {
             window_queue_repository: WindowQueueRepository::default(),
             source_reader_repository: SourceReaderRepository::new(config.source_reader),
             sink_writer_repository: SinkWriterRepository::new(config.sink_writer),
+            table_repository: TableRepository::new(config.memory),
+            fatal_error: FatalError::default(),
         }
     }
 
@@ -42,4 +48,12 @@ impl Repositories {
     pub fn sink_writer_repository(&self) -> &SinkWriterRepository {
         &self.sink_writer_repository
     }
+
+    pub fn table_repository(&self) -> &TableRepository {
+        &self.table_repository
+    }
+
+    pub fn fatal_error(&self) -> &FatalError {
+        &self.fatal_error
+    }
 }
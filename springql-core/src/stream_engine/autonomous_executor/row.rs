@@ -11,7 +11,7 @@ mod value;
 
 pub use column::StreamColumns;
 pub use column_values::ColumnValues;
-pub use foreign_row::{JsonObject, JsonSourceRow, SourceRow};
+pub use foreign_row::{JsonObject, JsonPath, JsonSourceRow, SourceRow};
 pub use rowtime::RowTime;
 pub use schemaless_row::SchemalessRow;
 pub use stream_row::StreamRow;
@@ -7,7 +7,7 @@ use anyhow::{anyhow, Context};
 use crate::{
     api::error::{Result, SpringError},
     mem_size::{arc_overhead_size, MemSize},
-    pipeline::{ColumnDefinition, ColumnName, StreamModel},
+    pipeline::{ColumnDefinition, ColumnName, OnSchemaError, StreamModel},
     stream_engine::{
         autonomous_executor::row::{column_values::ColumnValues, value::SqlValue},
         time::SpringTimestamp,
@@ -41,14 +41,48 @@ impl StreamColumns {
     /// - `SpringError::Sql` when:
     ///   - `column_values` lacks any of `stream.columns()`.
     ///   - Type mismatch (and failed to convert type) with `stream_shape` and `column_values`.
-    pub fn new(stream_model: Arc<StreamModel>, mut column_values: ColumnValues) -> Result<Self> {
+    pub fn new(stream_model: Arc<StreamModel>, column_values: ColumnValues) -> Result<Self> {
+        Self::new_with_policy(stream_model, column_values, OnSchemaError::Drop)
+    }
+
+    /// Same as `new()` but tries to recover from schema errors as `on_schema_error` specifies.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `column_values` does not match with `stream_shape` even after recovery.
+    pub fn new_with_policy(
+        stream_model: Arc<StreamModel>,
+        mut column_values: ColumnValues,
+        on_schema_error: OnSchemaError,
+    ) -> Result<Self> {
         let values = stream_model
             .shape()
             .columns()
             .iter()
             .map(|coldef| {
-                let value = column_values.remove(coldef.column_data_type().column_name())?;
-                Self::validate_or_try_convert_value_type(value, coldef)
+                let value = column_values.remove(coldef.column_data_type().column_name());
+                match on_schema_error {
+                    OnSchemaError::Drop | OnSchemaError::Fail => {
+                        Self::validate_or_try_convert_value_type(value?, coldef)
+                    }
+                    OnSchemaError::NullFill => value
+                        .and_then(|value| Self::validate_or_try_convert_value_type(value, coldef))
+                        .or_else(|_| {
+                            Self::validate_or_try_convert_value_type(SqlValue::Null, coldef)
+                        }),
+                    OnSchemaError::Coerce => {
+                        let value = value?;
+                        Self::validate_or_try_convert_value_type(value.clone(), coldef).or_else(
+                            |e| match value {
+                                SqlValue::NotNull(nn_value) => nn_value
+                                    .try_cast(coldef.column_data_type().sql_type())
+                                    .map(SqlValue::NotNull),
+                                SqlValue::Null => Err(e),
+                            },
+                        )
+                    }
+                }
             })
             .collect::<Result<Vec<SqlValue>>>()?;
 
@@ -275,4 +309,47 @@ mod tests {
             SpringError::Sql(_)
         ));
     }
+
+    #[test]
+    fn test_on_schema_error() {
+        let column_values = |temperature: SqlValue| {
+            let mut column_values = ColumnValues::default();
+            column_values
+                .insert(
+                    ColumnName::fx_timestamp(),
+                    SqlValue::NotNull(NnSqlValue::Timestamp(SpringTimestamp::fx_ts1())),
+                )
+                .unwrap();
+            column_values
+                .insert(
+                    ColumnName::fx_city(),
+                    SqlValue::NotNull(NnSqlValue::Text("Tokyo".to_string())),
+                )
+                .unwrap();
+            column_values
+                .insert(ColumnName::fx_temperature(), temperature)
+                .unwrap();
+            column_values
+        };
+        let new_with_policy = |temperature: &str, on_schema_error| {
+            StreamColumns::new_with_policy(
+                Arc::new(StreamModel::fx_city_temperature()),
+                column_values(SqlValue::NotNull(NnSqlValue::Text(temperature.to_string()))),
+                on_schema_error,
+            )
+        };
+
+        let coerced = new_with_policy("21", OnSchemaError::Coerce).unwrap();
+        assert_eq!(
+            coerced
+                .get_by_column_name(&ColumnName::fx_temperature())
+                .unwrap(),
+            &SqlValue::NotNull(NnSqlValue::Integer(21))
+        );
+        assert!(new_with_policy("abc", OnSchemaError::Coerce).is_err());
+
+        // NOT NULL column cannot be filled with NULL
+        assert!(new_with_policy("21", OnSchemaError::NullFill).is_err());
+        assert!(new_with_policy("21", OnSchemaError::Drop).is_err());
+    }
 }
@@ -2,6 +2,7 @@
 
 use crate::{
     api::error::{Result, SpringError},
+    mem_size::MemSize,
     pipeline::ColumnName,
 };
 
@@ -14,6 +15,15 @@ use crate::stream_engine::autonomous_executor::row::value::SqlValue;
 #[derive(Clone, PartialEq, Debug, Default)]
 pub struct ColumnValues(Vec<(ColumnName, SqlValue)>);
 
+impl MemSize for ColumnValues {
+    fn mem_size(&self) -> usize {
+        self.0
+            .iter()
+            .map(|(column_name, value)| column_name.mem_size() + value.mem_size())
+            .sum()
+    }
+}
+
 impl ColumnValues {
     /// # Failure
     ///
@@ -3,5 +3,5 @@
 mod format;
 mod source_row;
 
-pub use format::JsonObject;
-pub use source_row::{ JsonSourceRow, SourceRow};
+pub use format::{JsonObject, JsonPath};
+pub use source_row::{JsonSourceRow, SourceRow};
@@ -2,4 +2,4 @@
 
 mod json;
 
-pub use json::JsonObject;
+pub use json::{JsonObject, JsonPath};
@@ -1,11 +1,18 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+mod json_path;
+
+pub use json_path::JsonPath;
+
 use anyhow::Context;
 
 use crate::{
     api::error::{Result, SpringError},
-    pipeline::ColumnName,
-    stream_engine::autonomous_executor::row::{column_values::ColumnValues, value::SqlValue},
+    pipeline::{ColumnName, JsonPathOptions},
+    stream_engine::autonomous_executor::row::{
+        column_values::ColumnValues,
+        value::{NnSqlValue, SqlValue},
+    },
 };
 
 #[derive(Clone, Eq, PartialEq, Debug, new)]
@@ -39,14 +46,41 @@ impl JsonObject {
         Ok(Self::new(json_v))
     }
 
-    /// # Failure
+    /// Sets the values at `JSON_PATH_<column>` options to top-level `<column>` fields.
     ///
-    /// - `SpringError::InvalidFormat` when:
-    ///   - Internal JSON cannot be mapped to SQL type (nested, for example).
+    /// A path not found in this object sets `null`.
+    /// A top-level value other than object is replaced with an object only with the mapped fields.
+    pub fn apply_json_paths(self, json_path_options: &JsonPathOptions) -> Self {
+        if json_path_options.column_paths.is_empty() {
+            return self;
+        }
+
+        let mapped = json_path_options
+            .column_paths
+            .iter()
+            .map(|(column_name, json_path)| {
+                let v = json_path
+                    .extract(&self.0)
+                    .cloned()
+                    .unwrap_or(serde_json::Value::Null);
+                (column_name.to_string(), v)
+            })
+            .collect::<Vec<_>>();
+
+        let mut top_object = match self.0 {
+            serde_json::Value::Object(top_object) => top_object,
+            _ => serde_json::Map::new(),
+        };
+        top_object.extend(mapped);
+        Self::new(serde_json::Value::Object(top_object))
+    }
+
+    /// Nested objects and arrays are mapped to TEXT in JSON format, which can be accessed by `->` operator.
     ///
-    /// # TODO
+    /// # Failure
     ///
-    /// See stream.options to more intelligently parse JSON. <https://docs.sqlstream.com/sql-reference-guide/create-statements/createforeignstream/#parsing-json>
+    /// - `SpringError::InvalidFormat` when:
+    ///   - Top-level is not a JSON object.
     pub fn into_column_values(self) -> Result<ColumnValues> {
         let json_object = self.0;
 
@@ -72,8 +106,73 @@ impl JsonObject {
         json_key: &str,
         json_value: &serde_json::Value,
     ) -> Result<(ColumnName, SqlValue)> {
-        let sql_value = SqlValue::try_from(json_value)?;
+        let sql_value = json_to_sql_value(json_value)?;
         let column_name = ColumnName::new(json_key.to_string());
         Ok((column_name, sql_value))
     }
 }
+
+/// Scalar JSON values are mapped to the corresponding SQL values, and objects and arrays to TEXT in JSON format.
+fn json_to_sql_value(json_value: &serde_json::Value) -> Result<SqlValue> {
+    match json_value {
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+            Ok(SqlValue::NotNull(NnSqlValue::Text(json_value.to_string())))
+        }
+        _ => SqlValue::try_from(json_value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ordered_float::OrderedFloat;
+    use serde_json::json;
+
+    use super::*;
+    use crate::pipeline::{Options, OptionsBuilder};
+
+    #[test]
+    fn test_nested_into_column_values() -> Result<()> {
+        let json = JsonObject::new(json!({
+            "id": 1,
+            "engine": { "rpm": 2500 },
+        }));
+        let column_values = json.into_column_values()?;
+        assert_eq!(
+            column_values.get_by_column_name(&ColumnName::new("engine".to_string()))?,
+            &SqlValue::NotNull(NnSqlValue::Text(r#"{"rpm":2500}"#.to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_json_paths() -> Result<()> {
+        let options: Options = OptionsBuilder::default()
+            .add("JSON_PATH_rpm", "$.engine.rpm")
+            .add("JSON_PATH_front_left", "$.wheels[0]")
+            .add("JSON_PATH_speed", "$.speed")
+            .build();
+        let json_path_options = JsonPathOptions::try_from(&options)?;
+
+        let json = JsonObject::new(json!({
+            "engine": { "rpm": 2500 },
+            "wheels": [2.1, 2.3],
+        }));
+        let column_values = json
+            .apply_json_paths(&json_path_options)
+            .into_column_values()?;
+
+        let get = |column_name: &str| {
+            column_values
+                .get_by_column_name(&ColumnName::new(column_name.to_string()))
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(get("rpm").unwrap(), NnSqlValue::BigInt(2500));
+        assert_eq!(
+            get("front_left").unwrap(),
+            NnSqlValue::Float(OrderedFloat(2.1))
+        );
+        assert!(matches!(get("speed"), SqlValue::Null));
+        Ok(())
+    }
+}
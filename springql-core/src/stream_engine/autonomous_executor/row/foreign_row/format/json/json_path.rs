@@ -0,0 +1,142 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    stream_engine::autonomous_executor::row::{
+        foreign_row::format::json::json_to_sql_value, value::SqlValue,
+    },
+};
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+enum JsonPathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Path to a value in a JSON document.
+///
+/// Starts with `$` (the root) followed by any number of `.key` and `[index]`, e.g. `$.engine.rpm`, `$.wheels[0].pressure`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct JsonPath(Vec<JsonPathSegment>);
+
+impl JsonPath {
+    /// # Returns
+    ///
+    /// `None` if the path does not exist in `json`.
+    pub fn extract<'a>(&self, json: &'a serde_json::Value) -> Option<&'a serde_json::Value> {
+        self.0.iter().try_fold(json, |json, segment| match segment {
+            JsonPathSegment::Key(key) => json.as_object()?.get(key),
+            JsonPathSegment::Index(index) => json.as_array()?.get(*index),
+        })
+    }
+
+    /// Objects and arrays are extracted as TEXT in JSON format.
+    ///
+    /// # Returns
+    ///
+    /// `SqlValue::Null` if the path does not exist in `json`.
+    pub fn extract_sql_value(&self, json: &serde_json::Value) -> Result<SqlValue> {
+        self.extract(json)
+            .map_or(Ok(SqlValue::Null), json_to_sql_value)
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = SpringError;
+
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `s` is not a valid JSON path.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |reason: &str| SpringError::InvalidFormat {
+            s: s.to_string(),
+            source: anyhow!("invalid JSON path: {}", reason),
+        };
+
+        let mut rest = s
+            .strip_prefix('$')
+            .ok_or_else(|| invalid("must start with `$`"))?;
+
+        let mut segments = vec![];
+        while !rest.is_empty() {
+            if let Some(after_dot) = rest.strip_prefix('.') {
+                let key_len = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+                if key_len == 0 {
+                    return Err(invalid("empty key"));
+                }
+                segments.push(JsonPathSegment::Key(after_dot[..key_len].to_string()));
+                rest = &after_dot[key_len..];
+            } else if let Some(after_bracket) = rest.strip_prefix('[') {
+                let (index, after_index) = after_bracket
+                    .split_once(']')
+                    .ok_or_else(|| invalid("unclosed `[`"))?;
+                let index = index
+                    .trim()
+                    .parse()
+                    .map_err(|_| invalid("array index must be a non-negative integer"))?;
+                segments.push(JsonPathSegment::Index(index));
+                rest = after_index;
+            } else {
+                return Err(invalid("expected `.` or `[`"));
+            }
+        }
+        Ok(Self(segments))
+    }
+}
+
+impl Display for JsonPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "$")?;
+        self.0.iter().try_for_each(|segment| match segment {
+            JsonPathSegment::Key(key) => write!(f, ".{}", key),
+            JsonPathSegment::Index(index) => write!(f, "[{}]", index),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_extract() -> Result<()> {
+        let json = json!({
+            "engine": { "rpm": 2500 },
+            "wheels": [{ "pressure": 2.1 }, { "pressure": 2.3 }],
+        });
+
+        for (path, expected) in [
+            ("$", Some(&json)),
+            ("$.engine.rpm", Some(&json!(2500))),
+            ("$.wheels[1].pressure", Some(&json!(2.3))),
+            ("$.wheels[2].pressure", None),
+            ("$.engine[0]", None),
+            ("$.speed", None),
+        ] {
+            let path = path.parse::<JsonPath>()?;
+            assert_eq!(path.extract(&json), expected, "{}", path);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        for path in ["engine.rpm", "$.", "$.wheels[", "$.wheels[-1]", "$engine"] {
+            assert!(
+                matches!(
+                    path.parse::<JsonPath>(),
+                    Err(SpringError::InvalidFormat { .. })
+                ),
+                "{}",
+                path
+            );
+        }
+    }
+}
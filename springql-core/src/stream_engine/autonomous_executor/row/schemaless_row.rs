@@ -4,6 +4,7 @@ use std::vec;
 
 use crate::{
     api::error::Result,
+    mem_size::MemSize,
     pipeline::ColumnName,
     stream_engine::{
         autonomous_executor::{ColumnValues, JsonObject},
@@ -50,6 +51,12 @@ impl SchemalessRow {
     }
 }
 
+impl MemSize for SchemalessRow {
+    fn mem_size(&self) -> usize {
+        self.colvals.mem_size()
+    }
+}
+
 impl From<StreamRow> for SchemalessRow {
     fn from(stream_row: StreamRow) -> Self {
         let colvals = stream_row.into();
@@ -5,7 +5,7 @@ use std::{sync::Arc, vec};
 use crate::{
     api::error::Result,
     mem_size::MemSize,
-    pipeline::{ColumnName, StreamModel},
+    pipeline::{ColumnName, OnSchemaError, StreamModel},
     stream_engine::{
         autonomous_executor::{row::schemaless_row::SchemalessRow, ColumnValues, StreamColumns},
         time::{SpringTimestamp, SystemTimestamp},
@@ -39,8 +39,16 @@ impl StreamRow {
         }
     }
 
-    pub fn from_schemaless_row(row: SchemalessRow, stream_model: Arc<StreamModel>) -> Result<Self> {
-        let cols = StreamColumns::new(stream_model, row.into_column_values())?;
+    pub fn from_schemaless_row(
+        row: SchemalessRow,
+        stream_model: Arc<StreamModel>,
+        on_schema_error: OnSchemaError,
+    ) -> Result<Self> {
+        let cols = StreamColumns::new_with_policy(
+            stream_model,
+            row.into_column_values(),
+            on_schema_error,
+        )?;
         Ok(Self::new(cols))
     }
 
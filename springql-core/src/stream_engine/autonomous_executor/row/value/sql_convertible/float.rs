@@ -20,6 +20,16 @@ impl SpringValue for f32 {
         Ok(*v as f32)
     }
 
+    fn try_from_u16(v: &u16) -> Result<Self> {
+        Ok(*v as f32)
+    }
+    fn try_from_u32(v: &u32) -> Result<Self> {
+        Ok(*v as f32)
+    }
+    fn try_from_u64(v: &u64) -> Result<Self> {
+        Ok(*v as f32)
+    }
+
     fn try_from_f32(v: &f32) -> Result<Self> {
         Ok(*v)
     }
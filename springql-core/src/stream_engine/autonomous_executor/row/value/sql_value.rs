@@ -11,7 +11,7 @@ pub use sql_value_hash_key::SqlValueHashKey;
 use std::{
     fmt::Display,
     hash::Hash,
-    ops::{Add, Mul},
+    ops::{Add, Div, Mul, Rem, Sub},
 };
 
 use anyhow::anyhow;
@@ -146,6 +146,23 @@ impl SqlValue {
         }
     }
 
+    /// Eval as bool in SQL three-valued logic if possible.
+    ///
+    /// # Returns
+    ///
+    /// None for NULL (UNKNOWN).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - this SqlValue cannot be evaluated as SQL BOOLEAN
+    pub fn to_nullable_bool(&self) -> Result<Option<bool>> {
+        match self {
+            SqlValue::Null => Ok(None),
+            SqlValue::NotNull(_) => self.to_bool().map(Some),
+        }
+    }
+
     /// Eval as i64 if possible.
     ///
     /// # Failures
@@ -186,10 +203,11 @@ impl TryFrom<&serde_json::Value> for SqlValue {
             serde_json::Value::Bool(b) => Ok(SqlValue::NotNull(NnSqlValue::Boolean(*b))),
 
             serde_json::Value::Number(n) => {
-                if let Some(f) = n.as_f64() {
-                    Ok(SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(f as f32))))
-                } else if let Some(i) = n.as_i64() {
+                // integers first not to lose precision of large integers (e.g. unix time in milliseconds) in f32
+                if let Some(i) = n.as_i64() {
                     Ok(SqlValue::NotNull(NnSqlValue::BigInt(i)))
+                } else if let Some(f) = n.as_f64() {
+                    Ok(SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(f as f32))))
                 } else {
                     Err(SpringError::Sql(anyhow!(
                         "unsupported number as SQL type: {:?} cannot be evaluated as BIGINT",
@@ -242,6 +260,42 @@ impl Mul for SqlValue {
         }
     }
 }
+impl Sub for SqlValue {
+    type Output = Result<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (SqlValue::Null, _) | (_, SqlValue::Null) => Ok(SqlValue::Null),
+            (SqlValue::NotNull(lhs_nn), SqlValue::NotNull(rhs_nn)) => {
+                (lhs_nn - rhs_nn).map(SqlValue::NotNull)
+            }
+        }
+    }
+}
+impl Div for SqlValue {
+    type Output = Result<Self>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (SqlValue::Null, _) | (_, SqlValue::Null) => Ok(SqlValue::Null),
+            (SqlValue::NotNull(lhs_nn), SqlValue::NotNull(rhs_nn)) => {
+                (lhs_nn / rhs_nn).map(SqlValue::NotNull)
+            }
+        }
+    }
+}
+impl Rem for SqlValue {
+    type Output = Result<Self>;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (SqlValue::Null, _) | (_, SqlValue::Null) => Ok(SqlValue::Null),
+            (SqlValue::NotNull(lhs_nn), SqlValue::NotNull(rhs_nn)) => {
+                (lhs_nn % rhs_nn).map(SqlValue::NotNull)
+            }
+        }
+    }
+}
 
 #[cfg(test)]
 impl SqlValue {
@@ -347,4 +401,34 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_try_from_json_number() -> Result<()> {
+        // exact even beyond the precision of f32
+        assert!(matches!(
+            SqlValue::try_from(&serde_json::json!(1_600_000_000_123_i64))?,
+            SqlValue::NotNull(NnSqlValue::BigInt(1_600_000_000_123))
+        ));
+        assert!(matches!(
+            SqlValue::try_from(&serde_json::json!(-1))?,
+            SqlValue::NotNull(NnSqlValue::BigInt(-1))
+        ));
+
+        assert!(matches!(
+            SqlValue::try_from(&serde_json::json!(1.5))?,
+            SqlValue::NotNull(NnSqlValue::Float(f)) if f == OrderedFloat(1.5)
+        ));
+        // integral value in floating-point notation
+        assert!(matches!(
+            SqlValue::try_from(&serde_json::json!(2.0))?,
+            SqlValue::NotNull(NnSqlValue::Float(f)) if f == OrderedFloat(2.0)
+        ));
+        // out of i64 range
+        assert!(matches!(
+            SqlValue::try_from(&serde_json::json!(u64::MAX))?,
+            SqlValue::NotNull(NnSqlValue::Float(_))
+        ));
+
+        Ok(())
+    }
 }
@@ -1,10 +1,12 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+mod cast;
+
 use std::{
     fmt::Display,
     hash::Hash,
     mem::size_of,
-    ops::{Add, Mul},
+    ops::{Add, Div, Mul, Rem, Sub},
 };
 
 use anyhow::anyhow;
@@ -21,7 +23,7 @@ use crate::{
         autonomous_executor::row::value::{
             sql_convertible::ToNnSqlValue, sql_value::sql_compare_result::SqlCompareResult,
         },
-        time::{SpringEventDuration, SpringTimestamp},
+        time::{SpringDuration, SpringEventDuration, SpringTimestamp},
         SpringValue,
     },
 };
@@ -365,19 +367,29 @@ impl From<NnSqlValue> for serde_json::Value {
 impl Add for NnSqlValue {
     type Output = Result<Self>;
 
+    /// An integer is promoted to FLOAT when the other operand is FLOAT.
+    /// Integer overflow is reported as `SpringError::Sql` (no panic).
+    /// `TIMESTAMP + DURATION` and `DURATION + TIMESTAMP` are evaluated into TIMESTAMP.
     fn add(self, rhs: Self) -> Self::Output {
         match (self.sql_type(), rhs.sql_type()) {
             (SqlType::NumericComparable(ref self_n), SqlType::NumericComparable(ref rhs_n)) => {
                 match (self_n, rhs_n) {
                     (NumericComparableType::I64Loose(_), NumericComparableType::I64Loose(_)) => {
                         let (self_i64, rhs_i64) = (self.unpack::<i64>()?, rhs.unpack::<i64>()?);
-                        Ok(Self::BigInt(self_i64 + rhs_i64))
+                        self_i64
+                            .checked_add(rhs_i64)
+                            .map(Self::BigInt)
+                            .ok_or_else(|| Self::overflow_error("+", &self, &rhs))
                     }
                     (NumericComparableType::U64Loose(_), NumericComparableType::U64Loose(_)) => {
                         let (self_u64, rhs_u64) = (self.unpack::<u64>()?, rhs.unpack::<u64>()?);
-                        Ok(Self::UnsignedBigInt(self_u64 + rhs_u64))
+                        self_u64
+                            .checked_add(rhs_u64)
+                            .map(Self::UnsignedBigInt)
+                            .ok_or_else(|| Self::overflow_error("+", &self, &rhs))
                     }
-                    (NumericComparableType::F32Loose(_), NumericComparableType::F32Loose(_)) => {
+                    (NumericComparableType::F32Loose(_), _)
+                    | (_, NumericComparableType::F32Loose(_)) => {
                         let (self_f32, rhs_f32) = (self.unpack::<f32>()?, rhs.unpack::<f32>()?);
                         Ok(Self::Float(OrderedFloat(self_f32 + rhs_f32)))
                     }
@@ -388,6 +400,12 @@ impl Add for NnSqlValue {
                     ))),
                 }
             }
+            (SqlType::TimestampComparable, SqlType::DurationComparable) => {
+                Self::add_duration_to_timestamp(&self, &rhs)
+            }
+            (SqlType::DurationComparable, SqlType::TimestampComparable) => {
+                Self::add_duration_to_timestamp(&rhs, &self)
+            }
             (_, _) => Err(SpringError::Sql(anyhow!(
                 "`self` + `rhs` is undefined - self: {:?}, other: {:?}",
                 self,
@@ -399,19 +417,28 @@ impl Add for NnSqlValue {
 impl Mul for NnSqlValue {
     type Output = Result<Self>;
 
+    /// An integer is promoted to FLOAT when the other operand is FLOAT.
+    /// Integer overflow is reported as `SpringError::Sql` (no panic).
     fn mul(self, rhs: Self) -> Self::Output {
         match (self.sql_type(), rhs.sql_type()) {
             (SqlType::NumericComparable(ref self_n), SqlType::NumericComparable(ref rhs_n)) => {
                 match (self_n, rhs_n) {
                     (NumericComparableType::I64Loose(_), NumericComparableType::I64Loose(_)) => {
                         let (self_i64, rhs_i64) = (self.unpack::<i64>()?, rhs.unpack::<i64>()?);
-                        Ok(Self::BigInt(self_i64 * rhs_i64))
+                        self_i64
+                            .checked_mul(rhs_i64)
+                            .map(Self::BigInt)
+                            .ok_or_else(|| Self::overflow_error("*", &self, &rhs))
                     }
                     (NumericComparableType::U64Loose(_), NumericComparableType::U64Loose(_)) => {
                         let (self_u64, rhs_u64) = (self.unpack::<u64>()?, rhs.unpack::<u64>()?);
-                        Ok(Self::UnsignedBigInt(self_u64 * rhs_u64))
+                        self_u64
+                            .checked_mul(rhs_u64)
+                            .map(Self::UnsignedBigInt)
+                            .ok_or_else(|| Self::overflow_error("*", &self, &rhs))
                     }
-                    (NumericComparableType::F32Loose(_), NumericComparableType::F32Loose(_)) => {
+                    (NumericComparableType::F32Loose(_), _)
+                    | (_, NumericComparableType::F32Loose(_)) => {
                         let (self_f32, rhs_f32) = (self.unpack::<f32>()?, rhs.unpack::<f32>()?);
                         Ok(Self::Float(OrderedFloat(self_f32 * rhs_f32)))
                     }
@@ -431,8 +458,201 @@ impl Mul for NnSqlValue {
     }
 }
 
+impl Sub for NnSqlValue {
+    type Output = Result<Self>;
+
+    /// An integer is promoted to FLOAT when the other operand is FLOAT.
+    /// Integer overflow is reported as `SpringError::Sql` (no panic).
+    /// `TIMESTAMP - DURATION` is evaluated into TIMESTAMP.
+    /// `TIMESTAMP - TIMESTAMP` is evaluated into DURATION (the left operand must not be earlier than the right one).
+    fn sub(self, rhs: Self) -> Self::Output {
+        match (self.sql_type(), rhs.sql_type()) {
+            (SqlType::NumericComparable(ref self_n), SqlType::NumericComparable(ref rhs_n)) => {
+                match (self_n, rhs_n) {
+                    (NumericComparableType::I64Loose(_), NumericComparableType::I64Loose(_)) => {
+                        let (self_i64, rhs_i64) = (self.unpack::<i64>()?, rhs.unpack::<i64>()?);
+                        self_i64
+                            .checked_sub(rhs_i64)
+                            .map(Self::BigInt)
+                            .ok_or_else(|| Self::overflow_error("-", &self, &rhs))
+                    }
+                    (NumericComparableType::U64Loose(_), NumericComparableType::U64Loose(_)) => {
+                        let (self_u64, rhs_u64) = (self.unpack::<u64>()?, rhs.unpack::<u64>()?);
+                        self_u64
+                            .checked_sub(rhs_u64)
+                            .map(Self::UnsignedBigInt)
+                            .ok_or_else(|| Self::overflow_error("-", &self, &rhs))
+                    }
+                    (NumericComparableType::F32Loose(_), _)
+                    | (_, NumericComparableType::F32Loose(_)) => {
+                        let (self_f32, rhs_f32) = (self.unpack::<f32>()?, rhs.unpack::<f32>()?);
+                        Ok(Self::Float(OrderedFloat(self_f32 - rhs_f32)))
+                    }
+                    _ => Err(SpringError::Sql(anyhow!(
+                        "Cannot subtract {:?} from {:?}",
+                        rhs_n,
+                        self_n
+                    ))),
+                }
+            }
+            (SqlType::TimestampComparable, SqlType::TimestampComparable) => {
+                let (self_ts, rhs_ts) = (
+                    self.unpack::<SpringTimestamp>()?,
+                    rhs.unpack::<SpringTimestamp>()?,
+                );
+                let duration = (self_ts - rhs_ts).to_std().map_err(|_| {
+                    SpringError::Sql(anyhow!(
+                        "TIMESTAMP - TIMESTAMP must not be negative - self: {:?}, other: {:?}",
+                        self,
+                        rhs
+                    ))
+                })?;
+                Ok(Self::Duration(SpringEventDuration::from_std(duration)))
+            }
+            (SqlType::TimestampComparable, SqlType::DurationComparable) => {
+                let (self_ts, rhs_duration) = (
+                    self.unpack::<SpringTimestamp>()?,
+                    rhs.unpack::<SpringEventDuration>()?,
+                );
+                self_ts
+                    .checked_sub(rhs_duration.to_duration())
+                    .map(Self::Timestamp)
+                    .map_err(|e| SpringError::Sql(anyhow!(e)))
+            }
+            (_, _) => Err(SpringError::Sql(anyhow!(
+                "`self` - `rhs` is undefined - self: {:?}, other: {:?}",
+                self,
+                rhs
+            ))),
+        }
+    }
+}
+impl Div for NnSqlValue {
+    type Output = Result<Self>;
+
+    /// An integer is promoted to FLOAT when the other operand is FLOAT.
+    /// Integer division truncates toward zero.
+    /// Division by zero (also for FLOAT) and integer overflow are reported as `SpringError::Sql` (no panic).
+    fn div(self, rhs: Self) -> Self::Output {
+        match (self.sql_type(), rhs.sql_type()) {
+            (SqlType::NumericComparable(ref self_n), SqlType::NumericComparable(ref rhs_n)) => {
+                match (self_n, rhs_n) {
+                    (NumericComparableType::I64Loose(_), NumericComparableType::I64Loose(_)) => {
+                        let (self_i64, rhs_i64) = (self.unpack::<i64>()?, rhs.unpack::<i64>()?);
+                        Self::ensure_non_zero_divisor(rhs_i64 == 0, &self, &rhs)?;
+                        self_i64
+                            .checked_div(rhs_i64)
+                            .map(Self::BigInt)
+                            .ok_or_else(|| Self::overflow_error("/", &self, &rhs))
+                    }
+                    (NumericComparableType::U64Loose(_), NumericComparableType::U64Loose(_)) => {
+                        let (self_u64, rhs_u64) = (self.unpack::<u64>()?, rhs.unpack::<u64>()?);
+                        Self::ensure_non_zero_divisor(rhs_u64 == 0, &self, &rhs)?;
+                        Ok(Self::UnsignedBigInt(self_u64 / rhs_u64))
+                    }
+                    (NumericComparableType::F32Loose(_), _)
+                    | (_, NumericComparableType::F32Loose(_)) => {
+                        let (self_f32, rhs_f32) = (self.unpack::<f32>()?, rhs.unpack::<f32>()?);
+                        Self::ensure_non_zero_divisor(rhs_f32 == 0.0, &self, &rhs)?;
+                        Ok(Self::Float(OrderedFloat(self_f32 / rhs_f32)))
+                    }
+                    _ => Err(SpringError::Sql(anyhow!(
+                        "Cannot divide {:?} by {:?}",
+                        self_n,
+                        rhs_n
+                    ))),
+                }
+            }
+            (_, _) => Err(SpringError::Sql(anyhow!(
+                "`self` / `rhs` is undefined - self: {:?}, other: {:?}",
+                self,
+                rhs
+            ))),
+        }
+    }
+}
+impl Rem for NnSqlValue {
+    type Output = Result<Self>;
+
+    /// An integer is promoted to FLOAT when the other operand is FLOAT.
+    /// The result has the same sign as the dividend.
+    /// Division by zero (also for FLOAT) and integer overflow are reported as `SpringError::Sql` (no panic).
+    fn rem(self, rhs: Self) -> Self::Output {
+        match (self.sql_type(), rhs.sql_type()) {
+            (SqlType::NumericComparable(ref self_n), SqlType::NumericComparable(ref rhs_n)) => {
+                match (self_n, rhs_n) {
+                    (NumericComparableType::I64Loose(_), NumericComparableType::I64Loose(_)) => {
+                        let (self_i64, rhs_i64) = (self.unpack::<i64>()?, rhs.unpack::<i64>()?);
+                        Self::ensure_non_zero_divisor(rhs_i64 == 0, &self, &rhs)?;
+                        self_i64
+                            .checked_rem(rhs_i64)
+                            .map(Self::BigInt)
+                            .ok_or_else(|| Self::overflow_error("%", &self, &rhs))
+                    }
+                    (NumericComparableType::U64Loose(_), NumericComparableType::U64Loose(_)) => {
+                        let (self_u64, rhs_u64) = (self.unpack::<u64>()?, rhs.unpack::<u64>()?);
+                        Self::ensure_non_zero_divisor(rhs_u64 == 0, &self, &rhs)?;
+                        Ok(Self::UnsignedBigInt(self_u64 % rhs_u64))
+                    }
+                    (NumericComparableType::F32Loose(_), _)
+                    | (_, NumericComparableType::F32Loose(_)) => {
+                        let (self_f32, rhs_f32) = (self.unpack::<f32>()?, rhs.unpack::<f32>()?);
+                        Self::ensure_non_zero_divisor(rhs_f32 == 0.0, &self, &rhs)?;
+                        Ok(Self::Float(OrderedFloat(self_f32 % rhs_f32)))
+                    }
+                    _ => Err(SpringError::Sql(anyhow!(
+                        "Cannot calculate modulo of {:?} by {:?}",
+                        self_n,
+                        rhs_n
+                    ))),
+                }
+            }
+            (_, _) => Err(SpringError::Sql(anyhow!(
+                "`self` % `rhs` is undefined - self: {:?}, other: {:?}",
+                self,
+                rhs
+            ))),
+        }
+    }
+}
+
+impl NnSqlValue {
+    fn ensure_non_zero_divisor(divisor_is_zero: bool, lhs: &Self, rhs: &Self) -> Result<()> {
+        if divisor_is_zero {
+            Err(SpringError::Sql(anyhow!(
+                "division by zero - self: {:?}, other: {:?}",
+                lhs,
+                rhs
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn add_duration_to_timestamp(ts: &Self, duration: &Self) -> Result<Self> {
+        let (ts, duration) = (
+            ts.unpack::<SpringTimestamp>()?,
+            duration.unpack::<SpringEventDuration>()?,
+        );
+        ts.checked_add(duration.to_duration())
+            .map(Self::Timestamp)
+            .map_err(|e| SpringError::Sql(anyhow!(e)))
+    }
+
+    fn overflow_error(op: &str, lhs: &Self, rhs: &Self) -> SpringError {
+        SpringError::Sql(anyhow!(
+            "integer overflow on `self` {} `rhs` - self: {:?}, other: {:?}",
+            op,
+            lhs,
+            rhs
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
     use super::*;
 
     #[test]
@@ -488,4 +708,97 @@ mod tests {
             b"hello".to_vec()
         );
     }
+
+    #[test]
+    fn test_sub_div_rem() -> Result<()> {
+        assert_eq!(
+            (NnSqlValue::Integer(5) - NnSqlValue::SmallInt(7))?.unpack::<i64>()?,
+            -2
+        );
+        assert_eq!(
+            (NnSqlValue::BigInt(-7) / NnSqlValue::Integer(2))?.unpack::<i64>()?,
+            -3
+        );
+        assert_eq!(
+            (NnSqlValue::BigInt(-7) % NnSqlValue::Integer(2))?.unpack::<i64>()?,
+            -1
+        );
+        assert_eq!(
+            (NnSqlValue::UnsignedInteger(7) % NnSqlValue::UnsignedBigInt(4))?.unpack::<u64>()?,
+            3
+        );
+        assert_eq!(
+            (NnSqlValue::Float(OrderedFloat(90.0)) / NnSqlValue::Float(OrderedFloat(1.8)))?
+                .unpack::<f32>()?,
+            50.0
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_arithmetic_errors_without_panic() {
+        fn assert_sql_error(res: Result<NnSqlValue>) {
+            assert!(matches!(res, Err(SpringError::Sql(_))), "{:?}", res);
+        }
+
+        assert_sql_error(NnSqlValue::Integer(1) / NnSqlValue::Integer(0));
+        assert_sql_error(NnSqlValue::Integer(1) % NnSqlValue::Integer(0));
+        assert_sql_error(NnSqlValue::UnsignedInteger(1) / NnSqlValue::UnsignedInteger(0));
+        assert_sql_error(
+            NnSqlValue::Float(OrderedFloat(1.0)) / NnSqlValue::Float(OrderedFloat(0.0)),
+        );
+        assert_sql_error(NnSqlValue::BigInt(i64::MIN) / NnSqlValue::BigInt(-1));
+        assert_sql_error(NnSqlValue::BigInt(i64::MIN) - NnSqlValue::BigInt(1));
+        assert_sql_error(NnSqlValue::UnsignedInteger(1) - NnSqlValue::UnsignedInteger(2));
+        assert_sql_error(NnSqlValue::BigInt(i64::MAX) + NnSqlValue::BigInt(1));
+        assert_sql_error(NnSqlValue::UnsignedBigInt(u64::MAX) + NnSqlValue::UnsignedInteger(1));
+        assert_sql_error(NnSqlValue::BigInt(i64::MAX) * NnSqlValue::SmallInt(2));
+        assert_sql_error(NnSqlValue::UnsignedBigInt(u64::MAX) * NnSqlValue::UnsignedInteger(2));
+        assert_sql_error(NnSqlValue::Integer(1) - NnSqlValue::UnsignedInteger(1));
+    }
+
+    #[test]
+    fn test_integer_promoted_to_float() -> Result<()> {
+        let float = |v: f32| NnSqlValue::Float(OrderedFloat(v));
+
+        // (c.raw - 32) / 1.8
+        let celsius = ((NnSqlValue::Integer(212) - NnSqlValue::SmallInt(32))? / float(1.8))?;
+        assert_eq!(celsius.sql_type(), float(0.0).sql_type());
+        assert!((celsius.unpack::<f32>()? - 100.0).abs() < 1e-4);
+
+        assert_eq!((float(0.5) + NnSqlValue::BigInt(1))?, float(1.5));
+        assert_eq!((NnSqlValue::UnsignedInteger(3) * float(0.5))?, float(1.5));
+        assert_eq!((float(3.5) - NnSqlValue::Integer(1))?, float(2.5));
+        assert_eq!((NnSqlValue::Integer(7) % float(2.0))?, float(1.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_timestamp_sub_timestamp() -> Result<()> {
+        let ts1 =
+            NnSqlValue::Timestamp(SpringTimestamp::from_str("2020-01-01 00:00:00.000000000")?);
+        let ts2 =
+            NnSqlValue::Timestamp(SpringTimestamp::from_str("2020-01-01 00:01:30.500000000")?);
+
+        let duration = (ts2.clone() - ts1.clone())?.unpack::<SpringEventDuration>()?;
+        assert_eq!(duration, SpringEventDuration::from_millis(90_500));
+
+        assert!(matches!(ts1 - ts2, Err(SpringError::Sql(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_timestamp_and_duration() -> Result<()> {
+        let ts = NnSqlValue::Timestamp(SpringTimestamp::from_str("2020-01-01 00:00:00.000000000")?);
+        let duration = NnSqlValue::Duration(SpringEventDuration::from_millis(90_500));
+        let later =
+            NnSqlValue::Timestamp(SpringTimestamp::from_str("2020-01-01 00:01:30.500000000")?);
+
+        assert_eq!((ts.clone() + duration.clone())?, later);
+        assert_eq!((duration.clone() + ts.clone())?, later);
+        assert_eq!((later - duration.clone())?, ts);
+
+        assert!(matches!(duration - ts, Err(SpringError::Sql(_))));
+        Ok(())
+    }
 }
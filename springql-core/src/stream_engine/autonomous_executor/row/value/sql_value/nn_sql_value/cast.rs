@@ -0,0 +1,295 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use anyhow::anyhow;
+use ordered_float::OrderedFloat;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{F32LooseType, NumericComparableType, SqlType, StringComparableLoseType},
+    stream_engine::{
+        autonomous_executor::row::value::sql_value::nn_sql_value::NnSqlValue, time::SpringTimestamp,
+    },
+};
+
+impl NnSqlValue {
+    /// Explicit type conversion by `CAST(value AS typ)`.
+    ///
+    /// Conversions also done implicitly by `try_convert()` are tried first.
+    /// Then the following explicit-only conversions are tried:
+    ///
+    /// - Any type -> TEXT
+    /// - TEXT -> numeric types, BOOLEAN and BLOB (by parsing)
+    /// - integer types <-> BOOLEAN (non-zero is TRUE)
+    /// - integer types <-> TIMESTAMP (as unix time in seconds)
+    /// - unsigned integer types <-> FLOAT (fractional part is truncated)
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `self` cannot be converted into `typ`.
+    pub fn try_cast(&self, typ: &SqlType) -> Result<NnSqlValue> {
+        if &self.sql_type() == typ {
+            Ok(self.clone())
+        } else {
+            self.try_convert(typ)
+                .or_else(|_| self.try_cast_explicitly(typ))
+                .map_err(|e| {
+                    SpringError::Sql(anyhow!(
+                        "cannot cast {} ({:?}) into {:?}: {}",
+                        self,
+                        self.sql_type(),
+                        typ,
+                        e
+                    ))
+                })
+        }
+    }
+
+    fn try_cast_explicitly(&self, typ: &SqlType) -> Result<NnSqlValue> {
+        match (self, typ) {
+            (_, SqlType::StringComparableLoose(StringComparableLoseType::Text)) => {
+                self.to_text().map(NnSqlValue::Text)
+            }
+
+            (NnSqlValue::Text(s), SqlType::NumericComparable(n)) => {
+                let s = s.trim();
+                let parsed = match n {
+                    NumericComparableType::I64Loose(_) => {
+                        s.parse::<i64>().map(NnSqlValue::BigInt).ok()
+                    }
+                    NumericComparableType::U64Loose(_) => {
+                        s.parse::<u64>().map(NnSqlValue::UnsignedBigInt).ok()
+                    }
+                    NumericComparableType::F32Loose(F32LooseType::Float) => s
+                        .parse::<f32>()
+                        .map(|f| NnSqlValue::Float(OrderedFloat(f)))
+                        .ok(),
+                };
+                parsed
+                    .ok_or_else(|| SpringError::Sql(anyhow!("invalid numeric format")))?
+                    .try_convert(typ)
+            }
+            (NnSqlValue::Text(s), SqlType::BooleanComparable) => {
+                match s.trim().to_lowercase().as_str() {
+                    "true" | "t" | "yes" | "y" | "on" | "1" => Ok(NnSqlValue::Boolean(true)),
+                    "false" | "f" | "no" | "n" | "off" | "0" => Ok(NnSqlValue::Boolean(false)),
+                    _ => Err(SpringError::Sql(anyhow!("invalid boolean format"))),
+                }
+            }
+            (NnSqlValue::Text(s), SqlType::BinaryComparable) => {
+                Ok(NnSqlValue::Blob(s.as_bytes().to_vec()))
+            }
+
+            (NnSqlValue::Boolean(b), SqlType::NumericComparable(_)) => {
+                NnSqlValue::SmallInt(i16::from(*b)).try_convert(typ)
+            }
+            (
+                NnSqlValue::SmallInt(_)
+                | NnSqlValue::Integer(_)
+                | NnSqlValue::BigInt(_)
+                | NnSqlValue::UnsignedInteger(_)
+                | NnSqlValue::UnsignedBigInt(_),
+                SqlType::BooleanComparable,
+            ) => Ok(NnSqlValue::Boolean(!self.is_zero())),
+
+            (
+                NnSqlValue::SmallInt(_)
+                | NnSqlValue::Integer(_)
+                | NnSqlValue::BigInt(_)
+                | NnSqlValue::UnsignedInteger(_)
+                | NnSqlValue::UnsignedBigInt(_),
+                SqlType::TimestampComparable,
+            ) => {
+                let secs = self.unpack::<i64>().or_else(|_| {
+                    let u = self.unpack::<u64>()?;
+                    i64::try_from(u).map_err(|e| SpringError::Sql(anyhow!(e)))
+                })?;
+                SpringTimestamp::from_unix_time(secs).map(NnSqlValue::Timestamp)
+            }
+            (NnSqlValue::Timestamp(ts), SqlType::NumericComparable(_)) => {
+                NnSqlValue::BigInt(ts.unix_time()).try_convert(typ)
+            }
+
+            (
+                NnSqlValue::UnsignedInteger(_) | NnSqlValue::UnsignedBigInt(_),
+                SqlType::NumericComparable(NumericComparableType::F32Loose(_)),
+            ) => {
+                let u = self.unpack::<u64>()?;
+                Ok(NnSqlValue::Float(OrderedFloat(u as f32)))
+            }
+            (
+                NnSqlValue::Float(f),
+                SqlType::NumericComparable(NumericComparableType::U64Loose(_)),
+            ) => {
+                let f = f.trunc();
+                if f.is_finite() && f >= 0.0 && f < u64::MAX as f32 {
+                    NnSqlValue::UnsignedBigInt(f as u64).try_convert(typ)
+                } else {
+                    Err(SpringError::Sql(anyhow!("out of range")))
+                }
+            }
+
+            _ => Err(SpringError::Sql(anyhow!("unsupported conversion"))),
+        }
+    }
+
+    fn to_text(&self) -> Result<String> {
+        match self {
+            NnSqlValue::Text(s) => Ok(s.clone()),
+            NnSqlValue::Blob(v) => {
+                String::from_utf8(v.clone()).map_err(|e| SpringError::Sql(anyhow!(e)))
+            }
+            _ => Ok(self.to_string()),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        match self {
+            NnSqlValue::UnsignedInteger(_) | NnSqlValue::UnsignedBigInt(_) => {
+                self.unpack::<u64>().map_or(false, |u| u == 0)
+            }
+            _ => self.unpack::<i64>().map_or(false, |i| i == 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> NnSqlValue {
+        NnSqlValue::Text(s.to_string())
+    }
+
+    #[test]
+    fn test_try_cast() -> Result<()> {
+        let ts: SpringTimestamp = "2020-01-01 00:00:00.000000000".parse()?;
+
+        for (value, typ, expected) in [
+            // implicit conversions
+            (
+                NnSqlValue::Integer(1),
+                SqlType::big_int(),
+                NnSqlValue::BigInt(1),
+            ),
+            (
+                text("2020-01-01 00:00:00.000000000"),
+                SqlType::timestamp(),
+                NnSqlValue::Timestamp(ts),
+            ),
+            // to TEXT
+            (NnSqlValue::Integer(-1), SqlType::text(), text("-1")),
+            (
+                NnSqlValue::Float(OrderedFloat(1.5)),
+                SqlType::text(),
+                text("1.5"),
+            ),
+            (NnSqlValue::Boolean(true), SqlType::text(), text("TRUE")),
+            (
+                NnSqlValue::Blob(b"abc".to_vec()),
+                SqlType::text(),
+                text("abc"),
+            ),
+            (
+                NnSqlValue::Timestamp(ts),
+                SqlType::text(),
+                text("2020-01-01 00:00:00.000000000"),
+            ),
+            // from TEXT
+            (text(" 42 "), SqlType::integer(), NnSqlValue::Integer(42)),
+            (
+                text("42"),
+                SqlType::unsigned_big_int(),
+                NnSqlValue::UnsignedBigInt(42),
+            ),
+            (
+                text("1.5"),
+                SqlType::float(),
+                NnSqlValue::Float(OrderedFloat(1.5)),
+            ),
+            (text("True"), SqlType::boolean(), NnSqlValue::Boolean(true)),
+            (text("0"), SqlType::boolean(), NnSqlValue::Boolean(false)),
+            (
+                text("abc"),
+                SqlType::blob(),
+                NnSqlValue::Blob(b"abc".to_vec()),
+            ),
+            // BOOLEAN <-> integer
+            (
+                NnSqlValue::Boolean(true),
+                SqlType::integer(),
+                NnSqlValue::Integer(1),
+            ),
+            (
+                NnSqlValue::Integer(0),
+                SqlType::boolean(),
+                NnSqlValue::Boolean(false),
+            ),
+            (
+                NnSqlValue::UnsignedInteger(3),
+                SqlType::boolean(),
+                NnSqlValue::Boolean(true),
+            ),
+            // TIMESTAMP <-> integer
+            (
+                NnSqlValue::BigInt(1_577_836_800),
+                SqlType::timestamp(),
+                NnSqlValue::Timestamp(ts),
+            ),
+            (
+                NnSqlValue::Timestamp(ts),
+                SqlType::big_int(),
+                NnSqlValue::BigInt(1_577_836_800),
+            ),
+            // unsigned <-> FLOAT
+            (
+                NnSqlValue::UnsignedBigInt(3),
+                SqlType::float(),
+                NnSqlValue::Float(OrderedFloat(3.0)),
+            ),
+            (
+                NnSqlValue::Float(OrderedFloat(1.2)),
+                SqlType::unsigned_integer(),
+                NnSqlValue::UnsignedInteger(1),
+            ),
+            (
+                NnSqlValue::Float(OrderedFloat(2.9)),
+                SqlType::unsigned_big_int(),
+                NnSqlValue::UnsignedBigInt(2),
+            ),
+        ] {
+            let casted = value.try_cast(&typ)?;
+            // compared by Debug since BLOB is not comparable by `sql_compare()`
+            assert_eq!(
+                format!("{:?}", casted),
+                format!("{:?}", expected),
+                "{} -> {:?}",
+                value,
+                typ
+            );
+        }
+
+        for (value, typ) in [
+            (text("abc"), SqlType::integer()),
+            (text("1.5"), SqlType::integer()),
+            (text("70000"), SqlType::small_int()),
+            (text("-1"), SqlType::unsigned_integer()),
+            (text("yes?"), SqlType::boolean()),
+            (
+                NnSqlValue::Float(OrderedFloat(-1.0)),
+                SqlType::unsigned_big_int(),
+            ),
+            (NnSqlValue::Blob(vec![0xff]), SqlType::text()),
+            (NnSqlValue::Boolean(true), SqlType::timestamp()),
+        ] {
+            assert!(
+                matches!(value.try_cast(&typ), Err(SpringError::Sql(_))),
+                "{} -> {:?}",
+                value,
+                typ
+            );
+        }
+
+        Ok(())
+    }
+}
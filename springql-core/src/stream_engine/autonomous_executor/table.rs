@@ -0,0 +1,7 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod reference_table;
+mod table_repository;
+
+pub use reference_table::ReferenceTable;
+pub use table_repository::TableRepository;
@@ -0,0 +1,176 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use anyhow::anyhow;
+use parking_lot::RwLock;
+
+use crate::{
+    api::error::{Result, SpringError},
+    mem_size::MemSize,
+    pipeline::{ColumnReference, StreamName, TableModel},
+    stream_engine::{autonomous_executor::task::Tuple, NnSqlValue, SqlValue},
+};
+
+/// Rows of a table, keyed by PRIMARY KEY values.
+///
+/// Unlike queues, rows are not consumed by tasks. A row is kept until it is replaced by a row with the same key.
+#[derive(Debug)]
+pub struct ReferenceTable {
+    name: StreamName,
+    key_colrefs: Vec<ColumnReference>,
+    max_rows: usize,
+
+    rows: RwLock<HashMap<Vec<NnSqlValue>, Tuple>>,
+    /// Running total of the tuples' bytes, not to scan all the rows on every read.
+    bytes: AtomicU64,
+}
+
+impl ReferenceTable {
+    pub fn new(table_model: &TableModel, max_rows: usize) -> Self {
+        Self {
+            name: table_model.name().clone(),
+            key_colrefs: table_model.key_colrefs(),
+            max_rows,
+            rows: RwLock::default(),
+            bytes: AtomicU64::new(0),
+        }
+    }
+
+    /// Inserts a tuple, or replaces the tuple with the same key.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - any of the key values is NULL.
+    ///   - the table already has `max_rows` rows and `tuple` has a new key.
+    pub fn upsert(&self, tuple: Tuple) -> Result<()> {
+        let key = self
+            .key_colrefs
+            .iter()
+            .map(|colref| match tuple.get_value(colref)? {
+                SqlValue::Null => Err(SpringError::Sql(anyhow!(
+                    "key column {:?} of table {} is NULL",
+                    colref,
+                    self.name
+                ))),
+                SqlValue::NotNull(v) => Ok(v),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut rows = self.rows.write();
+        if rows.len() >= self.max_rows && !rows.contains_key(&key) {
+            return Err(SpringError::Sql(anyhow!(
+                "table {} is full ({} rows)",
+                self.name,
+                self.max_rows
+            )));
+        }
+        self.bytes
+            .fetch_add(tuple.mem_size() as u64, Ordering::Relaxed);
+        if let Some(old) = rows.insert(key, tuple) {
+            self.bytes
+                .fetch_sub(old.mem_size() as u64, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Tuple whose key values are `key` (in the order of PRIMARY KEY columns).
+    pub fn get(&self, key: &[NnSqlValue]) -> Option<Tuple> {
+        self.rows.read().get(key).cloned()
+    }
+
+    /// All the tuples in arbitrary order.
+    pub fn tuples(&self) -> Vec<Tuple> {
+        self.rows.read().values().cloned().collect()
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pipeline::{
+            ColumnConstraint, ColumnDataType, ColumnDefinition, ColumnName, Field, OptionsBuilder,
+            SqlType, StreamShape,
+        },
+        stream_engine::{time::SpringTimestamp, RowTime},
+    };
+
+    fn table_model() -> TableModel {
+        let shape = StreamShape::new(vec![
+            ColumnDefinition::new(
+                ColumnDataType::new(ColumnName::new("id".to_string()), SqlType::integer(), false),
+                vec![ColumnConstraint::PrimaryKey],
+            ),
+            ColumnDefinition::new(
+                ColumnDataType::new(ColumnName::new("name".to_string()), SqlType::text(), false),
+                vec![],
+            ),
+        ])
+        .unwrap();
+        TableModel::new(
+            StreamName::new("t".to_string()),
+            shape,
+            OptionsBuilder::default().build(),
+        )
+        .unwrap()
+    }
+
+    fn tuple(id: i32, name: &str) -> Tuple {
+        Tuple::new(
+            RowTime::ProcessingTime(SpringTimestamp::fx_ts1()),
+            vec![
+                Field::new(
+                    ColumnReference::factory("t", "id"),
+                    SqlValue::factory_integer(id),
+                ),
+                Field::new(
+                    ColumnReference::factory("t", "name"),
+                    SqlValue::NotNull(NnSqlValue::Text(name.to_string())),
+                ),
+            ],
+        )
+    }
+
+    fn key(id: i32) -> Vec<NnSqlValue> {
+        vec![NnSqlValue::Integer(id)]
+    }
+
+    #[test]
+    fn test_upsert() {
+        let table = ReferenceTable::new(&table_model(), 2);
+
+        table.upsert(tuple(1, "a")).unwrap();
+        table.upsert(tuple(2, "b")).unwrap();
+        assert_eq!(table.tuples().len(), 2);
+        let bytes = table.bytes();
+        assert_eq!(
+            bytes,
+            (tuple(1, "a").mem_size() + tuple(2, "b").mem_size()) as u64
+        );
+
+        // replace
+        table.upsert(tuple(1, "cc")).unwrap();
+        assert_eq!(table.tuples().len(), 2);
+        assert_eq!(table.get(&key(1)), Some(tuple(1, "cc")));
+        assert_eq!(
+            table.bytes(),
+            bytes - tuple(1, "a").mem_size() as u64 + tuple(1, "cc").mem_size() as u64
+        );
+
+        // full
+        assert!(matches!(
+            table.upsert(tuple(3, "d")),
+            Err(SpringError::Sql(_))
+        ));
+        assert_eq!(table.get(&key(3)), None);
+    }
+}
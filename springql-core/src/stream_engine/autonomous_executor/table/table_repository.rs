@@ -0,0 +1,59 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::RwLock;
+
+use crate::{
+    api::SpringMemoryConfig,
+    pipeline::{StreamName, TableModel},
+    stream_engine::autonomous_executor::table::ReferenceTable,
+};
+
+/// Tables live across pipeline updates, unlike queues.
+#[derive(Debug)]
+pub struct TableRepository {
+    config: SpringMemoryConfig,
+
+    tables: RwLock<HashMap<StreamName, Arc<ReferenceTable>>>,
+}
+
+impl TableRepository {
+    pub fn new(config: SpringMemoryConfig) -> Self {
+        Self {
+            config,
+            tables: RwLock::default(),
+        }
+    }
+
+    /// Do nothing if a table with the same name already exists.
+    pub fn register(&self, table_model: &TableModel) {
+        let mut tables = self.tables.write();
+
+        if tables.get(table_model.name()).is_none() {
+            let max_rows = table_model
+                .options()
+                .max_rows
+                .unwrap_or(self.config.table_max_rows);
+            let table = ReferenceTable::new(table_model, max_rows as usize);
+            let _ = tables.insert(table_model.name().clone(), Arc::new(table));
+            log::debug!("[TableRepository] registered table: {}", table_model.name());
+        }
+    }
+
+    /// # Panics
+    ///
+    /// `name` is not registered yet
+    pub fn get(&self, name: &StreamName) -> Arc<ReferenceTable> {
+        self.tables
+            .read()
+            .get(name)
+            .unwrap_or_else(|| panic!("table name ({}) not registered yet", name))
+            .clone()
+    }
+
+    /// Bytes of rows in all the tables.
+    pub fn total_bytes(&self) -> u64 {
+        self.tables.read().values().map(|table| table.bytes()).sum()
+    }
+}
@@ -2,6 +2,7 @@
 
 mod tuple;
 
+mod dead_letter;
 mod pump_task;
 mod sink_task;
 mod source_task;
@@ -10,6 +11,7 @@ mod window;
 
 mod processed_rows;
 
+pub use dead_letter::error_message;
 pub use processed_rows::ProcessedRows;
 pub use sink_task::SinkWriterRepository;
 pub use source_task::{
@@ -17,7 +19,7 @@ pub use source_task::{
 };
 pub use task_context::TaskContext;
 pub use tuple::Tuple;
-pub use window::Window;
+pub use window::{AggregatedAndGroupingValues, Window};
 
 use crate::{
     api::error::Result,
@@ -0,0 +1,236 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{error::Error, sync::Arc};
+
+use crate::{
+    api::error::SpringError,
+    mem_size::MemSize,
+    pipeline::{
+        ColumnName, DeadLetterTarget, PumpName, SinkWriterName, SourceReaderName, StreamModel,
+    },
+    stream_engine::{
+        autonomous_executor::{
+            performance_metrics::OutQueueMetricsUpdateByTask,
+            repositories::Repositories,
+            row::{ColumnValues, SchemalessRow, StreamRow},
+            task::task_context::TaskContext,
+            task_graph::{QueueId, TaskId},
+            AutonomousExecutor, StreamColumns,
+        },
+        time::SystemTimestamp,
+        NnSqlValue, SqlValue,
+    },
+};
+
+/// A row (or rows failed together) in a task, on its way to the dead-letter streams for the task.
+#[derive(Debug)]
+pub struct DeadLetter {
+    error: SpringError,
+    /// Columns of the failing rows in JSON, or the foreign input which could not be parsed into a row.
+    raw_rows: Vec<Option<String>>,
+}
+
+impl DeadLetter {
+    pub fn new(error: SpringError, raw_row: Option<String>) -> Self {
+        Self::with_raw_rows(error, vec![raw_row])
+    }
+
+    /// Rows failed together with a single `error`, like a batch of a sink writer.
+    pub fn with_raw_rows(error: SpringError, raw_rows: Vec<Option<String>>) -> Self {
+        Self { error, raw_rows }
+    }
+
+    /// Whether any dead-letter stream is defined for the task in `context`.
+    ///
+    /// Tasks use this to keep a copy of input rows only when they can be dead-lettered.
+    pub fn is_routed(context: &TaskContext) -> bool {
+        let target = DeadLetterTarget::from(&context.task());
+        let pipeline_derivatives = context.pipeline_derivatives();
+        !pipeline_derivatives
+            .pipeline()
+            .dead_letter_streams(&target)
+            .is_empty()
+    }
+
+    /// Puts this dead letter (a row per raw row) into the queues to the sink writers of the dead-letter streams for the task in `context`.
+    /// Sink tasks of the dead-letter streams send them as any other rows.
+    ///
+    /// The original error is handled (logged) as usual.
+    ///
+    /// # Returns
+    ///
+    /// Metrics of the dead-letter queues, which the caller task reports along with its other output queues.
+    pub fn route(self, context: &TaskContext) -> Vec<OutQueueMetricsUpdateByTask> {
+        let task = context.task();
+        let target = DeadLetterTarget::from(&task);
+        let pipeline_derivatives = context.pipeline_derivatives();
+        let pipeline = pipeline_derivatives.pipeline();
+        let task_graph = pipeline_derivatives.task_graph();
+
+        let mut out_queues_metrics = vec![];
+        let dead_letter_streams = pipeline.dead_letter_streams(&target);
+        if !dead_letter_streams.is_empty() {
+            let rows = self.to_rows(&task);
+            let repos = context.repos();
+
+            for stream_name in dead_letter_streams {
+                let stream = pipeline
+                    .get_stream(stream_name)
+                    .expect("dead-letter stream must be in the pipeline");
+                let queue_ids = pipeline
+                    .all_sinks()
+                    .into_iter()
+                    .filter(|sink| sink.sink_upstream() == stream_name)
+                    .filter_map(|sink| {
+                        task_graph.input_queue(&TaskId::from_sink(sink), stream_name)
+                    });
+                for queue_id in queue_ids {
+                    out_queues_metrics.push(Self::put_rows_into(queue_id, &stream, &rows, &repos));
+                }
+            }
+        }
+
+        AutonomousExecutor::handle_error(self.error);
+        out_queues_metrics
+    }
+
+    fn put_rows_into(
+        queue_id: QueueId,
+        stream: &Arc<StreamModel>,
+        rows: &[SchemalessRow],
+        repos: &Repositories,
+    ) -> OutQueueMetricsUpdateByTask {
+        match queue_id {
+            QueueId::Row(row_queue_id) => {
+                let queue = repos.row_queue_repository().get(&row_queue_id);
+                let mut bytes_put = 0;
+                for row in rows {
+                    let cols = StreamColumns::new(stream.clone(), row.clone().into_column_values())
+                        .expect("dead letters must match the dead-letter stream shape");
+                    let row = StreamRow::new(cols);
+                    bytes_put += row.mem_size();
+                    queue.put(row);
+                }
+                OutQueueMetricsUpdateByTask::new(
+                    row_queue_id.into(),
+                    rows.len() as u64,
+                    bytes_put as u64,
+                )
+            }
+            QueueId::Window(_) => unreachable!("sink task must have row input queue"),
+        }
+    }
+
+    fn to_rows(&self, task: &TaskId) -> Vec<SchemalessRow> {
+        self.raw_rows
+            .iter()
+            .map(|raw_row| self.to_row(task, raw_row.clone()))
+            .collect()
+    }
+
+    fn to_row(&self, task: &TaskId, raw_row: Option<String>) -> SchemalessRow {
+        let text = |s: String| SqlValue::NotNull(NnSqlValue::Text(s));
+
+        let mut colvals = ColumnValues::default();
+        for (column_name, value) in [
+            (
+                "ts",
+                SqlValue::NotNull(NnSqlValue::Timestamp(SystemTimestamp::now())),
+            ),
+            ("task_id", text(task.to_string())),
+            ("error_kind", text(error_kind(&self.error).to_string())),
+            ("error_message", text(error_message(&self.error))),
+            ("raw_row", raw_row.map_or(SqlValue::Null, text)),
+        ] {
+            colvals
+                .insert(ColumnName::new(column_name.to_string()), value)
+                .expect("column names are unique");
+        }
+        SchemalessRow::from(colvals)
+    }
+}
+
+impl From<&TaskId> for DeadLetterTarget {
+    fn from(task: &TaskId) -> Self {
+        match task {
+            TaskId::Source { id } => {
+                DeadLetterTarget::SourceReader(SourceReaderName::new(id.clone()))
+            }
+            TaskId::Pump { id, .. } => DeadLetterTarget::Pump(PumpName::new(id.clone())),
+            TaskId::Sink { id } => DeadLetterTarget::SinkWriter(SinkWriterName::new(id.clone())),
+        }
+    }
+}
+
+fn error_kind(error: &SpringError) -> &'static str {
+    match error {
+        SpringError::ForeignIo { .. } => "ForeignIo",
+        SpringError::ForeignSourceTimeout { .. } => "ForeignSourceTimeout",
+        SpringError::InputTimeout { .. } => "InputTimeout",
+        SpringError::SpringQlCoreIo(_) => "SpringQlCoreIo",
+        SpringError::ThreadPoisoned(_) => "ThreadPoisoned",
+        SpringError::InvalidConfig { .. } => "InvalidConfig",
+        SpringError::InvalidOption { .. } => "InvalidOption",
+        SpringError::InvalidFormat { .. } => "InvalidFormat",
+        SpringError::Unavailable { .. } => "Unavailable",
+        SpringError::Sql(_) => "Sql",
+        SpringError::Null { .. } => "Null",
+        SpringError::Time(_) => "Time",
+    }
+}
+
+/// The error and its causes, joined by `: `.
+pub fn error_message(error: &SpringError) -> String {
+    match error {
+        SpringError::SpringQlCoreIo(e) | SpringError::ThreadPoisoned(e) | SpringError::Sql(e) => {
+            format!("{}: {:#}", error, e)
+        }
+        _ => {
+            let mut message = error.to_string();
+            let mut source = error.source();
+            while let Some(e) = source {
+                message.push_str(": ");
+                message.push_str(&e.to_string());
+                source = e.source();
+            }
+            message
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+    use crate::pipeline::PumpInputType;
+
+    #[test]
+    fn test_to_row() {
+        let task = TaskId::Pump {
+            id: "pu_a".to_string(),
+            input_type: PumpInputType::Row,
+        };
+        let dead_letter = DeadLetter::new(
+            SpringError::Sql(anyhow!("division by zero")),
+            Some(r#"{"c":0}"#.to_string()),
+        );
+        let rows = dead_letter.to_rows(&task);
+        assert_eq!(rows.len(), 1);
+        let row = &rows[0];
+
+        let get = |c: &str| {
+            row.get_by_column_name(&ColumnName::new(c.to_string()))
+                .unwrap()
+                .clone()
+                .unwrap()
+        };
+        assert_eq!(get("task_id"), NnSqlValue::Text("pu_a".to_string()));
+        assert_eq!(get("error_kind"), NnSqlValue::Text("Sql".to_string()));
+        assert_eq!(
+            get("error_message"),
+            NnSqlValue::Text("SQL error: division by zero".to_string())
+        );
+        assert_eq!(get("raw_row"), NnSqlValue::Text(r#"{"c":0}"#.to_string()));
+    }
+}
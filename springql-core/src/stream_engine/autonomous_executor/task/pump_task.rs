@@ -56,11 +56,12 @@ impl PumpTask {
 
     pub fn run(&self, context: &TaskContext) -> Result<TaskRunResult> {
         let stopwatch = WallClockStopwatch::start();
-        let (processed_rows, in_queue_metrics, out_queues_metrics) =
+        let (processed_rows, rows_filtered, in_queue_metrics, out_queues_metrics) =
             self.run_query_insert(context)?;
         let execution_time = stopwatch.stop();
 
-        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time);
+        let task_metrics =
+            TaskMetricsUpdateByTask::new(context.task(), execution_time, rows_filtered, 0, 0);
         let metrics = MetricsUpdateByTaskExecution::new(
             task_metrics,
             in_queue_metrics.map_or_else(Vec::new, |m| vec![m]),
@@ -78,22 +79,33 @@ impl PumpTask {
         context: &TaskContext,
     ) -> Result<(
         ProcessedRows,
+        u64,
         Option<InQueueMetricsUpdateByTask>,
         Vec<OutQueueMetricsUpdateByTask>,
     )> {
-        if let Some(query_subtask_out) = self.query_subtask.run(context)? {
+        if let Some(query_subtask_out) = self.query_subtask.run(context) {
             let processed_rows = query_subtask_out.processed_rows();
+            let dead_letter_queues_metrics = query_subtask_out
+                .dead_letters
+                .into_iter()
+                .flat_map(|dead_letter| dead_letter.route(context))
+                .collect::<Vec<_>>();
             let insert_subtask_out = self
                 .insert_subtask
                 .run(query_subtask_out.values_seq, context);
             Ok((
                 processed_rows,
+                query_subtask_out.rows_filtered,
                 Some(query_subtask_out.in_queue_metrics_update),
-                insert_subtask_out.out_queues_metrics_update,
+                insert_subtask_out
+                    .out_queues_metrics_update
+                    .into_iter()
+                    .chain(dead_letter_queues_metrics)
+                    .collect(),
             ))
         } else {
             thread::sleep(WAIT_ON_NO_INPUT);
-            Ok((ProcessedRows::default(), None, vec![]))
+            Ok((ProcessedRows::default(), 0, None, vec![]))
         }
     }
 
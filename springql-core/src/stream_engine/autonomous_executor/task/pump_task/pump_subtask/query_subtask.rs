@@ -2,8 +2,11 @@
 
 mod collect_subtask;
 mod group_aggregate_window_subtask;
+mod having_subtask;
 mod join_subtask;
+mod lookup_join_subtask;
 mod projection_subtask;
+mod selection_subtask;
 
 use std::sync::{Arc, Mutex, MutexGuard};
 
@@ -23,14 +26,17 @@ use crate::{
             },
             row::{ColumnValues, StreamColumns, StreamRow},
             task::{
+                dead_letter::DeadLetter,
                 pump_task::pump_subtask::query_subtask::{
                     collect_subtask::CollectSubtask,
                     group_aggregate_window_subtask::GroupAggregateWindowSubtask,
-                    join_subtask::JoinSubtask, projection_subtask::ProjectionSubtask,
+                    having_subtask::HavingSubtask, join_subtask::JoinSubtask,
+                    lookup_join_subtask::LookupJoinSubtask, projection_subtask::ProjectionSubtask,
+                    selection_subtask::SelectionSubtask,
                 },
                 task_context::TaskContext,
                 tuple::Tuple,
-                window::{AggrWindow, JoinDir, JoinWindow},
+                window::{AggrWindow, AggregatedAndGroupingValues, JoinDir, JoinWindow},
                 ProcessedRows,
             },
         },
@@ -46,8 +52,12 @@ pub struct QuerySubtask {
 
     projection_subtask: ProjectionSubtask,
 
+    selection_subtask: Option<SelectionSubtask>,
+
     group_aggr_window_subtask: Option<GroupAggregateWindowSubtask>,
 
+    having_subtask: Option<HavingSubtask>,
+
     // TODO recursive JOIN
     join: Option<(
         JoinSubtask,
@@ -55,6 +65,9 @@ pub struct QuerySubtask {
     )>,
     left_collect_subtask: CollectSubtask, // left stream
 
+    /// JOIN with a table (applied to each tuple from the left stream)
+    lookup_join_subtask: Option<LookupJoinSubtask>,
+
     rng: Mutex<SmallRng>,
 }
 
@@ -102,10 +115,17 @@ impl SqlValues {
     }
 }
 
+/// (tuples to upper ops, tuples failed in lower ops)
+type LowerOpsOut = (Vec<Tuple>, Vec<DeadLetter>);
+
 #[derive(Debug, new)]
 pub struct QuerySubtaskOut {
     pub values_seq: Vec<SqlValues>,
     pub in_queue_metrics_update: InQueueMetricsUpdateByTask,
+    /// Number of tuples dropped by WHERE clause.
+    pub rows_filtered: u64,
+    /// Tuples failed to be processed.
+    pub dead_letters: Vec<DeadLetter>,
 }
 impl QuerySubtaskOut {
     pub fn processed_rows(&self) -> ProcessedRows {
@@ -130,6 +150,15 @@ impl QuerySubtask {
         let rng =
             Mutex::new(SmallRng::from_rng(rand::thread_rng()).expect("this generally won't fail"));
 
+        let selection_subtask = plan
+            .lower_ops
+            .selection
+            .as_ref()
+            .map(|op| SelectionSubtask::new(op.predicate));
+        let lookup_join_subtask = match &plan.lower_ops.join {
+            JoinOp::LookupJoin(op) => Some(LookupJoinSubtask::from_lookup_join_op(op.clone())),
+            _ => None,
+        };
         let (left_collect_subtask, join) = Self::subtasks_from_lower_ops(plan.lower_ops);
 
         let group_aggr_window_subtask = plan
@@ -137,14 +166,22 @@ impl QuerySubtask {
             .group_aggr_window
             .map(|op| GroupAggregateWindowSubtask::new(op.window_param, op.op_param));
 
+        let having_subtask = plan
+            .upper_ops
+            .having
+            .map(|op| HavingSubtask::new(op.predicate));
+
         let projection_subtask = ProjectionSubtask::new(plan.upper_ops.projection.expr_labels);
 
         Self {
             expr_resolver: plan.expr_resolver,
             projection_subtask,
+            selection_subtask,
             group_aggr_window_subtask,
+            having_subtask,
             left_collect_subtask,
             join,
+            lookup_join_subtask,
             rng,
         }
     }
@@ -167,85 +204,161 @@ impl QuerySubtask {
                     Some((join_subtask, right_collect_subtask)),
                 )
             }
+            JoinOp::LookupJoin(lookup_join_op) => {
+                let collect_subtask = CollectSubtask::from_collect_op(lookup_join_op.left);
+                (collect_subtask, None)
+            }
         }
     }
 
+    /// Tuples failed in join, lookup join, selection, aggregation or projection are turned into dead letters instead of failing the whole run.
+    ///
     /// # Returns
     ///
     /// None when input queue does not exist or is empty.
-    ///
-    /// # Failures
-    ///
-    /// TODO
-    pub fn run(&self, context: &TaskContext) -> Result<Option<QuerySubtaskOut>> {
-        match self.run_lower_ops(context) {
-            None => Ok(None),
-            Some((lower_tuples, in_queue_metrics_update_by_task)) => {
-                let (values_seq, in_queue_metrics_update) =
-                    self.run_upper_ops(lower_tuples, in_queue_metrics_update_by_task)?;
-
-                Ok(Some(QuerySubtaskOut::new(
+    pub fn run(&self, context: &TaskContext) -> Option<QuerySubtaskOut> {
+        self.run_lower_ops(context)
+            .map(|(lower_res, in_queue_metrics_update_by_lower)| {
+                let keep_raw_rows = DeadLetter::is_routed(context);
+
+                let mut values_seq = Vec::new();
+                let mut rows_filtered = 0;
+                let mut window_in_flow_upper_total = WindowInFlowByWindowTask::zero();
+
+                let (lower_tuples, mut dead_letters) = lower_res;
+
+                for tuple in lower_tuples {
+                    let raw_row = keep_raw_rows.then(|| tuple.to_json().to_string());
+
+                    let (res, window_in_flow) = self.run_tuple(context, tuple);
+                    window_in_flow_upper_total = window_in_flow_upper_total + window_in_flow;
+                    match res {
+                        Ok((mut values, filtered)) => {
+                            values_seq.append(&mut values);
+                            rows_filtered += filtered;
+                        }
+                        Err(e) => dead_letters.push(DeadLetter::new(e, raw_row)),
+                    }
+                }
+
+                let in_queue_metrics_update = InQueueMetricsUpdateByTask::new(
+                    in_queue_metrics_update_by_lower.by_collect,
+                    Some(
+                        window_in_flow_upper_total
+                            + in_queue_metrics_update_by_lower.window_in_flow,
+                    ),
+                );
+                QuerySubtaskOut::new(
                     values_seq,
                     in_queue_metrics_update,
-                )))
-            }
-        }
+                    rows_filtered,
+                    dead_letters,
+                )
+            })
     }
 
-    fn run_upper_ops(
+    /// # Returns
+    ///
+    /// ((values to insert, number of filtered-out tuples), window in-flow)
+    fn run_tuple(
         &self,
-        tuples: Vec<Tuple>,
-        in_queue_metrics_update_by_lower: InQueueMetricsUpdateByTask,
-    ) -> Result<(Vec<SqlValues>, InQueueMetricsUpdateByTask)> {
-        let (values_seq, window_in_flow_upper_total) = tuples.into_iter().fold(
-            Ok((Vec::new(), WindowInFlowByWindowTask::zero())),
-            |res, tuple| {
-                let (mut values_seq_acc, window_in_flow_acc) = res?;
-
-                let (mut values_seq, window_in_flow) = self.run_upper_ops_inner(tuple)?;
-                values_seq_acc.append(&mut values_seq);
-                Ok((values_seq_acc, window_in_flow_acc + window_in_flow))
-            },
-        )?;
-        let in_queue_metrics_update_by_task = InQueueMetricsUpdateByTask::new(
-            in_queue_metrics_update_by_lower.by_collect,
-            Some(window_in_flow_upper_total + in_queue_metrics_update_by_lower.window_in_flow),
-        );
+        context: &TaskContext,
+        tuple: Tuple,
+    ) -> (Result<(Vec<SqlValues>, u64)>, WindowInFlowByWindowTask) {
+        match self
+            .run_lookup_join(context, tuple)
+            .and_then(|tuples| self.run_selection(tuples))
+        {
+            Ok((selected_tuples, rows_filtered)) => selected_tuples.into_iter().fold(
+                (
+                    Ok((Vec::new(), rows_filtered)),
+                    WindowInFlowByWindowTask::zero(),
+                ),
+                |(res_acc, window_in_flow_acc), tuple| {
+                    let (res, window_in_flow) = self.run_upper_ops_inner(tuple);
+                    let res_acc = res_acc.and_then(|(mut values_seq_acc, rows_filtered)| {
+                        values_seq_acc.append(&mut res?);
+                        Ok((values_seq_acc, rows_filtered))
+                    });
+                    (res_acc, window_in_flow_acc + window_in_flow)
+                },
+            ),
+            Err(e) => (Err(e), WindowInFlowByWindowTask::zero()),
+        }
+    }
 
-        Ok((values_seq, in_queue_metrics_update_by_task))
+    fn run_lookup_join(&self, context: &TaskContext, tuple: Tuple) -> Result<Vec<Tuple>> {
+        match &self.lookup_join_subtask {
+            Some(lookup_join_subtask) => {
+                lookup_join_subtask.run(&self.expr_resolver, context, tuple)
+            }
+            None => Ok(vec![tuple]),
+        }
     }
+
+    /// # Returns
+    ///
+    /// (tuples passed WHERE clause, number of filtered-out tuples)
+    fn run_selection(&self, tuples: Vec<Tuple>) -> Result<(Vec<Tuple>, u64)> {
+        match &self.selection_subtask {
+            Some(selection_subtask) => selection_subtask.run(&self.expr_resolver, tuples),
+            None => Ok((tuples, 0)),
+        }
+    }
+
+    /// Window in-flow is returned even on failure since the window may have been updated.
     fn run_upper_ops_inner(
         &self,
         tuple: Tuple,
-    ) -> Result<(Vec<SqlValues>, WindowInFlowByWindowTask)> {
+    ) -> (Result<Vec<SqlValues>>, WindowInFlowByWindowTask) {
         if let Some(group_aggr_window_subtask) = &self.group_aggr_window_subtask {
             let (aggregated_and_grouping_values_seq, window_in_flow) =
                 group_aggr_window_subtask.run(&self.expr_resolver, tuple);
 
             let values_seq = aggregated_and_grouping_values_seq
-                .into_iter()
-                .map(|aggregated_and_grouping_values| {
-                    self.projection_subtask
-                        .run_with_aggr(aggregated_and_grouping_values)
+                .and_then(|aggregated_and_grouping_values_seq| {
+                    self.run_having(aggregated_and_grouping_values_seq)
                 })
-                .collect::<Result<Vec<_>>>()?;
-
-            Ok((values_seq, window_in_flow))
+                .and_then(|aggregated_and_grouping_values_seq| {
+                    aggregated_and_grouping_values_seq
+                        .into_iter()
+                        .map(|aggregated_and_grouping_values| {
+                            self.projection_subtask
+                                .run_with_aggr(&self.expr_resolver, aggregated_and_grouping_values)
+                        })
+                        .collect::<Result<Vec<_>>>()
+                });
+
+            (values_seq, window_in_flow)
         } else {
             let values = self
                 .projection_subtask
-                .run_without_aggr(&self.expr_resolver, &tuple)?;
-            Ok((vec![values], WindowInFlowByWindowTask::zero()))
+                .run_without_aggr(&self.expr_resolver, &tuple)
+                .map(|values| vec![values]);
+            (values, WindowInFlowByWindowTask::zero())
+        }
+    }
+
+    fn run_having(
+        &self,
+        aggregated_and_grouping_values_seq: Vec<AggregatedAndGroupingValues>,
+    ) -> Result<Vec<AggregatedAndGroupingValues>> {
+        match &self.having_subtask {
+            Some(having_subtask) => {
+                having_subtask.run(&self.expr_resolver, aggregated_and_grouping_values_seq)
+            }
+            None => Ok(aggregated_and_grouping_values_seq),
         }
     }
 
     /// # Returns
     ///
     /// None when input queue does not exist or is empty or JOIN op does not emit output yet.
+    /// A collected tuple failed in JOIN, or a pair of tuples whose ON expression failed, is returned as a dead letter.
     fn run_lower_ops(
         &self,
         context: &TaskContext,
-    ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
+    ) -> Option<(LowerOpsOut, InQueueMetricsUpdateByTask)> {
         match &self.join {
             Some((join_subtask, right_collect_subtask)) => self.run_join(
                 context,
@@ -257,7 +370,7 @@ impl QuerySubtask {
                 .run_left_collect(context)
                 .map(|(tuple, metrics_collect)| {
                     (
-                        vec![tuple],
+                        (vec![tuple], vec![]),
                         InQueueMetricsUpdateByTask::new(
                             metrics_collect,
                             None, // single collect subtask does not use window yet
@@ -276,7 +389,7 @@ impl QuerySubtask {
         left_collect_subtask: &CollectSubtask,
         right_collect_subtask: &CollectSubtask,
         join_subtask: &JoinSubtask,
-    ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
+    ) -> Option<(LowerOpsOut, InQueueMetricsUpdateByTask)> {
         self.join_dir_candidates().into_iter().find_map(|dir| {
             let collect_subtask = match dir {
                 JoinDir::Left => left_collect_subtask,
@@ -301,13 +414,33 @@ impl QuerySubtask {
         collect_subtask: &CollectSubtask,
         join_subtask: &JoinSubtask,
         join_dir: JoinDir,
-    ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
+    ) -> Option<(LowerOpsOut, InQueueMetricsUpdateByTask)> {
         collect_subtask
             .run(context)
             .map(|(tuple, metrics_collect)| {
-                let (tuples, metrics_join) = join_subtask.run(&self.expr_resolver, tuple, join_dir);
+                let keep_raw_rows = DeadLetter::is_routed(context);
+                let raw_row = keep_raw_rows.then(|| tuple.to_json().to_string());
+                let (res, metrics_join) = join_subtask.run(&self.expr_resolver, tuple, join_dir);
                 let metrics = InQueueMetricsUpdateByTask::new(metrics_collect, Some(metrics_join));
-                (tuples, metrics)
+
+                let lower_res = match res {
+                    Ok(joined_tuples) => {
+                        let mut tuples = Vec::new();
+                        let mut dead_letters = Vec::new();
+                        for joined_tuple in joined_tuples {
+                            match joined_tuple {
+                                Ok(tuple) => tuples.push(tuple),
+                                Err((e, pair)) => dead_letters.push(DeadLetter::new(
+                                    e,
+                                    keep_raw_rows.then(|| pair.to_json().to_string()),
+                                )),
+                            }
+                        }
+                        (tuples, dead_letters)
+                    }
+                    Err(e) => (vec![], vec![DeadLetter::new(e, raw_row)]),
+                };
+                (lower_res, metrics)
             })
     }
 
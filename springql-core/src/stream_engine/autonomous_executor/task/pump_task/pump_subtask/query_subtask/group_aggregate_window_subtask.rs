@@ -3,6 +3,7 @@
 use std::sync::{Mutex, MutexGuard};
 
 use crate::{
+    api::error::Result,
     expr_resolver::ExprResolver,
     pipeline::{WindowOperationParameter, WindowParameter},
     stream_engine::autonomous_executor::{
@@ -23,16 +24,26 @@ impl GroupAggregateWindowSubtask {
         Self(Mutex::new(window))
     }
 
+    /// Window in-flow is returned even on failure since the window may have been updated.
     pub fn run(
         &self,
         expr_resolver: &ExprResolver,
         tuple: Tuple,
-    ) -> (Vec<AggregatedAndGroupingValues>, WindowInFlowByWindowTask) {
-        self.0
+    ) -> (
+        Result<Vec<AggregatedAndGroupingValues>>,
+        WindowInFlowByWindowTask,
+    ) {
+        match self
+            .0
             .lock()
             .expect("another thread accessing to window gets poisoned")
             .dispatch(expr_resolver, tuple, ())
-            .expect("dispatch failed")
+        {
+            Ok((aggregated_and_grouping_values_seq, window_in_flow)) => {
+                (Ok(aggregated_and_grouping_values_seq), window_in_flow)
+            }
+            Err((e, window_in_flow)) => (Err(e), window_in_flow),
+        }
     }
 
     pub fn get_window_mut(&self) -> MutexGuard<AggrWindow> {
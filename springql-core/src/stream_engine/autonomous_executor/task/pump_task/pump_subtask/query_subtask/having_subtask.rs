@@ -0,0 +1,40 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::Result,
+    expr_resolver::{ExprResolver, ValueExprLabel},
+    stream_engine::autonomous_executor::task::window::AggregatedAndGroupingValues,
+};
+
+#[derive(Debug, new)]
+pub struct HavingSubtask {
+    predicate: ValueExprLabel,
+}
+
+impl HavingSubtask {
+    /// Keeps groups whose predicate is evaluated to TRUE.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the predicate cannot be evaluated as BOOLEAN.
+    pub fn run(
+        &self,
+        expr_resolver: &ExprResolver,
+        aggregated_and_grouping_values_seq: Vec<AggregatedAndGroupingValues>,
+    ) -> Result<Vec<AggregatedAndGroupingValues>> {
+        aggregated_and_grouping_values_seq
+            .into_iter()
+            .filter_map(|aggregated_and_grouping_values| {
+                match expr_resolver
+                    .eval_value_expr_with_aggr(self.predicate, &aggregated_and_grouping_values)
+                    .and_then(|v| v.to_bool())
+                {
+                    Ok(true) => Some(Ok(aggregated_and_grouping_values)),
+                    Ok(false) => None,
+                    Err(e) => Some(Err(e)),
+                }
+            })
+            .collect()
+    }
+}
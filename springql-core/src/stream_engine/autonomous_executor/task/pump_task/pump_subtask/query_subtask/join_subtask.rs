@@ -3,13 +3,14 @@
 use std::sync::{Mutex, MutexGuard};
 
 use crate::{
+    api::error::Result,
     expr_resolver::ExprResolver,
     pipeline::{JoinParameter, WindowParameter},
     stream_engine::autonomous_executor::{
         performance_metrics::WindowInFlowByWindowTask,
         task::{
             tuple::Tuple,
-            window::{JoinDir, JoinWindow, Window},
+            window::{JoinDir, JoinWindow, JoinedTuple, Window},
         },
     },
 };
@@ -23,17 +24,22 @@ impl JoinSubtask {
         Self(Mutex::new(window))
     }
 
+    /// Window in-flow is returned even on failure since the window may have been updated.
     pub fn run(
         &self,
         expr_resolver: &ExprResolver,
         tuple: Tuple,
         dir: JoinDir,
-    ) -> (Vec<Tuple>, WindowInFlowByWindowTask) {
-        self.0
+    ) -> (Result<Vec<JoinedTuple>>, WindowInFlowByWindowTask) {
+        match self
+            .0
             .lock()
             .expect("another thread accessing to window gets poisoned")
             .dispatch(expr_resolver, tuple, dir)
-            .expect("dispatch failed")
+        {
+            Ok((tuples, window_in_flow)) => (Ok(tuples), window_in_flow),
+            Err((e, window_in_flow)) => (Err(e), window_in_flow),
+        }
     }
 
     pub fn get_window_mut(&self) -> MutexGuard<JoinWindow> {
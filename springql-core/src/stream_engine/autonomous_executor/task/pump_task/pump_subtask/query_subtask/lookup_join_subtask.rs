@@ -0,0 +1,98 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::Result,
+    expr_resolver::ExprResolver,
+    pipeline::{ColumnReference, JoinParameter, JoinType, StreamName},
+    stream_engine::{
+        autonomous_executor::{
+            table::ReferenceTable,
+            task::{task_context::TaskContext, tuple::Tuple},
+        },
+        command::LookupJoinOp,
+        SqlValue,
+    },
+};
+
+#[derive(Debug)]
+pub struct LookupJoinSubtask {
+    table: StreamName,
+    join_param: JoinParameter,
+    lookup_key: Option<Vec<ColumnReference>>,
+}
+
+impl LookupJoinSubtask {
+    pub fn from_lookup_join_op(op: LookupJoinOp) -> Self {
+        Self {
+            table: op.table,
+            join_param: op.join_param,
+            lookup_key: op.lookup_key,
+        }
+    }
+
+    pub fn run(
+        &self,
+        expr_resolver: &ExprResolver,
+        context: &TaskContext,
+        left_tuple: Tuple,
+    ) -> Result<Vec<Tuple>> {
+        let table = context.repos().table_repository().get(&self.table);
+        self.join(expr_resolver, &table, left_tuple)
+    }
+
+    /// Joined tuples take rowtime from `left_tuple`.
+    ///
+    /// ON expression is evaluated only for the table rows found by `candidates()`.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the ON expression cannot be evaluated as BOOLEAN.
+    fn join(
+        &self,
+        expr_resolver: &ExprResolver,
+        table: &ReferenceTable,
+        left_tuple: Tuple,
+    ) -> Result<Vec<Tuple>> {
+        let mut joined_tuples = Vec::new();
+        for right_tuple in self.candidates(table, &left_tuple)? {
+            let joined_tuple = left_tuple.clone().join(right_tuple);
+            if expr_resolver
+                .eval_value_expr(self.join_param.on_expr, &joined_tuple)?
+                .to_bool()?
+            {
+                joined_tuples.push(joined_tuple);
+            }
+        }
+
+        if joined_tuples.is_empty() && self.join_param.join_type == JoinType::LeftOuter {
+            let null_right = Tuple::nulls(left_tuple.rowtime(), &self.join_param.right_colrefs);
+            joined_tuples.push(left_tuple.join(null_right));
+        }
+
+        Ok(joined_tuples)
+    }
+
+    /// Table rows which may be joined to `left_tuple`.
+    ///
+    /// Looked up by PRIMARY KEY if `lookup_key` is available. Otherwise, all the rows (scan).
+    fn candidates(&self, table: &ReferenceTable, left_tuple: &Tuple) -> Result<Vec<Tuple>> {
+        match &self.lookup_key {
+            Some(lookup_key) => {
+                let key = lookup_key
+                    .iter()
+                    .map(|colref| {
+                        left_tuple.get_value(colref).map(|v| match v {
+                            SqlValue::Null => None,
+                            SqlValue::NotNull(v) => Some(v),
+                        })
+                    })
+                    .collect::<Result<Option<Vec<_>>>>()?;
+
+                // NULL key never equals to any key
+                Ok(key.and_then(|key| table.get(&key)).into_iter().collect())
+            }
+            None => Ok(table.tuples()),
+        }
+    }
+}
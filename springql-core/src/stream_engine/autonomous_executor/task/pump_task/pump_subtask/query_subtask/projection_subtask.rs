@@ -36,25 +36,32 @@ impl ProjectionSubtask {
     }
 
     /// Projection for SELECT with aggregate.
-    /// select_list must only have GROUP BY elements or aggregate expressions.
+    /// select_list must only have GROUP BY elements or expressions whose column references are all inside aggregate functions.
     /// (Column reference without aggregate is not allowed.)
     pub fn run_with_aggr(
         &self,
+        expr_resolver: &ExprResolver,
         aggregated_and_grouping_values: AggregatedAndGroupingValues,
     ) -> Result<SqlValues> {
         let values = self
             .exprs
             .iter()
-            .map(|label| {
-                let value = match label {
-                    ExprLabel::Value(group_by_value_label) => {
-                        aggregated_and_grouping_values.get_group_by_value(group_by_value_label)
-                    }
-                    ExprLabel::Aggr(aggr_label) => {
-                        aggregated_and_grouping_values.get_aggregated_value(aggr_label)
+            .map(|label| match label {
+                ExprLabel::Value(value_label) => {
+                    if aggregated_and_grouping_values.has_group_by_value(value_label) {
+                        aggregated_and_grouping_values
+                            .get_group_by_value(value_label)
+                            .cloned()
+                    } else {
+                        expr_resolver.eval_value_expr_with_aggr(
+                            *value_label,
+                            &aggregated_and_grouping_values,
+                        )
                     }
-                }?;
-                Ok(value.clone())
+                }
+                ExprLabel::Aggr(aggr_label) => aggregated_and_grouping_values
+                    .get_aggregated_value(aggr_label)
+                    .cloned(),
             })
             .collect::<Result<Vec<_>>>()?;
 
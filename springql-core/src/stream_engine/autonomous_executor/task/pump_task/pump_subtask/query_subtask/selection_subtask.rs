@@ -0,0 +1,49 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::Result,
+    expr_resolver::{ExprResolver, ValueExprLabel},
+    stream_engine::autonomous_executor::task::tuple::Tuple,
+};
+
+#[derive(Debug, new)]
+pub struct SelectionSubtask {
+    predicate: ValueExprLabel,
+}
+
+impl SelectionSubtask {
+    /// Keeps tuples whose predicate is evaluated to TRUE.
+    ///
+    /// # Returns
+    ///
+    /// (passed tuples, number of filtered-out tuples)
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the predicate cannot be evaluated as BOOLEAN.
+    pub fn run(
+        &self,
+        expr_resolver: &ExprResolver,
+        tuples: Vec<Tuple>,
+    ) -> Result<(Vec<Tuple>, u64)> {
+        let n_in = tuples.len();
+
+        let passed = tuples
+            .into_iter()
+            .filter_map(|tuple| {
+                match expr_resolver
+                    .eval_value_expr(self.predicate, &tuple)
+                    .and_then(|v| v.to_bool())
+                {
+                    Ok(true) => Some(Ok(tuple)),
+                    Ok(false) => None,
+                    Err(e) => Some(Err(e)),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let rows_filtered = (n_in - passed.len()) as u64;
+        Ok((passed, rows_filtered))
+    }
+}
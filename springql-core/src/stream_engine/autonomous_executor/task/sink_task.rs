@@ -6,6 +6,8 @@ pub use sink_writer::{NetSinkWriter, SinkWriter, SinkWriterRepository};
 
 use std::sync::Arc;
 
+use sink_writer::UnsentRows;
+
 use crate::{
     api::error::Result,
     mem_size::MemSize,
@@ -14,11 +16,13 @@ use crate::{
         autonomous_executor::{
             performance_metrics::{
                 InQueueMetricsUpdateByCollect, InQueueMetricsUpdateByTask,
-                MetricsUpdateByTaskExecution, TaskMetricsUpdateByTask,
+                MetricsUpdateByTaskExecution, OutQueueMetricsUpdateByTask, TaskMetricsUpdateByTask,
             },
             repositories::Repositories,
-            row::StreamRow,
-            task::{task_context::TaskContext, ProcessedRows, TaskRunResult},
+            row::{JsonObject, SchemalessRow, StreamRow},
+            task::{
+                dead_letter::DeadLetter, task_context::TaskContext, ProcessedRows, TaskRunResult,
+            },
             task_graph::{QueueId, TaskId},
         },
         time::WallClockStopwatch,
@@ -50,15 +54,25 @@ impl SinkTask {
         let stopwatch = WallClockStopwatch::start();
 
         let repos = context.repos();
+        let sink_writer = repos
+            .sink_writer_repository()
+            .get_sink_writer(&self.sink_writer_name);
+        let mut sink_writer = sink_writer
+            .lock()
+            .expect("other worker threads sharing the same sink subtask must not get panic");
+        let retry_buffer_bytes_before = sink_writer.retry_buffer_bytes();
 
         let opt_in_queue_id = context
             .pipeline_derivatives()
             .task_graph()
             .input_queue(&context.task(), &self.upstream);
 
+        // to dead-letter streams of this sink writer
+        let mut out_queues_metrics = vec![];
+
         let (processed_rows, in_queues_metrics) = if let Some(in_queue_id) = opt_in_queue_id {
-            if let Some((row, in_queue_metrics)) = self.use_row_from(in_queue_id, repos) {
-                self.emit(row, context)?;
+            if let Some((row, in_queue_metrics)) = self.use_row_from(in_queue_id, repos.clone()) {
+                out_queues_metrics.extend(Self::emit(sink_writer.as_mut(), row, context));
                 (ProcessedRows::new(1), vec![in_queue_metrics])
             } else {
                 (ProcessedRows::default(), vec![])
@@ -67,10 +81,26 @@ impl SinkTask {
             (ProcessedRows::default(), vec![])
         };
 
+        let given_up = sink_writer.poll_retries();
+        let flush_result = sink_writer.poll_flush();
+        let retry_buffer_gain_bytes =
+            sink_writer.retry_buffer_bytes() as i64 - retry_buffer_bytes_before as i64;
+        drop(sink_writer);
+
+        given_up
+            .into_iter()
+            .chain(flush_result.err())
+            .for_each(|unsent| out_queues_metrics.extend(Self::dead_letter(unsent, context)));
+
         let execution_time = stopwatch.stop();
 
-        let out_queues_metrics = vec![];
-        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time);
+        let task_metrics = TaskMetricsUpdateByTask::new(
+            context.task(),
+            execution_time,
+            0,
+            0,
+            retry_buffer_gain_bytes,
+        );
         let metrics =
             MetricsUpdateByTaskExecution::new(task_metrics, in_queues_metrics, out_queues_metrics);
         Ok(TaskRunResult {
@@ -79,6 +109,38 @@ impl SinkTask {
         })
     }
 
+    /// Routes rows in the sink writer's retry buffer to dead-letter streams (if any), to free memory.
+    pub fn purge_retry_buffer(&self, context: &TaskContext) -> MetricsUpdateByTaskExecution {
+        let stopwatch = WallClockStopwatch::start();
+
+        let sink_writer = context
+            .repos()
+            .sink_writer_repository()
+            .get_sink_writer(&self.sink_writer_name);
+        let mut sink_writer = sink_writer
+            .lock()
+            .expect("other worker threads sharing the same sink subtask must not get panic");
+        let retry_buffer_bytes_before = sink_writer.retry_buffer_bytes();
+        let purged = sink_writer.purge_retry_buffer();
+        let retry_buffer_gain_bytes =
+            sink_writer.retry_buffer_bytes() as i64 - retry_buffer_bytes_before as i64;
+        drop(sink_writer);
+
+        let out_queues_metrics = purged
+            .into_iter()
+            .flat_map(|unsent| Self::dead_letter(unsent, context))
+            .collect();
+
+        let task_metrics = TaskMetricsUpdateByTask::new(
+            context.task(),
+            stopwatch.stop(),
+            0,
+            0,
+            retry_buffer_gain_bytes,
+        );
+        MetricsUpdateByTaskExecution::new(task_metrics, vec![], out_queues_metrics)
+    }
+
     fn use_row_from(
         &self,
         queue_id: QueueId,
@@ -107,17 +169,26 @@ impl SinkTask {
         }
     }
 
-    fn emit(&self, row: StreamRow, context: &TaskContext) -> Result<()> {
-        let sink_writer = context
-            .repos()
-            .sink_writer_repository()
-            .get_sink_writer(&self.sink_writer_name);
-
+    /// Rows failed to be sent are routed to dead-letter streams (if any).
+    fn emit(
+        sink_writer: &mut dyn SinkWriter,
+        row: StreamRow,
+        context: &TaskContext,
+    ) -> Vec<OutQueueMetricsUpdateByTask> {
+        let row = SchemalessRow::from(row);
         sink_writer
-            .lock()
-            .expect("other worker threads sharing the same sink subtask must not get panic")
-            .send_row(row.into())?;
+            .send_row(row)
+            .err()
+            .map_or_else(Vec::new, |unsent| Self::dead_letter(unsent, context))
+    }
 
-        Ok(())
+    fn dead_letter(unsent: UnsentRows, context: &TaskContext) -> Vec<OutQueueMetricsUpdateByTask> {
+        let keep_raw_rows = DeadLetter::is_routed(context);
+        let raw_rows = unsent
+            .rows
+            .into_iter()
+            .map(|row| keep_raw_rows.then(|| JsonObject::from(row).to_string()))
+            .collect();
+        DeadLetter::with_raw_rows(unsent.error, raw_rows).route(context)
     }
 }
@@ -3,6 +3,7 @@
 mod http_client;
 mod in_memory_queue;
 mod net;
+mod retry;
 mod sink_writer_factory;
 mod sink_writer_repository;
 
@@ -12,11 +13,23 @@ pub use sink_writer_repository::SinkWriterRepository;
 use std::fmt::Debug;
 
 use crate::{
-    api::{error::Result, SpringSinkWriterConfig},
+    api::{
+        error::{Result, SpringError},
+        SpringSinkWriterConfig,
+    },
     pipeline::Options,
     stream_engine::autonomous_executor::SchemalessRow,
 };
 
+/// Rows failed to be sent together (e.g. in a batch), with the cause.
+///
+/// Callers retry or dead-letter `rows` as a unit.
+#[derive(Debug, new)]
+pub struct UnsentRows {
+    pub rows: Vec<SchemalessRow>,
+    pub error: SpringError,
+}
+
 /// Instance of SinkWriterModel.
 ///
 /// Since agents and servers may live as long as a program lives, sink task cannot hold hold implementations of this trait.
@@ -28,6 +41,8 @@ pub trait SinkWriter: Debug + Sync + Send + 'static {
 
     /// # Failure
     ///
+    /// `UnsentRows` holds `row` and the rows batched with it (if any), with the error below.
+    ///
     /// - `SpringError::ForeignSourceTimeout` when:
     ///   - Remote sink does not accept row within timeout.
     /// - `SpringError::ForeignIo` when:
@@ -35,5 +50,49 @@ pub trait SinkWriter: Debug + Sync + Send + 'static {
     ///   - Unknown foreign error.
     /// - `SpringError::Null` when:
     ///   - `row` contains unexpected NULL column.
-    fn send_row(&mut self, row: SchemalessRow) -> Result<()>;
+    fn send_row(&mut self, row: SchemalessRow) -> std::result::Result<(), UnsentRows>;
+
+    /// Sends `rows` failed before as a unit, without batching them with other rows.
+    ///
+    /// # Failure
+    ///
+    /// Same as `send_row()`.
+    fn send_rows(&mut self, rows: Vec<SchemalessRow>) -> Result<()> {
+        rows.into_iter()
+            .try_for_each(|row| self.send_row(row).map_err(|unsent| unsent.error))
+    }
+
+    /// Re-sends rows failed before (if this sink writer retries).
+    /// Called on every sink task execution, even without input rows.
+    ///
+    /// # Returns
+    ///
+    /// Rows given up sending, with the last errors.
+    fn poll_retries(&mut self) -> Vec<UnsentRows> {
+        vec![]
+    }
+
+    /// Sends rows buffered in this sink writer if they have waited for long (if this sink writer batches rows).
+    /// Called on every sink task execution, even without input rows.
+    ///
+    /// # Failure
+    ///
+    /// Same as `send_row()`.
+    fn poll_flush(&mut self) -> std::result::Result<(), UnsentRows> {
+        Ok(())
+    }
+
+    /// Bytes of rows waiting for retries or in a batch.
+    fn retry_buffer_bytes(&self) -> u64 {
+        0
+    }
+
+    /// Takes out all the rows waiting for retries, to free memory.
+    ///
+    /// # Returns
+    ///
+    /// Rows taken out, with `SpringError::Unavailable`.
+    fn purge_retry_buffer(&mut self) -> Vec<UnsentRows> {
+        vec![]
+    }
 }
@@ -1,15 +1,28 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::{net::SocketAddr, time::Duration};
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
 
-use reqwest::{header::HeaderMap, Method, Url};
+use anyhow::anyhow;
+use reqwest::{
+    blocking::Client,
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    Method, Url,
+};
 
 use crate::{
     api::error::{foreign_info::ForeignInfo, Result, SpringError},
     api::SpringSinkWriterConfig,
-    pipeline::{ColumnName, Http1ClientOptions, HttpMethod, Options},
+    mem_size::MemSize,
+    pipeline::{Http1ClientOptions, HttpBodyFormat, HttpMethod, Options},
     stream_engine::{
-        autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
+        autonomous_executor::{
+            row::JsonObject,
+            task::sink_task::sink_writer::{SinkWriter, UnsentRows},
+            SchemalessRow,
+        },
         SqlValue,
     },
 };
@@ -18,18 +31,21 @@ use crate::{
 pub struct HttpClientSinkWriter {
     foreign_addr: SocketAddr,
 
-    timeout: Duration,
-    connect_timeout: Duration,
+    client: Client,
 
     http_method: Method,
     url: Url,
     http_headers: HeaderMap,
-    http_body_blob_column: ColumnName,
+    body_format: HttpBodyFormat,
+
+    batch: Batch,
 }
 impl From<HttpMethod> for Method {
     fn from(m: HttpMethod) -> Self {
         match m {
             HttpMethod::Post => Method::POST,
+            HttpMethod::Put => Method::PUT,
+            HttpMethod::Patch => Method::PATCH,
         }
     }
 }
@@ -42,62 +58,300 @@ impl SinkWriter for HttpClientSinkWriter {
         let timeout = Duration::from_millis(config.http_timeout_msec as u64);
         let connect_timeout = Duration::from_millis(config.http_connect_timeout_msec as u64);
 
+        let client = Client::builder()
+            .connect_timeout(connect_timeout)
+            .timeout(timeout)
+            .build()
+            .map_err(|e| SpringError::ForeignIo {
+                foreign_info: ForeignInfo::Http(sock_addr),
+                source: anyhow::Error::from(e).context("failed to create HTTP client"),
+            })?;
+
         let http_method = Method::from(options.method);
         let url = options.url.clone();
-        let http_headers =
+        let mut http_headers =
             HeaderMap::try_from(&options.headers).expect("don't know why this fails");
-        let http_body_blob_column = options.blob_body_column;
+        if !http_headers.contains_key(CONTENT_TYPE) {
+            let content_type = match options.body_format {
+                HttpBodyFormat::Blob(_) => None,
+                HttpBodyFormat::Json | HttpBodyFormat::JsonArray => Some("application/json"),
+                HttpBodyFormat::Ndjson => Some("application/x-ndjson"),
+            };
+            if let Some(content_type) = content_type {
+                http_headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+            }
+        }
+
+        let batch = Batch::new(
+            options
+                .batch_max_rows
+                .unwrap_or(config.http_batch_max_rows)
+                .max(1) as usize,
+            options
+                .batch_max_bytes
+                .unwrap_or(config.http_batch_max_bytes) as usize,
+            Duration::from_millis(
+                options
+                    .batch_max_latency_msec
+                    .unwrap_or(config.http_batch_max_latency_msec) as u64,
+            ),
+        );
 
         log::info!("[HttpClientSinkWriter] Ready to connect {}", sock_addr);
 
         Ok(Self {
             foreign_addr: sock_addr,
-            timeout,
-            connect_timeout,
+            client,
             http_method,
             url,
             http_headers,
-            http_body_blob_column,
+            body_format: options.body_format,
+            batch,
         })
     }
 
-    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
-        let blob_column = row.get_by_column_name(&self.http_body_blob_column)?;
-        if let SqlValue::NotNull(nn_sql_value) = blob_column {
-            let body = nn_sql_value.unpack::<Vec<u8>>()?;
-            self.send_request(body)
+    /// With batched body formats, `row` is sent when the batch gets full.
+    /// If sending the batch fails, the whole batch is taken out and returned as `UnsentRows`.
+    fn send_row(&mut self, row: SchemalessRow) -> std::result::Result<(), UnsentRows> {
+        if is_batched(&self.body_format) {
+            self.batch.push(row);
+            if self.batch.is_full() {
+                self.flush()
+            } else {
+                Ok(())
+            }
+        } else {
+            self.send_single(&row)
+                .map_err(|e| UnsentRows::new(vec![row], e))
+        }
+    }
+
+    /// With batched body formats, `rows` are sent in a single request.
+    fn send_rows(&mut self, rows: Vec<SchemalessRow>) -> Result<()> {
+        if is_batched(&self.body_format) {
+            let jsons = rows
+                .into_iter()
+                .map(|row| JsonObject::from(row).to_string())
+                .collect::<Vec<_>>();
+            let body = batch_body(&jsons, &self.body_format);
+            self.send_request(body.into_bytes())
+        } else {
+            rows.iter().try_for_each(|row| self.send_single(row))
+        }
+    }
+
+    /// Sends the batch if its oldest row has waited for `BATCH_MAX_LATENCY_MSEC`.
+    /// If it fails, the whole batch is taken out and returned as `UnsentRows`.
+    fn poll_flush(&mut self) -> std::result::Result<(), UnsentRows> {
+        if self.batch.is_expired() {
+            self.flush()
         } else {
-            unimplemented!("NULL blob column is not supported yet");
+            Ok(())
+        }
+    }
+
+    fn retry_buffer_bytes(&self) -> u64 {
+        self.batch.mem_bytes as u64
+    }
+}
+
+impl Drop for HttpClientSinkWriter {
+    /// Sends the rows left in the batch.
+    fn drop(&mut self) {
+        if !self.batch.rows.is_empty() {
+            if let Err(unsent) = self.flush() {
+                log::warn!(
+                    "[HttpClientSinkWriter] {} rows in the last batch are lost: {:?}",
+                    unsent.rows.len(),
+                    unsent.error
+                );
+            }
         }
     }
 }
 
 impl HttpClientSinkWriter {
+    /// Sends the batch and clears it, or takes it out on failure.
+    fn flush(&mut self) -> std::result::Result<(), UnsentRows> {
+        let body = batch_body(&self.batch.jsons, &self.body_format);
+        let res = self.send_request(body.into_bytes());
+        let rows = self.batch.take();
+        res.map_err(|e| UnsentRows::new(rows, e))
+    }
+
+    /// Sends `row` in a request, with non-batched body formats.
+    fn send_single(&mut self, row: &SchemalessRow) -> Result<()> {
+        match &self.body_format {
+            HttpBodyFormat::Blob(blob_column) => {
+                let blob_column = row.get_by_column_name(blob_column)?;
+                if let SqlValue::NotNull(nn_sql_value) = blob_column {
+                    let body = nn_sql_value.unpack::<Vec<u8>>()?;
+                    self.send_request(body)
+                } else {
+                    unimplemented!("NULL blob column is not supported yet");
+                }
+            }
+            HttpBodyFormat::Json => {
+                let body = JsonObject::from(row.clone()).to_string();
+                self.send_request(body.into_bytes())
+            }
+            HttpBodyFormat::Ndjson | HttpBodyFormat::JsonArray => {
+                unreachable!("{:?} batches rows", self.body_format)
+            }
+        }
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - Failed to send the request.
+    ///   - Response status is not 2xx.
     fn send_request(&mut self, body: Vec<u8>) -> Result<()> {
-        let req_builder = if self.http_method == Method::POST {
-            reqwest::blocking::Client::builder()
-                .connect_timeout(self.connect_timeout)
-                .timeout(self.timeout)
-                .build()
-                .expect("msg: failed to create reqwest client")
-                .post(self.url.to_string())
-        } else {
-            unimplemented!("HTTP method {} is not supported yet", self.http_method);
+        let foreign_io = |e: anyhow::Error| SpringError::ForeignIo {
+            foreign_info: ForeignInfo::Http(self.foreign_addr),
+            source: e,
         };
 
-        let content_length = body.len();
-        self.http_headers
-            .insert("Content-Length", content_length.into());
-
-        let _resp = req_builder
+        let resp = self
+            .client
+            .request(self.http_method.clone(), self.url.clone())
             .headers(self.http_headers.clone())
             .body(body)
             .send()
-            .map_err(|e| SpringError::ForeignIo {
-                foreign_info: ForeignInfo::Http(self.foreign_addr),
-                source: e.into(),
-            })?;
+            .map_err(|e| foreign_io(e.into()))?;
+
+        let status = resp.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            let resp_body = resp.text().unwrap_or_default();
+            Err(foreign_io(anyhow!(
+                "HTTP request failed with status {}: {}",
+                status,
+                resp_body
+            )))
+        }
+    }
+}
+
+fn is_batched(body_format: &HttpBodyFormat) -> bool {
+    matches!(
+        body_format,
+        HttpBodyFormat::Ndjson | HttpBodyFormat::JsonArray
+    )
+}
+
+fn batch_body(jsons: &[String], body_format: &HttpBodyFormat) -> String {
+    match body_format {
+        HttpBodyFormat::Ndjson => jsons.iter().map(|json| format!("{}\n", json)).collect(),
+        HttpBodyFormat::JsonArray => format!("[{}]", jsons.join(",")),
+        HttpBodyFormat::Blob(_) | HttpBodyFormat::Json => {
+            unreachable!("{:?} does not batch rows", body_format)
+        }
+    }
+}
+
+/// Rows waiting to be sent in a request.
+#[derive(Debug)]
+struct Batch {
+    max_rows: usize,
+    max_bytes: usize,
+    max_latency: Duration,
+
+    /// Kept to be retried or dead-lettered when the batch fails.
+    rows: Vec<SchemalessRow>,
+    /// `rows` in JSON.
+    jsons: Vec<String>,
+    /// Bytes of the request body.
+    bytes: usize,
+    /// Memory used by `rows` and `jsons`.
+    mem_bytes: usize,
+    /// When the oldest row was pushed.
+    since: Option<Instant>,
+}
+
+impl Batch {
+    fn new(max_rows: usize, max_bytes: usize, max_latency: Duration) -> Self {
+        Self {
+            max_rows,
+            max_bytes,
+            max_latency,
+            rows: vec![],
+            jsons: vec![],
+            bytes: 0,
+            mem_bytes: 0,
+            since: None,
+        }
+    }
+
+    fn push(&mut self, row: SchemalessRow) {
+        let json = JsonObject::from(row.clone()).to_string();
+        self.bytes += json.len() + 1; // + separator
+        self.mem_bytes += row.mem_size() + json.len();
+        self.rows.push(row);
+        self.jsons.push(json);
+        self.since.get_or_insert_with(Instant::now);
+    }
+
+    /// Takes out all the rows and clears the batch.
+    fn take(&mut self) -> Vec<SchemalessRow> {
+        self.jsons.clear();
+        self.bytes = 0;
+        self.mem_bytes = 0;
+        self.since = None;
+        std::mem::take(&mut self.rows)
+    }
+
+    fn is_full(&self) -> bool {
+        self.rows.len() >= self.max_rows || self.bytes >= self.max_bytes
+    }
+
+    fn is_expired(&self) -> bool {
+        self.since
+            .map_or(false, |since| since.elapsed() >= self.max_latency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch() {
+        let tokyo = SchemalessRow::fx_city_temperature_tokyo();
+        let osaka = SchemalessRow::fx_city_temperature_osaka();
+        let tokyo_json = JsonObject::from(tokyo.clone()).to_string();
+        let osaka_json = JsonObject::from(osaka.clone()).to_string();
+
+        let mut batch = Batch::new(3, 1024, Duration::from_secs(60));
+        assert!(!batch.is_expired());
+
+        batch.push(tokyo.clone());
+        batch.push(osaka.clone());
+        assert!(!batch.is_full());
+        assert_eq!(
+            batch_body(&batch.jsons, &HttpBodyFormat::Ndjson),
+            format!("{}\n{}\n", tokyo_json, osaka_json)
+        );
+        assert_eq!(
+            batch_body(&batch.jsons, &HttpBodyFormat::JsonArray),
+            format!("[{},{}]", tokyo_json, osaka_json)
+        );
+        assert_eq!(batch.bytes, tokyo_json.len() + osaka_json.len() + 2);
+        assert!(batch.mem_bytes > 0);
+
+        batch.push(tokyo.clone());
+        assert!(batch.is_full());
+        assert_eq!(batch.take(), vec![tokyo.clone(), osaka, tokyo.clone()]);
+        assert!(!batch.is_full());
+        assert_eq!(batch.mem_bytes, 0);
 
-        Ok(())
+        let mut batch = Batch::new(100, tokyo_json.len() + 1, Duration::ZERO);
+        assert!(!batch.is_expired());
+        batch.push(tokyo);
+        assert!(batch.is_full());
+        assert!(batch.is_expired());
+        batch.take();
+        assert!(!batch.is_expired());
     }
 }
@@ -4,7 +4,10 @@ use crate::{
     api::{error::Result, SpringSinkWriterConfig},
     pipeline::{InMemoryQueueOptions, Options, QueueName},
     stream_engine::{
-        autonomous_executor::{task::sink_task::sink_writer::SinkWriter, SchemalessRow},
+        autonomous_executor::{
+            task::sink_task::sink_writer::{SinkWriter, UnsentRows},
+            SchemalessRow,
+        },
         in_memory_queue_repository::InMemoryQueueRepository,
     },
 };
@@ -23,9 +26,13 @@ impl SinkWriter for InMemoryQueueSinkWriter {
         Ok(Self(queue_name))
     }
 
-    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
-        let q = InMemoryQueueRepository::instance().get(&self.0)?;
-        q.push(row);
-        Ok(())
+    fn send_row(&mut self, row: SchemalessRow) -> std::result::Result<(), UnsentRows> {
+        match InMemoryQueueRepository::instance().get(&self.0) {
+            Ok(q) => {
+                q.push(row);
+                Ok(())
+            }
+            Err(e) => Err(UnsentRows::new(vec![row], e)),
+        }
     }
 }
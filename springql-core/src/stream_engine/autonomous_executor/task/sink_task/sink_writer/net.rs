@@ -13,14 +13,20 @@ use crate::{
     api::SpringSinkWriterConfig,
     pipeline::{NetClientOptions, Options},
     stream_engine::autonomous_executor::{
-        row::JsonObject, task::sink_task::sink_writer::SinkWriter, SchemalessRow,
+        row::JsonObject,
+        task::sink_task::sink_writer::{SinkWriter, UnsentRows},
+        SchemalessRow,
     },
 };
 
 #[derive(Debug)]
 pub struct NetSinkWriter {
     foreign_addr: SocketAddr,
-    tcp_stream_writer: BufWriter<TcpStream>, // TODO UDP
+    connect_timeout: Duration,
+    write_timeout: Duration,
+
+    /// `None` after a write failure. Reconnected on the next write.
+    tcp_stream_writer: Option<BufWriter<TcpStream>>, // TODO UDP
 }
 
 impl SinkWriter for NetSinkWriter {
@@ -28,47 +34,62 @@ impl SinkWriter for NetSinkWriter {
         let options = NetClientOptions::try_from(options)?;
         let sock_addr = SocketAddr::new(options.remote_host, options.remote_port);
 
-        let tcp_stream = TcpStream::connect_timeout(
-            &sock_addr,
-            Duration::from_millis(config.net_connect_timeout_msec as u64),
-        )
-        .context("failed to connect to remote host")
-        .map_err(|e| SpringError::ForeignIo {
-            source: e,
-            foreign_info: ForeignInfo::GenericTcp(sock_addr),
-        })?;
-        tcp_stream
-            .set_write_timeout(Some(Duration::from_millis(
-                config.net_write_timeout_msec as u64,
-            )))
-            .context("failed to set timeout to remote host")
-            .map_err(|e| SpringError::ForeignIo {
-                source: e,
-                foreign_info: ForeignInfo::GenericTcp(sock_addr),
-            })?;
-
-        let tcp_stream_writer = BufWriter::new(tcp_stream);
+        let mut sink_writer = Self {
+            foreign_addr: sock_addr,
+            connect_timeout: Duration::from_millis(config.net_connect_timeout_msec as u64),
+            write_timeout: Duration::from_millis(config.net_write_timeout_msec as u64),
+            tcp_stream_writer: None,
+        };
+        sink_writer.connect()?;
 
         log::info!("[NetSinkWriter] Ready to write into {}", sock_addr);
 
-        Ok(Self {
-            tcp_stream_writer,
-            foreign_addr: sock_addr,
-        })
+        Ok(sink_writer)
     }
 
-    fn send_row(&mut self, row: SchemalessRow) -> Result<()> {
-        let mut json_s = JsonObject::from(row).to_string();
+    fn send_row(&mut self, row: SchemalessRow) -> std::result::Result<(), UnsentRows> {
+        let mut json_s = JsonObject::from(row.clone()).to_string();
         json_s.push('\n');
 
         log::debug!("[NetSinkWriter] Writing message to remote: {}", json_s);
         self.write_row(json_s.as_bytes())
+            .map_err(|e| UnsentRows::new(vec![row], e))
     }
 }
 
 impl NetSinkWriter {
+    fn connect(&mut self) -> Result<&mut BufWriter<TcpStream>> {
+        let sock_addr = self.foreign_addr;
+
+        let tcp_stream = TcpStream::connect_timeout(&sock_addr, self.connect_timeout)
+            .context("failed to connect to remote host")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericTcp(sock_addr),
+            })?;
+        tcp_stream
+            .set_write_timeout(Some(self.write_timeout))
+            .context("failed to set timeout to remote host")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::GenericTcp(sock_addr),
+            })?;
+
+        Ok(self.tcp_stream_writer.insert(BufWriter::new(tcp_stream)))
+    }
+
+    /// Connection is dropped on failure so that the next write reconnects to the remote sink.
     fn write_row(&mut self, content: &[u8]) -> Result<()> {
-        self.tcp_stream_writer
+        let foreign_addr = self.foreign_addr;
+        let tcp_stream_writer = match self.tcp_stream_writer.as_mut() {
+            Some(tcp_stream_writer) => tcp_stream_writer,
+            None => {
+                log::info!("[NetSinkWriter] Reconnecting to {}", foreign_addr);
+                self.connect()?
+            }
+        };
+
+        let res = tcp_stream_writer
             .write_all(content)
             .with_context(|| {
                 format!(
@@ -76,19 +97,20 @@ impl NetSinkWriter {
                     String::from_utf8_lossy(content)
                 )
             })
+            .and_then(|_| {
+                tcp_stream_writer
+                    .flush()
+                    .with_context(|| "failed to flush row to remote sink")
+            })
             .map_err(|e| SpringError::ForeignIo {
                 source: e,
-                foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
-            })?;
-        self.tcp_stream_writer
-            .flush()
-            .with_context(|| "failed to flush row to remote sink")
-            .map_err(|e| SpringError::ForeignIo {
-                source: e,
-                foreign_info: ForeignInfo::GenericTcp(self.foreign_addr),
-            })?;
+                foreign_info: ForeignInfo::GenericTcp(foreign_addr),
+            });
 
-        Ok(())
+        if res.is_err() {
+            self.tcp_stream_writer = None;
+        }
+        res
     }
 }
 
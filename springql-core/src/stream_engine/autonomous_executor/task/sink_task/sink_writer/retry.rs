@@ -0,0 +1,477 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use rand::Rng;
+
+use crate::{
+    api::{
+        error::{Result, SpringError},
+        SpringSinkWriterConfig,
+    },
+    mem_size::MemSize,
+    pipeline::{Options, RetryOptions},
+    stream_engine::autonomous_executor::{
+        task::sink_task::sink_writer::{SinkWriter, UnsentRows},
+        SchemalessRow,
+    },
+};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct RetryPolicy {
+    retry_max: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter_percent: u8,
+    buffer_max_rows: usize,
+}
+
+impl RetryPolicy {
+    fn new(options: RetryOptions, config: &SpringSinkWriterConfig) -> Self {
+        let msec = |v: u32| Duration::from_millis(v as u64);
+        Self {
+            retry_max: options.retry_max.unwrap_or(config.retry_max),
+            initial_backoff: msec(
+                options
+                    .retry_initial_backoff_msec
+                    .unwrap_or(config.retry_initial_backoff_msec),
+            ),
+            max_backoff: msec(
+                options
+                    .retry_max_backoff_msec
+                    .unwrap_or(config.retry_max_backoff_msec),
+            ),
+            jitter_percent: options
+                .retry_jitter_percent
+                .unwrap_or(config.retry_jitter_percent)
+                .min(100),
+            buffer_max_rows: options
+                .retry_buffer_max_rows
+                .unwrap_or(config.retry_buffer_max_rows) as usize,
+        }
+    }
+
+    /// Exponential backoff with jitter before the `n_failures`-th retry.
+    fn backoff(&self, n_failures: u32) -> Duration {
+        let exp = 2u32.saturating_pow(n_failures.saturating_sub(1));
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(exp)
+            .min(self.max_backoff);
+
+        let jitter = self.jitter_percent as f64 / 100.0;
+        if jitter > 0.0 {
+            backoff.mul_f64(rand::thread_rng().gen_range((1.0 - jitter)..=(1.0 + jitter)))
+        } else {
+            backoff
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RetryEntry {
+    /// Sent as a unit. Rows waiting behind failed ones are queued one by one with `n_failures == 0`.
+    rows: Vec<SchemalessRow>,
+    bytes: u64,
+    n_failures: u32,
+    next_retry_at: Instant,
+}
+
+/// Decorator of a sink writer to retry failed rows with exponential backoff and jitter.
+///
+/// Failed rows are kept in a bounded retry buffer and re-sent from `poll_retries()`, so that backoff does not block worker threads.
+/// Rows failed together (e.g. a batch of the decorated sink writer) are retried as a unit.
+/// Rows are sent in order: while the buffer is not empty, new rows wait behind the buffered ones.
+#[derive(Debug)]
+pub struct RetrySinkWriter<W: SinkWriter> {
+    inner: W,
+    policy: RetryPolicy,
+    buffer: VecDeque<RetryEntry>,
+    buffer_rows: usize,
+    buffer_bytes: u64,
+}
+
+impl<W: SinkWriter> SinkWriter for RetrySinkWriter<W> {
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - a `RETRY_*` option is invalid.
+    /// - Failures of the decorated sink writer's `start()`.
+    fn start(options: &Options, config: &SpringSinkWriterConfig) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let policy = RetryPolicy::new(RetryOptions::try_from(options)?, config);
+        let inner = W::start(options, config)?;
+        Ok(Self::new(inner, policy))
+    }
+
+    /// # Failure
+    ///
+    /// Failures of the decorated sink writer when retries are disabled or the retry buffer is full.
+    ///
+    /// - `SpringError::Unavailable` when:
+    ///   - `row` cannot be sent immediately and the retry buffer is full.
+    fn send_row(&mut self, row: SchemalessRow) -> std::result::Result<(), UnsentRows> {
+        if self.policy.retry_max == 0 {
+            self.inner.send_row(row)
+        } else if self.buffer.is_empty() {
+            match self.inner.send_row(row) {
+                Ok(()) => Ok(()),
+                Err(unsent) => self.push_failed(unsent),
+            }
+        } else {
+            self.push_queued(row)
+        }
+    }
+
+    fn poll_retries(&mut self) -> Vec<UnsentRows> {
+        let now = Instant::now();
+        let mut given_up = vec![];
+
+        while self
+            .buffer
+            .front()
+            .map_or(false, |entry| entry.next_retry_at <= now)
+        {
+            let entry = self.pop_buffer();
+            let res = if entry.n_failures == 0 {
+                let row = entry.rows.into_iter().next().expect("queued one by one");
+                self.inner.send_row(row)
+            } else {
+                self.inner
+                    .send_rows(entry.rows.clone())
+                    .map_err(|e| UnsentRows::new(entry.rows, e))
+            };
+
+            if let Err(unsent) = res {
+                let n_failures = entry.n_failures + 1;
+                if n_failures > self.policy.retry_max {
+                    given_up.push(unsent);
+                } else {
+                    self.push_front(unsent.rows, n_failures);
+                    break;
+                }
+            }
+        }
+        given_up
+    }
+
+    /// # Failure
+    ///
+    /// Failures of the decorated sink writer when retries are disabled or the retry buffer is full.
+    fn poll_flush(&mut self) -> std::result::Result<(), UnsentRows> {
+        match self.inner.poll_flush() {
+            Err(unsent) if self.policy.retry_max > 0 => self.push_failed(unsent),
+            res => res,
+        }
+    }
+
+    fn retry_buffer_bytes(&self) -> u64 {
+        self.buffer_bytes + self.inner.retry_buffer_bytes()
+    }
+
+    /// Rows in a batch of the decorated sink writer are left since they are flushed soon.
+    fn purge_retry_buffer(&mut self) -> Vec<UnsentRows> {
+        let mut purged = vec![];
+        while !self.buffer.is_empty() {
+            let entry = self.pop_buffer();
+            purged.push(UnsentRows::new(
+                entry.rows,
+                SpringError::Unavailable {
+                    resource: "retry buffer of sink writer".to_string(),
+                    source: anyhow!("purged to free memory"),
+                },
+            ));
+        }
+        purged
+    }
+}
+
+impl<W: SinkWriter> RetrySinkWriter<W> {
+    fn new(inner: W, policy: RetryPolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            buffer: VecDeque::new(),
+            buffer_rows: 0,
+            buffer_bytes: 0,
+        }
+    }
+
+    fn is_buffer_full(&self) -> bool {
+        self.buffer_rows >= self.policy.buffer_max_rows
+    }
+
+    /// Failed rows are older than any buffered row, so they go to the front.
+    /// `unsent` is returned back when the buffer is full.
+    fn push_failed(&mut self, unsent: UnsentRows) -> std::result::Result<(), UnsentRows> {
+        if self.is_buffer_full() {
+            Err(unsent)
+        } else {
+            self.push_front(unsent.rows, 1);
+            Ok(())
+        }
+    }
+
+    fn push_queued(&mut self, row: SchemalessRow) -> std::result::Result<(), UnsentRows> {
+        if self.is_buffer_full() {
+            Err(UnsentRows::new(
+                vec![row],
+                SpringError::Unavailable {
+                    resource: "retry buffer of sink writer".to_string(),
+                    source: anyhow!(
+                        "retry buffer is full ({} rows)",
+                        self.policy.buffer_max_rows
+                    ),
+                },
+            ))
+        } else {
+            let entry = self.new_entry(vec![row], 0);
+            self.buffer.push_back(entry);
+            Ok(())
+        }
+    }
+
+    fn push_front(&mut self, rows: Vec<SchemalessRow>, n_failures: u32) {
+        let entry = self.new_entry(rows, n_failures);
+        self.buffer.push_front(entry);
+    }
+
+    fn new_entry(&mut self, rows: Vec<SchemalessRow>, n_failures: u32) -> RetryEntry {
+        let next_retry_at = if n_failures == 0 {
+            Instant::now()
+        } else {
+            Instant::now() + self.policy.backoff(n_failures)
+        };
+        let bytes = rows.iter().map(|row| row.mem_size() as u64).sum();
+
+        self.buffer_rows += rows.len();
+        self.buffer_bytes += bytes;
+        RetryEntry {
+            rows,
+            bytes,
+            n_failures,
+            next_retry_at,
+        }
+    }
+
+    fn pop_buffer(&mut self) -> RetryEntry {
+        let entry = self.buffer.pop_front().expect("buffer must not be empty");
+        self.buffer_rows -= entry.rows.len();
+        self.buffer_bytes -= entry.bytes;
+        entry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api::error::foreign_info::ForeignInfo, pipeline::QueueName};
+
+    /// Fails the first `n_failures` sends.
+    /// Sends rows in batches of `batch_rows` if it is more than 1.
+    #[derive(Debug, Default)]
+    struct FlakySinkWriter {
+        n_failures: u32,
+        batch_rows: usize,
+        batch: Vec<SchemalessRow>,
+        sent: Vec<SchemalessRow>,
+    }
+
+    impl SinkWriter for FlakySinkWriter {
+        fn start(_options: &Options, _config: &SpringSinkWriterConfig) -> Result<Self> {
+            unreachable!()
+        }
+
+        fn send_row(&mut self, row: SchemalessRow) -> std::result::Result<(), UnsentRows> {
+            self.batch.push(row);
+            if self.batch.len() >= self.batch_rows {
+                let rows = std::mem::take(&mut self.batch);
+                self.send_rows(rows.clone())
+                    .map_err(|e| UnsentRows::new(rows, e))
+            } else {
+                Ok(())
+            }
+        }
+
+        fn send_rows(&mut self, rows: Vec<SchemalessRow>) -> Result<()> {
+            if self.n_failures > 0 {
+                self.n_failures -= 1;
+                Err(SpringError::ForeignIo {
+                    foreign_info: ForeignInfo::InMemoryQueue(QueueName::new("flaky".to_string())),
+                    source: anyhow!("flaky"),
+                })
+            } else {
+                self.sent.extend(rows);
+                Ok(())
+            }
+        }
+
+        fn retry_buffer_bytes(&self) -> u64 {
+            self.batch.iter().map(|row| row.mem_size() as u64).sum()
+        }
+    }
+
+    fn policy(retry_max: u32, buffer_max_rows: usize) -> RetryPolicy {
+        RetryPolicy {
+            retry_max,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            jitter_percent: 0,
+            buffer_max_rows,
+        }
+    }
+
+    #[test]
+    fn test_retry_in_order() {
+        let inner = FlakySinkWriter {
+            n_failures: 2,
+            ..FlakySinkWriter::default()
+        };
+        let mut writer = RetrySinkWriter::new(inner, policy(2, 10));
+
+        writer
+            .send_row(SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+        writer
+            .send_row(SchemalessRow::fx_city_temperature_osaka())
+            .unwrap();
+        assert!(writer.retry_buffer_bytes() > 0);
+        assert!(writer.inner.sent.is_empty());
+
+        assert!(writer.poll_retries().is_empty()); // 2nd failure
+        assert!(writer.poll_retries().is_empty()); // succeeds
+        assert_eq!(
+            writer.inner.sent,
+            vec![
+                SchemalessRow::fx_city_temperature_tokyo(),
+                SchemalessRow::fx_city_temperature_osaka()
+            ]
+        );
+        assert_eq!(writer.retry_buffer_bytes(), 0);
+    }
+
+    #[test]
+    fn test_give_up() {
+        let inner = FlakySinkWriter {
+            n_failures: 3,
+            ..FlakySinkWriter::default()
+        };
+        let mut writer = RetrySinkWriter::new(inner, policy(1, 1));
+
+        writer
+            .send_row(SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+        // buffer is full
+        assert!(matches!(
+            writer.send_row(SchemalessRow::fx_city_temperature_osaka()),
+            Err(UnsentRows {
+                error: SpringError::Unavailable { .. },
+                ..
+            })
+        ));
+
+        let given_up = writer.poll_retries();
+        assert_eq!(given_up.len(), 1);
+        assert_eq!(
+            given_up[0].rows,
+            vec![SchemalessRow::fx_city_temperature_tokyo()]
+        );
+        assert_eq!(writer.retry_buffer_bytes(), 0);
+    }
+
+    #[test]
+    fn test_purge_retry_buffer() {
+        let inner = FlakySinkWriter {
+            n_failures: 1,
+            ..FlakySinkWriter::default()
+        };
+        let mut writer = RetrySinkWriter::new(inner, policy(2, 10));
+
+        writer
+            .send_row(SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+        writer
+            .send_row(SchemalessRow::fx_city_temperature_osaka())
+            .unwrap();
+
+        let purged = writer.purge_retry_buffer();
+        assert_eq!(
+            purged
+                .iter()
+                .flat_map(|unsent| unsent.rows.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                SchemalessRow::fx_city_temperature_tokyo(),
+                SchemalessRow::fx_city_temperature_osaka()
+            ]
+        );
+        assert!(purged
+            .iter()
+            .all(|unsent| matches!(unsent.error, SpringError::Unavailable { .. })));
+        assert_eq!(writer.retry_buffer_bytes(), 0);
+
+        assert!(writer.poll_retries().is_empty());
+        assert!(writer.inner.sent.is_empty());
+    }
+
+    #[test]
+    fn test_retry_batch_as_unit() {
+        let inner = FlakySinkWriter {
+            n_failures: 1,
+            batch_rows: 2,
+            ..FlakySinkWriter::default()
+        };
+        let mut writer = RetrySinkWriter::new(inner, policy(2, 10));
+
+        writer
+            .send_row(SchemalessRow::fx_city_temperature_tokyo())
+            .unwrap();
+        assert!(writer.retry_buffer_bytes() > 0); // in the batch
+        writer
+            .send_row(SchemalessRow::fx_city_temperature_osaka())
+            .unwrap(); // batch fails and is buffered
+        assert_eq!(writer.buffer.len(), 1);
+        assert_eq!(writer.buffer_rows, 2);
+
+        writer
+            .send_row(SchemalessRow::fx_city_temperature_london())
+            .unwrap(); // queued behind the batch
+        assert!(writer.poll_retries().is_empty());
+        assert_eq!(
+            writer.inner.sent,
+            vec![
+                SchemalessRow::fx_city_temperature_tokyo(),
+                SchemalessRow::fx_city_temperature_osaka()
+            ]
+        );
+        assert_eq!(writer.inner.batch.len(), 1);
+        assert_eq!(writer.buffer_rows, 0);
+        assert!(writer.retry_buffer_bytes() > 0);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(1000),
+            ..policy(10, 10)
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(5), Duration::from_millis(1000));
+        assert_eq!(policy.backoff(100), Duration::from_millis(1000));
+
+        let policy = RetryPolicy {
+            jitter_percent: 20,
+            ..policy
+        };
+        let backoff = policy.backoff(1);
+        assert!(Duration::from_millis(80) <= backoff && backoff <= Duration::from_millis(120));
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     pipeline::{Options, SinkWriterType},
     stream_engine::autonomous_executor::task::sink_task::sink_writer::{
         http_client::HttpClientSinkWriter, in_memory_queue::InMemoryQueueSinkWriter,
-        net::NetSinkWriter, SinkWriter,
+        net::NetSinkWriter, retry::RetrySinkWriter, SinkWriter,
     },
 };
 
@@ -19,11 +19,11 @@ impl SinkWriterFactory {
     ) -> Result<Box<dyn SinkWriter>> {
         match sink_writer_type {
             SinkWriterType::Net => {
-                let sink_writer = NetSinkWriter::start(options, config)?;
+                let sink_writer = RetrySinkWriter::<NetSinkWriter>::start(options, config)?;
                 Ok(Box::new(sink_writer) as Box<dyn SinkWriter>)
             }
             SinkWriterType::Http1Client => {
-                let sink_writer = HttpClientSinkWriter::start(options, config)?;
+                let sink_writer = RetrySinkWriter::<HttpClientSinkWriter>::start(options, config)?;
                 Ok(Box::new(sink_writer) as Box<dyn SinkWriter>)
             }
             SinkWriterType::InMemoryQueue => {
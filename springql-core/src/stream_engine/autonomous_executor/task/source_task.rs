@@ -10,17 +10,20 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use crate::{
-    api::error::Result,
+    api::error::{Result, SpringError},
     mem_size::MemSize,
-    pipeline::{SourceReaderModel, SourceReaderName, StreamName},
+    pipeline::{OnSchemaError, SourceReaderModel, SourceReaderName, StreamName},
     stream_engine::{
         autonomous_executor::{
             performance_metrics::{
                 MetricsUpdateByTaskExecution, OutQueueMetricsUpdateByTask, TaskMetricsUpdateByTask,
             },
             repositories::Repositories,
-            row::{SchemalessRow, StreamRow},
-            task::{task_context::TaskContext, ProcessedRows, TaskRunResult},
+            row::{JsonObject, SchemalessRow, StreamRow},
+            task::{
+                dead_letter::DeadLetter, task_context::TaskContext, tuple::Tuple, ProcessedRows,
+                TaskRunResult,
+            },
             task_graph::{QueueId, RowQueueId, TaskId, WindowQueueId},
             AutonomousExecutor,
         },
@@ -33,6 +36,7 @@ pub struct SourceTask {
     id: TaskId,
     source_reader_name: SourceReaderName,
     source_stream_name: StreamName,
+    on_schema_error: OnSchemaError,
 }
 
 impl SourceTask {
@@ -42,6 +46,8 @@ impl SourceTask {
             id,
             source_reader_name: source_reader.name().clone(),
             source_stream_name: source_reader.dest_source_stream().clone(),
+            on_schema_error: OnSchemaError::try_from(source_reader.options())
+                .expect("ON_SCHEMA_ERROR is validated on CREATE SOURCE READER"),
         }
     }
 
@@ -52,21 +58,33 @@ impl SourceTask {
     pub fn run(&self, context: &TaskContext) -> Result<TaskRunResult> {
         let stopwatch = WallClockStopwatch::start();
 
-        let (processed_rows, out_queue_metrics_seq) = match self.collect_next(context) {
+        let (row, rows_rejected, mut out_queue_metrics_seq) = self.collect_next(context);
+        let processed_rows = match row {
+            Some(row) if self.is_table_dest(context) => {
+                match self.upsert_into_table(row, context) {
+                    Ok(()) => ProcessedRows::new(1),
+                    Err(dead_letter_queues_metrics) => {
+                        out_queue_metrics_seq.extend(dead_letter_queues_metrics);
+                        ProcessedRows::default()
+                    }
+                }
+            }
             Some(row) => {
-                let out_queue_metrics_seq = context
-                    .output_queues()
-                    .into_iter()
-                    .map(|out_qid| self.put_row_into(out_qid, row.clone(), context)) // remove None metrics
-                    .collect::<Vec<OutQueueMetricsUpdateByTask>>();
-                (ProcessedRows::new(1), out_queue_metrics_seq)
+                out_queue_metrics_seq.extend(
+                    context
+                        .output_queues()
+                        .into_iter()
+                        .map(|out_qid| self.put_row_into(out_qid, row.clone(), context)),
+                );
+                ProcessedRows::new(1)
             }
-            None => (ProcessedRows::default(), vec![]),
+            None => ProcessedRows::default(),
         };
 
         let execution_time = stopwatch.stop();
 
-        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time);
+        let task_metrics =
+            TaskMetricsUpdateByTask::new(context.task(), execution_time, 0, rows_rejected, 0);
         let metrics =
             MetricsUpdateByTaskExecution::new(task_metrics, vec![], out_queue_metrics_seq);
         Ok(TaskRunResult {
@@ -75,6 +93,36 @@ impl SourceTask {
         })
     }
 
+    fn is_table_dest(&self, context: &TaskContext) -> bool {
+        context
+            .pipeline_derivatives()
+            .pipeline()
+            .get_table(&self.source_stream_name)
+            .is_some()
+    }
+
+    /// A row failed to be upserted (e.g. the table is full) is routed to the dead letter stream.
+    ///
+    /// # Returns
+    ///
+    /// Err with metrics of the dead-letter queues if the row is not upserted.
+    fn upsert_into_table(
+        &self,
+        row: StreamRow,
+        context: &TaskContext,
+    ) -> std::result::Result<(), Vec<OutQueueMetricsUpdateByTask>> {
+        let table = context
+            .repos()
+            .table_repository()
+            .get(&self.source_stream_name);
+
+        let tuple = Tuple::from_row(row);
+        let raw_row = DeadLetter::is_routed(context).then(|| tuple.to_json().to_string());
+        table
+            .upsert(tuple)
+            .map_err(|e| DeadLetter::new(e, raw_row).route(context))
+    }
+
     fn put_row_into(
         &self,
         queue_id: QueueId,
@@ -115,7 +163,13 @@ impl SourceTask {
         OutQueueMetricsUpdateByTask::new(queue_id.into(), 1, bytes_put as u64)
     }
 
-    fn collect_next(&self, context: &TaskContext) -> Option<StreamRow> {
+    /// # Returns
+    ///
+    /// The next row (if any), the number of rows rejected by the source stream definition, and metrics of the dead-letter queues.
+    fn collect_next(
+        &self,
+        context: &TaskContext,
+    ) -> (Option<StreamRow>, u64, Vec<OutQueueMetricsUpdateByTask>) {
         let source_reader = context
             .repos()
             .source_reader_repository()
@@ -130,18 +184,52 @@ impl SourceTask {
         let mut source_reader = source_reader
             .lock()
             .expect("other worker threads sharing the same subtask must not get panic");
-        source_reader
-            .next_row()
-            .and_then(|source_row| {
-                let schemaless_row = SchemalessRow::try_from(source_row)?;
-                StreamRow::from_schemaless_row(schemaless_row, source_stream)
-            })
-            .map_or_else(
-                |e| {
-                    AutonomousExecutor::handle_error(e);
-                    None
-                },
-                Some,
-            )
+        let source_row = match source_reader.next_row() {
+            Ok(source_row) => source_row,
+            Err(SpringError::InvalidFormat { s, source }) => {
+                // foreign input which cannot be parsed into a row
+                let raw_row = Some(s.clone());
+                let dead_letter_queues_metrics =
+                    DeadLetter::new(SpringError::InvalidFormat { s, source }, raw_row)
+                        .route(context);
+                return (None, 0, dead_letter_queues_metrics);
+            }
+            Err(e) => {
+                AutonomousExecutor::handle_error(e);
+                return (None, 0, vec![]);
+            }
+        };
+
+        let schemaless_row = match SchemalessRow::try_from(source_row) {
+            Ok(schemaless_row) => schemaless_row,
+            Err(e) => return self.reject(e, None, context),
+        };
+        let raw_row = DeadLetter::is_routed(context)
+            .then(|| JsonObject::from(schemaless_row.clone()).to_string());
+
+        match StreamRow::from_schemaless_row(schemaless_row, source_stream, self.on_schema_error) {
+            Ok(row) => (Some(row), 0, vec![]),
+            Err(e) => self.reject(e, raw_row, context),
+        }
+    }
+
+    /// Handles a row rejected by the source stream definition.
+    ///
+    /// With `ON_SCHEMA_ERROR 'FAIL'`, a fatal error is raised to stop the pipeline.
+    fn reject(
+        &self,
+        e: SpringError,
+        raw_row: Option<String>,
+        context: &TaskContext,
+    ) -> (Option<StreamRow>, u64, Vec<OutQueueMetricsUpdateByTask>) {
+        if self.on_schema_error == OnSchemaError::Fail {
+            log::error!(
+                "[SourceTask] Stop the pipeline because of ON_SCHEMA_ERROR 'FAIL' of {}",
+                self.source_reader_name
+            );
+            context.repos().fatal_error().raise(&e);
+        }
+        let dead_letter_queues_metrics = DeadLetter::new(e, raw_row).route(context);
+        (None, 1, dead_letter_queues_metrics)
     }
 }
@@ -1,6 +1,8 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 
+mod file;
+mod http1_server;
 mod in_memory_queue;
 mod net_client;
 mod net_server;
@@ -0,0 +1,320 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{collections::VecDeque, fs, path::PathBuf, sync::Arc};
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{FileFormat, FileOptions, Options, SqlType, StreamModel},
+    stream_engine::autonomous_executor::{
+        row::{JsonObject, JsonSourceRow, SourceRow},
+        task::source_task::source_reader::SourceReader,
+    },
+};
+
+/// Reads rows from a static JSON or CSV file.
+///
+/// The whole file is read on start. After all the rows are provided, the reader behaves like an empty source.
+#[derive(Debug)]
+pub struct FileSourceReader {
+    path: PathBuf,
+    rows: VecDeque<JsonObject>,
+}
+
+impl SourceReader for FileSourceReader {
+    /// CSV values other than empty ones are provided as strings.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to read the file.
+    /// - `SpringError::InvalidFormat` when:
+    ///   - the file content is not in `FORMAT`.
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        Self::start_with_stream(options, config, None)
+    }
+
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignSourceTimeout` when:
+    ///   - all the rows in the file have been provided (does not wait a bit)
+    fn next_row(&mut self) -> Result<SourceRow> {
+        self.rows
+            .pop_front()
+            .map(|json_obj| SourceRow::Json(JsonSourceRow::from_json(json_obj)))
+            .ok_or_else(|| SpringError::ForeignSourceTimeout {
+                source: anyhow!("all rows in the file have been read"),
+                foreign_info: ForeignInfo::File(self.path.clone()),
+            })
+    }
+}
+
+impl FileSourceReader {
+    /// Reads the file typing CSV values by the column types of `source_stream`.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to read the file.
+    /// - `SpringError::InvalidFormat` when:
+    ///   - the file content is not in `FORMAT`.
+    /// - `SpringError::InvalidOption`
+    pub fn start_with_stream(
+        options: &Options,
+        _config: &SpringSourceReaderConfig,
+        source_stream: Option<Arc<StreamModel>>,
+    ) -> Result<Self> {
+        let options = FileOptions::try_from(options)?;
+
+        let content = fs::read_to_string(&options.path)
+            .context("failed to read file")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::File(options.path.clone()),
+            })?;
+
+        let rows = match options.format {
+            FileFormat::Json => Self::parse_json(&content)?,
+            FileFormat::Csv => Self::parse_csv(&content, source_stream.as_deref())?,
+        };
+        let rows = rows
+            .into_iter()
+            .map(|json_obj| json_obj.apply_json_paths(&options.json_paths))
+            .collect::<VecDeque<_>>();
+
+        log::info!(
+            "[FileSourceReader] Read {} rows from {}",
+            rows.len(),
+            options.path.display()
+        );
+
+        Ok(Self {
+            path: options.path,
+            rows,
+        })
+    }
+
+    /// A JSON array of objects, or a JSON object per line.
+    fn parse_json(content: &str) -> Result<Vec<JsonObject>> {
+        if content.trim_start().starts_with('[') {
+            let values: Vec<serde_json::Value> = serde_json::from_str(content)
+                .context("failed to parse file as JSON array")
+                .map_err(|e| SpringError::InvalidFormat {
+                    s: content.to_string(),
+                    source: e,
+                })?;
+            Ok(values.into_iter().map(JsonObject::new).collect())
+        } else {
+            content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(JsonObject::parse)
+                .collect()
+        }
+    }
+
+    /// The first record is the header of column names.
+    fn parse_csv(content: &str, source_stream: Option<&StreamModel>) -> Result<Vec<JsonObject>> {
+        let mut records = csv_records(content)?.into_iter();
+
+        let header = match records.next() {
+            Some(header) => header
+                .into_iter()
+                .map(|name| name.unwrap_or_default().trim().to_string())
+                .collect::<Vec<_>>(),
+            None => return Ok(vec![]),
+        };
+        let sql_types = header
+            .iter()
+            .map(|name| {
+                source_stream.and_then(|stream| {
+                    stream
+                        .shape()
+                        .columns()
+                        .iter()
+                        .find(|cd| cd.column_data_type().column_name().as_ref() == name.as_str())
+                        .map(|cd| cd.column_data_type().sql_type().clone())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        records
+            .enumerate()
+            .map(|(i, record)| {
+                if record.len() != header.len() {
+                    return Err(SpringError::InvalidFormat {
+                        s: format!("{:?}", record),
+                        source: anyhow!(
+                            "record {} has {} fields while header has {}",
+                            i + 1,
+                            record.len(),
+                            header.len()
+                        ),
+                    });
+                }
+                let obj = header
+                    .iter()
+                    .zip(sql_types.iter())
+                    .zip(record)
+                    .map(|((name, sql_type), field)| {
+                        (name.clone(), csv_json_value(field, sql_type.as_ref()))
+                    })
+                    .collect::<serde_json::Map<_, _>>();
+                Ok(JsonObject::new(serde_json::Value::Object(obj)))
+            })
+            .collect()
+    }
+}
+
+/// Splits CSV `content` into records.
+///
+/// Fields may be quoted with `"` (`""` for a `"` in a quoted field). An empty unquoted field is `None`.
+fn csv_records(content: &str) -> Result<Vec<Vec<Option<String>>>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    let _ = chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+        } else {
+            match c {
+                '"' => {
+                    in_quotes = true;
+                    quoted = true;
+                }
+                ',' => record.push(csv_field(&mut field, &mut quoted)),
+                '\r' => {}
+                '\n' => {
+                    record.push(csv_field(&mut field, &mut quoted));
+                    if !(record.len() == 1 && record[0].is_none()) {
+                        records.push(record);
+                    }
+                    record = vec![];
+                }
+                _ => field.push(c),
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(SpringError::InvalidFormat {
+            s: content.to_string(),
+            source: anyhow!("unterminated quoted field in CSV"),
+        });
+    }
+    if !field.is_empty() || quoted || !record.is_empty() {
+        record.push(csv_field(&mut field, &mut quoted));
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn csv_field(field: &mut String, quoted: &mut bool) -> Option<String> {
+    let value = (!field.is_empty() || *quoted).then(|| field.clone());
+    field.clear();
+    *quoted = false;
+    value
+}
+
+/// Numbers and booleans are typed when `sql_type` says so and the field is parsable. Other fields are strings.
+fn csv_json_value(field: Option<String>, sql_type: Option<&SqlType>) -> serde_json::Value {
+    let field = match field {
+        Some(field) => field,
+        None => return serde_json::Value::Null,
+    };
+
+    match sql_type {
+        Some(SqlType::NumericComparable(_)) => {
+            let s = field.trim();
+            s.parse::<i64>()
+                .map(serde_json::Value::from)
+                .or_else(|_| s.parse::<u64>().map(serde_json::Value::from))
+                .or_else(|_| s.parse::<f64>().map(serde_json::Value::from))
+                .unwrap_or(serde_json::Value::String(field))
+        }
+        Some(SqlType::BooleanComparable) => match field.trim().to_ascii_lowercase().as_str() {
+            "true" => serde_json::Value::Bool(true),
+            "false" => serde_json::Value::Bool(false),
+            _ => serde_json::Value::String(field),
+        },
+        _ => serde_json::Value::String(field),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_records() {
+        let content = "a,b,c\n1,,\"x, \"\"y\"\"\"\r\n\n2,\"\",\"multi\nline\"";
+        assert_eq!(
+            csv_records(content).unwrap(),
+            vec![
+                vec![Some("a".into()), Some("b".into()), Some("c".into())],
+                vec![Some("1".into()), None, Some("x, \"y\"".into())],
+                vec![
+                    Some("2".into()),
+                    Some("".into()),
+                    Some("multi\nline".into())
+                ],
+            ]
+        );
+
+        assert!(csv_records("a\n\"unterminated").is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_typed() {
+        let stream = StreamModel::fx_city_temperature();
+        let content = "ts,city,temperature\n2020-01-01 00:00:00.000000000,Tokyo,21\n";
+
+        let rows = FileSourceReader::parse_csv(content, Some(&stream)).unwrap();
+        assert_eq!(
+            rows,
+            vec![JsonObject::new(serde_json::json!({
+                "ts": "2020-01-01 00:00:00.000000000",
+                "city": "Tokyo",
+                "temperature": 21
+            }))]
+        );
+
+        let rows = FileSourceReader::parse_csv(content, None).unwrap();
+        assert_eq!(
+            rows,
+            vec![JsonObject::new(serde_json::json!({
+                "ts": "2020-01-01 00:00:00.000000000",
+                "city": "Tokyo",
+                "temperature": "21"
+            }))]
+        );
+
+        assert!(FileSourceReader::parse_csv("a,b\n1\n", None).is_err());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let array = r#"[{"n": 1}, {"n": 2}]"#;
+        let lines = "{\"n\": 1}\n\n{\"n\": 2}\n";
+        let expected = vec![
+            JsonObject::new(serde_json::json!({"n": 1})),
+            JsonObject::new(serde_json::json!({"n": 2})),
+        ];
+        assert_eq!(FileSourceReader::parse_json(array).unwrap(), expected);
+        assert_eq!(FileSourceReader::parse_json(lines).unwrap(), expected);
+    }
+}
@@ -0,0 +1,652 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Read, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex, MutexGuard,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use anyhow::Context;
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    api::SpringSourceReaderConfig,
+    pipeline::{Http1ServerOptions, Options, StreamModel},
+    stream_engine::autonomous_executor::{
+        row::{JsonObject, JsonSourceRow, SchemalessRow, SourceRow, StreamRow},
+        task::{dead_letter::error_message, source_task::source_reader::SourceReader},
+    },
+};
+
+/// Accepts rows via HTTP/1.1 `POST` requests.
+///
+/// A request body is a JSON object, a JSON array of objects, or objects in NDJSON.
+/// Rows in a request are validated against the source stream all together:
+///
+/// - `202 Accepted`: all rows are accepted.
+/// - `400 Bad Request`: the body is not in the formats above.
+/// - `422 Unprocessable Entity`: any of the rows does not match the source stream (and `ON_SCHEMA_ERROR` option). No rows are accepted.
+///
+/// Connections are served by a fixed number of worker threads.
+/// Connections over [N_WORKERS] + [CONNECTION_BACKLOG] are refused with `503 Service Unavailable`.
+#[derive(Debug)]
+pub struct Http1ServerSourceReader {
+    my_addr: SocketAddr,
+
+    rx: Mutex<mpsc::Receiver<JsonObject>>,
+
+    timeout: Duration,
+
+    stopped: Arc<AtomicBool>,
+    acceptor: Option<JoinHandle<()>>,
+}
+
+/// Number of connections served at the same time.
+const N_WORKERS: usize = 4;
+
+/// Number of accepted connections waiting for a worker.
+const CONNECTION_BACKLOG: usize = 4;
+
+/// Interval to check if the reader is stopped while no connection comes.
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Connections without any request for this duration are closed.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Max duration and bytes to discard the rest of the request before closing the connection.
+const LINGER_TIMEOUT: Duration = Duration::from_secs(1);
+const MAX_LINGER_BYTES: u64 = 1024 * 1024;
+
+/// Max bytes of the request line and headers in a request. Requests with larger headers are responded with `431 Request Header Fields Too Large`.
+const MAX_HEADER_BYTES: u64 = 8 * 1024;
+
+impl SourceReader for Http1ServerSourceReader {
+    /// Rows are not validated against the source stream.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo`
+    /// - `SpringError::InvalidOption`
+    fn start(options: &Options, config: &SpringSourceReaderConfig) -> Result<Self> {
+        Self::start_with_stream(options, config, None)
+    }
+
+    fn next_row(&mut self) -> Result<SourceRow> {
+        let rx = self.rx();
+
+        rx.try_recv()
+            .or_else(|_| {
+                thread::sleep(self.timeout);
+                rx.try_recv()
+            })
+            .map(|json_obj| SourceRow::Json(JsonSourceRow::from_json(json_obj)))
+            .map_err(|e| SpringError::ForeignSourceTimeout {
+                source: anyhow::Error::from(e),
+                foreign_info: ForeignInfo::Http(self.my_addr),
+            })
+    }
+}
+
+impl Http1ServerSourceReader {
+    /// Starts the server validating request rows against `source_stream`.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::ForeignIo` when:
+    ///   - failed to listen on `HOST` and `PORT`.
+    /// - `SpringError::InvalidOption`
+    pub fn start_with_stream(
+        options: &Options,
+        config: &SpringSourceReaderConfig,
+        source_stream: Option<Arc<StreamModel>>,
+    ) -> Result<Self> {
+        let options = Http1ServerOptions::try_from(options)?;
+        let addr = SocketAddr::new(options.host, options.port);
+
+        let listener = TcpListener::bind(addr)
+            .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+            .context("failed to listen")
+            .map_err(|e| SpringError::ForeignIo {
+                source: e,
+                foreign_info: ForeignInfo::Http(addr),
+            })?;
+        let my_addr = listener.local_addr().unwrap();
+
+        let (tx, rx) = mpsc::channel();
+
+        let timeout = Duration::from_millis(config.net_read_timeout_msec as u64);
+        let handler = RequestHandler {
+            options,
+            source_stream,
+            max_body_bytes: config.http_max_body_bytes as usize,
+            tx,
+        };
+
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let (conn_tx, conn_rx) = mpsc::sync_channel::<TcpStream>(CONNECTION_BACKLOG);
+        let conn_rx = Arc::new(Mutex::new(conn_rx));
+        for _ in 0..N_WORKERS {
+            let (handler, conn_rx, stopped) = (handler.clone(), conn_rx.clone(), stopped.clone());
+            let _ = thread::Builder::new()
+                .name("Http1ServerSourceReader-worker".into())
+                .spawn(move || loop {
+                    let stream = conn_rx.lock().expect("failed to lock mutex").recv();
+                    match stream {
+                        Ok(stream) => handler.serve(stream, &stopped),
+                        Err(_) => break, // acceptor stopped
+                    }
+                });
+        }
+
+        let acceptor = {
+            let stopped = stopped.clone();
+            thread::Builder::new()
+                .name("Http1ServerSourceReader".into())
+                .spawn(move || Self::accept_loop(listener, conn_tx, &stopped))
+                .context("failed to spawn acceptor thread")
+                .map_err(|e| SpringError::ForeignIo {
+                    source: e,
+                    foreign_info: ForeignInfo::Http(my_addr),
+                })?
+        };
+
+        log::info!(
+            "[Http1ServerSourceReader] Ready to accept rows at {}",
+            my_addr
+        );
+
+        Ok(Self {
+            my_addr,
+            rx: Mutex::new(rx),
+            timeout,
+            stopped,
+            acceptor: Some(acceptor),
+        })
+    }
+
+    /// Passes accepted connections to workers until `stopped`.
+    ///
+    /// The listener is closed on return, and workers stop after their current connections.
+    fn accept_loop(
+        listener: TcpListener,
+        conn_tx: mpsc::SyncSender<TcpStream>,
+        stopped: &AtomicBool,
+    ) {
+        while !stopped.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    if let Err(e) = stream.set_nonblocking(false) {
+                        log::warn!("[Http1ServerSourceReader] {:?}", e);
+                        continue;
+                    }
+                    if let Err(mpsc::TrySendError::Full(mut stream)) = conn_tx.try_send(stream) {
+                        let response = Response::error(
+                            503,
+                            "Service Unavailable",
+                            "too many connections".to_string(),
+                        );
+                        let _ = RequestHandler::write_response(&mut stream, &response);
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => thread::sleep(ACCEPT_POLL_INTERVAL),
+                Err(e) => log::warn!("[Http1ServerSourceReader] {:?}", e),
+            }
+        }
+        log::info!(
+            "[Http1ServerSourceReader] Stopped listening at {:?}",
+            listener.local_addr()
+        );
+    }
+
+    fn rx(&self) -> MutexGuard<'_, mpsc::Receiver<JsonObject>> {
+        self.rx.lock().expect("failed to lock mutex")
+    }
+}
+
+impl Drop for Http1ServerSourceReader {
+    /// Stops listening.
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        if let Some(acceptor) = self.acceptor.take() {
+            let _ = acceptor.join();
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct RequestHandler {
+    options: Http1ServerOptions,
+    source_stream: Option<Arc<StreamModel>>,
+    max_body_bytes: usize,
+    tx: mpsc::Sender<JsonObject>,
+}
+
+#[derive(Eq, PartialEq, Debug)]
+struct Response {
+    status: u16,
+    reason: &'static str,
+    body: serde_json::Value,
+}
+
+impl Response {
+    fn error(status: u16, reason: &'static str, message: String) -> Self {
+        Self {
+            status,
+            reason,
+            body: serde_json::json!({ "error": message }),
+        }
+    }
+
+    fn header_too_large() -> Self {
+        Self::error(
+            431,
+            "Request Header Fields Too Large",
+            format!(
+                "request line and headers must be up to {} bytes",
+                MAX_HEADER_BYTES
+            ),
+        )
+    }
+}
+
+enum HeaderLine {
+    Line(String),
+    Eof,
+    TooLarge,
+}
+
+impl RequestHandler {
+    /// Serves requests in a (keep-alive) connection until it gets idle or the reader is `stopped`.
+    fn serve(&self, stream: TcpStream, stopped: &AtomicBool) {
+        let peer = stream.peer_addr().ok();
+        log::info!("[Http1ServerSourceReader] Connection from {:?}", peer);
+
+        let mut writer = match stream
+            .set_read_timeout(Some(IDLE_TIMEOUT))
+            .and_then(|_| stream.try_clone())
+        {
+            Ok(writer) => writer,
+            Err(e) => {
+                log::warn!("[Http1ServerSourceReader] {:?}", e);
+                return;
+            }
+        };
+        let mut reader = BufReader::new(stream);
+
+        loop {
+            match self.serve_request(&mut reader) {
+                Ok(Some((response, keep_alive))) => {
+                    if let Err(e) = Self::write_response(&mut writer, &response) {
+                        log::warn!("[Http1ServerSourceReader] failed to respond: {:?}", e);
+                        return;
+                    }
+                    if !keep_alive || stopped.load(Ordering::Relaxed) {
+                        Self::close(&writer, reader);
+                        return;
+                    }
+                }
+                Ok(None) => return, // EOF
+                Err(e)
+                    if e.downcast_ref::<std::io::Error>().map_or(false, |e| {
+                        matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+                    }) =>
+                {
+                    log::debug!(
+                        "[Http1ServerSourceReader] idle connection from {:?} closed",
+                        peer
+                    );
+                    return;
+                }
+                Err(e) => {
+                    log::warn!(
+                        "[Http1ServerSourceReader] connection from {:?}: {:?}",
+                        peer,
+                        e
+                    );
+                    return;
+                }
+            }
+        }
+    }
+
+    /// # Returns
+    ///
+    /// `None` on EOF. Otherwise, the response and whether the connection is kept alive.
+    fn serve_request(
+        &self,
+        reader: &mut BufReader<TcpStream>,
+    ) -> anyhow::Result<Option<(Response, bool)>> {
+        let mut header_bytes_left = MAX_HEADER_BYTES;
+        let request_line = match Self::read_header_line(reader, &mut header_bytes_left)? {
+            HeaderLine::Line(line) => line,
+            HeaderLine::Eof => return Ok(None),
+            HeaderLine::TooLarge => return Ok(Some((Response::header_too_large(), false))),
+        };
+        let method = request_line
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        let mut content_length = 0;
+        let mut keep_alive = true;
+        loop {
+            let line = match Self::read_header_line(reader, &mut header_bytes_left)? {
+                HeaderLine::Line(line) => line,
+                HeaderLine::Eof => return Ok(None),
+                HeaderLine::TooLarge => return Ok(Some((Response::header_too_large(), false))),
+            };
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                let (name, value) = (name.trim().to_ascii_lowercase(), value.trim());
+                match name.as_str() {
+                    "content-length" => {
+                        content_length = value.parse().context("invalid Content-Length")?
+                    }
+                    "transfer-encoding" => {
+                        let response = Response::error(
+                            411,
+                            "Length Required",
+                            "Transfer-Encoding is not supported".to_string(),
+                        );
+                        return Ok(Some((response, false)));
+                    }
+                    "connection" => keep_alive = !value.eq_ignore_ascii_case("close"),
+                    _ => {}
+                }
+            }
+        }
+
+        if content_length > self.max_body_bytes {
+            let response = Response::error(
+                413,
+                "Payload Too Large",
+                format!("body must be up to {} bytes", self.max_body_bytes),
+            );
+            return Ok(Some((response, false)));
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+
+        let response = if method == "POST" {
+            self.accept(&body)
+        } else {
+            Response::error(
+                405,
+                "Method Not Allowed",
+                "only POST is allowed".to_string(),
+            )
+        };
+        Ok(Some((response, keep_alive)))
+    }
+
+    /// Closes the connection after discarding the rest of the request (up to [LINGER_TIMEOUT]),
+    /// so that the client can read the response before the connection gets reset.
+    fn close(writer: &TcpStream, reader: BufReader<TcpStream>) {
+        let _ = writer.shutdown(Shutdown::Write);
+        let _ = writer.set_read_timeout(Some(LINGER_TIMEOUT));
+        let _ = std::io::copy(&mut reader.take(MAX_LINGER_BYTES), &mut std::io::sink());
+    }
+
+    /// Reads a line of the request line or headers, up to `bytes_left` bytes in total.
+    fn read_header_line(
+        reader: &mut BufReader<TcpStream>,
+        bytes_left: &mut u64,
+    ) -> std::io::Result<HeaderLine> {
+        let mut line = String::new();
+        let n = reader.by_ref().take(*bytes_left).read_line(&mut line)?;
+        *bytes_left -= n as u64;
+
+        if n == 0 && *bytes_left > 0 {
+            Ok(HeaderLine::Eof)
+        } else if !line.ends_with('\n') && *bytes_left == 0 {
+            Ok(HeaderLine::TooLarge)
+        } else {
+            Ok(HeaderLine::Line(line))
+        }
+    }
+
+    fn write_response(writer: &mut TcpStream, response: &Response) -> std::io::Result<()> {
+        let body = response.body.to_string();
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            response.status,
+            response.reason,
+            body.len(),
+            body
+        )?;
+        writer.flush()
+    }
+
+    /// Validates rows in `body` and passes them to the source task if all of them are valid.
+    fn accept(&self, body: &[u8]) -> Response {
+        match self.parse_rows(body) {
+            Ok(rows) => {
+                let n_rows = rows.len();
+                for row in rows {
+                    let _ = self.tx.send(row);
+                }
+                Response {
+                    status: 202,
+                    reason: "Accepted",
+                    body: serde_json::json!({ "accepted": n_rows }),
+                }
+            }
+            Err(response) => response,
+        }
+    }
+
+    fn parse_rows(&self, body: &[u8]) -> std::result::Result<Vec<JsonObject>, Response> {
+        let bad_request = |message: String| Response::error(400, "Bad Request", message);
+
+        let body = std::str::from_utf8(body).map_err(|e| bad_request(e.to_string()))?;
+        let values = match serde_json::from_str::<serde_json::Value>(body) {
+            Ok(serde_json::Value::Array(values)) => values,
+            Ok(value) => vec![value],
+            Err(_) => body
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .enumerate()
+                .map(|(i, line)| {
+                    serde_json::from_str(line)
+                        .map_err(|e| bad_request(format!("line {}: {}", i + 1, e)))
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()?,
+        };
+
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                if !value.is_object() {
+                    return Err(bad_request(format!("row {}: not a JSON object", i)));
+                }
+                let json_obj = JsonObject::new(value).apply_json_paths(&self.options.json_paths);
+                self.validate(json_obj.clone()).map_err(|e| {
+                    Response::error(
+                        422,
+                        "Unprocessable Entity",
+                        format!("row {}: {}", i, error_message(&e)),
+                    )
+                })?;
+                Ok(json_obj)
+            })
+            .collect()
+    }
+
+    fn validate(&self, json_obj: JsonObject) -> Result<()> {
+        if let Some(source_stream) = &self.source_stream {
+            let row = SchemalessRow::try_from(SourceRow::Json(JsonSourceRow::from_json(json_obj)))?;
+            StreamRow::from_schemaless_row(
+                row,
+                source_stream.clone(),
+                self.options.on_schema_error,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pipeline::{
+            test_support::fixture::default_source_reader_config, OptionsBuilder, StreamModel,
+        },
+        stream_engine::autonomous_executor::row::JsonSourceRow,
+    };
+
+    fn ephemeral_port() -> u16 {
+        let addr = TcpListener::bind("127.0.0.1:0").unwrap();
+        addr.local_addr().unwrap().port()
+    }
+
+    /// Sends a request and returns the response status.
+    fn request(port: u16, method: &str, body: &str) -> u16 {
+        request_with_header(port, method, "", body)
+    }
+
+    fn request_with_header(port: u16, method: &str, header: &str, body: &str) -> u16 {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(
+            stream,
+            "{} / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n{}Content-Length: {}\r\n\r\n{}",
+            method,
+            header,
+            body.len(),
+            body
+        )
+        .unwrap();
+        status(stream)
+    }
+
+    fn status(mut stream: TcpStream) -> u16 {
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response.split_whitespace().nth(1).unwrap().parse().unwrap()
+    }
+
+    fn reader(port: u16) -> Http1ServerSourceReader {
+        let options = OptionsBuilder::default()
+            .add("HOST", "127.0.0.1")
+            .add("PORT", port.to_string())
+            .build();
+        Http1ServerSourceReader::start_with_stream(
+            &options,
+            &default_source_reader_config(),
+            Some(Arc::new(StreamModel::fx_city_temperature())),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_http1_server() -> crate::api::error::Result<()> {
+        let port = ephemeral_port();
+        let mut reader = reader(port);
+
+        let tokyo = JsonObject::fx_city_temperature_tokyo().to_string();
+        let osaka = JsonObject::fx_city_temperature_osaka().to_string();
+        let london = JsonObject::fx_city_temperature_london().to_string();
+
+        assert_eq!(request(port, "POST", &tokyo), 202);
+        assert_eq!(
+            request(port, "POST", &format!("{}\n{}\n", osaka, london)),
+            202
+        );
+
+        assert_eq!(
+            reader.next_row()?,
+            SourceRow::Json(JsonSourceRow::fx_city_temperature_tokyo())
+        );
+        assert_eq!(
+            reader.next_row()?,
+            SourceRow::Json(JsonSourceRow::fx_city_temperature_osaka())
+        );
+        assert_eq!(
+            reader.next_row()?,
+            SourceRow::Json(JsonSourceRow::fx_city_temperature_london())
+        );
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_http1_server_rejects() -> crate::api::error::Result<()> {
+        let port = ephemeral_port();
+        let mut reader = reader(port);
+
+        let tokyo = JsonObject::fx_city_temperature_tokyo().to_string();
+
+        assert_eq!(request(port, "GET", ""), 405);
+        assert_eq!(request(port, "POST", "not a JSON"), 400);
+        assert_eq!(request(port, "POST", "[1, 2]"), 400);
+        // the whole batch is rejected
+        assert_eq!(
+            request(
+                port,
+                "POST",
+                &format!(r#"[{}, {{"city": "Tokyo"}}]"#, tokyo)
+            ),
+            422
+        );
+
+        assert!(matches!(
+            reader.next_row().unwrap_err(),
+            SpringError::ForeignSourceTimeout { .. }
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_http1_server_header_too_large() {
+        let port = ephemeral_port();
+        let _reader = reader(port);
+
+        let header = format!("X-Padding: {}\r\n", "a".repeat(MAX_HEADER_BYTES as usize));
+        assert_eq!(request_with_header(port, "POST", &header, "{}"), 431);
+    }
+
+    #[test]
+    fn test_http1_server_too_many_connections() {
+        let port = ephemeral_port();
+        let _reader = reader(port);
+
+        // idle connections occupying all the workers and the backlog
+        let _idle_conns = (0..N_WORKERS + CONNECTION_BACKLOG)
+            .map(|_| {
+                let conn = TcpStream::connect(("127.0.0.1", port)).unwrap();
+                thread::sleep(ACCEPT_POLL_INTERVAL * 5);
+                conn
+            })
+            .collect::<Vec<_>>();
+
+        let conn = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        assert_eq!(status(conn), 503);
+    }
+
+    #[test]
+    fn test_http1_server_stops_listening_on_drop() {
+        let port = ephemeral_port();
+        let reader = reader(port);
+        assert_eq!(request(port, "GET", ""), 405);
+
+        drop(reader);
+        assert!(TcpListener::bind(("127.0.0.1", port)).is_ok());
+    }
+}
@@ -11,9 +11,10 @@ use anyhow::Context;
 use crate::{
     api::error::{foreign_info::ForeignInfo, Result, SpringError},
     api::SpringSourceReaderConfig,
-    pipeline::{NetClientOptions, Options},
+    pipeline::{JsonPathOptions, NetClientOptions, Options},
     stream_engine::autonomous_executor::{
-        row::SourceRow, task::source_task::source_reader::SourceReader,
+        row::{JsonObject, JsonSourceRow, SourceRow},
+        task::source_task::source_reader::SourceReader,
     },
 };
 
@@ -21,6 +22,7 @@ use crate::{
 pub struct NetClientSourceReader {
     foreign_addr: SocketAddr,
     tcp_stream_reader: BufReader<TcpStream>, // TODO UDP
+    json_paths: JsonPathOptions,
 }
 
 impl SourceReader for NetClientSourceReader {
@@ -58,6 +60,7 @@ impl SourceReader for NetClientSourceReader {
         Ok(Self {
             tcp_stream_reader,
             foreign_addr: sock_addr,
+            json_paths: options.json_paths,
         })
     }
 
@@ -87,7 +90,8 @@ impl SourceReader for NetClientSourceReader {
 impl NetClientSourceReader {
     // TODO other formats than JSON
     fn parse_resp(&self, json_s: &str) -> Result<SourceRow> {
-        SourceRow::from_json(json_s)
+        let json_obj = JsonObject::parse(json_s)?.apply_json_paths(&self.json_paths);
+        Ok(SourceRow::Json(JsonSourceRow::from_json(json_obj)))
     }
 }
 
@@ -11,7 +11,7 @@ use std::{
 use crate::{
     api::error::{foreign_info::ForeignInfo, Result, SpringError},
     api::SpringSourceReaderConfig,
-    pipeline::{NetProtocol, NetServerOptions, Options},
+    pipeline::{JsonPathOptions, NetProtocol, NetServerOptions, Options},
     stream_engine::autonomous_executor::{
         row::{JsonObject, JsonSourceRow, SourceRow},
         task::source_task::source_reader::SourceReader,
@@ -26,6 +26,7 @@ pub struct NetServerSourceReader {
     rx: Mutex<mpsc::Receiver<serde_json::Value>>,
 
     timeout: Duration,
+    json_paths: JsonPathOptions,
 }
 
 impl SourceReader for NetServerSourceReader {
@@ -66,6 +67,7 @@ impl SourceReader for NetServerSourceReader {
             my_addr,
             rx: Mutex::new(rx),
             timeout,
+            json_paths: options.json_paths,
         })
     }
 
@@ -78,7 +80,7 @@ impl SourceReader for NetServerSourceReader {
                 rx.try_recv()
             })
             .map(|json| {
-                let json_obj = JsonObject::new(json);
+                let json_obj = JsonObject::new(json).apply_json_paths(&self.json_paths);
                 SourceRow::Json(JsonSourceRow::from_json(json_obj))
             })
             .map_err(|e| SpringError::ForeignSourceTimeout {
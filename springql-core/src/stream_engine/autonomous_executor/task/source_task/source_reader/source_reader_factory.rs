@@ -1,9 +1,12 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::sync::Arc;
+
 use crate::{
     api::{error::Result, SpringSourceReaderConfig},
-    pipeline::{Options, SourceReaderType},
+    pipeline::{Options, SourceReaderType, StreamModel},
     stream_engine::autonomous_executor::task::source_task::source_reader::{
+        file::FileSourceReader, http1_server::Http1ServerSourceReader,
         net_client::NetClientSourceReader, net_server::NetServerSourceReader,
         InMemoryQueueSourceReader, SourceReader,
     },
@@ -16,6 +19,7 @@ impl SourceReaderFactory {
         source_reader_type: &SourceReaderType,
         options: &Options,
         config: &SpringSourceReaderConfig,
+        source_stream: Arc<StreamModel>,
     ) -> Result<Box<dyn SourceReader>> {
         match source_reader_type {
             SourceReaderType::NetClient => {
@@ -24,6 +28,14 @@ impl SourceReaderFactory {
             SourceReaderType::NetServer => {
                 Ok(Box::new(NetServerSourceReader::start(options, config)?))
             }
+            SourceReaderType::Http1Server => Ok(Box::new(
+                Http1ServerSourceReader::start_with_stream(options, config, Some(source_stream))?,
+            )),
+            SourceReaderType::File => Ok(Box::new(FileSourceReader::start_with_stream(
+                options,
+                config,
+                Some(source_stream),
+            )?)),
             SourceReaderType::InMemoryQueue => {
                 Ok(Box::new(InMemoryQueueSourceReader::start(options, config)?))
             }
@@ -9,7 +9,7 @@ use parking_lot::RwLock;
 
 use crate::{
     api::{error::Result, SpringSourceReaderConfig},
-    pipeline::{SourceReaderModel, SourceReaderName},
+    pipeline::{SourceReaderModel, SourceReaderName, StreamModel},
     stream_engine::autonomous_executor::task::source_task::source_reader::{
         source_reader_factory::SourceReaderFactory, SourceReader,
     },
@@ -33,11 +33,17 @@ impl SourceReaderRepository {
 
     /// Do nothing if a source reader with the same name already exists.
     ///
+    /// `source_stream` is the destination of `source_reader`.
+    ///
     /// # Failures
     ///
     /// - `SpringError::ForeignIo` when:
     ///   - failed to start subtask.
-    pub fn register(&self, source_reader: &SourceReaderModel) -> Result<()> {
+    pub fn register(
+        &self,
+        source_reader: &SourceReaderModel,
+        source_stream: Arc<StreamModel>,
+    ) -> Result<()> {
         let mut sources = self.sources.write();
 
         if sources.get(source_reader.name()).is_some() {
@@ -47,6 +53,7 @@ impl SourceReaderRepository {
                 source_reader.source_reader_type(),
                 source_reader.options(),
                 &self.config,
+                source_stream,
             )?;
             let subtask = Arc::new(Mutex::new(subtask as Box<dyn SourceReader>));
             let _ = sources.insert(source_reader.name().clone(), subtask);
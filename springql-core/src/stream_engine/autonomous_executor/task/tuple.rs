@@ -5,7 +5,7 @@ use crate::{
     mem_size::MemSize,
     pipeline::{ColumnReference, Field},
     stream_engine::{
-        autonomous_executor::row::{RowTime, StreamRow},
+        autonomous_executor::row::{JsonObject, RowTime, StreamRow},
         NnSqlValue, SqlValue,
     },
 };
@@ -52,6 +52,15 @@ impl Tuple {
         Self { rowtime, fields }
     }
 
+    /// Tuple with NULL for all `colrefs`. Used to pad unjoined tuples in OUTER JOIN.
+    pub fn nulls(rowtime: RowTime, colrefs: &[ColumnReference]) -> Self {
+        let fields = colrefs
+            .iter()
+            .map(|colref| Field::new(colref.clone(), SqlValue::Null))
+            .collect();
+        Self { rowtime, fields }
+    }
+
     pub fn rowtime(&self) -> RowTime {
         self.rowtime
     }
@@ -106,6 +115,37 @@ impl Tuple {
             fields: new_fields,
         }
     }
+
+    /// Fields in a JSON object.
+    ///
+    /// Keys are column names, or `stream.column` if this tuple is joined from multiple streams.
+    pub fn to_json(&self) -> JsonObject {
+        let stream_name = |field: &Field| match field.name() {
+            ColumnReference::Column { stream_name, .. }
+            | ColumnReference::PTime { stream_name } => stream_name.clone(),
+        };
+        let joined = self
+            .fields
+            .iter()
+            .any(|f| stream_name(f) != stream_name(&self.fields[0]));
+
+        let map = self
+            .fields
+            .iter()
+            .map(|field| {
+                let key = match field.name() {
+                    ColumnReference::Column {
+                        stream_name,
+                        column_name,
+                    } if joined => format!("{}.{}", stream_name, column_name),
+                    ColumnReference::Column { column_name, .. } => column_name.to_string(),
+                    ColumnReference::PTime { stream_name } => format!("{}.ptime", stream_name),
+                };
+                (key, serde_json::Value::from(field.sql_value().clone()))
+            })
+            .collect::<serde_json::Map<String, serde_json::Value>>();
+        JsonObject::new(serde_json::Value::from(map))
+    }
 }
 
 #[cfg(test)]
@@ -149,7 +189,7 @@ mod tests {
             TestDatum::new(
                 ValueExpr::factory_eq(ValueExpr::factory_null(), ValueExpr::factory_null()),
                 Tuple::fx_trade_oracle(),
-                SqlValue::factory_bool(false),
+                SqlValue::Null,
             ),
             TestDatum::new(
                 ValueExpr::factory_eq(
@@ -167,6 +207,35 @@ mod tests {
                 Tuple::fx_trade_oracle(),
                 SqlValue::factory_bool(false),
             ),
+            TestDatum::new(
+                ValueExpr::factory_ne(
+                    ValueExpr::factory_integer(123),
+                    ValueExpr::factory_integer(-123),
+                ),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_lt(
+                    ValueExpr::factory_integer(-123),
+                    ValueExpr::factory_integer(123),
+                ),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_lt(
+                    ValueExpr::factory_integer(123),
+                    ValueExpr::factory_integer(123),
+                ),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_lt(ValueExpr::factory_integer(123), ValueExpr::factory_null()),
+                Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
             TestDatum::new(
                 ValueExpr::factory_and(
                     ValueExpr::factory_eq(
@@ -195,12 +264,70 @@ mod tests {
                 Tuple::fx_trade_oracle(),
                 SqlValue::factory_bool(false),
             ),
+            // three-valued logic
+            TestDatum::new(
+                ValueExpr::factory_and(ValueExpr::factory_null(), ValueExpr::factory_bool(false)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_and(ValueExpr::factory_null(), ValueExpr::factory_bool(true)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
+            TestDatum::new(
+                ValueExpr::factory_or(ValueExpr::factory_null(), ValueExpr::factory_bool(true)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_or(ValueExpr::factory_null(), ValueExpr::factory_bool(false)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
+            TestDatum::new(
+                ValueExpr::factory_or(
+                    ValueExpr::factory_bool(false),
+                    ValueExpr::factory_bool(false),
+                ),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_uni_op(UnaryOperator::Not, ValueExpr::factory_bool(true)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_uni_op(UnaryOperator::Not, ValueExpr::factory_null()),
+                Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
+            TestDatum::new(
+                ValueExpr::factory_uni_op(UnaryOperator::IsNull, ValueExpr::factory_null()),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_uni_op(UnaryOperator::IsNotNull, ValueExpr::factory_null()),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_uni_op(UnaryOperator::IsNull, ValueExpr::factory_integer(1)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
         ];
 
         for t in test_data {
             let expr_ph2 = t.in_expr.resolve_colref(&t.tuple).unwrap();
             let sql_value = expr_ph2.eval().unwrap();
-            assert_eq!(sql_value, t.expected_sql_value);
+            match t.expected_sql_value {
+                // NULL != NULL in SQL semantics
+                SqlValue::Null => assert!(matches!(sql_value, SqlValue::Null)),
+                expected_sql_value => assert_eq!(sql_value, expected_sql_value),
+            }
         }
     }
 }
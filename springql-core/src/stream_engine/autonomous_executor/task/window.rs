@@ -8,7 +8,7 @@ mod watermark;
 
 pub use aggregate::{AggrWindow, AggregatedAndGroupingValues};
 pub use join_window::JoinWindow;
-pub use panes::{AggrPane, GroupByValues, JoinDir, JoinPane, Pane, Panes};
+pub use panes::{AggrPane, GroupByValues, JoinDir, JoinPane, JoinedTuple, Pane, Panes};
 
 use crate::{
     api::SpringError,
@@ -22,6 +22,8 @@ use crate::{
 };
 
 type Success<T> = (Vec<T>, WindowInFlowByWindowTask);
+/// Window in-flow is returned even on failure since panes may have been updated.
+pub type Failure = (SpringError, WindowInFlowByWindowTask);
 
 pub trait Window {
     type Pane: Pane;
@@ -40,23 +42,26 @@ pub trait Window {
         expr_resolver: &ExprResolver,
         tuple: Tuple,
         arg: <<Self as Window>::Pane as Pane>::DispatchArg,
-    ) -> Result<Success<<Self::Pane as Pane>::CloseOut>, SpringError> {
+    ) -> Result<Success<<Self::Pane as Pane>::CloseOut>, Failure> {
         let rowtime = tuple.rowtime().as_timestamp();
 
-        if rowtime < self.watermark().as_timestamp() {
+        if rowtime < self.watermark().as_timestamp() && !self.panes().is_count_based() {
             // too late tuple does not have any chance to be dispatched nor to close a pane.
             Ok((Vec::new(), WindowInFlowByWindowTask::zero()))
         } else {
             self.watermark_mut().update(rowtime);
             let wm = *self.watermark();
 
-            let window_in_flow_dispatch = self
-                .panes_mut()
-                .panes_to_dispatch(rowtime)?
-                .map(|pane| pane.dispatch(expr_resolver, &tuple, arg.clone()))
-                .fold(WindowInFlowByWindowTask::zero(), |acc, window_in_flow| {
-                    acc + window_in_flow
-                });
+            let (panes, window_in_flow_merge) =
+                self.panes_mut().panes_to_dispatch(expr_resolver, &tuple)?;
+            let window_in_flow_dispatch =
+                panes
+                    .into_iter()
+                    .try_fold(window_in_flow_merge, |acc, pane| {
+                        pane.dispatch(expr_resolver, &tuple, arg.clone())
+                            .map(|window_in_flow| acc + window_in_flow)
+                            .map_err(|(e, window_in_flow)| (e, acc + window_in_flow))
+                    })?;
 
             let (out, window_in_flow_close) = self
                 .panes_mut()
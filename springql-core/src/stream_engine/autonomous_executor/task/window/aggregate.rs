@@ -48,6 +48,10 @@ impl AggregatedAndGroupingValues {
             .ok_or_else(|| SpringError::Sql(anyhow!("aggregate label not found: {:?}", label)))
     }
 
+    pub fn has_group_by_value(&self, label: &ValueExprLabel) -> bool {
+        self.group_by.contains_key(label)
+    }
+
     /// # Failures
     ///
     /// - `SpringError::Sql` when:
@@ -111,8 +115,8 @@ mod tests {
         expr_resolver::{ExprLabel, ExprResolver},
         expression::{AggrExpr, ValueExpr},
         pipeline::{
-            AggrAlias, AggregateFunctionParameter, AggregateParameter, ColumnName, GroupByLabels,
-            StreamName,
+            AggrAlias, AggrExprParameter, AggregateFunctionParameter, AggregateParameter,
+            ColumnName, GroupByLabels, StreamName,
         },
         sql_processor::SelectFieldSyntax,
         stream_engine::{
@@ -194,6 +198,10 @@ mod tests {
             },
         ];
 
+        // AVG state (f64 mean and u64 count) + GROUP BY value
+        let googl_bytes = 16 + "GOOGL".len() as i64;
+        let orcl_bytes = 16 + "ORCL".len() as i64;
+
         let (expr_resolver, labels) = ExprResolver::new(select_list);
         match &labels[..] {
             &[ExprLabel::Value(group_by_label), ExprLabel::Aggr(aggr_label)] => {
@@ -204,8 +212,10 @@ mod tests {
                         allowed_delay: SpringEventDuration::from_secs(1),
                     },
                     WindowOperationParameter::Aggregate(AggregateParameter {
-                        aggr_func: AggregateFunctionParameter::Avg,
-                        aggr_expr: aggr_label,
+                        aggr_exprs: vec![AggrExprParameter::new(
+                            AggregateFunctionParameter::Avg,
+                            aggr_label,
+                        )],
                         group_by: GroupByLabels::new(vec![group_by_label]),
                     }),
                 );
@@ -224,7 +234,7 @@ mod tests {
                     )
                     .unwrap();
                 assert!(out.is_empty());
-                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+                assert_eq!(window_in_flow.window_gain_bytes_states, 2 * googl_bytes);
                 assert_eq!(window_in_flow.window_gain_bytes_rows, 0);
 
                 // [:55, :05): ("GOOGL", 100), ("ORCL", 100)
@@ -241,7 +251,7 @@ mod tests {
                     )
                     .unwrap();
                 assert!(out.is_empty());
-                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+                assert_eq!(window_in_flow.window_gain_bytes_states, 2 * orcl_bytes);
                 assert_eq!(window_in_flow.window_gain_bytes_rows, 0);
 
                 // [:55, :05): -> "GOOGL" AVG = 100; "ORCL" AVG = 100
@@ -277,7 +287,10 @@ mod tests {
                     "ORCL",
                     100,
                 );
-                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+                assert_eq!(
+                    window_in_flow.window_gain_bytes_states,
+                    orcl_bytes - (googl_bytes + orcl_bytes)
+                );
                 assert_eq!(window_in_flow.window_gain_bytes_rows, 0);
 
                 // [:00, :10): ("GOOGL", 100), ("ORCL", 100), ("ORCL", 400) <-- !!NOT CLOSED YET (within delay)!!
@@ -295,7 +308,7 @@ mod tests {
                     )
                     .unwrap();
                 assert!(out.is_empty());
-                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+                assert_eq!(window_in_flow.window_gain_bytes_states, orcl_bytes);
                 assert_eq!(window_in_flow.window_gain_bytes_rows, 0);
 
                 // too late data to be ignored
@@ -369,7 +382,10 @@ mod tests {
                     "ORCL",
                     200,
                 );
-                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+                assert_eq!(
+                    window_in_flow.window_gain_bytes_states,
+                    -(googl_bytes + orcl_bytes)
+                );
                 assert_eq!(window_in_flow.window_gain_bytes_rows, 0);
 
                 // [:05, :15): -> "ORCL" = 175
@@ -442,6 +458,10 @@ mod tests {
             },
         ];
 
+        // AVG state (f64 mean and u64 count) + GROUP BY value
+        let googl_bytes = 16 + "GOOGL".len() as i64;
+        let orcl_bytes = 16 + "ORCL".len() as i64;
+
         let (expr_resolver, labels) = ExprResolver::new(select_list);
         match &labels[..] {
             &[ExprLabel::Value(group_by_label), ExprLabel::Aggr(aggr_label)] => {
@@ -451,8 +471,10 @@ mod tests {
                         allowed_delay: SpringEventDuration::from_secs(1),
                     },
                     WindowOperationParameter::Aggregate(AggregateParameter {
-                        aggr_func: AggregateFunctionParameter::Avg,
-                        aggr_expr: aggr_label,
+                        aggr_exprs: vec![AggrExprParameter::new(
+                            AggregateFunctionParameter::Avg,
+                            aggr_label,
+                        )],
                         group_by: GroupByLabels::new(vec![group_by_label]),
                     }),
                 );
@@ -470,7 +492,7 @@ mod tests {
                     )
                     .unwrap();
                 assert!(out.is_empty());
-                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+                assert_eq!(window_in_flow.window_gain_bytes_states, googl_bytes);
                 assert_eq!(window_in_flow.window_gain_bytes_rows, 0);
 
                 // [:00, :10): ("GOOGL", 100), ("ORCL", 100)
@@ -486,7 +508,7 @@ mod tests {
                     )
                     .unwrap();
                 assert!(out.is_empty());
-                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+                assert_eq!(window_in_flow.window_gain_bytes_states, orcl_bytes);
                 assert_eq!(window_in_flow.window_gain_bytes_rows, 0);
 
                 // [:00, :10): ("GOOGL", 100), ("ORCL", 100), ("ORCL", 400)
@@ -519,7 +541,7 @@ mod tests {
                     )
                     .unwrap();
                 assert!(out.is_empty());
-                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+                assert_eq!(window_in_flow.window_gain_bytes_states, orcl_bytes);
                 assert_eq!(window_in_flow.window_gain_bytes_rows, 0);
 
                 // too late data to be ignored
@@ -590,7 +612,10 @@ mod tests {
                     "ORCL",
                     200,
                 );
-                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+                assert_eq!(
+                    window_in_flow.window_gain_bytes_states,
+                    -(googl_bytes + orcl_bytes)
+                );
                 assert_eq!(window_in_flow.window_gain_bytes_rows, 0);
 
                 // [:10, :20): -> "ORCL" = 100
@@ -621,4 +646,253 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_session_window_aggregation() {
+        setup_test_logger();
+
+        // SELECT ticker, AVG(amount) AS avg_amount
+        //   FROM trade
+        //   SESSION WINDOW duration_secs(5), duration_secs(10)
+        //   GROUP BY ticker;
+
+        let ticker_expr = ValueExpr::factory_colref(
+            StreamName::fx_trade().as_ref(),
+            ColumnName::fx_ticker().as_ref(),
+        );
+        let avg_amount_expr = AggrExpr {
+            func: AggregateFunctionParameter::Avg,
+            aggregated: ValueExpr::factory_colref(
+                StreamName::fx_trade().as_ref(),
+                ColumnName::fx_amount().as_ref(),
+            ),
+        };
+
+        let select_list = vec![
+            SelectFieldSyntax::ValueExpr {
+                value_expr: ticker_expr,
+                alias: None,
+            },
+            SelectFieldSyntax::AggrExpr {
+                aggr_expr: avg_amount_expr,
+                alias: Some(AggrAlias::new("avg_amount".to_string())),
+            },
+        ];
+
+        // AVG state (f64 mean and u64 count) + GROUP BY value
+        let googl_bytes = 16 + "GOOGL".len() as i64;
+        let orcl_bytes = 16 + "ORCL".len() as i64;
+
+        let (expr_resolver, labels) = ExprResolver::new(select_list);
+        match &labels[..] {
+            &[ExprLabel::Value(group_by_label), ExprLabel::Aggr(aggr_label)] => {
+                let mut window = AggrWindow::new(
+                    WindowParameter::SessionWindow {
+                        gap: SpringEventDuration::from_secs(5),
+                        allowed_delay: SpringEventDuration::from_secs(10),
+                    },
+                    WindowOperationParameter::Aggregate(AggregateParameter {
+                        aggr_exprs: vec![AggrExprParameter::new(
+                            AggregateFunctionParameter::Avg,
+                            aggr_label,
+                        )],
+                        group_by: GroupByLabels::new(vec![group_by_label]),
+                    }),
+                );
+
+                let mut dispatch = |ts: &str, ticker: &str, amount: i16| {
+                    window
+                        .dispatch(
+                            &expr_resolver,
+                            Tuple::factory_trade(
+                                SpringTimestamp::from_str(ts).unwrap(),
+                                ticker,
+                                amount,
+                            ),
+                            (),
+                        )
+                        .unwrap()
+                };
+
+                // "GOOGL" [:00, :05): 100
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:00.000000000", "GOOGL", 100);
+                assert!(out.is_empty());
+                assert_eq!(window_in_flow.window_gain_bytes_states, googl_bytes);
+
+                // "GOOGL" [:00, :05): 100
+                // "ORCL"  [:03, :08): 100
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:03.000000000", "ORCL", 100);
+                assert!(out.is_empty());
+                assert_eq!(window_in_flow.window_gain_bytes_states, orcl_bytes);
+
+                // "GOOGL" [:00, :05): 100
+                // "GOOGL" [:08, :13): 300
+                // "ORCL"  [:03, :08): 100
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:08.000000000", "GOOGL", 300);
+                assert!(out.is_empty());
+                assert_eq!(window_in_flow.window_gain_bytes_states, googl_bytes);
+
+                // sessions overlapping with [:04, :09) are merged
+                //
+                // "GOOGL" [:00, :13): 100, 200, 300
+                // "ORCL"  [:03, :08): 100
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:04.000000000", "GOOGL", 200);
+                assert!(out.is_empty());
+                assert_eq!(window_in_flow.window_gain_bytes_states, -googl_bytes);
+
+                // watermark = :10
+                //
+                // "ORCL"  [:03, :08): -> "ORCL" AVG = 100
+                //
+                // "GOOGL" [:00, :13): 100, 200, 300
+                // "ORCL"  [:20, :25): 400
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:20.000000000", "ORCL", 400);
+                assert_eq!(out.len(), 1);
+                t_expect(
+                    aggr_label,
+                    group_by_label,
+                    out.get(0).cloned().unwrap(),
+                    "ORCL",
+                    100,
+                );
+                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+
+                // watermark = :20
+                //
+                // "GOOGL" [:00, :13): -> "GOOGL" AVG = 200
+                //
+                // "ORCL"  [:20, :25): 400
+                // "ORCL"  [:30, :35): 100
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:30.000000000", "ORCL", 100);
+                assert_eq!(out.len(), 1);
+                t_expect(
+                    aggr_label,
+                    group_by_label,
+                    out.get(0).cloned().unwrap(),
+                    "GOOGL",
+                    200,
+                );
+                assert_eq!(
+                    window_in_flow.window_gain_bytes_states,
+                    orcl_bytes - googl_bytes
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_count_sliding_window_aggregation() {
+        setup_test_logger();
+
+        // SELECT ticker, AVG(amount) AS avg_amount
+        //   FROM trade
+        //   SLIDING WINDOW rows(3), rows(2)
+        //   GROUP BY ticker;
+
+        let ticker_expr = ValueExpr::factory_colref(
+            StreamName::fx_trade().as_ref(),
+            ColumnName::fx_ticker().as_ref(),
+        );
+        let avg_amount_expr = AggrExpr {
+            func: AggregateFunctionParameter::Avg,
+            aggregated: ValueExpr::factory_colref(
+                StreamName::fx_trade().as_ref(),
+                ColumnName::fx_amount().as_ref(),
+            ),
+        };
+
+        let select_list = vec![
+            SelectFieldSyntax::ValueExpr {
+                value_expr: ticker_expr,
+                alias: None,
+            },
+            SelectFieldSyntax::AggrExpr {
+                aggr_expr: avg_amount_expr,
+                alias: Some(AggrAlias::new("avg_amount".to_string())),
+            },
+        ];
+
+        // AVG state (f64 mean and u64 count) + GROUP BY value
+        let googl_bytes = 16 + "GOOGL".len() as i64;
+
+        let (expr_resolver, labels) = ExprResolver::new(select_list);
+        match &labels[..] {
+            &[ExprLabel::Value(group_by_label), ExprLabel::Aggr(aggr_label)] => {
+                let mut window = AggrWindow::new(
+                    WindowParameter::CountSlidingWindow {
+                        length: 3,
+                        period: 2,
+                    },
+                    WindowOperationParameter::Aggregate(AggregateParameter {
+                        aggr_exprs: vec![AggrExprParameter::new(
+                            AggregateFunctionParameter::Avg,
+                            aggr_label,
+                        )],
+                        group_by: GroupByLabels::new(vec![group_by_label]),
+                    }),
+                );
+
+                let mut dispatch = |ts: &str, amount: i16| {
+                    window
+                        .dispatch(
+                            &expr_resolver,
+                            Tuple::factory_trade(
+                                SpringTimestamp::from_str(ts).unwrap(),
+                                "GOOGL",
+                                amount,
+                            ),
+                            (),
+                        )
+                        .unwrap()
+                };
+
+                // rows [0, 3): 100
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:10.000000000", 100);
+                assert!(out.is_empty());
+                assert_eq!(window_in_flow.window_gain_bytes_states, googl_bytes);
+
+                // rowtime older than the previous one is not dropped
+                //
+                // rows [0, 3): 100, 200
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:05.000000000", 200);
+                assert!(out.is_empty());
+                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+
+                // rows [0, 3): -> "GOOGL" AVG = 200
+                //
+                // rows [2, 5): 300
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:20.000000000", 300);
+                assert_eq!(out.len(), 1);
+                t_expect(
+                    aggr_label,
+                    group_by_label,
+                    out.get(0).cloned().unwrap(),
+                    "GOOGL",
+                    200,
+                );
+                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+
+                // rows [2, 5): 300, 400
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:21.000000000", 400);
+                assert!(out.is_empty());
+                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+
+                // rows [2, 5): -> "GOOGL" AVG = 400
+                //
+                // rows [4, 7): 500
+                let (out, window_in_flow) = dispatch("2020-01-01 00:00:22.000000000", 500);
+                assert_eq!(out.len(), 1);
+                t_expect(
+                    aggr_label,
+                    group_by_label,
+                    out.get(0).cloned().unwrap(),
+                    "GOOGL",
+                    400,
+                );
+                assert_eq!(window_in_flow.window_gain_bytes_states, 0);
+            }
+            _ => unreachable!(),
+        }
+    }
 }
@@ -61,7 +61,7 @@ mod tests {
         pipeline::{ColumnName, ColumnReference, JoinParameter, JoinType, StreamName},
         sql_processor::SelectFieldSyntax,
         stream_engine::{
-            autonomous_executor::task::window::panes::JoinDir,
+            autonomous_executor::task::window::panes::{JoinDir, JoinedTuple},
             time::{SpringDuration, SpringEventDuration, SpringTimestamp},
             SqlValue, Tuple,
         },
@@ -70,11 +70,12 @@ mod tests {
     use super::*;
 
     fn t_expect(
-        tuple: Tuple,
+        joined: &JoinedTuple,
         expected_timestamp: SpringTimestamp,
         expected_amount: i32,
         expected_temperature: Option<i32>,
     ) {
+        let tuple = joined.as_ref().expect("ON expression must be evaluated");
         let timestamp = tuple
             .get_value(&ColumnReference::fx_trade_timestamp())
             .unwrap()
@@ -102,16 +103,12 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_timed_fixed_window_left_out_join() {
-        setup_test_logger();
-
-        // SELECT trade.timestamp, trade.amount, city_temperature.temperature
-        //   FROM trade
-        //   LEFT OUTER JOIN city_temperature
-        //   ON trade.timestamp = city_temperature.timestamp
-        //   FIXED WINDOW duration_secs(10), duration_secs(1);
-
+    /// SELECT trade.timestamp, trade.amount, city_temperature.temperature
+    ///   FROM trade
+    ///   LEFT OUTER JOIN city_temperature
+    ///   ON trade.timestamp = city_temperature.timestamp
+    ///   <window_param>;
+    fn left_outer_join_window(window_param: WindowParameter) -> (ExprResolver, JoinWindow) {
         let trade_timestamp_expr = ValueExpr::factory_colref(
             StreamName::fx_trade().as_ref(),
             ColumnName::fx_timestamp().as_ref(),
@@ -153,11 +150,8 @@ mod tests {
 
         let on_expr_label = expr_resolver.register_value_expr(on_expr);
 
-        let mut window = JoinWindow::new(
-            WindowParameter::TimedFixedWindow {
-                length: SpringEventDuration::from_secs(10),
-                allowed_delay: SpringEventDuration::from_secs(1),
-            },
+        let window = JoinWindow::new(
+            window_param,
             JoinParameter {
                 join_type: JoinType::LeftOuter,
                 left_colrefs: vec![
@@ -171,9 +165,32 @@ mod tests {
                     ColumnReference::fx_city_temperature_temperature(),
                 ],
                 on_expr: on_expr_label,
+                equi_keys: vec![(
+                    ColumnReference::fx_trade_timestamp(),
+                    ColumnReference::fx_city_temperature_timestamp(),
+                )],
             },
         );
 
+        (expr_resolver, window)
+    }
+
+    #[test]
+    fn test_timed_fixed_window_left_out_join() {
+        setup_test_logger();
+
+        // SELECT trade.timestamp, trade.amount, city_temperature.temperature
+        //   FROM trade
+        //   LEFT OUTER JOIN city_temperature
+        //   ON trade.timestamp = city_temperature.timestamp
+        //   FIXED WINDOW duration_secs(10), duration_secs(1);
+
+        let (expr_resolver, mut window) =
+            left_outer_join_window(WindowParameter::TimedFixedWindow {
+                length: SpringEventDuration::from_secs(10),
+                allowed_delay: SpringEventDuration::from_secs(1),
+            });
+
         // [:00, :10): t(:00, 100)
         let (out, window_in_flow) = window
             .dispatch(
@@ -291,19 +308,19 @@ mod tests {
             .unwrap();
         assert_eq!(out.len(), 3);
         t_expect(
-            out.get(0).cloned().unwrap(),
+            &out[0],
             SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),
             100,
             Some(10),
         );
         t_expect(
-            out.get(1).cloned().unwrap(),
+            &out[1],
             SpringTimestamp::from_str("2020-01-01 00:00:09.999999999").unwrap(),
             200,
             None,
         );
         t_expect(
-            out.get(2).cloned().unwrap(),
+            &out[2],
             SpringTimestamp::from_str("2020-01-01 00:00:09.999999999").unwrap(),
             500,
             None,
@@ -311,4 +328,99 @@ mod tests {
         assert!(window_in_flow.window_gain_bytes_rows < 0);
         assert_eq!(window_in_flow.window_gain_bytes_states, 0);
     }
+
+    #[test]
+    fn test_session_window_left_outer_join() {
+        setup_test_logger();
+
+        let (expr_resolver, mut window) = left_outer_join_window(WindowParameter::SessionWindow {
+            gap: SpringEventDuration::from_secs(5),
+            allowed_delay: SpringEventDuration::from_secs(10),
+        });
+
+        let mut dispatch =
+            |tuple: Tuple, dir: JoinDir| window.dispatch(&expr_resolver, tuple, dir).unwrap();
+        let ts = |s: &str| SpringTimestamp::from_str(s).unwrap();
+
+        // [:00, :05): c(:00, 10)
+        // [:06, :11): c(:06, 20), t(:06, 200)
+        for (tuple, dir) in [
+            (
+                Tuple::factory_city_temperature(ts("2020-01-01 00:00:00.000000000"), "", 10),
+                JoinDir::Right,
+            ),
+            (
+                Tuple::factory_city_temperature(ts("2020-01-01 00:00:06.000000000"), "", 20),
+                JoinDir::Right,
+            ),
+            (
+                Tuple::factory_trade(ts("2020-01-01 00:00:06.000000000"), "", 200),
+                JoinDir::Left,
+            ),
+        ] {
+            let (out, _) = dispatch(tuple, dir);
+            assert!(out.is_empty());
+        }
+
+        // [:03, :08) bridges the sessions
+        //
+        // [:00, :11): c(:00, 10), c(:06, 20), t(:06, 200), t(:03, 100)
+        let (out, window_in_flow) = dispatch(
+            Tuple::factory_trade(ts("2020-01-01 00:00:03.000000000"), "", 100),
+            JoinDir::Left,
+        );
+        assert!(out.is_empty());
+        assert!(window_in_flow.window_gain_bytes_rows > 0);
+
+        // watermark = :20
+        //
+        // [:00, :11): -> tc(:06, 200, 20), tc(:03, 100, NULL)
+        //
+        // [:30, :35): t(:30, 300)
+        let (out, _) = dispatch(
+            Tuple::factory_trade(ts("2020-01-01 00:00:30.000000000"), "", 300),
+            JoinDir::Left,
+        );
+        assert_eq!(out.len(), 2);
+        t_expect(&out[0], ts("2020-01-01 00:00:06.000000000"), 200, Some(20));
+        t_expect(&out[1], ts("2020-01-01 00:00:03.000000000"), 100, None);
+    }
+
+    #[test]
+    fn test_count_fixed_window_left_outer_join() {
+        setup_test_logger();
+
+        let (expr_resolver, mut window) =
+            left_outer_join_window(WindowParameter::CountFixedWindow { length: 3 });
+
+        let mut dispatch =
+            |tuple: Tuple, dir: JoinDir| window.dispatch(&expr_resolver, tuple, dir).unwrap();
+        let ts = |s: &str| SpringTimestamp::from_str(s).unwrap();
+
+        // rows [0, 3): t(:10, 100), c(:10, 10)
+        for (tuple, dir) in [
+            (
+                Tuple::factory_trade(ts("2020-01-01 00:00:10.000000000"), "", 100),
+                JoinDir::Left,
+            ),
+            (
+                Tuple::factory_city_temperature(ts("2020-01-01 00:00:10.000000000"), "", 10),
+                JoinDir::Right,
+            ),
+        ] {
+            let (out, window_in_flow) = dispatch(tuple, dir);
+            assert!(out.is_empty());
+            assert!(window_in_flow.window_gain_bytes_rows > 0);
+        }
+
+        // rows [0, 3): -> tc(:10, 100, 10), tc(:00, 200, NULL)
+        let (out, window_in_flow) = dispatch(
+            Tuple::factory_trade(ts("2020-01-01 00:00:00.000000000"), "", 200),
+            JoinDir::Left,
+        );
+        assert_eq!(out.len(), 2);
+        t_expect(&out[0], ts("2020-01-01 00:00:10.000000000"), 100, Some(10));
+        t_expect(&out[1], ts("2020-01-01 00:00:00.000000000"), 200, None);
+        assert!(window_in_flow.window_gain_bytes_rows < 0);
+    }
 }
@@ -2,16 +2,21 @@
 
 mod pane;
 
-pub use pane::{AggrPane, AggrPaneInner, GroupByValues, JoinDir, JoinPane, Pane};
+pub use pane::{AggrPane, GroupByValues, JoinDir, JoinPane, JoinedTuple, Pane};
 
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::HashMap};
 
 use crate::{
     api::SpringError,
-    pipeline::{WindowOperationParameter, WindowParameter},
+    expr_resolver::ExprResolver,
+    pipeline::{WindowLengthPeriod, WindowOperationParameter, WindowParameter},
     stream_engine::{
-        autonomous_executor::task::window::watermark::Watermark,
-        time::{SpringDuration, SpringTimestamp},
+        autonomous_executor::{
+            performance_metrics::WindowInFlowByWindowTask,
+            task::window::{watermark::Watermark, Failure},
+        },
+        time::{SpringDuration, SpringEventDuration, SpringTimestamp},
+        Tuple,
     },
 };
 
@@ -22,9 +27,17 @@ where
 {
     /// FIXME want to use `LinkedList::drain_filter` but it's unstable.
     ///
-    /// Sorted by `Pane::open_at`.
+    /// Sorted by `Pane::open_at`. Used by timed windows.
     panes: Vec<P>,
 
+    /// Session key -> non-overlapping sessions sorted by `Pane::open_at`. Used by session windows.
+    sessions: HashMap<GroupByValues, Vec<P>>,
+
+    /// (index of the first row, pane) sorted by the index. Used by count-based windows.
+    counted_panes: Vec<(u64, P)>,
+    /// Number of rows dispatched so far. Used by count-based windows.
+    n_rows: u64,
+
     window_param: WindowParameter,
     op_param: WindowOperationParameter,
 }
@@ -36,25 +49,53 @@ where
     pub fn new(window_param: WindowParameter, op_param: WindowOperationParameter) -> Self {
         Self {
             panes: vec![],
+            sessions: HashMap::new(),
+            counted_panes: vec![],
+            n_rows: 0,
             window_param,
             op_param,
         }
     }
 
-    /// Generate new panes if not exists.
-    /// Then, return all panes to get a tuple with the `rowtime`.
+    /// Generate new panes if not exists (and merge sessions overlapping with the tuple for session windows).
+    /// Then, return all panes to get the tuple, along with window in-flow by the merge.
+    ///
+    /// Caller must assure rowtime is not smaller than watermark (unless the window is count-based).
     ///
-    /// Caller must assure rowtime is not smaller than watermark.
+    /// # Failures
+    ///
+    /// Failures of `Pane::session_key()` and `Pane::merge()`, with window in-flow by the merge.
     pub fn panes_to_dispatch(
         &mut self,
-        rowtime: SpringTimestamp,
-    ) -> Result<impl Iterator<Item = &mut P>, SpringError> {
-        self.generate_panes_if_not_exist(rowtime)?;
+        expr_resolver: &ExprResolver,
+        tuple: &Tuple,
+    ) -> Result<(Vec<&mut P>, WindowInFlowByWindowTask), Failure> {
+        let no_gain = |e| (e, WindowInFlowByWindowTask::zero());
+        let rowtime = tuple.rowtime().as_timestamp();
 
-        Ok(self
-            .panes
-            .iter_mut()
-            .filter(move |pane| pane.is_acceptable(&rowtime)))
+        match self.window_param.length_period() {
+            WindowLengthPeriod::Count { length, period } => Ok((
+                self.counted_panes_to_dispatch(rowtime, length, period),
+                WindowInFlowByWindowTask::zero(),
+            )),
+            WindowLengthPeriod::EventTime { length, .. }
+                if matches!(self.window_param, WindowParameter::SessionWindow { .. }) =>
+            {
+                self.session_to_dispatch(expr_resolver, tuple, rowtime, length)
+                    .map(|(pane, window_in_flow)| (vec![pane], window_in_flow))
+            }
+            WindowLengthPeriod::EventTime { length, period } => {
+                self.generate_panes_if_not_exist(rowtime, length, period)
+                    .map_err(no_gain)?;
+
+                let panes = self
+                    .panes
+                    .iter_mut()
+                    .filter(|pane| pane.is_acceptable(&rowtime))
+                    .collect();
+                Ok((panes, WindowInFlowByWindowTask::zero()))
+            }
+        }
     }
 
     pub fn remove_panes_to_close(&mut self, watermark: &Watermark) -> Vec<P> {
@@ -72,17 +113,124 @@ where
             }
         }
 
+        for sessions in self.sessions.values_mut() {
+            while sessions
+                .first()
+                .map_or(false, |session| session.should_close(watermark))
+            {
+                panes_to_close.push(sessions.remove(0));
+            }
+        }
+        self.sessions.retain(|_, sessions| !sessions.is_empty());
+
+        if let WindowLengthPeriod::Count { length, .. } = self.window_param.length_period() {
+            while self
+                .counted_panes
+                .first()
+                .map_or(false, |(first_row, _)| first_row + length <= self.n_rows)
+            {
+                let (_, pane) = self.counted_panes.remove(0);
+                panes_to_close.push(pane);
+            }
+        }
+
         panes_to_close
     }
 
     pub fn purge(&mut self) {
-        self.panes.clear()
+        self.panes.clear();
+        self.sessions.clear();
+        self.counted_panes.clear();
+        self.n_rows = 0;
+    }
+
+    /// Count-based windows take every row in arrival order, even if it is older than watermark.
+    pub fn is_count_based(&self) -> bool {
+        matches!(
+            self.window_param.length_period(),
+            WindowLengthPeriod::Count { .. }
+        )
+    }
+
+    /// A pane opens every `period` rows and gets `length` rows.
+    ///
+    /// Counted panes do not have event-time bounds. The rowtime of the first row is given as both `open_at` and `close_at`.
+    fn counted_panes_to_dispatch(
+        &mut self,
+        rowtime: SpringTimestamp,
+        length: u64,
+        period: u64,
+    ) -> Vec<&mut P> {
+        let row_idx = self.n_rows;
+        self.n_rows += 1;
+
+        if row_idx % period == 0 {
+            let pane = P::new(rowtime, rowtime, self.op_param.clone());
+            self.counted_panes.push((row_idx, pane));
+        }
+
+        self.counted_panes
+            .iter_mut()
+            .filter(|(first_row, _)| row_idx < first_row + length)
+            .map(|(_, pane)| pane)
+            .collect()
     }
 
-    fn generate_panes_if_not_exist(&mut self, rowtime: SpringTimestamp) -> Result<(), SpringError> {
+    /// A tuple opens a session `[rowtime, rowtime + gap)` for its session key.
+    /// Sessions with the key overlapping with it are merged into a session.
+    ///
+    /// All the overlapping sessions are merged even if some of them fail to merge. Then the first failure is returned.
+    fn session_to_dispatch(
+        &mut self,
+        expr_resolver: &ExprResolver,
+        tuple: &Tuple,
+        rowtime: SpringTimestamp,
+        gap: SpringEventDuration,
+    ) -> Result<(&mut P, WindowInFlowByWindowTask), Failure> {
+        let key = P::session_key(&self.op_param, expr_resolver, tuple)
+            .map_err(|e| (e, WindowInFlowByWindowTask::zero()))?;
+        let mut new_session = self.generate_pane(rowtime, gap);
+
+        let sessions = self.sessions.entry(key).or_default();
+
+        // sessions are sorted and not overlapping with each other
+        let first_overlap = sessions
+            .iter()
+            .position(|session| rowtime < session.close_at())
+            .unwrap_or(sessions.len());
+        let n_overlaps = sessions[first_overlap..]
+            .iter()
+            .take_while(|session| session.open_at() < new_session.close_at())
+            .count();
+
+        let mut window_in_flow = WindowInFlowByWindowTask::zero();
+        let mut first_error = None;
+        for session in sessions.drain(first_overlap..first_overlap + n_overlaps) {
+            match new_session.merge(session) {
+                Ok(window_in_flow_merge) => window_in_flow = window_in_flow + window_in_flow_merge,
+                Err((e, window_in_flow_merge)) => {
+                    window_in_flow = window_in_flow + window_in_flow_merge;
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        sessions.insert(first_overlap, new_session);
+
+        match first_error {
+            Some(e) => Err((e, window_in_flow)),
+            None => Ok((&mut sessions[first_overlap], window_in_flow)),
+        }
+    }
+
+    fn generate_panes_if_not_exist(
+        &mut self,
+        rowtime: SpringTimestamp,
+        length: SpringEventDuration,
+        period: SpringEventDuration,
+    ) -> Result<(), SpringError> {
         // Sort-Merge Join like algorithm
         let mut pane_idx = 0;
-        for open_at in self.valid_open_at_s(rowtime)? {
+        for open_at in Self::valid_open_at_s(rowtime, length, period)? {
             loop {
                 if pane_idx < self.panes.len() {
                     match open_at.cmp(&self.panes[pane_idx].open_at()) {
@@ -98,7 +246,7 @@ where
                     }
                 } else {
                     // no pane has the open_at
-                    self.panes.push(self.generate_pane(open_at));
+                    self.panes.push(self.generate_pane(open_at, length));
                     break; // next open_at
                 }
             }
@@ -107,35 +255,35 @@ where
     }
 
     fn valid_open_at_s(
-        &self,
         rowtime: SpringTimestamp,
+        length: SpringEventDuration,
+        period: SpringEventDuration,
     ) -> Result<Vec<SpringTimestamp>, SpringError> {
         let mut ret = vec![];
 
         let leftmost_open_at = {
-            let l = (rowtime - self.window_param.length().to_duration())
-                .ceil(self.window_param.period().to_duration())?;
+            let l = (rowtime - length.to_duration()).ceil(period.to_duration())?;
 
             // edge case
-            if l == rowtime - self.window_param.length().to_duration() {
-                l + self.window_param.period().to_duration()
+            if l == rowtime - length.to_duration() {
+                l + period.to_duration()
             } else {
                 l
             }
         };
-        let rightmost_open_at = rowtime.floor(self.window_param.period().to_duration())?;
+        let rightmost_open_at = rowtime.floor(period.to_duration())?;
 
         let mut open_at = leftmost_open_at;
         while open_at <= rightmost_open_at {
             ret.push(open_at);
-            open_at = open_at + self.window_param.period().to_duration();
+            open_at = open_at + period.to_duration();
         }
 
         Ok(ret)
     }
 
-    fn generate_pane(&self, open_at: SpringTimestamp) -> P {
-        let close_at = open_at + self.window_param.length().to_duration();
+    fn generate_pane(&self, open_at: SpringTimestamp, length: SpringEventDuration) -> P {
+        let close_at = open_at + length.to_duration();
         P::new(open_at, close_at, self.op_param.clone())
     }
 }
@@ -147,7 +295,9 @@ mod tests {
     use crate::{
         expr_resolver::{ExprLabel, ExprResolver},
         expression::{AggrExpr, ValueExpr},
-        pipeline::{AggregateFunctionParameter, AggregateParameter, GroupByLabels},
+        pipeline::{
+            AggrExprParameter, AggregateFunctionParameter, AggregateParameter, GroupByLabels,
+        },
         sql_processor::SelectFieldSyntax,
         stream_engine::{
             autonomous_executor::task::window::panes::pane::AggrPane,
@@ -175,77 +325,50 @@ mod tests {
             GroupByLabels::new(vec![expr_resolver.register_value_expr(group_by_expr)]);
 
         WindowOperationParameter::Aggregate(AggregateParameter {
-            aggr_func: AggregateFunctionParameter::Avg,
-            aggr_expr: if let ExprLabel::Aggr(l) = labels[0] {
-                l
-            } else {
-                unreachable!()
-            },
+            aggr_exprs: vec![AggrExprParameter::new(
+                AggregateFunctionParameter::Avg,
+                if let ExprLabel::Aggr(l) = labels[0] {
+                    l
+                } else {
+                    unreachable!()
+                },
+            )],
             group_by: group_by_labels,
         })
     }
 
     #[test]
     fn test_valid_open_at_s() {
-        fn sliding_window_panes(
-            length: SpringEventDuration,
-            period: SpringEventDuration,
-        ) -> Panes<AggrPane> {
-            Panes::new(
-                WindowParameter::TimedSlidingWindow {
-                    length,
-                    period,
-                    allowed_delay: SpringEventDuration::from_secs(0),
-                },
-                dont_care_window_operation_parameter(),
+        let valid_open_at_s = |rowtime: &str, length_secs: u64, period_secs: u64| {
+            Panes::<AggrPane>::valid_open_at_s(
+                SpringTimestamp::from_str(rowtime).unwrap(),
+                SpringEventDuration::from_secs(length_secs),
+                SpringEventDuration::from_secs(period_secs),
             )
-        }
+            .unwrap()
+        };
 
-        let panes = sliding_window_panes(
-            SpringEventDuration::from_secs(10),
-            SpringEventDuration::from_secs(5),
-        );
         assert_eq!(
-            panes
-                .valid_open_at_s(
-                    SpringTimestamp::from_str("2020-01-01 00:00:05.000000000").unwrap()
-                )
-                .unwrap(),
+            valid_open_at_s("2020-01-01 00:00:05.000000000", 10, 5),
             vec![
                 SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),
                 SpringTimestamp::from_str("2020-01-01 00:00:05.000000000").unwrap()
             ]
         );
         assert_eq!(
-            panes
-                .valid_open_at_s(
-                    SpringTimestamp::from_str("2020-01-01 00:00:09.999999999").unwrap()
-                )
-                .unwrap(),
+            valid_open_at_s("2020-01-01 00:00:09.999999999", 10, 5),
             vec![
                 SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),
                 SpringTimestamp::from_str("2020-01-01 00:00:05.000000000").unwrap()
             ]
         );
 
-        let panes = sliding_window_panes(
-            SpringEventDuration::from_secs(10),
-            SpringEventDuration::from_secs(10),
-        );
         assert_eq!(
-            panes
-                .valid_open_at_s(
-                    SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap()
-                )
-                .unwrap(),
+            valid_open_at_s("2020-01-01 00:00:00.000000000", 10, 10),
             vec![SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),]
         );
         assert_eq!(
-            panes
-                .valid_open_at_s(
-                    SpringTimestamp::from_str("2020-01-01 00:00:09.999999999").unwrap()
-                )
-                .unwrap(),
+            valid_open_at_s("2020-01-01 00:00:09.999999999", 10, 10),
             vec![SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),]
         );
     }
@@ -3,15 +3,17 @@
 mod aggregate_pane;
 mod join_pane;
 
-pub use aggregate_pane::{AggrPane, AggrPaneInner, GroupByValues};
-pub use join_pane::{JoinDir, JoinPane};
+pub use aggregate_pane::{AggrPane, GroupByValues};
+pub use join_pane::{JoinDir, JoinPane, JoinedTuple};
 
 use crate::{
+    api::error::Result,
     expr_resolver::ExprResolver,
     pipeline::WindowOperationParameter,
     stream_engine::{
         autonomous_executor::{
-            performance_metrics::WindowInFlowByWindowTask, task::window::watermark::Watermark,
+            performance_metrics::WindowInFlowByWindowTask,
+            task::window::{watermark::Watermark, Failure},
         },
         time::SpringTimestamp,
         Tuple,
@@ -28,6 +30,13 @@ pub trait Pane {
         param: WindowOperationParameter,
     ) -> Self;
 
+    /// Key to separate session windows. Tuples with different keys never share a session.
+    fn session_key(
+        param: &WindowOperationParameter,
+        expr_resolver: &ExprResolver,
+        tuple: &Tuple,
+    ) -> Result<GroupByValues>;
+
     fn open_at(&self) -> SpringTimestamp;
     fn close_at(&self) -> SpringTimestamp;
 
@@ -39,12 +48,25 @@ pub trait Pane {
         self.close_at() <= watermark.as_timestamp()
     }
 
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the tuple cannot be evaluated for the window operation.
     fn dispatch(
         &mut self,
         expr_resolver: &ExprResolver,
         tuple: &Tuple,
         arg: Self::DispatchArg,
-    ) -> WindowInFlowByWindowTask;
+    ) -> std::result::Result<WindowInFlowByWindowTask, Failure>;
+
+    /// Merges an overlapping session pane with the same session key into this one.
+    /// This pane gets `[min(open_at), max(close_at))`.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - states of `other` cannot be merged into this pane's. `other` is merged as much as possible then.
+    fn merge(&mut self, other: Self) -> std::result::Result<WindowInFlowByWindowTask, Failure>;
 
     fn close(self, expr_resolver: &ExprResolver)
         -> (Vec<Self::CloseOut>, WindowInFlowByWindowTask);
@@ -4,14 +4,11 @@ mod aggregate_state;
 
 use std::collections::HashMap;
 
-use ordered_float::OrderedFloat;
-
 use crate::{
     api::error::Result,
     expr_resolver::ExprResolver,
-    pipeline::{
-        AggregateFunctionParameter, AggregateParameter, GroupByLabels, WindowOperationParameter,
-    },
+    mem_size::MemSize,
+    pipeline::{AggregateParameter, GroupByLabels, WindowOperationParameter},
     stream_engine::{
         autonomous_executor::{
             performance_metrics::WindowInFlowByWindowTask,
@@ -19,7 +16,8 @@ use crate::{
                 tuple::Tuple,
                 window::{
                     aggregate::AggregatedAndGroupingValues,
-                    panes::pane::{aggregate_pane::aggregate_state::AvgState, Pane},
+                    panes::pane::{aggregate_pane::aggregate_state::AggregateState, Pane},
+                    Failure,
                 },
             },
         },
@@ -35,7 +33,8 @@ pub struct AggrPane {
 
     aggregate_parameter: AggregateParameter,
 
-    inner: AggrPaneInner,
+    /// States are in the same order as `aggregate_parameter.aggr_exprs`.
+    states: HashMap<GroupByValues, Vec<AggregateState>>,
 }
 
 impl Pane for AggrPane {
@@ -51,23 +50,34 @@ impl Pane for AggrPane {
         op_param: WindowOperationParameter,
     ) -> Self {
         if let WindowOperationParameter::Aggregate(aggregate_parameter) = op_param {
-            let inner = match aggregate_parameter.aggr_func {
-                AggregateFunctionParameter::Avg => AggrPaneInner::Avg {
-                    states: HashMap::new(),
-                },
-            };
-
             Self {
                 open_at,
                 close_at,
                 aggregate_parameter,
-                inner,
+                states: HashMap::new(),
             }
         } else {
             panic!("op_param {:?} is not GroupAggregateParameter", op_param)
         }
     }
 
+    /// Sessions are kept per GROUP BY group.
+    fn session_key(
+        op_param: &WindowOperationParameter,
+        expr_resolver: &ExprResolver,
+        tuple: &Tuple,
+    ) -> Result<GroupByValues> {
+        if let WindowOperationParameter::Aggregate(aggregate_parameter) = op_param {
+            GroupByValues::from_group_by_labels(
+                aggregate_parameter.group_by.clone(),
+                expr_resolver,
+                tuple,
+            )
+        } else {
+            panic!("op_param {:?} is not GroupAggregateParameter", op_param)
+        }
+    }
+
     fn open_at(&self) -> SpringTimestamp {
         self.open_at
     }
@@ -76,93 +86,160 @@ impl Pane for AggrPane {
         self.close_at
     }
 
+    /// Values are evaluated before any state is updated. A tuple failed in a new group leaves no state.
+    /// A tuple failed in an aggregate function of an existing group may have been taken by the preceding aggregate functions.
     fn dispatch(
         &mut self,
         expr_resolver: &ExprResolver,
         tuple: &Tuple,
         _arg: (),
-    ) -> WindowInFlowByWindowTask {
+    ) -> std::result::Result<WindowInFlowByWindowTask, Failure> {
+        let no_gain = |e| (e, WindowInFlowByWindowTask::zero());
+
         let group_by_values = GroupByValues::from_group_by_labels(
             self.aggregate_parameter.group_by.clone(),
             expr_resolver,
             tuple,
         )
-        .expect("TODO handle Result");
+        .map_err(no_gain)?;
 
-        let aggregated_value = expr_resolver
-            .eval_aggr_expr_inner(self.aggregate_parameter.aggr_expr, tuple)
-            .expect("TODO Result");
-        let aggregated_value = if let SqlValue::NotNull(v) = aggregated_value {
-            v
-        } else {
-            unimplemented!("aggregation with NULL value is not supported")
+        let aggr_exprs = &self.aggregate_parameter.aggr_exprs;
+        let aggregated_values = aggr_exprs
+            .iter()
+            .map(|aggr_expr| expr_resolver.eval_aggr_expr_inner(aggr_expr.aggr_expr, tuple))
+            .collect::<Result<Vec<_>>>()
+            .map_err(no_gain)?;
+
+        let rowtime = tuple.rowtime().as_timestamp();
+        let next_all = |states: &mut Vec<AggregateState>| {
+            states
+                .iter_mut()
+                .zip(aggregated_values)
+                .try_for_each(|(state, aggregated_value)| state.next(aggregated_value, rowtime))
         };
 
-        match &mut self.inner {
-            AggrPaneInner::Avg { states } => {
-                let state = states
-                    .entry(group_by_values)
-                    .or_insert_with(AvgState::default);
+        match self.states.get_mut(&group_by_values) {
+            Some(states) => {
+                let states_size_before = states_mem_size(states);
+                let res = next_all(states);
+
+                let gain_bytes_states = states_mem_size(states) as i64 - states_size_before as i64;
+                let window_in_flow = WindowInFlowByWindowTask::new(gain_bytes_states, 0);
+                res.map(|()| window_in_flow)
+                    .map_err(|e| (e, window_in_flow))
+            }
+            None => {
+                let mut states = aggr_exprs
+                    .iter()
+                    .map(|aggr_expr| AggregateState::new(aggr_expr.aggr_func))
+                    .collect();
+                next_all(&mut states).map_err(no_gain)?;
+
+                let gain_bytes_states = group_by_values.mem_size() + states_mem_size(&states);
+                self.states.insert(group_by_values, states);
+                Ok(WindowInFlowByWindowTask::new(gain_bytes_states as i64, 0))
+            }
+        }
+    }
+
+    /// A state failed to merge keeps this pane's value, and the first error is returned after merging all the groups.
+    fn merge(&mut self, other: Self) -> std::result::Result<WindowInFlowByWindowTask, Failure> {
+        self.open_at = self.open_at.min(other.open_at);
+        self.close_at = self.close_at.max(other.close_at);
 
-                state.next(
-                    aggregated_value
-                        .unpack::<f32>()
-                        .expect("only f32 is supported currently"),
-                );
+        // Groups only in `other` are moved as they are. Groups in both are merged and `other`'s copy is released.
+        let mut gain_bytes_states = 0;
+        let mut first_error = None;
+        for (group_by_values, other_states) in other.states {
+            match self.states.get_mut(&group_by_values) {
+                Some(states) => {
+                    let states_size_before = states_mem_size(states);
+                    let other_size = group_by_values.mem_size() + states_mem_size(&other_states);
 
-                WindowInFlowByWindowTask::zero() // state in AVG is constant
+                    for (state, other_state) in states.iter_mut().zip(other_states) {
+                        if let Err(e) = state.merge(other_state) {
+                            first_error.get_or_insert(e);
+                        }
+                    }
+
+                    gain_bytes_states +=
+                        states_mem_size(states) as i64 - (states_size_before + other_size) as i64;
+                }
+                None => {
+                    self.states.insert(group_by_values, other_states);
+                }
             }
         }
+
+        let window_in_flow = WindowInFlowByWindowTask::new(gain_bytes_states, 0);
+        match first_error {
+            Some(e) => Err((e, window_in_flow)),
+            None => Ok(window_in_flow),
+        }
     }
 
     fn close(
         self,
         _expr_resolver: &ExprResolver,
     ) -> (Vec<Self::CloseOut>, WindowInFlowByWindowTask) {
-        let aggr_label = self.aggregate_parameter.aggr_expr;
+        let aggr_labels = self
+            .aggregate_parameter
+            .aggr_exprs
+            .iter()
+            .map(|aggr_expr| aggr_expr.aggr_expr)
+            .collect::<Vec<_>>();
         let group_by_labels = self.aggregate_parameter.group_by;
 
-        match self.inner {
-            AggrPaneInner::Avg { states } => {
-                let aggregated_and_grouping_values_seq = states
-                    .into_iter()
-                    .map(|(group_by_values, state)| {
-                        let aggr_value =
-                            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(state.finalize())));
-
-                        let group_bys = group_by_labels
-                            .as_labels()
-                            .iter()
-                            .cloned()
-                            .zip(group_by_values.into_sql_values())
-                            .collect();
-
-                        AggregatedAndGroupingValues::new(vec![(aggr_label, aggr_value)], group_bys)
-                    })
+        let states_size: usize = self
+            .states
+            .iter()
+            .map(|(group_by_values, states)| group_by_values.mem_size() + states_mem_size(states))
+            .sum();
+
+        let aggregated_and_grouping_values_seq = self
+            .states
+            .into_iter()
+            .map(|(group_by_values, states)| {
+                let aggregates = aggr_labels
+                    .iter()
+                    .cloned()
+                    .zip(states.into_iter().map(AggregateState::finalize))
                     .collect();
 
-                (
-                    aggregated_and_grouping_values_seq,
-                    WindowInFlowByWindowTask::zero(),
-                )
-            }
-        }
+                let group_bys = group_by_labels
+                    .as_labels()
+                    .iter()
+                    .cloned()
+                    .zip(group_by_values.into_sql_values())
+                    .collect();
+
+                AggregatedAndGroupingValues::new(aggregates, group_bys)
+            })
+            .collect();
+
+        (
+            aggregated_and_grouping_values_seq,
+            WindowInFlowByWindowTask::new(-(states_size as i64), 0),
+        )
     }
 }
 
-#[derive(Debug)]
-pub enum AggrPaneInner {
-    Avg {
-        states: HashMap<GroupByValues, AvgState>,
-    },
+fn states_mem_size(states: &[AggregateState]) -> usize {
+    states.iter().map(AggregateState::mem_size).sum()
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
 pub struct GroupByValues(
     /// TODO support NULL in GROUP BY elements
     Vec<NnSqlValue>,
 );
 
+impl MemSize for GroupByValues {
+    fn mem_size(&self) -> usize {
+        self.0.iter().map(NnSqlValue::mem_size).sum()
+    }
+}
+
 impl GroupByValues {
     /// Order of elements in GROUP BY clause is preserved.
     fn from_group_by_labels(
@@ -191,3 +268,74 @@ impl GroupByValues {
         self.0.into_iter().map(SqlValue::NotNull).collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        api::SpringError,
+        expr_resolver::{AggrExprLabel, ExprLabel},
+        expression::{AggrExpr, ValueExpr},
+        pipeline::{
+            AggrAlias, AggrExprParameter, AggregateFunctionParameter, ColumnName, StreamName,
+        },
+        sql_processor::SelectFieldSyntax,
+    };
+
+    fn sum_pane(aggr_label: AggrExprLabel, sum: NnSqlValue) -> AggrPane {
+        let mut pane = AggrPane::new(
+            SpringTimestamp::fx_ts1(),
+            SpringTimestamp::fx_ts2(),
+            WindowOperationParameter::Aggregate(AggregateParameter {
+                aggr_exprs: vec![AggrExprParameter::new(
+                    AggregateFunctionParameter::Sum,
+                    aggr_label,
+                )],
+                group_by: GroupByLabels::new(vec![]),
+            }),
+        );
+
+        let mut state = AggregateState::new(AggregateFunctionParameter::Sum);
+        state
+            .next(SqlValue::NotNull(sum), SpringTimestamp::fx_ts1())
+            .unwrap();
+        pane.states.insert(GroupByValues::default(), vec![state]);
+        pane
+    }
+
+    #[test]
+    fn test_merge_failure() {
+        // SELECT SUM(amount) AS sum_amount FROM trade ...;
+        let sum_amount_expr = AggrExpr {
+            func: AggregateFunctionParameter::Sum,
+            aggregated: ValueExpr::factory_colref(
+                StreamName::fx_trade().as_ref(),
+                ColumnName::fx_amount().as_ref(),
+            ),
+        };
+        let (_, labels) = ExprResolver::new(vec![SelectFieldSyntax::AggrExpr {
+            aggr_expr: sum_amount_expr,
+            alias: Some(AggrAlias::new("sum_amount".to_string())),
+        }]);
+        let aggr_label = match &labels[..] {
+            &[ExprLabel::Aggr(aggr_label)] => aggr_label,
+            _ => unreachable!(),
+        };
+
+        // signed and unsigned sums cannot be added
+        let mut pane = sum_pane(aggr_label, NnSqlValue::BigInt(1));
+        let other = sum_pane(aggr_label, NnSqlValue::UnsignedBigInt(1));
+
+        let (e, _) = pane.merge(other).unwrap_err();
+        assert!(matches!(e, SpringError::Sql(_)));
+
+        // this pane's sum is kept
+        let (out, _) = pane.close(&ExprResolver::new(vec![]).0);
+        assert_eq!(out.len(), 1);
+        assert_eq!(
+            out[0].get_aggregated_value(&aggr_label).unwrap(),
+            &SqlValue::NotNull(NnSqlValue::BigInt(1))
+        );
+    }
+}
@@ -1,40 +1,768 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-// TODO more generic avg
+use std::mem::size_of;
+
+use anyhow::anyhow;
+use ordered_float::OrderedFloat;
+
+use crate::{
+    api::error::{Result, SpringError},
+    mem_size::MemSize,
+    pipeline::AggregateFunctionParameter,
+    stream_engine::{time::SpringTimestamp, NnSqlValue, SqlCompareResult, SqlValue},
+};
+
+/// State of an aggregate function for a group in a pane.
+///
+/// NULL values are skipped by every aggregate function.
+/// If a group has no non-NULL value, `COUNT` results in 0 and other functions result in NULL.
+#[derive(Debug)]
+pub enum AggregateState {
+    Avg(AvgState),
+    Sum(SumState),
+    Count(CountState),
+    Min(MinMaxState),
+    Max(MinMaxState),
+    VarPop(VarianceState),
+    StddevPop(VarianceState),
+    StddevSamp(VarianceState),
+    FirstValue(FirstLastState),
+    LastValue(FirstLastState),
+    ApproxPercentile(PercentileSketchState),
+}
+
+impl AggregateState {
+    pub fn new(func: AggregateFunctionParameter) -> Self {
+        match func {
+            AggregateFunctionParameter::Avg => Self::Avg(AvgState::default()),
+            AggregateFunctionParameter::Sum => Self::Sum(SumState::default()),
+            AggregateFunctionParameter::Count => Self::Count(CountState::default()),
+            AggregateFunctionParameter::Min => Self::Min(MinMaxState::default()),
+            AggregateFunctionParameter::Max => Self::Max(MinMaxState::default()),
+            AggregateFunctionParameter::VarPop => Self::VarPop(VarianceState::default()),
+            AggregateFunctionParameter::StddevPop => Self::StddevPop(VarianceState::default()),
+            AggregateFunctionParameter::StddevSamp => Self::StddevSamp(VarianceState::default()),
+            AggregateFunctionParameter::FirstValue => Self::FirstValue(FirstLastState::default()),
+            AggregateFunctionParameter::LastValue => Self::LastValue(FirstLastState::default()),
+            AggregateFunctionParameter::ApproxPercentile { percentile } => {
+                Self::ApproxPercentile(PercentileSketchState::new(percentile.into_inner()))
+            }
+        }
+    }
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `next_val` is not in a type the aggregate function accepts.
+    pub fn next(&mut self, next_val: SqlValue, rowtime: SpringTimestamp) -> Result<()> {
+        match next_val {
+            SqlValue::Null => Ok(()),
+            SqlValue::NotNull(v) => match self {
+                AggregateState::Avg(state) => state.next(&v),
+                AggregateState::Sum(state) => state.next(v),
+                AggregateState::Count(state) => {
+                    state.next();
+                    Ok(())
+                }
+                AggregateState::Min(state) => state.next(v, SqlCompareResult::LessThan),
+                AggregateState::Max(state) => state.next(v, SqlCompareResult::GreaterThan),
+                AggregateState::VarPop(state)
+                | AggregateState::StddevPop(state)
+                | AggregateState::StddevSamp(state) => state.next(&v),
+                AggregateState::FirstValue(state) => {
+                    state.next(v, rowtime, |next, current| next < current);
+                    Ok(())
+                }
+                AggregateState::LastValue(state) => {
+                    state.next(v, rowtime, |next, current| next >= current);
+                    Ok(())
+                }
+                AggregateState::ApproxPercentile(state) => state.next(&v),
+            },
+        }
+    }
+
+    /// Merges `other` state of the same aggregate function into this one, as if all the values given to `other` were given to this one.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - values in the states are not comparable (or addable).
+    ///
+    /// # Panics
+    ///
+    /// if `other` is for a different aggregate function.
+    pub fn merge(&mut self, other: Self) -> Result<()> {
+        match (self, other) {
+            (AggregateState::Avg(state), AggregateState::Avg(other)) => {
+                state.merge(other);
+                Ok(())
+            }
+            (AggregateState::Sum(state), AggregateState::Sum(other)) => state.merge(other),
+            (AggregateState::Count(state), AggregateState::Count(other)) => {
+                state.current_n += other.current_n;
+                Ok(())
+            }
+            (AggregateState::Min(state), AggregateState::Min(other)) => other
+                .current
+                .map_or(Ok(()), |v| state.next(v, SqlCompareResult::LessThan)),
+            (AggregateState::Max(state), AggregateState::Max(other)) => other
+                .current
+                .map_or(Ok(()), |v| state.next(v, SqlCompareResult::GreaterThan)),
+            (AggregateState::VarPop(state), AggregateState::VarPop(other))
+            | (AggregateState::StddevPop(state), AggregateState::StddevPop(other))
+            | (AggregateState::StddevSamp(state), AggregateState::StddevSamp(other)) => {
+                state.merge(other);
+                Ok(())
+            }
+            (AggregateState::FirstValue(state), AggregateState::FirstValue(other)) => {
+                if let Some((v, rowtime)) = other.current {
+                    state.next(v, rowtime, |next, current| next < current);
+                }
+                Ok(())
+            }
+            (AggregateState::LastValue(state), AggregateState::LastValue(other)) => {
+                if let Some((v, rowtime)) = other.current {
+                    state.next(v, rowtime, |next, current| next >= current);
+                }
+                Ok(())
+            }
+            (AggregateState::ApproxPercentile(state), AggregateState::ApproxPercentile(other)) => {
+                state.merge(other);
+                Ok(())
+            }
+            (state, other) => panic!(
+                "cannot merge aggregate states of different functions: {:?} and {:?}",
+                state, other
+            ),
+        }
+    }
+
+    pub fn finalize(self) -> SqlValue {
+        match self {
+            AggregateState::Avg(state) => state.finalize(),
+            AggregateState::Sum(state) => state.finalize(),
+            AggregateState::Count(state) => state.finalize(),
+            AggregateState::Min(state) | AggregateState::Max(state) => state.finalize(),
+            AggregateState::VarPop(state) => state.finalize_var_pop(),
+            AggregateState::StddevPop(state) => state.finalize_stddev_pop(),
+            AggregateState::StddevSamp(state) => state.finalize_stddev_samp(),
+            AggregateState::FirstValue(state) | AggregateState::LastValue(state) => {
+                state.finalize()
+            }
+            AggregateState::ApproxPercentile(state) => state.finalize(),
+        }
+    }
+}
+
+impl MemSize for AggregateState {
+    fn mem_size(&self) -> usize {
+        match self {
+            AggregateState::Avg(_) => size_of::<f64>() + size_of::<u64>(),
+            AggregateState::Sum(state) => option_mem_size(&state.current_sum),
+            AggregateState::Count(_) => size_of::<i64>(),
+            AggregateState::Min(state) | AggregateState::Max(state) => {
+                option_mem_size(&state.current)
+            }
+            AggregateState::VarPop(_)
+            | AggregateState::StddevPop(_)
+            | AggregateState::StddevSamp(_) => size_of::<u64>() + size_of::<f64>() * 2,
+            AggregateState::FirstValue(state) | AggregateState::LastValue(state) => state
+                .current
+                .as_ref()
+                .map_or(0, |(value, rowtime)| value.mem_size() + rowtime.mem_size()),
+            AggregateState::ApproxPercentile(state) => {
+                size_of::<f64>()
+                    + size_of::<bool>()
+                    + state
+                        .levels
+                        .iter()
+                        .map(|level| level.capacity() * size_of::<f64>())
+                        .sum::<usize>()
+            }
+        }
+    }
+}
+
+fn option_mem_size(v: &Option<NnSqlValue>) -> usize {
+    v.as_ref().map_or(0, NnSqlValue::mem_size)
+}
+
+/// Incremental mean calculated in f64.
+///
+/// The result is FLOAT regardless of the input numeric type.
 #[derive(Debug, Default)]
 pub struct AvgState {
-    current_avg: f32,
+    current_avg: f64,
     current_n: u64,
 }
 
 impl AvgState {
-    pub fn next<V>(&mut self, next_val: V)
-    where
-        V: Into<f32>,
-    {
-        let next_val: f32 = next_val.into();
+    fn next(&mut self, next_val: &NnSqlValue) -> Result<()> {
+        let next_val = to_f64(next_val)?;
         let next_n = self.current_n + 1;
 
-        self.current_avg =
-            self.current_avg + (next_val - self.current_avg) * (1.0 / (next_n as f32));
+        self.current_avg += (next_val - self.current_avg) / (next_n as f64);
         self.current_n = next_n;
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) {
+        let n = self.current_n + other.current_n;
+        if n > 0 {
+            self.current_avg +=
+                (other.current_avg - self.current_avg) * (other.current_n as f64) / (n as f64);
+        }
+        self.current_n = n;
+    }
+
+    fn finalize(self) -> SqlValue {
+        if self.current_n == 0 {
+            SqlValue::Null
+        } else {
+            float_value(self.current_avg)
+        }
+    }
+}
+
+/// Integers are promoted to BIGINT (signed) or UNSIGNED BIGINT (unsigned). FLOAT stays FLOAT.
+#[derive(Debug, Default)]
+pub struct SumState {
+    current_sum: Option<NnSqlValue>,
+}
+
+impl SumState {
+    /// The current sum is kept on failure.
+    fn next(&mut self, next_val: NnSqlValue) -> Result<()> {
+        let next_sum = match &self.current_sum {
+            None => promote_for_sum(next_val)?,
+            Some(current_sum) => (current_sum.clone() + next_val)?,
+        };
+        self.current_sum = Some(next_sum);
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) -> Result<()> {
+        other
+            .current_sum
+            .map_or(Ok(()), |other_sum| self.next(other_sum))
+    }
+
+    fn finalize(self) -> SqlValue {
+        self.current_sum.map_or(SqlValue::Null, SqlValue::NotNull)
+    }
+}
+
+/// Result is BIGINT.
+#[derive(Debug, Default)]
+pub struct CountState {
+    current_n: i64,
+}
+
+impl CountState {
+    fn next(&mut self) {
+        self.current_n += 1;
+    }
+
+    fn finalize(self) -> SqlValue {
+        SqlValue::NotNull(NnSqlValue::BigInt(self.current_n))
+    }
+}
+
+/// Keeps the input type.
+#[derive(Debug, Default)]
+pub struct MinMaxState {
+    current: Option<NnSqlValue>,
+}
+
+impl MinMaxState {
+    /// Replaces the current value if `next_val` compared to it results in `replace_if`.
+    fn next(&mut self, next_val: NnSqlValue, replace_if: SqlCompareResult) -> Result<()> {
+        let replace = match &self.current {
+            None => true,
+            Some(current) => next_val.sql_compare(current)? == replace_if,
+        };
+        if replace {
+            self.current = Some(next_val);
+        }
+        Ok(())
+    }
+
+    fn finalize(self) -> SqlValue {
+        self.current.map_or(SqlValue::Null, SqlValue::NotNull)
+    }
+}
+
+/// Welford's online algorithm for variance.
+///
+/// Results are FLOAT.
+#[derive(Debug, Default)]
+pub struct VarianceState {
+    current_n: u64,
+    current_mean: f64,
+    /// Sum of squares of differences from the current mean.
+    current_m2: f64,
+}
+
+impl VarianceState {
+    fn next(&mut self, next_val: &NnSqlValue) -> Result<()> {
+        let next_val = to_f64(next_val)?;
+
+        self.current_n += 1;
+        let delta = next_val - self.current_mean;
+        self.current_mean += delta / (self.current_n as f64);
+        self.current_m2 += delta * (next_val - self.current_mean);
+        Ok(())
+    }
+
+    /// Chan's parallel algorithm.
+    fn merge(&mut self, other: Self) {
+        let n = self.current_n + other.current_n;
+        if n == 0 {
+            return;
+        }
+        let (n_a, n_b) = (self.current_n as f64, other.current_n as f64);
+        let delta = other.current_mean - self.current_mean;
+
+        self.current_mean += delta * n_b / n as f64;
+        self.current_m2 += other.current_m2 + delta * delta * n_a * n_b / n as f64;
+        self.current_n = n;
+    }
+
+    fn finalize_var_pop(self) -> SqlValue {
+        if self.current_n == 0 {
+            SqlValue::Null
+        } else {
+            float_value(self.current_m2 / self.current_n as f64)
+        }
+    }
+
+    fn finalize_stddev_pop(self) -> SqlValue {
+        if self.current_n == 0 {
+            SqlValue::Null
+        } else {
+            float_value((self.current_m2 / self.current_n as f64).sqrt())
+        }
+    }
+
+    /// NULL for a group with only 1 value.
+    fn finalize_stddev_samp(self) -> SqlValue {
+        if self.current_n < 2 {
+            SqlValue::Null
+        } else {
+            float_value((self.current_m2 / (self.current_n - 1) as f64).sqrt())
+        }
+    }
+}
+
+/// Keeps the input type.
+///
+/// Values are ordered by rowtime, not by arrival. Among values with the same rowtime, the first arrival is the first value and the last arrival is the last value.
+#[derive(Debug, Default)]
+pub struct FirstLastState {
+    current: Option<(NnSqlValue, SpringTimestamp)>,
+}
+
+impl FirstLastState {
+    /// Replaces the current value if `replace_if(rowtime, current_rowtime)` holds.
+    fn next<F>(&mut self, next_val: NnSqlValue, rowtime: SpringTimestamp, replace_if: F)
+    where
+        F: Fn(SpringTimestamp, SpringTimestamp) -> bool,
+    {
+        let replace = match &self.current {
+            None => true,
+            Some((_, current_rowtime)) => replace_if(rowtime, *current_rowtime),
+        };
+        if replace {
+            self.current = Some((next_val, rowtime));
+        }
+    }
+
+    fn finalize(self) -> SqlValue {
+        self.current
+            .map_or(SqlValue::Null, |(value, _)| SqlValue::NotNull(value))
+    }
+}
+
+/// Max number of values in a level of `PercentileSketchState`.
+const PERCENTILE_SKETCH_LEVEL_CAPACITY: usize = 256;
+
+/// Bounded-memory quantile sketch (a simplified KLL sketch).
+///
+/// Values in `levels[i]` have weight `2^i`. When a level gets full, its values are sorted and
+/// every other value is promoted to the next level, so memory usage grows only logarithmically
+/// to the number of values. The result is exact while a group has at most
+/// `PERCENTILE_SKETCH_LEVEL_CAPACITY` values.
+///
+/// The result is FLOAT, picked by nearest-rank method.
+#[derive(Debug)]
+pub struct PercentileSketchState {
+    percentile: f64,
+    levels: Vec<Vec<f64>>,
+    /// Alternates which half of values survives a compaction not to bias the result.
+    compact_odd: bool,
+}
+
+impl PercentileSketchState {
+    fn new(percentile: f64) -> Self {
+        Self {
+            percentile,
+            levels: vec![],
+            compact_odd: false,
+        }
+    }
+
+    fn next(&mut self, next_val: &NnSqlValue) -> Result<()> {
+        let next_val = to_f64(next_val)?;
+
+        if self.levels.is_empty() {
+            self.levels
+                .push(Vec::with_capacity(PERCENTILE_SKETCH_LEVEL_CAPACITY));
+        }
+        self.levels[0].push(next_val);
+
+        self.compact();
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) {
+        for (level, values) in other.levels.into_iter().enumerate() {
+            if self.levels.len() == level {
+                self.levels
+                    .push(Vec::with_capacity(PERCENTILE_SKETCH_LEVEL_CAPACITY));
+            }
+            self.levels[level].extend(values);
+        }
+        self.compact();
+    }
+
+    /// Compacts full levels from the lowest one.
+    fn compact(&mut self) {
+        let mut level = 0;
+        while level < self.levels.len() {
+            if self.levels[level].len() >= PERCENTILE_SKETCH_LEVEL_CAPACITY {
+                let mut values = std::mem::take(&mut self.levels[level]);
+                values.sort_by(f64::total_cmp);
+
+                let offset = usize::from(self.compact_odd);
+                self.compact_odd = !self.compact_odd;
+
+                if self.levels.len() == level + 1 {
+                    self.levels
+                        .push(Vec::with_capacity(PERCENTILE_SKETCH_LEVEL_CAPACITY));
+                }
+                self.levels[level + 1].extend(values.into_iter().skip(offset).step_by(2));
+            }
+            level += 1;
+        }
     }
 
-    pub fn finalize(self) -> f32 {
-        self.current_avg.round()
+    fn finalize(self) -> SqlValue {
+        let mut weighted_values = self
+            .levels
+            .iter()
+            .enumerate()
+            .flat_map(|(level, values)| values.iter().map(move |v| (*v, 1u64 << level)))
+            .collect::<Vec<_>>();
+        weighted_values.sort_by(|(v1, _), (v2, _)| v1.total_cmp(v2));
+
+        let total_weight: u64 = weighted_values.iter().map(|(_, weight)| weight).sum();
+        let rank = ((self.percentile * total_weight as f64).ceil() as u64).max(1);
+
+        let mut cumulative_weight = 0;
+        weighted_values
+            .into_iter()
+            .find(|(_, weight)| {
+                cumulative_weight += weight;
+                cumulative_weight >= rank
+            })
+            .map_or(SqlValue::Null, |(v, _)| float_value(v))
+    }
+}
+
+fn float_value(v: f64) -> SqlValue {
+    SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(v as f32)))
+}
+
+fn to_f64(v: &NnSqlValue) -> Result<f64> {
+    match v {
+        NnSqlValue::SmallInt(i) => Ok(*i as f64),
+        NnSqlValue::Integer(i) => Ok(*i as f64),
+        NnSqlValue::BigInt(i) => Ok(*i as f64),
+        NnSqlValue::UnsignedInteger(u) => Ok(*u as f64),
+        NnSqlValue::UnsignedBigInt(u) => Ok(*u as f64),
+        NnSqlValue::Float(f) => Ok(f.into_inner() as f64),
+        _ => Err(SpringError::Sql(anyhow!(
+            "numeric value is required for the aggregate function but got {:?}",
+            v.sql_type()
+        ))),
+    }
+}
+
+fn promote_for_sum(v: NnSqlValue) -> Result<NnSqlValue> {
+    match v {
+        NnSqlValue::SmallInt(_) | NnSqlValue::Integer(_) | NnSqlValue::BigInt(_) => {
+            Ok(NnSqlValue::BigInt(v.unpack::<i64>()?))
+        }
+        NnSqlValue::UnsignedInteger(_) | NnSqlValue::UnsignedBigInt(_) => {
+            Ok(NnSqlValue::UnsignedBigInt(v.unpack::<u64>()?))
+        }
+        NnSqlValue::Float(_) => Ok(v),
+        _ => Err(SpringError::Sql(anyhow!(
+            "SUM is not defined for {:?}",
+            v.sql_type()
+        ))),
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use float_cmp::approx_eq;
+
     use super::*;
 
+    fn aggregate(func: AggregateFunctionParameter, values: Vec<SqlValue>) -> Result<SqlValue> {
+        let mut state = AggregateState::new(func);
+        for v in values {
+            state.next(v, SpringTimestamp::fx_ts1())?;
+        }
+        Ok(state.finalize())
+    }
+
+    fn unpack_float(v: SqlValue) -> f32 {
+        match v {
+            SqlValue::NotNull(NnSqlValue::Float(f)) => f.into_inner(),
+            _ => panic!("FLOAT is expected but got {:?}", v),
+        }
+    }
+
+    fn int(i: i32) -> SqlValue {
+        SqlValue::NotNull(NnSqlValue::Integer(i))
+    }
+
+    fn float(f: f32) -> SqlValue {
+        SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(f)))
+    }
+
+    #[test]
+    fn test_avg_state() -> Result<()> {
+        let avg = aggregate(
+            AggregateFunctionParameter::Avg,
+            vec![int(100), int(400), int(101)],
+        )?;
+        match avg {
+            SqlValue::NotNull(NnSqlValue::Float(f)) => {
+                assert!(approx_eq!(f32, f.into_inner(), 601. / 3.))
+            }
+            _ => panic!("unexpected AVG result: {:?}", avg),
+        }
+        Ok(())
+    }
+
     #[test]
-    fn test_avg_state() {
-        let mut state = AvgState::default();
-        state.next(100.);
-        state.next(400.);
-        state.next(100.);
-        assert_eq!(state.finalize().round() as i32, 200);
+    fn test_sum_state() -> Result<()> {
+        let sum = aggregate(AggregateFunctionParameter::Sum, vec![int(i32::MAX), int(1)])?;
+        assert!(
+            matches!(sum, SqlValue::NotNull(NnSqlValue::BigInt(v)) if v == i32::MAX as i64 + 1)
+        );
+
+        let sum = aggregate(AggregateFunctionParameter::Sum, vec![int(5)])?;
+        assert!(matches!(sum, SqlValue::NotNull(NnSqlValue::BigInt(5))));
+
+        let sum = aggregate(
+            AggregateFunctionParameter::Sum,
+            vec![float(0.5), float(1.25)],
+        )?;
+        assert_eq!(sum, float(1.75));
+
+        assert!(aggregate(
+            AggregateFunctionParameter::Sum,
+            vec![SqlValue::NotNull(NnSqlValue::Text("a".to_string()))]
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_min_max_state() -> Result<()> {
+        let values = vec![int(3), SqlValue::Null, int(-2), int(7)];
+
+        let count = aggregate(AggregateFunctionParameter::Count, values.clone())?;
+        assert!(matches!(count, SqlValue::NotNull(NnSqlValue::BigInt(3))));
+
+        let min = aggregate(AggregateFunctionParameter::Min, values.clone())?;
+        assert!(matches!(min, SqlValue::NotNull(NnSqlValue::Integer(-2))));
+
+        let max = aggregate(AggregateFunctionParameter::Max, values)?;
+        assert!(matches!(max, SqlValue::NotNull(NnSqlValue::Integer(7))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_all_null_input() -> Result<()> {
+        let values = vec![SqlValue::Null, SqlValue::Null];
+
+        let count = aggregate(AggregateFunctionParameter::Count, values.clone())?;
+        assert!(matches!(count, SqlValue::NotNull(NnSqlValue::BigInt(0))));
+
+        for func in [
+            AggregateFunctionParameter::Avg,
+            AggregateFunctionParameter::Sum,
+            AggregateFunctionParameter::Min,
+            AggregateFunctionParameter::Max,
+        ] {
+            assert!(matches!(aggregate(func, values.clone())?, SqlValue::Null));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_variance_states() -> Result<()> {
+        let values = vec![2, 4, 4, 4, 5, 5, 7, 9]
+            .into_iter()
+            .map(int)
+            .chain([SqlValue::Null])
+            .collect::<Vec<_>>();
+
+        let var_pop = aggregate(AggregateFunctionParameter::VarPop, values.clone())?;
+        assert!(approx_eq!(f32, unpack_float(var_pop), 4.0));
+
+        let stddev_pop = aggregate(AggregateFunctionParameter::StddevPop, values.clone())?;
+        assert!(approx_eq!(f32, unpack_float(stddev_pop), 2.0));
+
+        let stddev_samp = aggregate(AggregateFunctionParameter::StddevSamp, values)?;
+        assert!(approx_eq!(
+            f32,
+            unpack_float(stddev_samp),
+            (32.0f32 / 7.0).sqrt()
+        ));
+
+        assert!(matches!(
+            aggregate(AggregateFunctionParameter::StddevSamp, vec![int(1)])?,
+            SqlValue::Null
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_first_last_value_states() -> Result<()> {
+        // arrival order differs from rowtime order
+        let values = vec![
+            (int(2), SpringTimestamp::fx_ts2()),
+            (int(1), SpringTimestamp::fx_ts1()),
+            (int(3), SpringTimestamp::fx_ts3()),
+            (int(33), SpringTimestamp::fx_ts3()),
+            (SqlValue::Null, SpringTimestamp::fx_ts3()),
+        ];
+
+        for (func, expected) in [
+            (AggregateFunctionParameter::FirstValue, 1),
+            (AggregateFunctionParameter::LastValue, 33),
+        ] {
+            let mut state = AggregateState::new(func);
+            for (v, rowtime) in values.clone() {
+                state.next(v, rowtime)?;
+            }
+            assert!(
+                matches!(state.finalize(), SqlValue::NotNull(NnSqlValue::Integer(v)) if v == expected)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() -> Result<()> {
+        let values = (1..=600).map(int).collect::<Vec<_>>();
+        let (values_a, values_b) = values.split_at(250);
+
+        for func in [
+            AggregateFunctionParameter::Avg,
+            AggregateFunctionParameter::Sum,
+            AggregateFunctionParameter::Count,
+            AggregateFunctionParameter::Min,
+            AggregateFunctionParameter::Max,
+            AggregateFunctionParameter::VarPop,
+            AggregateFunctionParameter::StddevSamp,
+            AggregateFunctionParameter::ApproxPercentile {
+                percentile: OrderedFloat(0.5),
+            },
+        ] {
+            let mut state_a = AggregateState::new(func);
+            for v in values_a {
+                state_a.next(v.clone(), SpringTimestamp::fx_ts1())?;
+            }
+            let mut state_b = AggregateState::new(func);
+            for v in values_b {
+                state_b.next(v.clone(), SpringTimestamp::fx_ts1())?;
+            }
+            state_a.merge(state_b)?;
+
+            let merged = state_a.finalize();
+            let expected = aggregate(func, values.clone())?;
+            match (&merged, &expected) {
+                (
+                    SqlValue::NotNull(NnSqlValue::Float(_)),
+                    SqlValue::NotNull(NnSqlValue::Float(_)),
+                ) if matches!(func, AggregateFunctionParameter::ApproxPercentile { .. }) => {
+                    assert!((unpack_float(merged) - 300.0).abs() < 10.0)
+                }
+                (
+                    SqlValue::NotNull(NnSqlValue::Float(_)),
+                    SqlValue::NotNull(NnSqlValue::Float(_)),
+                ) => assert!(approx_eq!(
+                    f32,
+                    unpack_float(merged),
+                    unpack_float(expected),
+                    epsilon = 0.01
+                )),
+                _ => assert_eq!(merged, expected),
+            }
+        }
+
+        let mut first = AggregateState::new(AggregateFunctionParameter::FirstValue);
+        first.next(int(2), SpringTimestamp::fx_ts2())?;
+        let mut other = AggregateState::new(AggregateFunctionParameter::FirstValue);
+        other.next(int(1), SpringTimestamp::fx_ts1())?;
+        first.merge(other)?;
+        assert_eq!(first.finalize(), int(1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_approx_percentile_state() -> Result<()> {
+        let percentile = |p: f64| AggregateFunctionParameter::ApproxPercentile {
+            percentile: OrderedFloat(p),
+        };
+
+        // exact while values fit in a level
+        let values = (1..=100).rev().map(int).collect::<Vec<_>>();
+        assert_eq!(
+            unpack_float(aggregate(percentile(0.95), values.clone())?),
+            95.0
+        );
+        assert_eq!(
+            unpack_float(aggregate(percentile(0.0), values.clone())?),
+            1.0
+        );
+        assert_eq!(unpack_float(aggregate(percentile(1.0), values)?), 100.0);
+
+        // approximate with bounded memory
+        let n = 100_000;
+        let mut state = AggregateState::new(percentile(0.5));
+        for i in 0..n {
+            // shuffle values deterministically
+            let v = (i * 7919) % n;
+            state.next(int(v), SpringTimestamp::fx_ts1())?;
+        }
+        assert!(
+            state.mem_size() < PERCENTILE_SKETCH_LEVEL_CAPACITY * size_of::<f64>() * 10,
+            "{}",
+            state.mem_size()
+        );
+        let median = unpack_float(state.finalize());
+        assert!((median - 50_000.0).abs() < 1_000.0, "{}", median);
+
+        Ok(())
     }
 }
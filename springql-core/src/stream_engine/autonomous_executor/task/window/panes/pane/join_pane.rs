@@ -1,21 +1,32 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
 use crate::{
+    api::error::{Result, SpringError},
     expr_resolver::ExprResolver,
     mem_size::MemSize,
-    pipeline::{Field, JoinParameter, JoinType, WindowOperationParameter},
+    pipeline::{JoinParameter, JoinType, WindowOperationParameter},
     stream_engine::{
         autonomous_executor::{
             performance_metrics::WindowInFlowByWindowTask,
-            task::{tuple::Tuple, window::panes::pane::Pane},
+            task::{
+                tuple::Tuple,
+                window::{
+                    panes::pane::{GroupByValues, Pane},
+                    Failure,
+                },
+            },
+            SqlValueHashKey,
         },
         time::SpringTimestamp,
         RowTime, SqlValue,
     },
 };
 
+/// A tuple joined on close, or a pair of left and right tuples whose ON expression cannot be evaluated (joined together).
+pub type JoinedTuple = std::result::Result<Tuple, (SpringError, Tuple)>;
+
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum JoinDir {
     Left,
@@ -30,11 +41,18 @@ pub struct JoinPane {
     join_parameter: JoinParameter,
 
     left_tuples: Vec<Tuple>,
+    /// Hash of `JoinParameter::equi_keys` of each left tuple (None for NULL keys or no equi-join keys).
+    left_keys: Vec<Option<Vec<SqlValueHashKey>>>,
     right_tuples: Vec<Tuple>,
+
+    /// Hash of `JoinParameter::equi_keys` -> indexes of `right_tuples`.
+    ///
+    /// Right tuples with NULL key are not indexed since they never equal to any left tuple.
+    right_index: HashMap<Vec<SqlValueHashKey>, Vec<usize>>,
 }
 
 impl Pane for JoinPane {
-    type CloseOut = Tuple;
+    type CloseOut = JoinedTuple;
     type DispatchArg = JoinDir;
 
     /// # Panics
@@ -55,11 +73,22 @@ impl Pane for JoinPane {
             open_at,
             close_at,
             left_tuples: Vec::new(),
+            left_keys: Vec::new(),
             right_tuples: Vec::new(),
+            right_index: HashMap::new(),
             join_parameter,
         }
     }
 
+    /// Join windows have a single session for all tuples.
+    fn session_key(
+        _param: &WindowOperationParameter,
+        _expr_resolver: &ExprResolver,
+        _tuple: &Tuple,
+    ) -> Result<GroupByValues> {
+        Ok(GroupByValues::default())
+    }
+
     fn open_at(&self) -> SpringTimestamp {
         self.open_at
     }
@@ -68,18 +97,57 @@ impl Pane for JoinPane {
         self.close_at
     }
 
-    /// Dispatch to left_tuples
+    /// Dispatch to left_tuples (and left_keys) or right_tuples (and right_index)
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `tuple` does not have an equi-join key column. The pane is not updated then.
     fn dispatch(
         &mut self,
         _expr_resolver: &ExprResolver,
         tuple: &Tuple,
         dir: JoinDir,
-    ) -> WindowInFlowByWindowTask {
+    ) -> std::result::Result<WindowInFlowByWindowTask, Failure> {
+        let key = self
+            .hash_key(tuple, dir)
+            .map_err(|e| (e, WindowInFlowByWindowTask::zero()))?;
+
         match dir {
-            JoinDir::Left => self.left_tuples.push(tuple.clone()),
-            JoinDir::Right => self.right_tuples.push(tuple.clone()),
+            JoinDir::Left => {
+                self.left_keys.push(key);
+                self.left_tuples.push(tuple.clone())
+            }
+            JoinDir::Right => {
+                if let Some(key) = key {
+                    self.right_index
+                        .entry(key)
+                        .or_default()
+                        .push(self.right_tuples.len());
+                }
+                self.right_tuples.push(tuple.clone())
+            }
         }
-        WindowInFlowByWindowTask::new(0, tuple.mem_size() as i64)
+        Ok(WindowInFlowByWindowTask::new(0, tuple.mem_size() as i64))
+    }
+
+    fn merge(&mut self, other: Self) -> std::result::Result<WindowInFlowByWindowTask, Failure> {
+        self.open_at = self.open_at.min(other.open_at);
+        self.close_at = self.close_at.max(other.close_at);
+
+        self.left_tuples.extend(other.left_tuples);
+        self.left_keys.extend(other.left_keys);
+
+        let offset = self.right_tuples.len();
+        for (key, indexes) in other.right_index {
+            self.right_index
+                .entry(key)
+                .or_default()
+                .extend(indexes.into_iter().map(|idx| idx + offset));
+        }
+        self.right_tuples.extend(other.right_tuples);
+
+        Ok(WindowInFlowByWindowTask::zero())
     }
 
     fn close(
@@ -87,51 +155,132 @@ impl Pane for JoinPane {
         expr_resolver: &ExprResolver,
     ) -> (Vec<Self::CloseOut>, WindowInFlowByWindowTask) {
         match self.join_parameter.join_type {
-            JoinType::LeftOuter => self.left_outer_join(expr_resolver),
+            JoinType::Inner => self.join(expr_resolver, false, false),
+            JoinType::LeftOuter => self.join(expr_resolver, true, false),
+            JoinType::RightOuter => self.join(expr_resolver, false, true),
+            JoinType::FullOuter => self.join(expr_resolver, true, true),
         }
     }
 }
 
 impl JoinPane {
-    fn left_outer_join(
+    /// Joined tuples are in the order of left tuples. Right tuples not joined to any left tuple follow them (if `pad_right`).
+    ///
+    /// - `pad_left`: left tuples not joined to any right tuple are joined to a NULL right tuple.
+    /// - `pad_right`: right tuples not joined to any left tuple are joined to a NULL left tuple.
+    ///
+    /// ON expression is evaluated only for the right tuples found by `join_candidates()`.
+    /// A pair whose ON expression cannot be evaluated as BOOLEAN is returned as an error and counted as not joined.
+    fn join(
         self,
         expr_resolver: &ExprResolver,
-    ) -> (Vec<Tuple>, WindowInFlowByWindowTask) {
+        pad_left: bool,
+        pad_right: bool,
+    ) -> (Vec<JoinedTuple>, WindowInFlowByWindowTask) {
         let window_in_flow = self.calc_window_in_flow_on_close();
 
         let null_right = self.null_right_tuple();
+        let mut right_joined = vec![false; self.right_tuples.len()];
 
-        // using Nested Loop Join.
         let mut res_tuples = Vec::new();
-        for left_tuple in self.left_tuples {
+        for (left_tuple, left_key) in self.left_tuples.iter().zip(&self.left_keys) {
             let mut joined_to_the_left = vec![];
 
-            for right_tuple in &self.right_tuples {
+            for right_idx in self.join_candidates(left_key) {
+                let right_tuple = &self.right_tuples[right_idx];
+
                 // TODO less clone. ExprResolver takes two tuples to resolve ColumnReference?
                 let joined_tuple = left_tuple.clone().join(right_tuple.clone());
 
                 let on_bool = expr_resolver
                     .eval_value_expr(self.join_parameter.on_expr, &joined_tuple)
-                    .expect("TODO Result")
-                    .to_bool()
-                    .expect("TODO Result");
+                    .and_then(|v| v.to_bool());
 
-                if on_bool {
-                    joined_to_the_left.push(joined_tuple);
+                match on_bool {
+                    Ok(true) => {
+                        joined_to_the_left.push(joined_tuple);
+                        right_joined[right_idx] = true;
+                    }
+                    Ok(false) => {}
+                    Err(e) => res_tuples.push(Err((e, joined_tuple))),
                 }
             }
 
-            if joined_to_the_left.is_empty() {
-                let joined_tuple = left_tuple.join(null_right.clone());
+            if joined_to_the_left.is_empty() && pad_left {
+                let joined_tuple = left_tuple.clone().join(null_right.clone());
                 joined_to_the_left.push(joined_tuple);
             }
 
-            res_tuples.extend(joined_to_the_left);
+            res_tuples.extend(joined_to_the_left.into_iter().map(Ok));
+        }
+
+        if pad_right {
+            for (right_tuple, _) in self
+                .right_tuples
+                .iter()
+                .zip(right_joined)
+                .filter(|(_, joined)| !joined)
+            {
+                let joined_tuple = self
+                    .null_left_tuple(right_tuple.rowtime())
+                    .join(right_tuple.clone());
+                res_tuples.push(Ok(joined_tuple));
+            }
         }
 
         (res_tuples, window_in_flow)
     }
 
+    /// Indexes of right tuples which may be joined to a left tuple with `left_key`, in the dispatched order.
+    ///
+    /// All the right tuples (nested loop) if the ON expression has no equi-join keys.
+    fn join_candidates(&self, left_key: &Option<Vec<SqlValueHashKey>>) -> Vec<usize> {
+        if self.join_parameter.equi_keys.is_empty() {
+            (0..self.right_tuples.len()).collect()
+        } else {
+            left_key
+                .as_ref()
+                .and_then(|key| self.right_index.get(key))
+                .cloned()
+                .unwrap_or_default()
+        }
+    }
+
+    /// Hash of the equi-join key columns of a tuple from `dir`.
+    ///
+    /// None if the ON expression has no equi-join keys or any of the key values is NULL.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `tuple` does not have an equi-join key column.
+    fn hash_key(&self, tuple: &Tuple, dir: JoinDir) -> Result<Option<Vec<SqlValueHashKey>>> {
+        let equi_keys = &self.join_parameter.equi_keys;
+        if equi_keys.is_empty() {
+            return Ok(None);
+        }
+
+        let values = equi_keys
+            .iter()
+            .map(|(left_colref, right_colref)| {
+                let colref = match dir {
+                    JoinDir::Left => left_colref,
+                    JoinDir::Right => right_colref,
+                };
+                tuple.get_value(colref)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(values
+            .iter()
+            .map(|v| match v {
+                SqlValue::Null => None,
+                v => Some(SqlValueHashKey::from(v)),
+            })
+            .collect())
+    }
+
+    /// All the tuples in this pane are released on close, whether joined or not.
     fn calc_window_in_flow_on_close(&self) -> WindowInFlowByWindowTask {
         let left_size = self.left_tuples.iter().map(|t| t.mem_size()).sum::<usize>();
         let right_size = self
@@ -148,13 +297,219 @@ impl JoinPane {
         let rowtime =
             RowTime::EventTime(SpringTimestamp::from_str("1970-01-01 00:00:00.000000000").unwrap());
 
-        let fields = self
-            .join_parameter
-            .right_colrefs
-            .iter()
-            .map(|colref| Field::new(colref.clone(), SqlValue::Null))
-            .collect();
+        Tuple::nulls(rowtime, &self.join_parameter.right_colrefs)
+    }
+
+    /// Joined tuple takes rowtime from left. So a NULL left tuple has the rowtime of the right tuple to join.
+    fn null_left_tuple(&self, rowtime: RowTime) -> Tuple {
+        Tuple::nulls(rowtime, &self.join_parameter.left_colrefs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::error::SpringError,
+        expression::ValueExpr,
+        pipeline::{ColumnName, ColumnReference, StreamName},
+        sql_processor::SelectFieldSyntax,
+    };
+
+    fn ts(s: &str) -> SpringTimestamp {
+        SpringTimestamp::from_str(&format!("2020-01-01 00:00:{}.000000000", s)).unwrap()
+    }
+
+    fn join_pane(
+        join_type: JoinType,
+        equi_keys: Vec<(ColumnReference, ColumnReference)>,
+    ) -> (JoinPane, ExprResolver) {
+        // ... FROM trade [join_type] city_temperature ON trade.timestamp = city_temperature.timestamp
+        let on_expr = ValueExpr::factory_eq(
+            ValueExpr::factory_colref(
+                StreamName::fx_trade().as_ref(),
+                ColumnName::fx_timestamp().as_ref(),
+            ),
+            ValueExpr::factory_colref(
+                StreamName::fx_city_temperature().as_ref(),
+                ColumnName::fx_timestamp().as_ref(),
+            ),
+        );
+        join_pane_on(on_expr, join_type, equi_keys)
+    }
+
+    fn join_pane_on(
+        on_expr: ValueExpr,
+        join_type: JoinType,
+        equi_keys: Vec<(ColumnReference, ColumnReference)>,
+    ) -> (JoinPane, ExprResolver) {
+        let (mut expr_resolver, _) = ExprResolver::new(Vec::<SelectFieldSyntax>::new());
+        let on_expr = expr_resolver.register_value_expr(on_expr);
+
+        let join_parameter = JoinParameter {
+            join_type,
+            left_colrefs: vec![
+                ColumnReference::fx_trade_timestamp(),
+                ColumnReference::fx_trade_ticker(),
+                ColumnReference::fx_trade_amount(),
+            ],
+            right_colrefs: vec![
+                ColumnReference::fx_city_temperature_timestamp(),
+                ColumnReference::fx_city_temperature_city(),
+                ColumnReference::fx_city_temperature_temperature(),
+            ],
+            on_expr,
+            equi_keys,
+        };
+        let pane = JoinPane::new(
+            ts("00"),
+            ts("10"),
+            WindowOperationParameter::Join(join_parameter),
+        );
+        (pane, expr_resolver)
+    }
+
+    fn hash_equi_keys() -> Vec<(ColumnReference, ColumnReference)> {
+        vec![(
+            ColumnReference::fx_trade_timestamp(),
+            ColumnReference::fx_city_temperature_timestamp(),
+        )]
+    }
+
+    /// (trade.amount, city_temperature.temperature) of joined tuples.
+    fn join(
+        join_type: JoinType,
+        equi_keys: Vec<(ColumnReference, ColumnReference)>,
+    ) -> Vec<(Option<i32>, Option<i32>)> {
+        let (mut pane, expr_resolver) = join_pane(join_type, equi_keys);
+
+        for (t, amount) in [("00", 100), ("01", 200)] {
+            pane.dispatch(
+                &expr_resolver,
+                &Tuple::factory_trade(ts(t), "", amount),
+                JoinDir::Left,
+            )
+            .unwrap();
+        }
+        for (t, temperature) in [("00", 10), ("02", 20)] {
+            pane.dispatch(
+                &expr_resolver,
+                &Tuple::factory_city_temperature(ts(t), "", temperature),
+                JoinDir::Right,
+            )
+            .unwrap();
+        }
+
+        let (tuples, window_in_flow) = pane.close(&expr_resolver);
+        assert!(window_in_flow.window_gain_bytes_rows < 0);
+
+        tuples
+            .into_iter()
+            .map(|joined| {
+                let tuple = joined.unwrap();
+                let get = |colref| match tuple.get_value(&colref).unwrap() {
+                    SqlValue::Null => None,
+                    SqlValue::NotNull(v) => Some(v.unpack::<i32>().unwrap()),
+                };
+                if get(ColumnReference::fx_trade_amount()).is_none() {
+                    // NULL left tuple takes rowtime from right
+                    assert_eq!(tuple.rowtime().as_timestamp(), ts("02"));
+                }
+                (
+                    get(ColumnReference::fx_trade_amount()),
+                    get(ColumnReference::fx_city_temperature_temperature()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_join_types() {
+        let nested_loop = vec![];
+        let hash = hash_equi_keys();
+
+        for equi_keys in [nested_loop, hash] {
+            assert_eq!(
+                join(JoinType::Inner, equi_keys.clone()),
+                vec![(Some(100), Some(10))]
+            );
+            assert_eq!(
+                join(JoinType::LeftOuter, equi_keys.clone()),
+                vec![(Some(100), Some(10)), (Some(200), None)]
+            );
+            assert_eq!(
+                join(JoinType::RightOuter, equi_keys.clone()),
+                vec![(Some(100), Some(10)), (None, Some(20))]
+            );
+            assert_eq!(
+                join(JoinType::FullOuter, equi_keys),
+                vec![(Some(100), Some(10)), (Some(200), None), (None, Some(20))]
+            );
+        }
+    }
+
+    #[test]
+    fn test_on_expr_failure() {
+        // ... FROM trade LEFT OUTER JOIN city_temperature ON trade.amount
+        let on_expr = ValueExpr::factory_colref(
+            StreamName::fx_trade().as_ref(),
+            ColumnName::fx_amount().as_ref(),
+        );
+        let (mut pane, expr_resolver) = join_pane_on(on_expr, JoinType::LeftOuter, vec![]);
+
+        pane.dispatch(
+            &expr_resolver,
+            &Tuple::factory_trade(ts("00"), "", 100),
+            JoinDir::Left,
+        )
+        .unwrap();
+        pane.dispatch(
+            &expr_resolver,
+            &Tuple::factory_city_temperature(ts("00"), "", 10),
+            JoinDir::Right,
+        )
+        .unwrap();
+
+        let (tuples, _) = pane.close(&expr_resolver);
+        assert_eq!(tuples.len(), 2);
+
+        // failed pair
+        let (e, pair) = tuples[0].as_ref().unwrap_err();
+        assert!(matches!(e, SpringError::Sql(_)));
+        assert_eq!(
+            pair.get_value(&ColumnReference::fx_city_temperature_temperature())
+                .unwrap()
+                .unwrap()
+                .unpack::<i32>()
+                .unwrap(),
+            10
+        );
+
+        // the left tuple is padded since it is not joined
+        let padded = tuples[1].as_ref().unwrap();
+        assert!(matches!(
+            padded
+                .get_value(&ColumnReference::fx_city_temperature_temperature())
+                .unwrap(),
+            SqlValue::Null
+        ));
+    }
+
+    #[test]
+    fn test_dispatch_without_key_column() {
+        let (mut pane, expr_resolver) = join_pane(JoinType::Inner, hash_equi_keys());
 
-        Tuple::new(rowtime, fields)
+        // left tuple must have trade.timestamp
+        let (e, window_in_flow) = pane
+            .dispatch(
+                &expr_resolver,
+                &Tuple::factory_city_temperature(ts("00"), "", 10),
+                JoinDir::Left,
+            )
+            .unwrap_err();
+        assert!(matches!(e, SpringError::Sql(_)));
+        assert_eq!(window_in_flow.window_gain_bytes_rows, 0);
+        assert!(pane.left_tuples.is_empty());
+        assert!(pane.left_keys.is_empty());
     }
 }
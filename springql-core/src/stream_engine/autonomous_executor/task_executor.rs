@@ -77,9 +77,10 @@ impl TaskExecutor {
             .all_sources()
             .into_iter()
             .try_for_each(|source_reader| {
+                let source_stream = pipeline.get_stream(source_reader.dest_source_stream())?;
                 self.repos
                     .source_reader_repository()
-                    .register(source_reader)
+                    .register(source_reader, source_stream)
             })?;
         pipeline
             .all_sinks()
@@ -87,6 +88,10 @@ impl TaskExecutor {
             .try_for_each(|sink_writer| {
                 self.repos.sink_writer_repository().register(sink_writer)
             })?;
+        pipeline
+            .all_tables()
+            .iter()
+            .for_each(|table| self.repos.table_repository().register(table));
 
         Ok(())
     }
@@ -76,7 +76,11 @@ impl TaskWorkerThreadHandler {
     where
         S: Scheduler,
     {
-        if let (Some(pipeline_derivatives), Some(metrics)) =
+        if thread_arg.repos.fatal_error().is_raised() {
+            // Pipeline stopped
+            thread::sleep(Duration::from_millis(TASK_WAIT_MSEC));
+            current_state
+        } else if let (Some(pipeline_derivatives), Some(metrics)) =
             (&current_state.pipeline_derivatives, &current_state.metrics)
         {
             let task_executor_lock = &thread_arg.task_executor_lock;
@@ -17,12 +17,12 @@ mod edge_ref;
 pub use queue_id::{QueueId, RowQueueId, WindowQueueId};
 pub use task_id::TaskId;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use petgraph::graph::{DiGraph, NodeIndex};
 
 use crate::{
-    pipeline::{Edge, Pipeline, PipelineVersion, StreamName},
+    pipeline::{DeadLetterTarget, Edge, Pipeline, PipelineVersion, StreamName},
     stream_engine::autonomous_executor::task_graph::edge_ref::MyEdgeRef,
 };
 
@@ -40,6 +40,9 @@ pub struct TaskGraph {
     g: DiGraph<TaskId, QueueIdWithUpstream>,
     task_id_node_map: HashMap<TaskId, NodeIndex>,
     queue_id_edge_map: HashMap<QueueId, MyEdgeRef>,
+
+    /// Queues from tasks to the sink writers of their dead-letter streams.
+    dead_letter_queues: HashSet<QueueId>,
 }
 
 impl TaskGraph {
@@ -49,6 +52,7 @@ impl TaskGraph {
             g: DiGraph::default(),
             task_id_node_map: HashMap::default(),
             queue_id_edge_map: HashMap::default(),
+            dead_letter_queues: HashSet::default(),
         }
     }
 
@@ -82,7 +86,14 @@ impl TaskGraph {
             .cloned()
             .collect()
     }
+    /// Dead-letter queues are not included: rows produced by `task_id` are not put into them.
     pub fn output_queues(&self, task_id: &TaskId) -> Vec<QueueId> {
+        self.outgoing_queues(task_id)
+            .into_iter()
+            .filter(|q| !self.dead_letter_queues.contains(q))
+            .collect()
+    }
+    fn outgoing_queues(&self, task_id: &TaskId) -> Vec<QueueId> {
         let i = self.find_node(task_id);
         self.g
             .edges_directed(i, petgraph::EdgeDirection::Outgoing)
@@ -106,8 +117,9 @@ impl TaskGraph {
             })
     }
 
+    /// Including the sink tasks of dead-letter streams.
     pub fn downstream_tasks(&self, task_id: &TaskId) -> Vec<TaskId> {
-        self.output_queues(task_id)
+        self.outgoing_queues(task_id)
             .iter()
             .map(|q| self.downstream_task(q))
             .collect()
@@ -176,6 +188,19 @@ impl TaskGraph {
         let _ = self.queue_id_edge_map.insert(queue_id.queue_id, edge_ref);
     }
 
+    /// # Panics
+    ///
+    /// `source` or `target` task is not added in the graph.
+    pub fn add_dead_letter_queue(
+        &mut self,
+        queue_id: QueueIdWithUpstream,
+        source: TaskId,
+        target: TaskId,
+    ) {
+        let _ = self.dead_letter_queues.insert(queue_id.queue_id.clone());
+        self.add_queue(queue_id, source, target);
+    }
+
     /// # Panics
     ///
     /// `task_id` is not added in the graph.
@@ -233,19 +258,26 @@ impl From<&Pipeline> for TaskGraph {
                         })
                 }
                 Edge::Sink(sink) => {
-                    let queue_id = QueueId::from_sink(sink);
-                    let target = TaskId::from_sink(sink);
-                    let source_edge = pipeline_graph
-                        .upstream_edges(&edge_ref)
-                        .first()
-                        .expect("sink writer must have 1 upstream pump")
-                        .weight();
-                    let source = TaskId::from(source_edge);
-                    task_graph.add_queue(
-                        QueueIdWithUpstream::new(queue_id, sink.sink_upstream().clone()),
-                        source,
-                        target,
+                    let queue_id = QueueIdWithUpstream::new(
+                        QueueId::from_sink(sink),
+                        sink.sink_upstream().clone(),
                     );
+                    let target = TaskId::from_sink(sink);
+                    if let Some(source_edge_ref) = pipeline_graph.upstream_edges(&edge_ref).first()
+                    {
+                        let source = TaskId::from(source_edge_ref.weight());
+                        task_graph.add_queue(queue_id, source, target);
+                    } else if let Some(dead_letter_target) =
+                        pipeline.dead_letter_target(sink.sink_upstream())
+                    {
+                        // dead letters come from the target task of the dead-letter stream
+                        let source = task_graph
+                            .tasks()
+                            .into_iter()
+                            .find(|task| DeadLetterTarget::from(task) == *dead_letter_target)
+                            .expect("target of dead-letter stream must be in the pipeline");
+                        task_graph.add_dead_letter_queue(queue_id, source, target);
+                    }
                 }
                 Edge::Source(_) => {} // no queue is created for source task
             };
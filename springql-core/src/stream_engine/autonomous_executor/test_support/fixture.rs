@@ -476,6 +476,9 @@ impl MetricsUpdateByTaskExecution {
         let task = TaskMetricsUpdateByTask::new(
             TaskId::fx_split_join_t1(),
             WallClockDuration::from_micros(200),
+            0,
+            0,
+            0,
         );
         let out_queues = vec![OutQueueMetricsUpdateByTask::new(
             QueueId::fx_split_join_q2(),
@@ -488,6 +491,9 @@ impl MetricsUpdateByTaskExecution {
         let task = TaskMetricsUpdateByTask::new(
             TaskId::fx_split_join_t2(),
             WallClockDuration::from_micros(50),
+            0,
+            0,
+            0,
         );
         let in_queues = vec![InQueueMetricsUpdateByTask::new(
             InQueueMetricsUpdateByCollect::Row {
@@ -508,6 +514,9 @@ impl MetricsUpdateByTaskExecution {
         let task = TaskMetricsUpdateByTask::new(
             TaskId::fx_split_join_t3(),
             WallClockDuration::from_micros(90),
+            0,
+            0,
+            0,
         );
         let in_queues = vec![InQueueMetricsUpdateByTask::new(
             InQueueMetricsUpdateByCollect::Window {
@@ -524,6 +533,9 @@ impl MetricsUpdateByTaskExecution {
         let task = TaskMetricsUpdateByTask::new(
             TaskId::fx_split_join_t6(),
             WallClockDuration::from_micros(800),
+            0,
+            0,
+            0,
         );
         let out_queues = vec![OutQueueMetricsUpdateByTask::new(
             QueueId::fx_split_join_q7(),
@@ -536,6 +548,9 @@ impl MetricsUpdateByTaskExecution {
         let task = TaskMetricsUpdateByTask::new(
             TaskId::fx_split_join_t7(),
             WallClockDuration::from_micros(50),
+            0,
+            0,
+            0,
         );
         let in_queues = vec![InQueueMetricsUpdateByTask::new(
             InQueueMetricsUpdateByCollect::Row {
@@ -555,6 +570,9 @@ impl MetricsUpdateByTaskExecution {
         let task = TaskMetricsUpdateByTask::new(
             TaskId::fx_split_join_t8(),
             WallClockDuration::from_micros(50),
+            0,
+            0,
+            0,
         );
         let in_queues = vec![InQueueMetricsUpdateByTask::new(
             InQueueMetricsUpdateByCollect::Row {
@@ -575,6 +593,9 @@ impl MetricsUpdateByTaskExecution {
         let task = TaskMetricsUpdateByTask::new(
             TaskId::fx_split_join_t9(),
             WallClockDuration::from_micros(50),
+            0,
+            0,
+            0,
         );
         let in_queues = vec![InQueueMetricsUpdateByTask::new(
             InQueueMetricsUpdateByCollect::Row {
@@ -595,6 +616,9 @@ impl MetricsUpdateByTaskExecution {
         let task = TaskMetricsUpdateByTask::new(
             TaskId::fx_split_join_t10(),
             WallClockDuration::from_micros(200),
+            0,
+            0,
+            0,
         );
         let in_queues = vec![InQueueMetricsUpdateByTask::new(
             InQueueMetricsUpdateByCollect::Row {
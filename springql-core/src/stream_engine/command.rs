@@ -7,8 +7,8 @@ mod query_plan;
 pub use crate::stream_engine::command::alter_pipeline_command::AlterPipelineCommand;
 pub use insert_plan::InsertPlan;
 pub use query_plan::{
-    CollectOp, GroupAggregateWindowOp, JoinOp, JoinWindowOp, LowerOps, ProjectionOp, QueryPlan,
-    UpperOps,
+    CollectOp, GroupAggregateWindowOp, HavingOp, JoinOp, JoinWindowOp, LookupJoinOp, LowerOps,
+    ProjectionOp, QueryPlan, SelectionOp, UpperOps,
 };
 
 #[derive(Clone, PartialEq, Debug)]
@@ -1,6 +1,8 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::pipeline::{PumpModel, SinkWriterModel, SourceReaderModel, StreamModel};
+use crate::pipeline::{
+    DeadLetterStreamModel, PumpModel, SinkWriterModel, SourceReaderModel, StreamModel, TableModel,
+};
 
 #[allow(clippy::enum_variant_names)]
 #[derive(Clone, PartialEq, Debug)]
@@ -9,6 +11,8 @@ pub enum AlterPipelineCommand {
     CreateSourceReader(SourceReaderModel),
     CreateStream(StreamModel),
     CreateSinkStream(StreamModel),
+    CreateDeadLetterStream(DeadLetterStreamModel),
     CreateSinkWriter(SinkWriterModel),
     CreatePump(Box<PumpModel>),
+    CreateTable(TableModel),
 }
@@ -3,7 +3,8 @@
 mod query_plan_operation;
 
 pub use query_plan_operation::{
-    CollectOp, GroupAggregateWindowOp, JoinOp, JoinWindowOp, LowerOps, ProjectionOp, UpperOps,
+    CollectOp, GroupAggregateWindowOp, HavingOp, JoinOp, JoinWindowOp, LookupJoinOp, LowerOps,
+    ProjectionOp, SelectionOp, UpperOps,
 };
 
 use crate::{
@@ -36,6 +37,8 @@ impl QueryPlan {
             JoinOp::JoinWindow(JoinWindowOp { left, right, .. }) => {
                 vec![&left.stream, &right.stream]
             }
+            // tables are not upstreams since they are looked up, not collected
+            JoinOp::LookupJoin(LookupJoinOp { left, .. }) => vec![&left.stream],
         }
     }
 }
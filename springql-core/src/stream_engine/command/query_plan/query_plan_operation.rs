@@ -1,14 +1,19 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use crate::{
-    expr_resolver::ExprLabel,
-    pipeline::{JoinParameter, StreamName, WindowOperationParameter, WindowParameter},
+    expr_resolver::{ExprLabel, ValueExprLabel},
+    pipeline::{
+        ColumnReference, JoinParameter, StreamName, WindowOperationParameter, WindowParameter,
+    },
 };
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct UpperOps {
     pub projection: ProjectionOp,
     pub group_aggr_window: Option<GroupAggregateWindowOp>,
+
+    /// None when no HAVING clause is supplied.
+    pub having: Option<HavingOp>,
 }
 impl UpperOps {
     pub fn has_window(&self) -> bool {
@@ -19,6 +24,9 @@ impl UpperOps {
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct LowerOps {
     pub join: JoinOp,
+
+    /// None when no WHERE clause is supplied.
+    pub selection: Option<SelectionOp>,
 }
 impl LowerOps {
     pub fn has_window(&self) -> bool {
@@ -31,6 +39,22 @@ pub struct ProjectionOp {
     pub expr_labels: Vec<ExprLabel>,
 }
 
+/// Filters tuples by a WHERE clause (multi stream selection).
+///
+/// A tuple is dropped unless its predicate evaluates to TRUE (FALSE and NULL both drop it).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SelectionOp {
+    pub predicate: ValueExprLabel,
+}
+
+/// Filters aggregated values emitted from a window by a HAVING clause.
+///
+/// Like `SelectionOp`, a group is dropped unless its predicate evaluates to TRUE.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HavingOp {
+    pub predicate: ValueExprLabel,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct GroupAggregateWindowOp {
     pub window_param: WindowParameter,
@@ -47,6 +71,7 @@ pub struct CollectOp {
 pub enum JoinOp {
     Collect(CollectOp),
     JoinWindow(JoinWindowOp),
+    LookupJoin(LookupJoinOp),
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -57,3 +82,16 @@ pub struct JoinWindowOp {
     pub window_param: WindowParameter,
     pub join_param: JoinParameter,
 }
+
+/// Joins each tuple from `left` stream to the rows in `table`, without windows.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LookupJoinOp {
+    pub left: CollectOp,
+    pub table: StreamName,
+
+    pub join_param: JoinParameter,
+
+    /// Left columns to look up the table by its PRIMARY KEY (in the order of the key columns).
+    /// None when the ON expression does not have equi-join keys covering the PRIMARY KEY. Then all rows in the table are scanned.
+    pub lookup_key: Option<Vec<ColumnReference>>,
+}
@@ -5,7 +5,8 @@ use std::sync::Arc;
 use crate::{
     api::error::Result,
     pipeline::{
-        Pipeline, PipelineVersion, PumpModel, SinkWriterModel, SourceReaderModel, StreamModel,
+        DeadLetterStreamModel, Pipeline, PipelineVersion, PumpModel, SinkWriterModel,
+        SourceReaderModel, StreamModel, TableModel,
     },
     stream_engine::command::AlterPipelineCommand,
 };
@@ -49,10 +50,14 @@ impl SqlExecutor {
             AlterPipelineCommand::CreateSinkStream(sink_stream) => {
                 Self::create_sink_stream(pipeline, sink_stream)
             }
+            AlterPipelineCommand::CreateDeadLetterStream(dead_letter_stream) => {
+                Self::create_dead_letter_stream(pipeline, dead_letter_stream)
+            }
             AlterPipelineCommand::CreateSinkWriter(sink_writer) => {
                 Self::create_sink_writer(pipeline, sink_writer)
             }
             AlterPipelineCommand::CreatePump(pump) => Self::create_pump(pipeline, *pump),
+            AlterPipelineCommand::CreateTable(table) => Self::create_table(pipeline, table),
         }
     }
 
@@ -80,6 +85,13 @@ impl SqlExecutor {
         pipeline.add_stream(Arc::new(sink_stream))?;
         Ok(pipeline)
     }
+    fn create_dead_letter_stream(
+        mut pipeline: Pipeline,
+        dead_letter_stream: DeadLetterStreamModel,
+    ) -> Result<Pipeline> {
+        pipeline.add_dead_letter_stream(dead_letter_stream)?;
+        Ok(pipeline)
+    }
     fn create_sink_writer(
         mut pipeline: Pipeline,
         sink_writer: SinkWriterModel,
@@ -92,4 +104,9 @@ impl SqlExecutor {
         pipeline.add_pump(pump)?;
         Ok(pipeline)
     }
+
+    fn create_table(mut pipeline: Pipeline, table: TableModel) -> Result<Pipeline> {
+        pipeline.add_table(table)?;
+        Ok(pipeline)
+    }
 }
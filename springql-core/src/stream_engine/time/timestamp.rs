@@ -61,6 +61,126 @@ impl SpringTimestamp {
         }
     }
 
+    /// From unix time (seconds since `1970-01-01 00:00:00` UTC).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Time` when:
+    ///   - `secs` is out of range.
+    pub fn from_unix_time(secs: i64) -> Result<Self> {
+        NaiveDateTime::from_timestamp(secs, 0)
+            .map(SpringTimestamp)
+            .map_err(SpringError::Time)
+    }
+
+    /// Unix time (seconds since `1970-01-01 00:00:00` UTC), rounded down.
+    pub fn unix_time(&self) -> i64 {
+        self.0.timestamp_nanos().div_euclid(1_000_000_000) as i64
+    }
+
+    /// From unix time in milliseconds.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Time` when:
+    ///   - `millis` is out of range.
+    pub fn from_unix_time_millis(millis: i64) -> Result<Self> {
+        let (secs, sub_millis) = (millis.div_euclid(1_000), millis.rem_euclid(1_000));
+        NaiveDateTime::from_timestamp(secs, (sub_millis * 1_000_000) as u32)
+            .map(SpringTimestamp)
+            .map_err(SpringError::Time)
+    }
+
+    /// Unix time in milliseconds, rounded down.
+    pub fn unix_time_millis(&self) -> i64 {
+        self.0.timestamp_nanos().div_euclid(1_000_000) as i64
+    }
+
+    /// `self + rhs` without panic.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Time` when:
+    ///   - the result is out of range.
+    pub fn checked_add(&self, rhs: Duration) -> Result<Self> {
+        self.0
+            .checked_add(rhs)
+            .map(SpringTimestamp)
+            .map_err(SpringError::Time)
+    }
+
+    /// `self - rhs` without panic.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Time` when:
+    ///   - the result is out of range.
+    pub fn checked_sub(&self, rhs: Duration) -> Result<Self> {
+        self.0
+            .checked_sub(rhs)
+            .map(SpringTimestamp)
+            .map_err(SpringError::Time)
+    }
+
+    /// Hour (0 to 23).
+    pub fn hour(&self) -> u8 {
+        self.0.hour()
+    }
+
+    /// Minute (0 to 59).
+    pub fn minute(&self) -> u8 {
+        self.0.minute()
+    }
+
+    /// Second (0 to 59).
+    pub fn second(&self) -> u8 {
+        self.0.second()
+    }
+
+    /// Millisecond part of the second (0 to 999).
+    pub fn millisecond(&self) -> u16 {
+        self.0.millisecond()
+    }
+
+    /// 0 (Sunday) to 6 (Saturday).
+    pub fn day_of_week(&self) -> u8 {
+        self.0.weekday_from_sunday()
+    }
+
+    /// Format with a format description of `time` crate, e.g. `[year]/[month]/[day] [hour]:[minute]`.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Time` when:
+    ///   - `format_description` is invalid.
+    pub fn format_with(&self, format_description: &str) -> Result<String> {
+        self.0
+            .format_with(format_description)
+            .map_err(SpringError::Time)
+    }
+
+    /// Parse with a format description of `time` crate, e.g. `[year]/[month]/[day] [hour]:[minute]`.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::InvalidFormat` when:
+    ///   - `s` does not match `format_description`.
+    ///   - `format_description` is invalid.
+    pub fn parse_with_format(s: &str, format_description: &str) -> Result<Self> {
+        let ndt = NaiveDateTime::parse_from_str_with(s, format_description)
+            .with_context(|| {
+                format!(
+                    "failed to parse timestamp `{}` with format `{}`",
+                    s, format_description
+                )
+            })
+            .map_err(|e| SpringError::InvalidFormat {
+                s: s.to_string(),
+                source: e,
+            })?;
+        Ok(SpringTimestamp(ndt))
+    }
+
     fn try_parse_original(s: &str) -> Result<Self> {
         let ndt = NaiveDateTime::parse_from_str(s)
             .with_context(|| format!("failed to parse timestamp: {}", s))
@@ -343,4 +463,69 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_unix_time() -> Result<()> {
+        let ts: SpringTimestamp = "2020-01-01 00:00:01.500000000".parse()?;
+        assert_eq!(ts.unix_time(), 1_577_836_801);
+        assert_eq!(
+            SpringTimestamp::from_unix_time(1_577_836_801)?,
+            "2020-01-01 00:00:01.000000000".parse()?
+        );
+
+        let before_epoch: SpringTimestamp = "1969-12-31 23:59:59.500000000".parse()?;
+        assert_eq!(before_epoch.unix_time(), -1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unix_time_millis() -> Result<()> {
+        let ts: SpringTimestamp = "2020-01-01 00:00:01.500999999".parse()?;
+        assert_eq!(ts.unix_time_millis(), 1_577_836_801_500);
+        assert_eq!(
+            SpringTimestamp::from_unix_time_millis(1_577_836_801_500)?,
+            "2020-01-01 00:00:01.500000000".parse()?
+        );
+        assert_eq!(
+            SpringTimestamp::from_unix_time_millis(-500)?,
+            "1969-12-31 23:59:59.500000000".parse()?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fields() -> Result<()> {
+        // Wednesday
+        let ts: SpringTimestamp = "2020-01-01 13:14:15.678000000".parse()?;
+        assert_eq!(ts.hour(), 13);
+        assert_eq!(ts.minute(), 14);
+        assert_eq!(ts.second(), 15);
+        assert_eq!(ts.millisecond(), 678);
+        assert_eq!(ts.day_of_week(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_parse_with() -> Result<()> {
+        let ts: SpringTimestamp = "2020-01-02 13:14:15.000000000".parse()?;
+        let format_description = "[day]/[month]/[year] [hour]:[minute]:[second]";
+
+        assert_eq!(ts.format_with(format_description)?, "02/01/2020 13:14:15");
+        assert_eq!(
+            SpringTimestamp::parse_with_format("02/01/2020 13:14:15", format_description)?,
+            ts
+        );
+        assert_eq!(
+            SpringTimestamp::parse_with_format("2020-01-02", "[year]-[month]-[day]")?,
+            "2020-01-02 00:00:00.000000000".parse()?
+        );
+
+        assert!(ts.format_with("[unknown]").is_err());
+        assert!(matches!(
+            SpringTimestamp::parse_with_format("2020-01-02", format_description),
+            Err(SpringError::InvalidFormat { .. })
+        ));
+        Ok(())
+    }
 }
@@ -16,6 +16,8 @@ pub enum TimeError {
     OverflowError(#[from] time::error::ConversionRange),
     #[error("Range {0}")]
     ComponentRange(#[from] time::error::ComponentRange),
+    #[error("Invalid format description {0}")]
+    InvalidFormatDescription(#[from] time::error::InvalidFormatDescription),
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -130,6 +132,60 @@ impl NaiveDateTime {
     pub fn format(&self) -> String {
         format_primitive(&self.0).unwrap() // TODO: avoid panic
     }
+
+    /// `format_description` is in the syntax of `time` crate, e.g. `[year]/[month]/[day] [hour]:[minute]`.
+    pub fn format_with(&self, format_description: &str) -> Result<String, TimeError> {
+        let items = time::format_description::parse_borrowed::<1>(format_description)?;
+        Ok(self.0.format(&items)?)
+    }
+
+    /// `format_description` is in the syntax of `time` crate, e.g. `[year]/[month]/[day] [hour]:[minute]`.
+    ///
+    /// Time is set to midnight if `format_description` has only date components.
+    pub fn parse_from_str_with(s: &str, format_description: &str) -> Result<Self, TimeError> {
+        let items = time::format_description::parse_borrowed::<1>(format_description)?;
+        let pri = time::PrimitiveDateTime::parse(s, &items).or_else(|e| {
+            time::Date::parse(s, &items)
+                .map(|date| date.midnight())
+                .map_err(|_| e)
+        })?;
+        Ok(Self(pri))
+    }
+
+    pub fn checked_add(&self, rhs: Duration) -> Result<Self, TimeError> {
+        self.0
+            .checked_add(rhs.0)
+            .map(Self)
+            .ok_or_else(|| TimeError::OutOfRange(format!("{:?} + {:?}", self, rhs)))
+    }
+
+    pub fn checked_sub(&self, rhs: Duration) -> Result<Self, TimeError> {
+        self.0
+            .checked_sub(rhs.0)
+            .map(Self)
+            .ok_or_else(|| TimeError::OutOfRange(format!("{:?} - {:?}", self, rhs)))
+    }
+
+    pub fn hour(&self) -> u8 {
+        self.0.hour()
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.0.minute()
+    }
+
+    pub fn second(&self) -> u8 {
+        self.0.second()
+    }
+
+    pub fn millisecond(&self) -> u16 {
+        self.0.millisecond()
+    }
+
+    /// 0 (Sunday) to 6 (Saturday).
+    pub fn weekday_from_sunday(&self) -> u8 {
+        self.0.weekday().number_days_from_sunday()
+    }
 }
 
 impl Add<Duration> for NaiveDateTime {
@@ -242,3 +242,567 @@ fn test_feat_aggregation_with_2_group_bys() -> Result<()> {
 
     Ok(())
 }
+
+fn run_aggregate_function_without_group_by(
+    aggr_expr: &str,
+    result_type: &str,
+) -> Vec<serde_json::Value> {
+    let source_input = gen_source_input();
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK STREAM sink_aggr (
+          result {result_type} NOT NULL
+        );
+        ",
+            result_type = result_type
+        ),
+        format!(
+            "
+        CREATE PUMP aggr AS
+        INSERT INTO sink_aggr (result)
+        SELECT STREAM
+            {aggr_expr} AS result
+        FROM source_trade
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        ",
+            aggr_expr = aggr_expr
+        ),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_trade FOR sink_aggr
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    run_and_drain(
+        &ddls,
+        ForeignSourceInput::new_fifo_batch(source_input),
+        test_source,
+        &test_sink,
+    )
+}
+
+#[test]
+fn test_feat_aggregate_functions() -> Result<()> {
+    setup_test_logger();
+
+    for (aggr_expr, result_type, expected) in [
+        (
+            "SUM(source_trade.amount)",
+            "BIGINT",
+            vec![json!(40), json!(50)],
+        ),
+        ("COUNT(*)", "BIGINT", vec![json!(2), json!(1)]),
+        (
+            "COUNT(source_trade.ticker)",
+            "BIGINT",
+            vec![json!(2), json!(1)],
+        ),
+        (
+            "MIN(source_trade.amount)",
+            "INTEGER",
+            vec![json!(10), json!(50)],
+        ),
+        (
+            "MAX(source_trade.amount)",
+            "INTEGER",
+            vec![json!(30), json!(50)],
+        ),
+        (
+            "AVG(source_trade.amount)",
+            "FLOAT",
+            vec![json!(20.0), json!(50.0)],
+        ),
+        (
+            "VAR_POP(source_trade.amount)",
+            "FLOAT",
+            vec![json!(100.0), json!(0.0)],
+        ),
+        (
+            "STDDEV_POP(source_trade.amount)",
+            "FLOAT",
+            vec![json!(10.0), json!(0.0)],
+        ),
+        (
+            "FIRST_VALUE(source_trade.amount)",
+            "INTEGER",
+            vec![json!(10), json!(50)],
+        ),
+        (
+            "LAST_VALUE(source_trade.amount)",
+            "INTEGER",
+            vec![json!(30), json!(50)],
+        ),
+        (
+            "APPROX_PERCENTILE(source_trade.amount, 0.5)",
+            "FLOAT",
+            vec![json!(10.0), json!(50.0)],
+        ),
+    ] {
+        let sink_received: Vec<serde_json::Value> =
+            run_aggregate_function_without_group_by(aggr_expr, result_type)
+                .into_iter()
+                .map(|row| row["result"].clone())
+                .collect();
+        assert_eq!(sink_received, expected, "{}", aggr_expr);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_multiple_aggregates_and_expression_over_aggregates() -> Result<()> {
+    setup_test_logger();
+
+    let source_input = vec![
+        json!({
+            "ts": "2020-01-01 00:00:00.000000000",
+            "ticker": "ORCL",
+            "amount": 10,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:01.000000000",
+            "ticker": "ORCL",
+            "amount": 40,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:02.000000000",
+            "ticker": "IBM",
+            "amount": 25,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:10.000000000",
+            "ticker": "IBM",
+            "amount": 50,
+        }),
+    ];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_stats (
+          ticker TEXT NOT NULL,
+          avg_amount FLOAT NOT NULL,
+          max_amount INTEGER NOT NULL,
+          amount_range INTEGER NOT NULL,
+          n BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP stats AS
+        INSERT INTO sink_stats (ticker, avg_amount, max_amount, amount_range, n)
+        SELECT STREAM
+            source_trade.ticker AS ticker,
+            AVG(source_trade.amount) AS avg_amount,
+            MAX(source_trade.amount) AS max_amount,
+            MAX(source_trade.amount) - MIN(source_trade.amount) AS amount_range,
+            COUNT(*) AS n
+        FROM source_trade
+        GROUP BY ticker
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_stats FOR sink_stats
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let mut sink_received = run_and_drain(
+        &ddls,
+        ForeignSourceInput::new_fifo_batch(source_input),
+        test_source,
+        &test_sink,
+    );
+    sink_received.sort_by_key(|r| r["ticker"].as_str().unwrap().to_string());
+
+    assert_eq!(
+        sink_received,
+        vec![
+            json!({
+                "ticker": "IBM",
+                "avg_amount": 25.0,
+                "max_amount": 25,
+                "amount_range": 0,
+                "n": 1,
+            }),
+            json!({
+                "ticker": "ORCL",
+                "avg_amount": 25.0,
+                "max_amount": 40,
+                "amount_range": 30,
+                "n": 2,
+            }),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_having() -> Result<()> {
+    setup_test_logger();
+
+    let source_input = vec![
+        json!({
+            "ts": "2020-01-01 00:00:00.000000000",
+            "ticker": "ORCL",
+            "amount": 10,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:01.000000000",
+            "ticker": "ORCL",
+            "amount": 40,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:02.000000000",
+            "ticker": "IBM",
+            "amount": 25,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:03.000000000",
+            "ticker": "GOOGL",
+            "amount": 5,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:10.000000000",
+            "ticker": "IBM",
+            "amount": 50,
+        }),
+    ];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_max (
+          ticker TEXT NOT NULL,
+          max_amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP max_over_threshold AS
+        INSERT INTO sink_max (ticker, max_amount)
+        SELECT STREAM
+            source_trade.ticker AS ticker,
+            MAX(source_trade.amount) AS max_amount
+        FROM source_trade
+        GROUP BY ticker
+        HAVING SUM(source_trade.amount) > 20 AND source_trade.ticker <> 'ORCL'
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_max FOR sink_max
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let sink_received = run_and_drain(
+        &ddls,
+        ForeignSourceInput::new_fifo_batch(source_input),
+        test_source,
+        &test_sink,
+    );
+
+    assert_eq!(
+        sink_received,
+        vec![json!({
+            "ticker": "IBM",
+            "max_amount": 25,
+        })]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_session_window() -> Result<()> {
+    setup_test_logger();
+
+    let source_input = vec![
+        json!({
+            "ts": "2020-01-01 00:00:00.000000000",
+            "ticker": "ORCL",
+            "amount": 10,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:03.000000000",
+            "ticker": "ORCL",
+            "amount": 20,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:04.000000000",
+            "ticker": "IBM",
+            "amount": 5,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:07.000000000",
+            "ticker": "ORCL",
+            "amount": 30,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:20.000000000",
+            "ticker": "IBM",
+            "amount": 50,
+        }),
+        json!({
+            "ts": "2020-01-01 00:00:30.000000000",
+            "ticker": "ORCL",
+            "amount": 70,
+        }),
+    ];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_session (
+          ticker TEXT NOT NULL,
+          sum_amount BIGINT NOT NULL,
+          n BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP session_sum AS
+        INSERT INTO sink_session (ticker, sum_amount, n)
+        SELECT STREAM
+            source_trade.ticker AS ticker,
+            SUM(source_trade.amount) AS sum_amount,
+            COUNT(source_trade.amount) AS n
+        FROM source_trade
+        GROUP BY ticker
+        SESSION WINDOW DURATION_SECS(5), DURATION_SECS(0);
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_session FOR sink_session
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let mut sink_received = run_and_drain(
+        &ddls,
+        ForeignSourceInput::new_fifo_batch(source_input),
+        test_source,
+        &test_sink,
+    );
+    // sessions of different groups closed at the same time are in arbitrary order
+    sink_received.sort_by_key(|row| {
+        (
+            row["ticker"].as_str().unwrap().to_string(),
+            row["sum_amount"].as_i64().unwrap(),
+        )
+    });
+
+    // ORCL session at :30 is not closed yet
+    assert_eq!(
+        sink_received,
+        vec![
+            json!({"ticker": "IBM", "sum_amount": 5, "n": 1}),
+            json!({"ticker": "IBM", "sum_amount": 50, "n": 1}),
+            json!({"ticker": "ORCL", "sum_amount": 60, "n": 3}),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_count_window() -> Result<()> {
+    setup_test_logger();
+
+    let source_input = gen_source_input();
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_moving_avg (
+          avg_amount FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP moving_avg AS
+        INSERT INTO sink_moving_avg (avg_amount)
+        SELECT STREAM
+            AVG(source_trade.amount) AS avg_amount
+        FROM source_trade
+        SLIDING WINDOW ROWS(3), ROWS(1);
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_moving_avg FOR sink_moving_avg
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let sink_received = run_and_drain(
+        &ddls,
+        ForeignSourceInput::new_fifo_batch(source_input),
+        test_source,
+        &test_sink,
+    );
+
+    // (10 + 30 + 50) / 3, (30 + 50 + 70) / 3. Panes not full yet are not closed.
+    let avg_amounts = sink_received
+        .iter()
+        .map(|row| row["avg_amount"].as_f64().unwrap().round() as i32)
+        .collect::<Vec<_>>();
+    assert_eq!(avg_amounts, vec![30, 50]);
+
+    Ok(())
+}
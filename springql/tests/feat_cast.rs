@@ -0,0 +1,108 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{apply_ddls, drain_from_sink};
+
+#[test]
+fn test_feat_cast() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "epoch": "1577836800",
+        "reading": "21.5",
+    });
+    let json2 = json!({
+        "ts": "2020-01-01 00:00:01.000000000",
+        "epoch": " 1577836801",
+        "reading": "N/A",
+    });
+    let source_input = vec![json1, json2];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          epoch TEXT NOT NULL,
+          reading TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          event_ts TIMESTAMP NOT NULL,
+          epoch BIGINT NOT NULL,
+          reading FLOAT,
+          truncated UNSIGNED INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_cast AS
+          INSERT INTO sink_1 (ts, event_ts, epoch, reading, truncated)
+          SELECT STREAM
+            source_1.ts,
+            CAST(CAST(source_1.epoch AS BIGINT) AS TIMESTAMP),
+            CAST(source_1.epoch AS BIGINT),
+            TRY_CAST(source_1.reading AS FLOAT),
+            CAST(1.2 AS UNSIGNED INTEGER)
+          FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    assert_eq!(sink_received.len(), 2);
+
+    let r = sink_received.get(0).unwrap();
+    assert_eq!(r["event_ts"], "2020-01-01 00:00:00.000000000");
+    assert_eq!(r["epoch"], 1577836800);
+    assert_eq!(r["reading"], 21.5);
+    assert_eq!(r["truncated"], 1);
+
+    // TRY_CAST evaluates to NULL instead of failing the row
+    let r = sink_received.get(1).unwrap();
+    assert_eq!(r["event_ts"], "2020-01-01 00:00:01.000000000");
+    assert_eq!(r["epoch"], 1577836801);
+    assert_eq!(r["reading"], serde_json::Value::Null);
+}
@@ -0,0 +1,123 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{apply_ddls, drain_from_sink};
+
+#[test]
+fn test_feat_comparison_ops() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "speed": 120,
+    });
+    let json2 = json!({
+        "ts": "2020-01-01 00:00:01.000000000",
+        "speed": 100,
+    });
+    let json3 = json!({
+        "ts": "2020-01-01 00:00:02.000000000",
+        "speed": 80,
+    });
+    let source_input = vec![json1, json2, json3];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          speed INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          speed INTEGER NOT NULL,
+          lt BOOLEAN NOT NULL,
+          le BOOLEAN NOT NULL,
+          gt BOOLEAN NOT NULL,
+          ge BOOLEAN NOT NULL,
+          ne1 BOOLEAN NOT NULL,
+          ne2 BOOLEAN NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_comparison AS
+          INSERT INTO sink_1 (ts, speed, lt, le, gt, ge, ne1, ne2)
+          SELECT STREAM
+            source_1.ts,
+            source_1.speed,
+            source_1.speed < 120,
+            source_1.speed <= 120,
+            source_1.speed > 100,
+            source_1.speed >= 100,
+            source_1.speed <> 100,
+            source_1.speed != 100
+          FROM source_1
+          WHERE source_1.speed >= 100;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+
+    assert_eq!(sink_received.len(), 2);
+
+    let r = &sink_received[0];
+    assert_eq!(r["speed"], 120);
+    assert_eq!(r["lt"], false);
+    assert_eq!(r["le"], true);
+    assert_eq!(r["gt"], true);
+    assert_eq!(r["ge"], true);
+    assert_eq!(r["ne1"], true);
+    assert_eq!(r["ne2"], true);
+
+    let r = &sink_received[1];
+    assert_eq!(r["speed"], 100);
+    assert_eq!(r["lt"], true);
+    assert_eq!(r["le"], true);
+    assert_eq!(r["gt"], false);
+    assert_eq!(r["ge"], true);
+    assert_eq!(r["ne1"], false);
+    assert_eq!(r["ne2"], false);
+}
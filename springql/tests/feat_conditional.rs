@@ -0,0 +1,131 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{apply_ddls, drain_from_sink};
+
+#[test]
+fn test_feat_conditional() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "rpm": 800,
+        "gear": 0,
+        "label": null,
+    });
+    let json2 = json!({
+        "ts": "2020-01-01 00:00:01.000000000",
+        "rpm": 2500,
+        "gear": 3,
+        "label": "cruise",
+    });
+    let json3 = json!({
+        "ts": "2020-01-01 00:00:02.000000000",
+        "rpm": 6000,
+        "gear": 5,
+        "label": null,
+    });
+    let source_input = vec![json1, json2, json3];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          rpm INTEGER NOT NULL,
+          gear INTEGER NOT NULL,
+          label TEXT
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          band TEXT NOT NULL,
+          gear_name TEXT,
+          label TEXT NOT NULL,
+          gear_or_null INTEGER,
+          load FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_conditional AS
+          INSERT INTO sink_1 (ts, band, gear_name, label, gear_or_null, load)
+          SELECT STREAM
+            source_1.ts,
+            CASE
+              WHEN source_1.rpm < 1000 THEN 'idle'
+              WHEN source_1.rpm < 5000 THEN 'normal'
+              ELSE 'high'
+            END,
+            CASE source_1.gear WHEN 0 THEN 'neutral' WHEN 5 THEN 'top' END,
+            COALESCE(source_1.label, 'unknown'),
+            NULLIF(source_1.gear, 0),
+            IF(source_1.rpm > 5000, 1, 0.5)
+          FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    assert_eq!(sink_received.len(), 3);
+
+    let r = sink_received.get(0).unwrap();
+    assert_eq!(r["band"], "idle");
+    assert_eq!(r["gear_name"], "neutral");
+    assert_eq!(r["label"], "unknown");
+    assert_eq!(r["gear_or_null"], serde_json::Value::Null);
+    assert_eq!(r["load"], 0.5);
+
+    let r = sink_received.get(1).unwrap();
+    assert_eq!(r["band"], "normal");
+    assert_eq!(r["gear_name"], serde_json::Value::Null);
+    assert_eq!(r["label"], "cruise");
+    assert_eq!(r["gear_or_null"], 3);
+    assert_eq!(r["load"], 0.5);
+
+    let r = sink_received.get(2).unwrap();
+    assert_eq!(r["band"], "high");
+    assert_eq!(r["gear_name"], "top");
+    assert_eq!(r["gear_or_null"], 5);
+    assert_eq!(r["load"], 1.0);
+}
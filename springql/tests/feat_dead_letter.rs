@@ -0,0 +1,339 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{apply_ddls, drain_from_sink};
+
+#[test]
+fn test_feat_dead_letter() {
+    setup_test_logger();
+
+    let source_input = vec![
+        json!({"ts": "2020-01-01 00:00:00.000000000", "payload": r#"{"rpm": 1000}"#}),
+        // rejected by source stream definition
+        json!({"ts": "2020-01-01 00:00:01.000000000", "payload": 2000}),
+        // fails in JSON_EXTRACT
+        json!({"ts": "2020-01-01 00:00:02.000000000", "payload": "not a JSON"}),
+        json!({"ts": "2020-01-01 00:00:03.000000000", "payload": r#"{"rpm": 4000}"#}),
+    ];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          payload TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          rpm BIGINT
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_extract AS
+          INSERT INTO sink_1 (ts, rpm)
+          SELECT STREAM source_1.ts, source_1.payload -> '$.rpm' FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM dead_letter_pump AS DEAD LETTER FOR PUMP pu_extract;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        "
+        CREATE SINK WRITER q_dead_letter_pump FOR dead_letter_pump
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_dead_letter_pump'
+          );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port(),
+        ),
+        // dead-letter streams are created after their targets
+        "
+        CREATE SINK STREAM dead_letter_reader AS DEAD LETTER FOR SOURCE READER tcp_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_dead_letter_reader FOR dead_letter_reader
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_dead_letter_reader'
+          );
+        "
+        .to_string(),
+    ];
+
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+
+    let sink_received = drain_from_sink(&test_sink);
+    assert_eq!(sink_received.len(), 2);
+    assert_eq!(sink_received[0]["rpm"], 1000);
+    assert_eq!(sink_received[1]["rpm"], 4000);
+
+    // (ts, task_id, error_kind, error_message, raw_row)
+    let row = pipeline.pop("q_dead_letter_pump").unwrap();
+    assert_eq!(
+        row.get_not_null_by_index::<String>(1).unwrap(),
+        "pu_extract"
+    );
+    assert_eq!(row.get_not_null_by_index::<String>(2).unwrap(), "Sql");
+    let raw_row: serde_json::Value =
+        serde_json::from_str(&row.get_not_null_by_index::<String>(4).unwrap()).unwrap();
+    assert_eq!(raw_row["payload"], "not a JSON");
+
+    let row = pipeline.pop("q_dead_letter_reader").unwrap();
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "tcp_1");
+    let raw_row: serde_json::Value =
+        serde_json::from_str(&row.get_not_null_by_index::<String>(4).unwrap()).unwrap();
+    assert_eq!(raw_row["payload"], 2000);
+}
+
+#[test]
+fn test_feat_dead_letter_aggregate_type_error() {
+    setup_test_logger();
+
+    let source_input = vec![
+        json!({"ts": "2020-01-01 00:00:00.000000000", "payload": r#"{"v": 10}"#}),
+        // SUM over TEXT fails
+        json!({"ts": "2020-01-01 00:00:01.000000000", "payload": r#"{"v": "x"}"#}),
+        json!({"ts": "2020-01-01 00:00:02.000000000", "payload": r#"{"v": 20}"#}),
+        json!({"ts": "2020-01-01 00:00:10.000000000", "payload": r#"{"v": 1}"#}),
+    ];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          payload TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          sum_v BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_sum AS
+          INSERT INTO sink_1 (sum_v)
+          SELECT STREAM SUM(source_1.payload -> '$.v') AS sum_v FROM source_1
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM dead_letter_pump AS DEAD LETTER FOR PUMP pu_sum;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        "
+        CREATE SINK WRITER q_dead_letter_pump FOR dead_letter_pump
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_dead_letter_sum'
+          );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port(),
+        ),
+    ];
+
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+
+    // the window keeps working after the failure
+    let sink_received = drain_from_sink(&test_sink);
+    assert_eq!(sink_received, vec![json!({"sum_v": 30})]);
+
+    let row = pipeline.pop("q_dead_letter_sum").unwrap();
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "pu_sum");
+    assert_eq!(row.get_not_null_by_index::<String>(2).unwrap(), "Sql");
+    let raw_row: serde_json::Value =
+        serde_json::from_str(&row.get_not_null_by_index::<String>(4).unwrap()).unwrap();
+    assert_eq!(raw_row["payload"], r#"{"v": "x"}"#);
+}
+
+#[test]
+fn test_feat_dead_letter_join_on_error() {
+    setup_test_logger();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE STREAM source_city_temperature (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          temperature INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_joined (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          amount INTEGER,
+          temperature INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_join AS
+          INSERT INTO sink_joined (ts, amount, temperature)
+          SELECT STREAM
+            source_trade.ts,
+            source_trade.amount,
+            source_city_temperature.temperature
+          FROM source_trade
+          INNER JOIN source_city_temperature
+            ON source_trade.ts = source_city_temperature.ts
+            AND source_trade.amount / source_city_temperature.temperature > 0
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM dead_letter_pump AS DEAD LETTER FOR PUMP pu_join;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_joined FOR sink_joined
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_dead_letter_join_sink'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_dead_letter_pump FOR dead_letter_pump
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_dead_letter_join'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_dead_letter_join_trade'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_city_temperature FOR source_city_temperature
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_dead_letter_join_city_temperature'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    // in rowtime order not to be dropped as late data
+    let rows = [
+        (
+            "city_temperature",
+            r#"{"ts": "2020-01-01 00:00:00.000000000", "temperature": 0}"#,
+        ),
+        (
+            "trade",
+            r#"{"ts": "2020-01-01 00:00:00.000000000", "amount": 10}"#,
+        ),
+        (
+            "city_temperature",
+            r#"{"ts": "2020-01-01 00:00:05.000000000", "temperature": 5}"#,
+        ),
+        (
+            "trade",
+            r#"{"ts": "2020-01-01 00:00:05.000000000", "amount": 20}"#,
+        ),
+        // closes the window
+        (
+            "trade",
+            r#"{"ts": "2020-01-01 00:00:20.000000000", "amount": 30}"#,
+        ),
+    ];
+    for (source, json) in rows {
+        pipeline
+            .push(
+                &format!("q_dead_letter_join_{}", source),
+                SpringSourceRow::from_json(json).unwrap(),
+            )
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(300));
+    }
+
+    // the other pair is joined
+    let row = pipeline.pop("q_dead_letter_join_sink").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 20);
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 5);
+
+    // division by zero in ON expression
+    let row = pipeline.pop("q_dead_letter_join").unwrap();
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "pu_join");
+    assert_eq!(row.get_not_null_by_index::<String>(2).unwrap(), "Sql");
+    let raw_row: serde_json::Value =
+        serde_json::from_str(&row.get_not_null_by_index::<String>(4).unwrap()).unwrap();
+    assert_eq!(raw_row["source_trade.amount"], 10);
+    assert_eq!(raw_row["source_city_temperature.temperature"], 0);
+}
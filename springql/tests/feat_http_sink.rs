@@ -0,0 +1,175 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::time::Duration;
+
+use pretty_assertions::assert_eq;
+use springql::*;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{
+    apply_ddls,
+    http_server::{HttpRequest, HttpServer},
+};
+
+fn pipeline(source_queue_name: &str, http_server: &HttpServer, options: &str) -> SpringPipeline {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_1 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER http_sink_1 FOR sink_1
+          TYPE HTTP1_CLIENT OPTIONS (
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}',
+            URL 'http://{remote_host}:{remote_port}/rows',
+            {options}
+        );
+        ",
+            remote_host = http_server.host_ip(),
+            remote_port = http_server.port(),
+            options = options,
+        ),
+        format!(
+            "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+          );
+        ",
+            source_queue_name
+        ),
+    ];
+
+    apply_ddls(&ddls, SpringConfig::default())
+}
+
+fn push_rows(pipeline: &SpringPipeline, queue: &str, ns: &[i32]) {
+    for n in ns {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "n": {}}}"#,
+            n
+        ))
+        .unwrap();
+        pipeline.push(queue, row).unwrap();
+    }
+}
+
+fn receive(http_server: &HttpServer) -> HttpRequest {
+    http_server
+        .try_receive(Duration::from_secs(5))
+        .expect("request must be received")
+}
+
+#[test]
+fn test_http_sink_json() {
+    setup_test_logger();
+
+    let http_server = HttpServer::start(200);
+    let pipeline = pipeline(
+        "q_http_sink_json",
+        &http_server,
+        "METHOD 'PUT', BODY_FORMAT 'JSON'",
+    );
+    push_rows(&pipeline, "q_http_sink_json", &[1, 2]);
+
+    for n in [1, 2] {
+        let req = receive(&http_server);
+        assert_eq!(req.method, "PUT");
+        assert_eq!(req.path, "/rows");
+        assert_eq!(req.header("content-type"), Some("application/json"));
+        let body: serde_json::Value = serde_json::from_str(&req.body).unwrap();
+        assert_eq!(body["n"], n);
+    }
+}
+
+#[test]
+fn test_http_sink_ndjson_batch() {
+    setup_test_logger();
+
+    let http_server = HttpServer::start(200);
+    let pipeline = pipeline(
+        "q_http_sink_ndjson",
+        &http_server,
+        "METHOD 'POST', BODY_FORMAT 'NDJSON', BATCH_MAX_ROWS '2', BATCH_MAX_LATENCY_MSEC '500'",
+    );
+    push_rows(&pipeline, "q_http_sink_ndjson", &[1, 2, 3]);
+
+    // full batch
+    let req = receive(&http_server);
+    assert_eq!(req.header("content-type"), Some("application/x-ndjson"));
+    let ns: Vec<serde_json::Value> = req
+        .body
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["n"].clone())
+        .collect();
+    assert_eq!(ns, vec![1, 2]);
+
+    // flushed by latency
+    let req = receive(&http_server);
+    let ns: Vec<serde_json::Value> = req
+        .body
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["n"].clone())
+        .collect();
+    assert_eq!(ns, vec![3]);
+}
+
+#[test]
+fn test_http_sink_json_array_batch() {
+    setup_test_logger();
+
+    let http_server = HttpServer::start(200);
+    let pipeline = pipeline(
+        "q_http_sink_json_array",
+        &http_server,
+        "METHOD 'PATCH', BODY_FORMAT 'JSON_ARRAY', BATCH_MAX_ROWS '3'",
+    );
+    push_rows(&pipeline, "q_http_sink_json_array", &[1, 2, 3]);
+
+    let req = receive(&http_server);
+    assert_eq!(req.method, "PATCH");
+    let body: Vec<serde_json::Value> = serde_json::from_str(&req.body).unwrap();
+    let ns: Vec<serde_json::Value> = body.iter().map(|row| row["n"].clone()).collect();
+    assert_eq!(ns, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_http_sink_error_status() {
+    setup_test_logger();
+
+    let http_server = HttpServer::start(500);
+    let pipeline = pipeline(
+        "q_http_sink_error_status",
+        &http_server,
+        "METHOD 'POST', BODY_FORMAT 'JSON', RETRY_MAX '2', RETRY_INITIAL_BACKOFF_MSEC '10'",
+    );
+    push_rows(&pipeline, "q_http_sink_error_status", &[1]);
+
+    // 1st request and 2 retries for the failed row
+    for _ in 0..3 {
+        let req = receive(&http_server);
+        let body: serde_json::Value = serde_json::from_str(&req.body).unwrap();
+        assert_eq!(body["n"], 1);
+    }
+    assert_eq!(http_server.try_receive(Duration::from_secs(1)), None);
+}
@@ -0,0 +1,114 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::net::TcpListener;
+
+use pretty_assertions::assert_eq;
+use springql::*;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::apply_ddls;
+
+fn ephemeral_port() -> u16 {
+    let addr = TcpListener::bind("127.0.0.1:0").unwrap();
+    addr.local_addr().unwrap().port()
+}
+
+#[test]
+fn test_feat_http_source() {
+    setup_test_logger();
+
+    let port = ephemeral_port();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_1 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_1 FOR sink_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_http_source'
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SOURCE READER http_source_1 FOR source_1
+          TYPE HTTP1_SERVER OPTIONS (
+            HOST '127.0.0.1',
+            PORT '{}'
+          );
+        ",
+            port
+        ),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let client = reqwest::blocking::Client::new();
+    let url = format!("http://127.0.0.1:{}/rows", port);
+    let post = |body: &str| {
+        client
+            .post(&url)
+            .body(body.to_string())
+            .send()
+            .unwrap()
+            .status()
+            .as_u16()
+    };
+
+    assert_eq!(
+        post(r#"{"ts": "2022-01-01 13:00:00.000000000", "n": 1}"#),
+        202
+    );
+    assert_eq!(
+        post(
+            r#"[{"ts": "2022-01-01 13:00:01.000000000", "n": 2}, {"ts": "2022-01-01 13:00:02.000000000", "n": 3}]"#
+        ),
+        202
+    );
+    assert_eq!(
+        post(
+            "{\"ts\": \"2022-01-01 13:00:03.000000000\", \"n\": 4}\n{\"ts\": \"2022-01-01 13:00:04.000000000\", \"n\": 5}\n"
+        ),
+        202
+    );
+    // n is not an integer
+    assert_eq!(
+        post(r#"{"ts": "2022-01-01 13:00:05.000000000", "n": "six"}"#),
+        422
+    );
+    assert_eq!(post("{"), 400);
+
+    let ns: Vec<i32> = (0..5)
+        .map(|_| {
+            pipeline
+                .pop("q_http_source")
+                .unwrap()
+                .get_not_null_by_index::<i32>(1)
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(ns, vec![1, 2, 3, 4, 5]);
+    assert!(pipeline
+        .pop_non_blocking("q_http_source")
+        .unwrap()
+        .is_none());
+}
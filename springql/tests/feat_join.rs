@@ -183,3 +183,172 @@ fn test_feat_left_outer_join() {
     assert_eq!(r2["amount"].as_i64().unwrap(), 50);
     assert!(r2["temperature"].is_null());
 }
+
+fn run_in_memory(join_type: &str, pipeline_name: &str) -> Vec<SpringSinkRow> {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE STREAM source_city_temperature (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          city TEXT NOT NULL,
+          temperature INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_joined (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          amount INTEGER,
+          temperature INTEGER
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE PUMP pu_join AS
+          INSERT INTO sink_joined (ts, amount, temperature)
+          SELECT STREAM
+            COALESCE(source_trade.ts, source_city_temperature.ts),
+            source_trade.amount,
+            source_city_temperature.temperature
+          FROM source_trade
+          {} source_city_temperature
+            ON source_trade.ts = source_city_temperature.ts
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        ",
+            join_type
+        ),
+        format!(
+            "
+        CREATE SINK WRITER q_sink_joined FOR sink_joined
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}_sink'
+        );
+        ",
+            pipeline_name
+        ),
+        format!(
+            "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}_trade'
+          );
+        ",
+            pipeline_name
+        ),
+        format!(
+            "
+        CREATE SOURCE READER q_source_city_temperature FOR source_city_temperature
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}_city_temperature'
+          );
+        ",
+            pipeline_name
+        ),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    // in rowtime order not to be dropped as late data
+    let rows = [
+        (
+            "city_temperature",
+            r#"{"ts": "2020-01-01 00:00:00.000000000", "city": "Tokyo", "temperature": -3}"#,
+        ),
+        (
+            "trade",
+            r#"{"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10}"#,
+        ),
+        (
+            "city_temperature",
+            r#"{"ts": "2020-01-01 00:00:05.000000000", "city": "Osaka", "temperature": 5}"#,
+        ),
+        (
+            "trade",
+            r#"{"ts": "2020-01-01 00:00:09.000000000", "ticker": "GOOGL", "amount": 30}"#,
+        ),
+        (
+            "trade",
+            r#"{"ts": "2020-01-01 00:00:20.000000000", "ticker": "IBM", "amount": 70}"#,
+        ),
+    ];
+    for (source, json) in rows {
+        pipeline
+            .push(
+                &format!("{}_{}", pipeline_name, source),
+                SpringSourceRow::from_json(json).unwrap(),
+            )
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(300));
+    }
+
+    let mut rows = vec![];
+    while let Some(row) = pipeline
+        .pop_non_blocking(&format!("{}_sink", pipeline_name))
+        .unwrap()
+        .or_else(|| {
+            std::thread::sleep(std::time::Duration::from_secs(1));
+            pipeline
+                .pop_non_blocking(&format!("{}_sink", pipeline_name))
+                .unwrap()
+        })
+    {
+        rows.push(row);
+    }
+    rows.sort_by_key(|row| row.get_not_null_by_index::<String>(0).unwrap());
+    rows
+}
+
+/// (ts, amount, temperature)
+fn row_values(row: &SpringSinkRow) -> (String, Option<i32>, Option<i32>) {
+    let nullable = |i_col| match row.get_not_null_by_index::<i32>(i_col) {
+        Ok(v) => Some(v),
+        Err(SpringError::Null { .. }) => None,
+        Err(e) => panic!("{:?}", e),
+    };
+    (
+        row.get_not_null_by_index::<String>(0).unwrap(),
+        nullable(1),
+        nullable(2),
+    )
+}
+
+#[test]
+fn test_feat_inner_join() {
+    setup_test_logger();
+
+    let rows = run_in_memory("INNER JOIN", "inner_join");
+    assert_eq!(
+        rows.iter().map(row_values).collect::<Vec<_>>(),
+        vec![(
+            "2020-01-01 00:00:00.000000000".to_string(),
+            Some(10),
+            Some(-3)
+        )]
+    );
+}
+
+#[test]
+fn test_feat_full_outer_join() {
+    setup_test_logger();
+
+    let rows = run_in_memory("FULL OUTER JOIN", "full_outer_join");
+    assert_eq!(
+        rows.iter().map(row_values).collect::<Vec<_>>(),
+        vec![
+            (
+                "2020-01-01 00:00:00.000000000".to_string(),
+                Some(10),
+                Some(-3)
+            ),
+            ("2020-01-01 00:00:05.000000000".to_string(), None, Some(5)),
+            ("2020-01-01 00:00:09.000000000".to_string(), Some(30), None),
+        ]
+    );
+}
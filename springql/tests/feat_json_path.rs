@@ -0,0 +1,111 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{apply_ddls, drain_from_sink};
+
+#[test]
+fn test_feat_json_path() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "engine": {
+            "rpm": 2500,
+            "temp": 90.5,
+        },
+        "wheels": [
+            { "pressure": 2.1 },
+            { "pressure": 2.3 },
+        ],
+    });
+    let source_input = vec![json1];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          rpm INTEGER NOT NULL,
+          front_left_pressure FLOAT NOT NULL,
+          speed FLOAT,
+          engine TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          rpm INTEGER NOT NULL,
+          front_left_pressure FLOAT NOT NULL,
+          speed FLOAT,
+          engine_temp FLOAT NOT NULL,
+          rear_pressure FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_json AS
+          INSERT INTO sink_1 (ts, rpm, front_left_pressure, speed, engine_temp, rear_pressure)
+          SELECT STREAM
+            source_1.ts,
+            source_1.rpm,
+            source_1.front_left_pressure,
+            source_1.speed,
+            source_1.engine -> '$.temp',
+            JSON_EXTRACT('[{\"pressure\": 1.9}]', '$[0]') -> '$.pressure'
+          FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}',
+            JSON_PATH_rpm '$.engine.rpm',
+            JSON_PATH_front_left_pressure '$.wheels[0].pressure',
+            JSON_PATH_speed '$.vehicle.speed'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    assert_eq!(sink_received.len(), 1);
+
+    let r = sink_received.get(0).unwrap();
+    assert_eq!(r["rpm"], 2500);
+    assert_eq!(r["front_left_pressure"], json!(2.1_f32));
+    assert_eq!(r["speed"], serde_json::Value::Null);
+    assert_eq!(r["engine_temp"], 90.5);
+    assert_eq!(r["rear_pressure"], json!(1.9_f32));
+}
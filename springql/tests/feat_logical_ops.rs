@@ -82,3 +82,105 @@ fn test_feat_and() {
     assert_eq!(r["answer_true_and_false"], false);
     assert_eq!(r["answer_false_and_true"], false);
 }
+
+#[test]
+fn test_feat_or_not_is_null() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "temperature": 30,
+    });
+    let json2 = json!({
+        "ts": "2020-01-01 00:00:01.000000000",
+        "temperature": null,
+    });
+    let json3 = json!({
+        "ts": "2020-01-01 00:00:02.000000000",
+        "temperature": -5,
+    });
+    let source_input = vec![json1, json2, json3];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          temperature INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          answer_true_or_false BOOLEAN NOT NULL,
+          answer_not_true BOOLEAN NOT NULL,
+          answer_is_null BOOLEAN NOT NULL,
+          answer_is_not_null BOOLEAN NOT NULL,
+          answer_null_or_true BOOLEAN NOT NULL,
+          answer_null_and_true BOOLEAN
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_logical AS
+          INSERT INTO sink_1 (ts, answer_true_or_false, answer_not_true, answer_is_null, answer_is_not_null, answer_null_or_true, answer_null_and_true)
+          SELECT STREAM
+            source_1.ts,
+            TRUE OR FALSE,
+            NOT TRUE,
+            source_1.temperature IS NULL,
+            source_1.temperature IS NOT NULL,
+            TRUE OR source_1.temperature = 30,
+            TRUE AND source_1.temperature = 30
+          FROM source_1
+          WHERE source_1.temperature IS NULL OR source_1.temperature = 30;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    assert_eq!(sink_received.len(), 2);
+
+    let r = &sink_received[0];
+    assert_eq!(r["answer_true_or_false"], true);
+    assert_eq!(r["answer_not_true"], false);
+    assert_eq!(r["answer_is_null"], false);
+    assert_eq!(r["answer_is_not_null"], true);
+    assert_eq!(r["answer_null_or_true"], true);
+    assert_eq!(r["answer_null_and_true"], true);
+
+    let r = &sink_received[1];
+    assert_eq!(r["answer_is_null"], true);
+    assert_eq!(r["answer_is_not_null"], false);
+    assert_eq!(r["answer_null_or_true"], true);
+    assert_eq!(r["answer_null_and_true"], serde_json::Value::Null);
+}
@@ -0,0 +1,118 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{apply_ddls, drain_from_sink};
+
+fn assert_approx(actual: &serde_json::Value, expected: f64) {
+    let actual = actual.as_f64().unwrap();
+    assert!((actual - expected).abs() < 1e-4, "{} != {}", actual, expected);
+}
+
+#[test]
+fn test_feat_math_functions() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "ax": 3.0,
+        "ay": -4.0,
+        "az": 12.0,
+        "count": -25,
+    });
+    let source_input = vec![json1];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ax FLOAT NOT NULL,
+          ay FLOAT NOT NULL,
+          az FLOAT NOT NULL,
+          count INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          magnitude FLOAT NOT NULL,
+          heading FLOAT NOT NULL,
+          peak FLOAT NOT NULL,
+          bottom FLOAT NOT NULL,
+          abs_count INTEGER NOT NULL,
+          rounded_count INTEGER NOT NULL,
+          ceil_ax FLOAT NOT NULL,
+          log_az FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_math AS
+          INSERT INTO sink_1 (ts, magnitude, heading, peak, bottom, abs_count, rounded_count, ceil_ax, log_az)
+          SELECT STREAM
+            source_1.ts,
+            SQRT(POWER(source_1.ax, 2) + POWER(source_1.ay, 2) + POWER(source_1.az, 2)),
+            ROUND(ATAN2(source_1.ay, source_1.ax) * 180.0 / 3.1415927, 1),
+            GREATEST(source_1.ax, source_1.ay, source_1.az),
+            LEAST(source_1.ax, source_1.ay, source_1.az),
+            ABS(source_1.count),
+            ROUND(source_1.count, -1),
+            CEIL(source_1.ax / 2.0),
+            LN(EXP(LOG10(source_1.az)))
+          FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    assert_eq!(sink_received.len(), 1);
+
+    let r = sink_received.get(0).unwrap();
+    assert_approx(&r["magnitude"], 13.0);
+    assert_approx(&r["heading"], -53.1);
+    assert_approx(&r["peak"], 12.0);
+    assert_approx(&r["bottom"], -4.0);
+    assert_eq!(r["abs_count"], 25);
+    assert_eq!(r["rounded_count"], -30);
+    assert_approx(&r["ceil_ax"], 2.0);
+    assert_approx(&r["log_az"], 12.0_f64.log10());
+}
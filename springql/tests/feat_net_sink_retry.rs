@@ -0,0 +1,131 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{
+    io::{BufRead, BufReader},
+    net::{SocketAddr, TcpListener},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use pretty_assertions::assert_eq;
+use springql::*;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::apply_ddls;
+
+/// TCP server receiving JSON lines, which drops the 1st connection after its 1st row.
+///
+/// Rows are received with the index of the connection they came from.
+fn start_dropping_sink() -> (SocketAddr, mpsc::Receiver<(usize, serde_json::Value)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for (i_conn, stream) in listener.incoming().enumerate() {
+            let mut reader = BufReader::new(stream.unwrap());
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                    break;
+                }
+                let _ = tx.send((i_conn, line.parse().unwrap()));
+                if i_conn == 0 {
+                    break; // drops the connection
+                }
+            }
+        }
+    });
+
+    (addr, rx)
+}
+
+fn push_rows(pipeline: &SpringPipeline, queue: &str, ns: &[i32]) {
+    for n in ns {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "n": {}}}"#,
+            n
+        ))
+        .unwrap();
+        pipeline.push(queue, row).unwrap();
+    }
+}
+
+#[test]
+fn test_net_sink_retry_after_reconnect() {
+    setup_test_logger();
+
+    let (sink_addr, sink_rx) = start_dropping_sink();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          n INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_1 AS
+          INSERT INTO sink_1 (ts, n)
+          SELECT STREAM source_1.ts, source_1.n FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}',
+            RETRY_MAX '5',
+            RETRY_INITIAL_BACKOFF_MSEC '10'
+        );
+        ",
+            remote_host = sink_addr.ip(),
+            remote_port = sink_addr.port(),
+        ),
+        "
+        CREATE SOURCE READER q_source_1 FOR source_1
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_net_sink_retry'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let receive = || sink_rx.recv_timeout(Duration::from_secs(5)).ok();
+
+    push_rows(&pipeline, "q_net_sink_retry", &[1]);
+    let (i_conn, row) = receive().expect("1st row must be received");
+    assert_eq!((i_conn, row["n"].clone()), (0, 1.into()));
+
+    // The row written just after the drop may be lost in the dropped connection (the peer only resets it).
+    // Writing the next row fails, and the row is retried after reconnecting.
+    thread::sleep(Duration::from_millis(200));
+    push_rows(&pipeline, "q_net_sink_retry", &[2]);
+    thread::sleep(Duration::from_millis(200));
+    push_rows(&pipeline, "q_net_sink_retry", &[3, 4, 5]);
+
+    let mut received = vec![];
+    while let Some((i_conn, row)) = receive() {
+        assert_eq!(i_conn, 1);
+        received.push(row["n"].clone());
+        if row["n"] == 5 {
+            break;
+        }
+    }
+    received.retain(|n| n != 2);
+    assert_eq!(received, vec![3, 4, 5]);
+}
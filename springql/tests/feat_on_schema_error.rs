@@ -0,0 +1,178 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{apply_ddls, drain_from_sink};
+
+fn run_pipeline(
+    on_schema_error: &str,
+    source_input: Vec<serde_json::Value>,
+) -> (SpringPipeline, Vec<serde_json::Value>) {
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          rpm INTEGER NOT NULL,
+          speed FLOAT
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          rpm INTEGER NOT NULL,
+          speed FLOAT
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_passthrough AS
+          INSERT INTO sink_1 (ts, rpm, speed)
+          SELECT STREAM source_1.ts, source_1.rpm, source_1.speed FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}',
+            ON_SCHEMA_ERROR '{on_schema_error}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port(),
+            on_schema_error = on_schema_error,
+        ),
+    ];
+
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    (pipeline, sink_received)
+}
+
+fn source_input() -> Vec<serde_json::Value> {
+    vec![
+        // mistyped nullable column
+        json!({"ts": "2020-01-01 00:00:00.000000000", "rpm": 1000, "speed": "12.5"}),
+        // missing nullable column
+        json!({"ts": "2020-01-01 00:00:01.000000000", "rpm": 2000}),
+        // mistyped NOT NULL column
+        json!({"ts": "2020-01-01 00:00:02.000000000", "rpm": "3000", "speed": 30.0}),
+        // valid
+        json!({"ts": "2020-01-01 00:00:03.000000000", "rpm": 4000, "speed": 40.0}),
+    ]
+}
+
+#[test]
+fn test_feat_on_schema_error_drop() {
+    setup_test_logger();
+
+    let (_pipeline, sink_received) = run_pipeline("DROP", source_input());
+    assert_eq!(sink_received.len(), 1);
+    assert_eq!(sink_received[0]["rpm"], 4000);
+}
+
+#[test]
+fn test_feat_on_schema_error_null_fill() {
+    setup_test_logger();
+
+    let (_pipeline, sink_received) = run_pipeline("NULL_FILL", source_input());
+    assert_eq!(sink_received.len(), 3);
+    assert_eq!(sink_received[0]["rpm"], 1000);
+    assert_eq!(sink_received[0]["speed"], serde_json::Value::Null);
+    assert_eq!(sink_received[1]["rpm"], 2000);
+    assert_eq!(sink_received[1]["speed"], serde_json::Value::Null);
+    assert_eq!(sink_received[2]["rpm"], 4000);
+}
+
+#[test]
+fn test_feat_on_schema_error_coerce() {
+    setup_test_logger();
+
+    let (_pipeline, sink_received) = run_pipeline("COERCE", source_input());
+    assert_eq!(sink_received.len(), 3);
+    assert_eq!(sink_received[0]["rpm"], 1000);
+    assert_eq!(sink_received[0]["speed"], 12.5);
+    assert_eq!(sink_received[1]["rpm"], 3000);
+    assert_eq!(sink_received[2]["rpm"], 4000);
+}
+
+#[test]
+fn test_feat_on_schema_error_fail() {
+    setup_test_logger();
+
+    let (pipeline, sink_received) = run_pipeline(
+        "FAIL",
+        vec![
+            json!({"ts": "2020-01-01 00:00:00.000000000", "rpm": 1000, "speed": 10.0}),
+            json!({"ts": "2020-01-01 00:00:01.000000000", "rpm": "x", "speed": 20.0}),
+            json!({"ts": "2020-01-01 00:00:02.000000000", "rpm": 3000, "speed": 30.0}),
+        ],
+    );
+    // the whole pipeline stops: the valid row before the rejected one may or may not have reached the sink
+    assert!(sink_received.len() <= 1);
+    assert!(sink_received.iter().all(|row| row["rpm"] == 1000));
+
+    // fatal error is visible to the API caller
+    assert!(matches!(
+        pipeline
+            .command("CREATE SOURCE STREAM source_2 (ts TIMESTAMP NOT NULL ROWTIME);")
+            .unwrap_err(),
+        SpringError::Unavailable { .. }
+    ));
+}
+
+#[test]
+fn test_feat_on_schema_error_invalid_option() {
+    setup_test_logger();
+
+    let pipeline = SpringPipeline::new(&SpringConfig::default()).unwrap();
+    pipeline
+        .command(
+            "CREATE SOURCE STREAM source_1 (ts TIMESTAMP NOT NULL ROWTIME, rpm INTEGER NOT NULL);",
+        )
+        .unwrap();
+    assert!(matches!(
+        pipeline
+            .command(
+                "
+                CREATE SOURCE READER tcp_1 FOR source_1
+                  TYPE NET_CLIENT OPTIONS (
+                    PROTOCOL 'TCP',
+                    REMOTE_HOST '127.0.0.1',
+                    REMOTE_PORT '0',
+                    ON_SCHEMA_ERROR 'IGNORE'
+                  );
+                "
+            )
+            .unwrap_err(),
+        SpringError::InvalidOption { .. }
+    ));
+}
@@ -0,0 +1,100 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{apply_ddls, drain_from_sink};
+
+#[test]
+fn test_feat_operator_precedence() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "raw": 212,
+    });
+    let source_input = vec![json1];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          raw INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          mul_first INTEGER NOT NULL,
+          paren INTEGER NOT NULL,
+          left_assoc INTEGER NOT NULL,
+          conversion INTEGER NOT NULL,
+          and_first BOOLEAN NOT NULL,
+          cmp_arith BOOLEAN NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_precedence AS
+          INSERT INTO sink_1 (ts, mul_first, paren, left_assoc, conversion, and_first, cmp_arith)
+          SELECT STREAM
+            source_1.ts,
+            2 * 3 + 4,
+            2 * (3 + 4),
+            10 - 4 - 3,
+            (source_1.raw - 32) * 5 / 9,
+            TRUE OR FALSE AND FALSE,
+            source_1.raw - 12 = 100 * 2 AND NOT source_1.raw < 0
+          FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    let r = sink_received.get(0).unwrap();
+
+    assert_eq!(r["mul_first"], 10);
+    assert_eq!(r["paren"], 14);
+    assert_eq!(r["left_assoc"], 3);
+    assert_eq!(r["conversion"], 100);
+    assert_eq!(r["and_first"], true);
+    assert_eq!(r["cmp_arith"], true);
+}
@@ -0,0 +1,110 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{apply_ddls, drain_from_sink};
+
+fn run_selection(
+    where_clause: &str,
+    source_input: Vec<serde_json::Value>,
+) -> Vec<serde_json::Value> {
+    setup_test_logger();
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE PUMP pu_selection AS
+          INSERT INTO sink_trade (ts, ticker, amount)
+          SELECT STREAM source_trade.ts, source_trade.ticker, source_trade.amount
+            FROM source_trade
+            {where_clause};
+        ",
+            where_clause = where_clause
+        ),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_trade FOR sink_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    drain_from_sink(&test_sink)
+}
+
+#[test]
+fn test_feat_where_clause() {
+    let source_input = vec![
+        json!({"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 20}),
+        json!({"ts": "2020-01-01 00:00:01.000000000", "ticker": "IBM", "amount": 30}),
+        json!({"ts": "2020-01-01 00:00:02.000000000", "ticker": "GOOGL", "amount": 100}),
+        json!({"ts": "2020-01-01 00:00:03.000000000", "ticker": "IBM", "amount": 40}),
+    ];
+
+    let sink_received = run_selection("WHERE source_trade.ticker = 'IBM'", source_input);
+
+    assert_eq!(sink_received.len(), 2);
+    assert_eq!(sink_received[0]["amount"], 30);
+    assert_eq!(sink_received[1]["amount"], 40);
+}
+
+#[test]
+fn test_feat_where_clause_null_is_filtered() {
+    let source_input = vec![
+        json!({"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": null}),
+        json!({"ts": "2020-01-01 00:00:01.000000000", "ticker": "IBM", "amount": 30}),
+    ];
+
+    let sink_received = run_selection("WHERE source_trade.amount = 30", source_input);
+
+    assert_eq!(sink_received.len(), 1);
+    assert_eq!(sink_received[0]["ticker"], "IBM");
+}
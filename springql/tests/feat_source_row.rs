@@ -12,22 +12,26 @@ use springql::{
 use crate::test_support::*;
 
 // Requires queue name parameters for: <https://github.com/SpringQL/SpringQL/issues/219>
-fn pipeline(source_queue_name: &str, sink_queue_name: &str) -> SpringPipeline {
+fn pipeline(n_type: &str, source_queue_name: &str, sink_queue_name: &str) -> SpringPipeline {
     let ddls = vec![
-        "
+        format!(
+            "
         CREATE SOURCE STREAM source_1 (
           ts TIMESTAMP NOT NULL ROWTIME,
-          n INTEGER NOT NULL
+          n {} NOT NULL
         );
-        "
-        .to_string(),
-        "
+        ",
+            n_type
+        ),
+        format!(
+            "
         CREATE SINK STREAM sink_1 (
           ts TIMESTAMP NOT NULL ROWTIME,
-          n INTEGER NOT NULL
+          n {} NOT NULL
         );
-        "
-        .to_string(),
+        ",
+            n_type
+        ),
         "
         CREATE PUMP pu_1 AS
           INSERT INTO sink_1 (ts, n)
@@ -59,7 +63,7 @@ fn pipeline(source_queue_name: &str, sink_queue_name: &str) -> SpringPipeline {
 
 #[test]
 fn test_source_row_from_json() {
-    let pipeline = pipeline("q_source_from_json", "q_sink_from_json");
+    let pipeline = pipeline("INTEGER", "q_source_from_json", "q_sink_from_json");
 
     let source_rows = vec![
         SpringSourceRow::from_json(r#"{"ts": "2022-01-01 13:00:00.000000000", "n": 42}"#).unwrap(),
@@ -86,9 +90,55 @@ fn test_source_row_from_json() {
     assert_eq!(sink_row2.get_not_null_by_index::<i32>(1).unwrap(), 43);
 }
 
+/// Large integers (e.g. unix time in milliseconds) keep their precision.
+#[test]
+fn test_source_row_from_json_bigint() {
+    let pipeline = pipeline(
+        "BIGINT",
+        "q_source_from_json_bigint",
+        "q_sink_from_json_bigint",
+    );
+
+    let row = SpringSourceRow::from_json(
+        r#"{"ts": "2022-01-01 13:00:00.000000000", "n": 1600000000123}"#,
+    )
+    .unwrap();
+    pipeline.push("q_source_from_json_bigint", row).unwrap();
+
+    let sink_row = pipeline.pop("q_sink_from_json_bigint").unwrap();
+    assert_eq!(
+        sink_row.get_not_null_by_index::<i64>(1).unwrap(),
+        1_600_000_000_123
+    );
+}
+
+/// JSON numbers are parsed as integers if possible. They must still be accepted by FLOAT columns.
+#[test]
+fn test_source_row_from_json_float() {
+    let pipeline = pipeline(
+        "FLOAT",
+        "q_source_from_json_float",
+        "q_sink_from_json_float",
+    );
+
+    for n in ["1.5", "-0.25", "2", "1e3"] {
+        let row = SpringSourceRow::from_json(&format!(
+            r#"{{"ts": "2022-01-01 13:00:00.000000000", "n": {}}}"#,
+            n
+        ))
+        .unwrap();
+        pipeline.push("q_source_from_json_float", row).unwrap();
+    }
+
+    for expected in [1.5, -0.25, 2.0, 1000.0] {
+        let sink_row = pipeline.pop("q_sink_from_json_float").unwrap();
+        assert_eq!(sink_row.get_not_null_by_index::<f32>(1).unwrap(), expected);
+    }
+}
+
 #[test]
 fn test_source_row_from_builder() -> Result<(), SpringError> {
-    let pipeline = pipeline("q_source_from_builder", "q_sink_from_builder");
+    let pipeline = pipeline("INTEGER", "q_source_from_builder", "q_sink_from_builder");
 
     let source_rows = vec![
         SpringSourceRowBuilder::default()
@@ -0,0 +1,135 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{apply_ddls, drain_from_sink};
+
+#[test]
+fn test_feat_string_functions() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "device": "  Sensor-A/room1 ",
+    });
+    let json2 = json!({
+        "ts": "2020-01-01 00:00:01.000000000",
+        "device": "Actuator-B/room2",
+    });
+    let json3 = json!({
+        "ts": "2020-01-01 00:00:02.000000000",
+        "device": null,
+    });
+    let source_input = vec![json1, json2, json3];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          device TEXT
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          trimmed TEXT,
+          upper_name TEXT,
+          lower_name TEXT,
+          name_length INTEGER,
+          kind TEXT,
+          room TEXT,
+          label TEXT,
+          replaced TEXT,
+          is_sensor BOOLEAN
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_string AS
+          INSERT INTO sink_1 (ts, trimmed, upper_name, lower_name, name_length, kind, room, label, replaced, is_sensor)
+          SELECT STREAM
+            source_1.ts,
+            TRIM(source_1.device),
+            UPPER(TRIM(source_1.device)),
+            LOWER(TRIM(source_1.device)),
+            LENGTH(TRIM(source_1.device)),
+            SUBSTRING(TRIM(source_1.device), 1, 6),
+            SPLIT_PART(source_1.device, '/', -1),
+            'device:' || TRIM(source_1.device),
+            REPLACE(TRIM(source_1.device), '/', '@'),
+            TRIM(source_1.device) LIKE 'Sensor-_/%'
+          FROM source_1
+          WHERE source_1.device NOT LIKE '%room2' OR source_1.device IS NULL;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    assert_eq!(sink_received.len(), 2);
+
+    let r = sink_received.get(0).unwrap();
+    assert_eq!(r["trimmed"], "Sensor-A/room1");
+    assert_eq!(r["upper_name"], "SENSOR-A/ROOM1");
+    assert_eq!(r["lower_name"], "sensor-a/room1");
+    assert_eq!(r["name_length"], 14);
+    assert_eq!(r["kind"], "Sensor");
+    assert_eq!(r["room"], "room1 ");
+    assert_eq!(r["label"], "device:Sensor-A/room1");
+    assert_eq!(r["replaced"], "Sensor-A@room1");
+    assert_eq!(r["is_sensor"], true);
+
+    // NULL propagates
+    let r = sink_received.get(1).unwrap();
+    for column in [
+        "trimmed",
+        "upper_name",
+        "lower_name",
+        "name_length",
+        "kind",
+        "room",
+        "label",
+        "replaced",
+        "is_sensor",
+    ] {
+        assert_eq!(r[column], serde_json::Value::Null, "{}", column);
+    }
+}
@@ -0,0 +1,254 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::{io::Write, thread, time::Duration};
+
+use pretty_assertions::assert_eq;
+use springql::*;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::apply_ddls;
+
+/// Rows are upserted into tables asynchronously.
+fn wait_for_table() {
+    thread::sleep(Duration::from_millis(500));
+}
+
+fn push(pipeline: &SpringPipeline, queue: &str, json: &str) {
+    pipeline
+        .push(queue, SpringSourceRow::from_json(json).unwrap())
+        .unwrap();
+}
+
+/// (vin, model) of rows in `queue`.
+fn pop_all(pipeline: &SpringPipeline, queue: &str) -> Vec<(String, Option<String>)> {
+    let mut rows = Vec::new();
+    while let Some(row) = pipeline.pop_non_blocking(queue).unwrap() {
+        rows.push((
+            row.get_not_null_by_index::<String>(1).unwrap(),
+            row.get_not_null_by_index::<String>(2).ok(),
+        ));
+    }
+    rows
+}
+
+fn ddls(table_reader: String, join: &str, queue_suffix: &str) -> Vec<String> {
+    vec![
+        "
+        CREATE TABLE vehicle_model (
+          vin TEXT PRIMARY KEY,
+          model TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE STREAM source_position (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          vin TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_position (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          vin TEXT NOT NULL,
+          model TEXT
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE PUMP pu_lookup AS
+          INSERT INTO sink_position (ts, vin, model)
+          SELECT STREAM source_position.ts, source_position.vin, vehicle_model.model
+          FROM source_position
+          {} vehicle_model
+            ON source_position.vin = vehicle_model.vin;
+        ",
+            join
+        ),
+        format!(
+            "
+        CREATE SINK WRITER q_sink_position FOR sink_position
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_{}'
+        );
+        ",
+            queue_suffix
+        ),
+        format!(
+            "
+        CREATE SOURCE READER q_source_position FOR source_position
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_position_{}'
+        );
+        ",
+            queue_suffix
+        ),
+        table_reader,
+    ]
+}
+
+fn push_positions(pipeline: &SpringPipeline, queue_suffix: &str, vins: &[&str]) {
+    for (i, vin) in vins.iter().enumerate() {
+        push(
+            pipeline,
+            &format!("q_position_{}", queue_suffix),
+            &format!(
+                r#"{{"ts": "2022-01-01 13:00:0{}.000000000", "vin": "{}"}}"#,
+                i, vin
+            ),
+        );
+    }
+}
+
+#[test]
+fn test_feat_table_left_outer_join() {
+    setup_test_logger();
+
+    let table_reader = "
+        CREATE SOURCE READER q_source_vehicle_model FOR vehicle_model
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_vehicle_model'
+          );
+        "
+    .to_string();
+    let pipeline = apply_ddls(
+        &ddls(table_reader, "LEFT OUTER JOIN", "left_outer"),
+        SpringConfig::default(),
+    );
+
+    push(
+        &pipeline,
+        "q_vehicle_model",
+        r#"{"vin": "V1", "model": "M1"}"#,
+    );
+    push(
+        &pipeline,
+        "q_vehicle_model",
+        r#"{"vin": "V2", "model": "M2"}"#,
+    );
+    // replaces V1
+    push(
+        &pipeline,
+        "q_vehicle_model",
+        r#"{"vin": "V1", "model": "M1b"}"#,
+    );
+    wait_for_table();
+
+    push_positions(&pipeline, "left_outer", &["V1", "V3", "V2"]);
+    thread::sleep(Duration::from_millis(500));
+
+    assert_eq!(
+        pop_all(&pipeline, "q_sink_left_outer"),
+        vec![
+            ("V1".to_string(), Some("M1b".to_string())),
+            ("V3".to_string(), None),
+            ("V2".to_string(), Some("M2".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn test_feat_table_from_file() {
+    setup_test_logger();
+
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(file, "vin,model\nV1,M1\n\"V2\",\"M2, 2022\"").unwrap();
+
+    let table_reader = format!(
+        "
+        CREATE SOURCE READER file_vehicle_model FOR vehicle_model
+          TYPE FILE OPTIONS (
+            PATH '{}',
+            FORMAT 'CSV'
+          );
+        ",
+        file.path().display()
+    );
+    let pipeline = apply_ddls(&ddls(table_reader, "JOIN", "file"), SpringConfig::default());
+    wait_for_table();
+
+    push_positions(&pipeline, "file", &["V1", "V3", "V2"]);
+    thread::sleep(Duration::from_millis(500));
+
+    assert_eq!(
+        pop_all(&pipeline, "q_sink_file"),
+        vec![
+            ("V1".to_string(), Some("M1".to_string())),
+            ("V2".to_string(), Some("M2, 2022".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn test_feat_table_errors() {
+    setup_test_logger();
+
+    let pipeline = apply_ddls(
+        &[
+            "
+            CREATE TABLE vehicle_model (
+              vin TEXT PRIMARY KEY,
+              model TEXT NOT NULL
+            );
+            "
+            .to_string(),
+            "
+            CREATE SOURCE STREAM source_position (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              vin TEXT NOT NULL
+            );
+            "
+            .to_string(),
+            "
+            CREATE SINK STREAM sink_position (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              vin TEXT NOT NULL
+            );
+            "
+            .to_string(),
+        ],
+        SpringConfig::default(),
+    );
+
+    let is_sql_error = |ddl: &str| matches!(pipeline.command(ddl), Err(SpringError::Sql(_)));
+
+    // no PRIMARY KEY
+    assert!(is_sql_error(
+        "CREATE TABLE t (vin TEXT NOT NULL, model TEXT NOT NULL);"
+    ));
+    // PRIMARY KEY in stream
+    assert!(is_sql_error(
+        "CREATE STREAM s (ts TIMESTAMP NOT NULL ROWTIME, vin TEXT PRIMARY KEY);"
+    ));
+    // table as a stream
+    assert!(is_sql_error(
+        "
+        CREATE PUMP pu AS
+          INSERT INTO sink_position (ts, vin)
+          SELECT STREAM source_position.ts, vehicle_model.vin
+          FROM vehicle_model
+          JOIN source_position ON source_position.vin = vehicle_model.vin;
+        "
+    ));
+    // unsupported JOIN type
+    assert!(is_sql_error(
+        "
+        CREATE PUMP pu AS
+          INSERT INTO sink_position (ts, vin)
+          SELECT STREAM source_position.ts, vehicle_model.vin
+          FROM source_position
+          FULL OUTER JOIN vehicle_model ON source_position.vin = vehicle_model.vin;
+        "
+    ));
+    // INSERT INTO table
+    assert!(is_sql_error(
+        "
+        CREATE PUMP pu AS
+          INSERT INTO vehicle_model (vin, model)
+          SELECT STREAM source_position.vin, source_position.vin FROM source_position;
+        "
+    ));
+}
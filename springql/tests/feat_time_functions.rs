@@ -0,0 +1,124 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use springql::*;
+use springql_foreign_service::{
+    sink::ForeignSink,
+    source::{ForeignSource, ForeignSourceInput},
+};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::{apply_ddls, drain_from_sink};
+
+#[test]
+fn test_feat_time_functions() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "epoch_millis": 1577840461500_i64,
+        "local_time": "01/01/2020 09:01",
+    });
+    let json2 = json!({
+        "ts": "2020-01-01 00:00:01.000000000",
+        "epoch_millis": null,
+        "local_time": "01/01/2020 23:59",
+    });
+    let source_input = vec![json1, json2];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          epoch_millis BIGINT,
+          local_time TEXT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          event_ts TIMESTAMP,
+          hour_start TIMESTAMP,
+          hour_end TIMESTAMP,
+          hour INTEGER,
+          millisecond INTEGER,
+          dow INTEGER,
+          shifted_millis BIGINT,
+          label TEXT,
+          local_ts TIMESTAMP NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_time AS
+          INSERT INTO sink_1 (ts, event_ts, hour_start, hour_end, hour, millisecond, dow, shifted_millis, label, local_ts)
+          SELECT STREAM
+            source_1.ts,
+            FROM_EPOCH_MILLIS(source_1.epoch_millis),
+            FLOOR_TIME(FROM_EPOCH_MILLIS(source_1.epoch_millis), DURATION_SECS(60 * 60)),
+            CEIL_TIME(FROM_EPOCH_MILLIS(source_1.epoch_millis), DURATION_SECS(60 * 60)),
+            EXTRACT(HOUR FROM FROM_EPOCH_MILLIS(source_1.epoch_millis)),
+            EXTRACT(MILLISECOND FROM FROM_EPOCH_MILLIS(source_1.epoch_millis)),
+            EXTRACT(DOW FROM source_1.ts),
+            TO_EPOCH_MILLIS(source_1.ts + DURATION_MILLIS(500)),
+            FORMAT_TIMESTAMP(source_1.ts - DURATION_SECS(60), '[year]/[month]/[day] [hour]:[minute]'),
+            PARSE_TIMESTAMP(source_1.local_time, '[day]/[month]/[year] [hour]:[minute]') - DURATION_SECS(9 * 60 * 60)
+          FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    assert_eq!(sink_received.len(), 2);
+
+    let r = sink_received.get(0).unwrap();
+    assert_eq!(r["event_ts"], "2020-01-01 01:01:01.500000000");
+    assert_eq!(r["hour_start"], "2020-01-01 01:00:00.000000000");
+    assert_eq!(r["hour_end"], "2020-01-01 02:00:00.000000000");
+    assert_eq!(r["hour"], 1);
+    assert_eq!(r["millisecond"], 500);
+    assert_eq!(r["dow"], 3);
+    assert_eq!(r["shifted_millis"], 1577836800500_i64);
+    assert_eq!(r["label"], "2019/12/31 23:59");
+    assert_eq!(r["local_ts"], "2020-01-01 00:01:00.000000000");
+
+    // NULL propagates
+    let r = sink_received.get(1).unwrap();
+    assert_eq!(r["event_ts"], serde_json::Value::Null);
+    assert_eq!(r["hour_start"], serde_json::Value::Null);
+    assert_eq!(r["hour"], serde_json::Value::Null);
+    assert_eq!(r["local_ts"], "2020-01-01 14:59:00.000000000");
+}
@@ -0,0 +1,118 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+/// Request received by `HttpServer`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    /// Header names are in lower case.
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpRequest {
+    #[allow(dead_code)]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Minimal HTTP/1.1 server responding every request with a fixed status code.
+pub struct HttpServer {
+    my_addr: SocketAddr,
+    rx: mpsc::Receiver<HttpRequest>,
+}
+
+impl HttpServer {
+    pub fn start(status: u16) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let my_addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = stream.unwrap();
+                let tx = tx.clone();
+                thread::spawn(move || Self::serve(stream, status, tx));
+            }
+        });
+
+        Self { my_addr, rx }
+    }
+
+    #[allow(dead_code)]
+    pub fn host_ip(&self) -> IpAddr {
+        Ipv4Addr::LOCALHOST.into()
+    }
+
+    #[allow(dead_code)]
+    pub fn port(&self) -> u16 {
+        self.my_addr.port()
+    }
+
+    #[allow(dead_code)]
+    pub fn try_receive(&self, timeout: Duration) -> Option<HttpRequest> {
+        self.rx.recv_timeout(timeout).ok()
+    }
+
+    /// Serves requests in a (keep-alive) connection.
+    fn serve(stream: TcpStream, status: u16, tx: mpsc::Sender<HttpRequest>) {
+        let mut writer = stream.try_clone().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        loop {
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+                return;
+            }
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+
+            let mut headers = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                let (k, v) = line.split_once(':').unwrap();
+                headers.push((k.trim().to_lowercase(), v.trim().to_string()));
+            }
+
+            let content_length = headers
+                .iter()
+                .find(|(k, _)| k == "content-length")
+                .map_or(0, |(_, v)| v.parse().unwrap());
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let _ = tx.send(HttpRequest {
+                method,
+                path,
+                headers,
+                body: String::from_utf8(body).unwrap(),
+            });
+
+            write!(
+                writer,
+                "HTTP/1.1 {} Status\r\nContent-Length: 0\r\n\r\n",
+                status
+            )
+            .unwrap();
+            writer.flush().unwrap();
+        }
+    }
+}
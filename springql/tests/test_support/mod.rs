@@ -5,6 +5,7 @@ use std::time::Duration;
 use springql::{SpringConfig, SpringPipeline};
 use springql_foreign_service::sink::ForeignSink;
 
+pub mod http_server;
 pub mod request_body;
 
 #[allow(dead_code)]
@@ -15,6 +15,8 @@ pub struct TaskRequest {
     pub type_: String,
     #[serde(rename = "avg-gain-bytes-per-sec")]
     pub avg_gain_bytes_per_sec: f32,
+    #[serde(rename = "rows-filtered")]
+    pub rows_filtered: u64,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize)]